@@ -0,0 +1,52 @@
+//! Refuses to build while a file in `assets` has no entry in `assets/credits.txt`, so nothing
+//! third-party is shipped without its author and license.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const REQUIRED: [&str; 3] = ["author", "license", "source"];
+
+fn files(dir: &Path, prefix: &str, found: &mut BTreeSet<String>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if entry.path().is_dir() {
+            files(&entry.path(), &format!("{}/", name), found);
+        } else if name != "credits.txt" {
+            found.insert(name);
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=assets");
+    let credits = fs::read_to_string("assets/credits.txt").expect("assets/credits.txt is missing");
+
+    // the keys given per section, see crate::credits for the format
+    let mut sections: Vec<(String, BTreeSet<String>)> = Vec::new();
+    for line in credits.lines().map(str::trim).filter(|line| !line.starts_with('#')) {
+        if let Some(path) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((path.to_string(), BTreeSet::new()));
+        } else if let (Some((_, keys)), Some((key, value))) = (sections.last_mut(), line.split_once('='))
+            && !value.trim().is_empty()
+        {
+            keys.insert(key.trim().to_string());
+        }
+    }
+
+    let mut assets = BTreeSet::new();
+    files(Path::new("assets"), "", &mut assets);
+    let mut problems: Vec<String> = assets.iter()
+        .filter(|asset| !sections.iter().any(|(path, _)| path == *asset))
+        .map(|asset| format!("assets/{} has no entry", asset))
+        .collect();
+    for (path, keys) in &sections {
+        let missing: Vec<&str> = REQUIRED.iter().copied().filter(|key| !keys.contains(*key)).collect();
+        if !missing.is_empty() {
+            problems.push(format!("[{}] lacks {}", path, missing.join(", ")));
+        }
+    }
+    if !problems.is_empty() {
+        panic!("assets/credits.txt is incomplete:\n  {}", problems.join("\n  "));
+    }
+}
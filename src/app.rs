@@ -1,95 +1,307 @@
-use crate::games::binary_numbers::Bits;
 use crate::games::main_screen_widget::MainScreenWidget;
+use crate::games::registry::{Category, GameRegistry};
 use crate::games::settings::SettingsMain;
-use crate::games::weather_main::WeatherMain;
-use crate::games::{ascii_art, binary_numbers};
+use crate::games::training_plan;
+use crate::config;
+use crate::saves::{self, Checkpoint, SaveManager};
+use crate::music::Tune;
+use crate::notifications::{self, Kind};
+use crate::effects;
+use crate::events::{EventLoop, Message};
+use crate::keymap;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage;
+use crate::ui::help_overlay::HelpOverlay;
+use crate::ui::inspector::{self, Inspector};
+use crate::ui::marquee::Marquee;
+use crate::ui::modal::{Modal, ModalResult};
+use crate::ui::background::BackgroundFill;
+use crate::ui::notification_center::NotificationCenter;
+use crate::ui::pause_menu::{PauseMenu, PauseOption};
+use crate::ui::session_summary::SessionSummary;
+use crate::ui::toast::{self, ToastArea};
+use crate::training::{Recommendation, TrainingPlan};
+use crate::tween::{Animator, Easing};
 use crate::utils::{ToDuration, When};
-use ascii_art::AsciiArtMain;
-use binary_numbers::BinaryNumbersGame;
+use crate::http;
+use crate::weather;
 use color_eyre::owo_colors::OwoColorize;
-use crossterm::event;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Alignment::Center;
-use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Margin, Rect};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, HighlightSpacing, List, ListState, Paragraph};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, HighlightSpacing, List, ListState, Paragraph};
 use ratatui::{prelude, DefaultTerminal};
-use std::time::Instant;
-use std::{cmp, thread};
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
-use strum_macros::{Display, EnumIter};
 
-#[derive(EnumIter, Display, Clone, PartialEq)]
+/// A line in the main menu: the saved session, a category submenu, a game from the [`GameRegistry`],
+/// the way back out of a submenu or the exit entry.
+#[derive(Clone, PartialEq)]
 pub enum MainMenuEntry {
-    Settings,
-    Weather,
-    AsciiArt,
-    BinaryNumbers,
-    DinoJump,
+    Resume,
+    Category { category: Category, label: String },
+    Game { index: usize, name: String },
+    Back,
     Exit,
 }
 
 impl MenuEntry for MainMenuEntry {
     fn name(&self) -> &str {
         match self {
-            MainMenuEntry::Settings => "Settings",
-            MainMenuEntry::Weather => "Weather",
-            MainMenuEntry::AsciiArt => "Ascii Art",
-            MainMenuEntry::BinaryNumbers => "Binary Numbers",
-            MainMenuEntry::DinoJump => "Dino Jump",
+            MainMenuEntry::Resume => "Resume last session",
+            MainMenuEntry::Category { label, .. } => label,
+            MainMenuEntry::Game { name, .. } => name,
+            MainMenuEntry::Back => "< Back",
             MainMenuEntry::Exit => "Exit",
         }
     }
 }
 
 impl MainMenuEntry {
-    pub fn get_main_screen_widget(&self) -> Option<Box<dyn MainScreenWidget>> {
+    /// The top level: one submenu per category that has games in it.
+    fn entries_for(registry: &GameRegistry, has_session: bool) -> Vec<MainMenuEntry> {
+        let resume = has_session.then_some(MainMenuEntry::Resume);
+        let categories = Category::iter()
+            .filter(|category| registry.entries().iter().any(|game| game.category == *category))
+            .map(|category| MainMenuEntry::Category { category, label: format!("{} >", category) });
+        resume.into_iter().chain(categories).chain([MainMenuEntry::Exit]).collect()
+    }
+
+    /// Every game of every category, what the filter searches from the top level.
+    fn all_games(registry: &GameRegistry) -> Vec<MainMenuEntry> {
+        Category::iter()
+            .flat_map(|category| Self::category_entries(registry, category))
+            .filter(|entry| *entry != MainMenuEntry::Back)
+            .collect()
+    }
+
+    fn category_entries(registry: &GameRegistry, category: Category) -> Vec<MainMenuEntry> {
+        let games = registry.entries().iter().enumerate()
+            .filter(|(_, game)| game.category == category)
+            .map(|(index, game)| MainMenuEntry::Game { index, name: game.name.clone() });
+        games.chain([MainMenuEntry::Back]).collect()
+    }
+
+    pub fn get_main_screen_widget(&self, registry: &GameRegistry) -> Option<Box<dyn MainScreenWidget>> {
         match self {
-            MainMenuEntry::Settings => Some(Box::new(SettingsMain::new())),
-            MainMenuEntry::Weather => Some(Box::new(WeatherMain::new())),
-            MainMenuEntry::AsciiArt => Some(Box::new(AsciiArtMain::new())),
-            MainMenuEntry::BinaryNumbers => Some(Box::new(BinaryNumbersGame::new(Bits::Eight))),
-            MainMenuEntry::DinoJump => None, // Dino Jump is not implemented yet
+            MainMenuEntry::Game { index, .. } => registry.get(*index).and_then(|game| game.create()),
+            MainMenuEntry::Resume => None, // restored from the saved session instead
+            MainMenuEntry::Category { .. } | MainMenuEntry::Back => None, // navigate the menu instead
             MainMenuEntry::Exit => None, // Exit does not return a widget
         }
     }
 }
 
 pub fn handle_input(app: &mut App, input: KeyEvent) -> color_eyre::Result<()> {
+    if app.summary.is_some() {
+        // any key skips the summary shown on quit
+        app.running = false;
+        return Ok(());
+    }
+
+    if app.recovery.is_some() {
+        handle_recovery_inputs(app, input);
+        return Ok(());
+    }
+
+    if app.help_visible {
+        // the overlay swallows all keys until it is dismissed
+        match input.code {
+            KeyCode::Esc | KeyCode::F(1) => app.help_visible = false,
+            KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let Some(inspector) = &mut app.inspector {
+        // like the help, the inspector keeps the keys until it is closed
+        match input.code {
+            KeyCode::Tab | KeyCode::Down => inspector.select_next(),
+            KeyCode::BackTab | KeyCode::Up => inspector.select_previous(),
+            KeyCode::Esc | KeyCode::F(5) => app.inspector = None,
+            KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if app.paused.is_some() {
+        handle_pause_inputs(app, input);
+        return Ok(());
+    }
+
+    if let Some(modal) = &mut app.exit_prompt {
+        match input.code {
+            KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => match modal.handle_key(keymap::translate(input)) {
+                Some(ModalResult::Pressed(0)) => app.quit(),
+                Some(_) => app.exit_prompt = None,
+                None => {}
+            },
+        }
+        return Ok(());
+    }
+
+    if app.notifications_visible {
+        match input.code {
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                app.notifications_visible = false;
+                notifications::mark_all_read();
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if app.current_main_widget.is_none() && app.main_menu.handle_filter_input(input) {
+        return Ok(());
+    }
+
     match input.code {
         KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => {
             app.quit();
         }
-        KeyCode::Char(' ') => app.refresh_without_inputs = !app.refresh_without_inputs,
-        KeyCode::Esc => app.current_main_widget = None,
+        // games get Space for themselves, e.g. to drop a piece; ticks have to keep coming meanwhile
+        KeyCode::Char(' ') if app.current_main_widget.is_none() => app.refresh_without_inputs = !app.refresh_without_inputs,
+        KeyCode::Esc => {
+            app.leave_main_widget();
+            return Ok(());
+        }
+        KeyCode::F(1) => {
+            app.help_visible = true;
+            return Ok(());
+        }
         KeyCode::F(4) => app.debug_mode = !app.debug_mode,
+        KeyCode::F(5) => {
+            app.inspector = Some(Inspector::open());
+            return Ok(());
+        }
+        KeyCode::F(6) => config::update(|c| c.presentation = !c.presentation),
         _ => {}
     }
     match &mut app.current_main_widget {
-        None => handle_main_menu_inputs(app, input),
+        None => handle_main_menu_inputs(app, keymap::translate(input)),
+        Some(game) if game.follows_controls_preset() => game.handle_input(keymap::translate(input)),
         Some(game) => game.handle_input(input),
     }
     Ok(())
 }
 
-fn handle_main_menu_inputs(app: &mut App, input: KeyEvent) -> () {
-    app.main_menu.handle_navigation(input);
+fn handle_recovery_inputs(app: &mut App, input: KeyEvent) -> () {
+    let input = keymap::translate(input);
+    match input.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            if let Some(checkpoint) = app.recovery.take() {
+                app.restore_checkpoint(checkpoint);
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.recovery = None;
+            saves::clear(&saves::AUTOSAVE);
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+        _ => {}
+    }
+}
 
+fn handle_pause_inputs(app: &mut App, input: KeyEvent) -> () {
+    let Some(menu) = &mut app.paused else { return };
+    let input = keymap::translate(input);
     match input.code {
+        KeyCode::Up => menu.select_previous(),
+        KeyCode::Down => menu.select_next(),
+        KeyCode::Esc => app.paused = None,
         KeyCode::Enter => {
-            if app.main_menu.get_selected_entry() == Some(&MainMenuEntry::Exit) {
-                app.quit();
-                return;
+            sound::emit(SoundEvent::MenuSelect);
+            let option = menu.selected();
+            app.paused = None;
+            match option {
+                PauseOption::Resume => {}
+                PauseOption::Restart => app.restart_main_widget(),
+                PauseOption::Settings => app.open_settings_over_game(),
+                PauseOption::QuitToMenu => app.close_main_widget(),
             }
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') if input.modifiers == KeyModifiers::CONTROL => app.quit(),
+        _ => {}
+    }
+}
+
+fn handle_main_menu_inputs(app: &mut App, input: KeyEvent) -> () {
+    app.main_menu.handle_navigation(input);
+
+    match input.code {
+        KeyCode::Enter => launch_selected_entry(app),
+        // in a multi-column menu the arrows move across columns first
+        KeyCode::Right if app.main_menu.move_column(1) => {}
+        KeyCode::Left if app.main_menu.move_column(-1) => {}
+        KeyCode::Right if matches!(app.main_menu.get_selected_entry(), Some(MainMenuEntry::Category { .. })) => {
+            launch_selected_entry(app)
+        }
+        KeyCode::Backspace | KeyCode::Left => app.main_menu.pop(),
+        KeyCode::Char('/') => app.main_menu.open_filter(),
+        KeyCode::Char('n') | KeyCode::Char('N') => app.notifications_visible = true,
+        KeyCode::Char('p') | KeyCode::Char('P') if app.main_menu.parents.is_empty() => launch_recommendation(app),
+        _ => {}
+    }
+}
 
-            app.current_main_widget = match app.main_menu.get_selected_entry() {
-                Some(entry) => entry.get_main_screen_widget(),
-                None => None,
+fn handle_main_menu_mouse(app: &mut App, mouse: MouseEvent) -> () {
+    // clicking the entry that is already highlighted launches it, like Enter
+    if app.main_menu.handle_mouse(mouse, app.main_menu_area) {
+        launch_selected_entry(app);
+    }
+}
+
+fn launch_selected_entry(app: &mut App) -> () {
+    sound::emit(SoundEvent::MenuSelect);
+    match app.main_menu.get_selected_entry() {
+        Some(MainMenuEntry::Exit) => return app.exit_prompt = Some(exit_prompt()),
+        Some(MainMenuEntry::Resume) => {
+            if let Some(checkpoint) = app.saves.take_last_session() {
+                app.restore_checkpoint(checkpoint);
+                app.refresh_main_menu();
             }
+            return;
+        }
+        Some(MainMenuEntry::Category { category, .. }) => {
+            let entries = MainMenuEntry::category_entries(&app.registry, *category);
+            return app.main_menu.push(entries);
         }
+        Some(MainMenuEntry::Back) => return app.main_menu.pop(),
         _ => {}
     }
+
+    app.current_main_widget = match app.main_menu.get_selected_entry() {
+        Some(entry) => entry.get_main_screen_widget(&app.registry),
+        None => None,
+    };
+    app.current_game = match app.main_menu.get_selected_entry() {
+        Some(MainMenuEntry::Game { name, .. }) if app.current_main_widget.is_some() => Some(name.clone()),
+        _ => None,
+    };
+    app.saves.started();
+    app.announce_music();
+    if let Some(game) = &app.current_game {
+        stats::record_session(game);
+    }
+}
+
+/// Start the game suggested on the home screen, like picking it from its category.
+fn launch_recommendation(app: &mut App) -> () {
+    let Some(recommendation) = &app.recommendation else { return };
+    let Some(index) = app.registry.position(&recommendation.game) else { return };
+    let Some(category) = app.registry.get(index).map(|game| game.category) else { return };
+    let entries = MainMenuEntry::category_entries(&app.registry, category);
+    let position = entries.iter().position(|entry| matches!(entry, MainMenuEntry::Game { index: game, .. } if *game == index));
+    app.main_menu.push(entries);
+    app.main_menu.state.select(position);
+    launch_selected_entry(app);
 }
 
 #[derive(Clone)]
@@ -102,9 +314,29 @@ pub enum MenuOrientation {
 struct StatefulMenu<T> {
     orientation: MenuOrientation,
     items: Vec<T>,
-    state: ListState,
+    state: ListState, // indexes the visible entries, which are all items unless a filter is typed
+    filter: Option<String>,
+    searched: Vec<T>, // what a filter typed at the top level narrows instead of `items`, if not empty
+    parents: Vec<(Vec<T>, ListState)>, // enclosing menu levels, innermost last
+    columns: usize, // of the last render, more than one on wide terminals
+    rows: usize, // per column in the last render
 }
 
+/// Width of one column of the main menu, and the terminal width from which it may use several.
+const MENU_COLUMN_WIDTH: u16 = 26;
+const WIDE_LAYOUT: u16 = 120;
+
+/// Shown one after another in the control bar, each for one pass of the ticker.
+const TIPS: [&str; 7] = [
+    "Press / in the main menu to filter the games by name",
+    "Press P on the home screen to start the recommended trainer",
+    "Press N in the main menu to read your notifications",
+    "Press F1 in any game to see its keys",
+    "Esc pauses a running game, the settings can be changed from there",
+    "Start with --guest to play without saving anything",
+    "Turn on Reduce motion in the settings to stop tickers, shakes and flashes",
+];
+
 pub trait MenuEntry {
     fn name(&self) -> &str;
 }
@@ -112,14 +344,100 @@ pub trait MenuEntry {
 impl<T: MenuEntry> StatefulMenu<T> {
     fn select_previous(&mut self) {
         self.state.select_previous();
+        sound::emit(SoundEvent::MenuMove);
     }
 
     fn select_next(&mut self) {
         self.state.select_next();
+        sound::emit(SoundEvent::MenuMove);
     }
 
     fn get_selected_entry(&self) -> Option<&T> {
-        self.state.selected().and_then(|i| self.items.get(i))
+        let visible = self.visible_entries();
+        self.state.selected().and_then(|i| visible.get(i)).map(|(index, _)| &self.filtered_items()[*index])
+    }
+
+    /// Whether something typed at the top level narrows the searched items instead of the items.
+    fn searches_beyond_items(&self) -> bool {
+        self.filter.as_ref().is_some_and(|filter| !filter.is_empty()) && self.parents.is_empty() && !self.searched.is_empty()
+    }
+
+    /// The items the filter narrows, see [`Self::searches_beyond_items`].
+    fn filtered_items(&self) -> &[T] {
+        if self.searches_beyond_items() { &self.searched } else { &self.items }
+    }
+
+    /// Open a submenu; [`pop`](Self::pop) returns to the current level with its selection.
+    fn push(&mut self, items: Vec<T>) {
+        let parent_items = std::mem::replace(&mut self.items, items);
+        let parent_state = std::mem::replace(&mut self.state, ListState::default().with_selected(Some(0)));
+        self.parents.push((parent_items, parent_state));
+        self.filter = None;
+    }
+
+    fn pop(&mut self) {
+        if let Some((items, state)) = self.parents.pop() {
+            self.items = items;
+            self.state = state;
+            self.filter = None;
+        }
+    }
+
+    fn is_filtering(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    fn open_filter(&mut self) {
+        self.filter = Some(String::new());
+    }
+
+    fn close_filter(&mut self) {
+        // keep the highlighted entry highlighted in the full list, unless it came from the searched ones
+        let selected = self.state.selected().and_then(|i| self.visible_entries().get(i).map(|(index, _)| *index))
+            .filter(|_| !self.searches_beyond_items());
+        self.filter = None;
+        self.state.select(selected.or(Some(0)));
+    }
+
+    /// Text input of an open filter: characters narrow the list, Backspace widens it, Esc closes it.
+    /// Returns false for keys the filter leaves to the menu, like navigation and Enter.
+    fn handle_filter_input(&mut self, input: KeyEvent) -> bool {
+        let Some(filter) = &mut self.filter else { return false };
+        match input.code {
+            KeyCode::Char(c) if !input.modifiers.contains(KeyModifiers::CONTROL) => filter.push(c),
+            KeyCode::Backspace => { filter.pop(); }
+            KeyCode::Esc => {
+                self.close_filter();
+                return true;
+            }
+            _ => return false,
+        }
+        let has_matches = !self.visible_entries().is_empty();
+        self.state.select(has_matches.then_some(0));
+        true
+    }
+
+    /// Indexes into [`Self::filtered_items`] of the items matching the filter, with the positions
+    /// of the matched characters.
+    fn visible_entries(&self) -> Vec<(usize, Vec<usize>)> {
+        self.filtered_items().iter().enumerate()
+            .filter_map(|(index, item)| match &self.filter {
+                Some(filter) => fuzzy_match(item.name(), filter).map(|positions| (index, positions)),
+                None => Some((index, Vec::new())),
+            })
+            .collect()
+    }
+
+    /// Jump `step` columns sideways in a multi-column menu. Returns false when there is no such column.
+    fn move_column(&mut self, step: isize) -> bool {
+        let Some(selected) = self.state.selected() else { return false };
+        let target = selected as isize + step * self.rows as isize;
+        if self.columns < 2 || target < 0 || target as usize >= self.visible_entries().len() {
+            return false;
+        }
+        self.state.select(Some(target as usize));
+        sound::emit(SoundEvent::MenuMove);
+        true
     }
 
     fn handle_navigation(&mut self, input: KeyEvent) -> () {
@@ -137,109 +455,433 @@ impl<T: MenuEntry> StatefulMenu<T> {
         }
     }
 
+    /// Scroll moves the selection, a left click selects the clicked row.
+    /// Returns true when the click landed on the entry that was already selected.
+    fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) -> bool {
+        let position = Position::new(mouse.column, mouse.row);
+        if !area.contains(position) {
+            return false;
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.select_previous(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                // the list is rendered inside a bordered block
+                let inner = area.inner(Margin { horizontal: 1, vertical: 1 });
+                if !inner.contains(position) {
+                    return false;
+                }
+                let column = if self.columns > 1 { ((position.x - inner.x) / MENU_COLUMN_WIDTH) as usize } else { 0 };
+                let offset = if self.columns > 1 { 0 } else { self.state.offset() }; // a grid never scrolls
+                let index = column * self.rows + (position.y - inner.y) as usize + offset;
+                if index < self.visible_entries().len() {
+                    let already_selected = self.state.selected() == Some(index);
+                    self.state.select(Some(index));
+                    return already_selected;
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+
     fn get_lines(&self) -> Vec<Line> {
-        self.items.iter().map(|item| Line::from(item.name())).collect()
+        self.visible_entries().into_iter().map(|(index, positions)| {
+            let spans: Vec<Span> = self.filtered_items()[index].name().chars().enumerate().map(|(i, c)| {
+                if positions.contains(&i) { c.to_string().yellow().underlined() } else { Span::raw(c.to_string()) }
+            }).collect();
+            Line::from(spans)
+        }).collect()
+    }
+}
+
+/// Case-insensitive subsequence match: every character of `query` appears in `name` in order.
+/// Returns the character positions in `name` that matched.
+fn fuzzy_match(name: &str, query: &str) -> Option<Vec<usize>> {
+    let mut positions = Vec::new();
+    let mut chars = name.chars().enumerate();
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        let (position, _) = chars.by_ref().find(|(_, c)| c.to_lowercase().eq([wanted]))?;
+        positions.push(position);
     }
+    Some(positions)
 }
 
 pub struct App {
     running: bool,
     debug_mode: bool,
+    help_visible: bool,
+    notifications_visible: bool,
+    inspector: Option<Inspector>, // outlines of the laid out areas over everything, for layout debugging
     frame_counter: u64,
     current_main_widget: Option<Box<dyn MainScreenWidget>>,
+    current_game: Option<String>, // registry name of the running widget, used for saves
+    box_opening: Animator, // 0 to 1 while the game box grows after a widget opens
+    tips: Marquee, // rotating tip in the control bar
+    tip: usize,
+    saves: SaveManager,
+    recovery: Option<Checkpoint>, // autosave left behind by a session that did not end cleanly
+    paused: Option<PauseMenu>, // shown over a running game after Esc, the game does not advance meanwhile
+    exit_prompt: Option<Modal>, // asks before the Exit entry of the main menu closes hackerman
+    summary: Option<SessionSummary>, // shown for a moment after quitting, the app ends when it is done
+    started_at: u64, // unix seconds
+    totals_at_start: (u32, u32, u32), // see [`stats::totals`]
+    suspended: Option<(String, Box<dyn MainScreenWidget>)>, // game waiting while settings are open from the pause menu
+    registry: GameRegistry,
+    training_plan: TrainingPlan, // read once, again when its screen closes
+    recommendation: Option<Recommendation>, // worked out again whenever a widget closes, as stats or the plan may have changed
     main_menu: StatefulMenu<MainMenuEntry>,
+    main_menu_area: Rect,
     refresh_without_inputs: bool,
+    dirty: bool, // something changed since the last draw
+    focused: bool, // false while the terminal is in the background, nothing advances meanwhile
     frame_times: Vec<Instant>,
 }
 
 impl App {
-    /// Construct a new instance of [`App`].
+    /// Construct a new instance of [`App`] with the built-in games.
     pub fn new() -> Self {
+        Self::with_registry(GameRegistry::with_builtin_games())
+    }
+
+    /// Construct an [`App`] whose main menu lists the games of `registry`.
+    pub fn with_registry(registry: GameRegistry) -> Self {
+        let recovery = saves::load(&saves::AUTOSAVE)
+            .filter(|checkpoint| registry.position(&checkpoint.game).is_some());
+        let saves = SaveManager::new();
+        let training_plan = TrainingPlan::load();
+        training_plan.reminders().into_iter().for_each(|reminder| notifications::notify(Kind::Reminder, reminder));
+        // asks the weather service, which may take a while
+        let _ = http::in_background(|| if let Some(alert) = weather::alert_notice() {
+            notifications::notify(Kind::Alert, alert);
+        });
+        let has_session = saves.last_session().is_some_and(|session| registry.position(&session.game).is_some());
+
         Self {
             running: true,
             debug_mode: true,
+            help_visible: false,
+            notifications_visible: false,
+            inspector: None,
             frame_counter: 0,
             main_menu: StatefulMenu {
                 orientation: MenuOrientation::Vertical,
-                items: MainMenuEntry::iter().collect(),
+                items: MainMenuEntry::entries_for(&registry, has_session),
                 state: ListState::default().with_selected(Some(0)),
+                filter: None,
+                searched: MainMenuEntry::all_games(&registry),
+                parents: Vec::new(),
+                columns: 1,
+                rows: 0,
             },
+            recommendation: training_plan.recommend(&registry.trainers()),
+            training_plan,
+            main_menu_area: Rect::default(),
             refresh_without_inputs: true,
+            dirty: true,
+            focused: true,
             frame_times: Vec::new(),
             current_main_widget: None,
+            current_game: None,
+            box_opening: Animator::new(0.0, 0.25, Easing::EaseOut),
+            tips: Marquee::new(tip_line(0)).speed(10.0),
+            tip: 0,
+            saves,
+            recovery,
+            paused: None,
+            exit_prompt: None,
+            summary: None,
+            started_at: storage::unix_now(),
+            totals_at_start: stats::totals(),
+            suspended: None,
+            registry,
         }
     }
 
     /// Run the application's main loop.
     pub fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         let mut last_frame_time = Instant::now(); // Initialize previous time
-        let target_frame_duration = 16.milliseconds(); // Target frame duration for 30 FPS
+        let events = EventLoop::start(16.milliseconds()); // ticks for ~60 FPS
+        http::wake(events.sender()); // weather and place searches answer in the background
+        self.announce_music();
 
         while self.running {
+            let was_focused = self.focused;
+            match events.next() {
+                // performance mode and the background: only inputs wake the UI
+                Message::Tick if !self.refresh_without_inputs || !self.focused => continue,
+                Message::Tick => {}
+                Message::Wake => self.dirty = true,
+                Message::Input(event) => {
+                    self.handle_crossterm_event(event);
+                    self.dirty = true;
+                }
+            }
+            if !self.running {
+                break;
+            }
+
             let now = Instant::now();
-            let dt = now - last_frame_time;
+            // time spent in the background is not played, timers resume where they stood
+            let dt = if was_focused && self.focused { now - last_frame_time } else { Duration::ZERO };
             last_frame_time = now;
 
-            if self.frame_times.len() > 10 {
-                self.frame_times.remove(0);
-            }
+            // sampled before the update as well, so the frame in which an animation ends is still drawn
+            let was_animating = self.is_animating();
+            self.update(dt.as_secs_f64());
 
-            self.frame_times.push(Instant::now());
+            // idle screens are only redrawn after something changed
+            if self.dirty || was_animating || self.is_animating() {
+                if self.frame_times.len() > 10 {
+                    self.frame_times.remove(0);
+                }
+                self.frame_times.push(Instant::now());
 
-            terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
+                terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
+                self.dirty = false;
+                self.frame_counter += 1;
+            }
+        }
 
-            if let Some(widget) = &mut self.current_main_widget {
-                widget.run(dt.as_secs_f64());
+        Ok(())
+    }
 
-                if widget.is_exit_intended() {
-                    self.current_main_widget = None;
-                }
-            }
+    /// Advance effects and the running widget by `dt` seconds. Called once per frame by [`App::run`],
+    /// or by a frontend that drives the frames itself.
+    pub fn update(&mut self, dt: f64) {
+        if let Some(summary) = &mut self.summary {
+            summary.tick(dt);
+            self.running = !summary.is_done();
+        }
+        effects::advance(dt);
+        toast::advance(dt);
+        notifications::set_quiet(self.is_quiet());
+        match (self.current_main_widget.is_some(), self.box_opening.target() > 0.0) {
+            (true, false) => self.box_opening.animate_to(1.0),
+            (false, true) => self.box_opening.jump_to(0.0), // closing just snaps back to the details
+            _ => {}
+        }
+        self.box_opening.tick(dt);
+        self.tips.tick(dt);
+        if self.tips.laps() > 0 {
+            self.tip = (self.tip + 1) % TIPS.len();
+            self.tips.set_line(tip_line(self.tip));
+        }
 
-            self.frame_counter += 1;
+        if let Some(widget) = self.current_main_widget.as_mut().filter(|_| self.paused.is_none()) {
+            widget.run(dt);
 
-            if self.refresh_without_inputs {
-                let poll_timeout = cmp::min(dt, target_frame_duration);
-                if event::poll(poll_timeout)? {
-                    self.handle_crossterm_events()?;
-                }
-            } else {
-                // performance mode: block thread until an input event occurs
-                self.handle_crossterm_events()?;
+            if let Some(game) = &self.current_game {
+                stats::add_time(game, dt);
             }
 
-            // Optional: sleep to avoid running too fast
-            let frame_duration = last_frame_time.elapsed();
-            if frame_duration < target_frame_duration {
-                thread::sleep(target_frame_duration - frame_duration);
+            if widget.is_exit_intended() {
+                self.close_main_widget();
+            } else if let Some(game) = &self.current_game {
+                if self.saves.tick(dt, game, widget.as_ref()) {
+                    stats::flush();
+                }
             }
         }
+    }
 
-        Ok(())
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn is_animating(&self) -> bool {
+        effects::is_active() || toast::is_active() || self.box_opening.is_animating() || (self.debug_mode && self.tips.is_scrolling()) || self.current_main_widget.as_ref().is_some_and(|widget| widget.needs_redraw()) || self.summary.is_some()
     }
 
-    /// Reads the crossterm events and updates the state of [`App`].
-    ///
-    /// If your application needs to perform work in between handling events, you can use the
-    /// [`event::poll`] function to check if there are any events available with a timeout.
-    fn handle_crossterm_events(&mut self) -> color_eyre::Result<()> {
-        match event::read()? {
+    /// Updates the state of [`App`] from a crossterm event delivered by the [`EventLoop`].
+    fn handle_crossterm_event(&mut self, event: Event) -> () {
+        match event {
             // it's important to check KeyEventKind::Press to avoid handling key release events
             Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_press(key),
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse) => self.on_mouse(mouse),
             Event::Resize(_, _) => {}
+            Event::FocusLost => self.focused = false,
+            Event::FocusGained => self.focused = true,
             _ => {}
         }
-        Ok(())
+    }
+
+    /// Do not disturb applies while a game of one of the quiet categories runs.
+    fn is_quiet(&self) -> bool {
+        let config = config::get();
+        config.do_not_disturb && self.current_game.as_ref()
+            .and_then(|game| self.registry.position(game))
+            .and_then(|index| self.registry.get(index))
+            .is_some_and(|game| config.quiet_during.contains(&game.category))
     }
 
     /// Handles the key events and updates the state of [`App`].
-    fn on_key_press(&mut self, key: KeyEvent) -> () {
+    pub fn on_key_press(&mut self, key: KeyEvent) -> () {
+        let raw = self.current_main_widget.as_ref().is_some_and(|widget| widget.wants_raw_keys());
+        let key = if raw { key } else { keymap::normalize(key) };
         handle_input(self, key).unwrap_or_else(|e| eprintln!("Error handling input: {}", e));
     }
 
+    /// Forwards mouse events to the active widget, or to the main menu when no widget is open.
+    fn on_mouse(&mut self, mouse: MouseEvent) -> () {
+        if self.summary.is_some() {
+            if let MouseEventKind::Down(_) = mouse.kind { self.running = false }
+            return;
+        }
+        if self.help_visible || self.notifications_visible || self.recovery.is_some() || self.paused.is_some() {
+            return;
+        }
+        if let Some(modal) = &mut self.exit_prompt {
+            match modal.handle_mouse(mouse) {
+                Some(ModalResult::Pressed(0)) => self.quit(),
+                Some(_) => self.exit_prompt = None,
+                None => {}
+            }
+            return;
+        }
+        match &mut self.current_main_widget {
+            None => handle_main_menu_mouse(self, mouse),
+            Some(widget) => widget.handle_mouse(mouse),
+        }
+    }
+
     fn quit(&mut self) {
-        self.running = false;
+        // a clean exit leaves nothing to recover, the running game becomes the session to resume
+        self.return_to_suspended_game();
+        self.close_main_widget();
+        self.exit_prompt = None;
+        if self.summary.is_none() && config::get().session_summary {
+            self.summary = Some(self.session_summary());
+        } else {
+            self.running = false;
+        }
+    }
+
+    /// What was played since hackerman started.
+    fn session_summary(&self) -> SessionSummary {
+        let (sessions, rounds, correct) = stats::totals();
+        let (sessions_before, rounds_before, correct_before) = self.totals_at_start;
+        let streaks = stats::all().into_iter().map(|(game, stats)| (game, stats.best_streak_today())).collect();
+        let achievements = notifications::recent().into_iter()
+            .filter(|notification| notification.kind == Kind::Achievement && notification.at >= self.started_at)
+            .map(|notification| notification.text)
+            .collect();
+        let seconds = storage::unix_now().saturating_sub(self.started_at) as f64;
+        SessionSummary::new(seconds, sessions.saturating_sub(sessions_before), (rounds.saturating_sub(rounds_before), correct.saturating_sub(correct_before)))
+            .streaks(streaks)
+            .achievements(achievements)
+    }
+
+    /// Esc: games get the pause menu, settings opened from it return to the game, other screens close.
+    /// Without an open screen it steps out of the current submenu.
+    fn leave_main_widget(&mut self) {
+        if self.current_main_widget.is_none() {
+            self.main_menu.pop();
+        } else if self.suspended.is_some() {
+            self.return_to_suspended_game();
+            self.paused = Some(PauseMenu::new());
+        } else if self.is_game_running() {
+            self.paused = Some(PauseMenu::new());
+        } else {
+            self.close_main_widget();
+        }
+    }
+
+    fn is_game_running(&self) -> bool {
+        let descriptor = self.current_game.as_ref()
+            .and_then(|game| self.registry.position(game))
+            .and_then(|index| self.registry.get(index));
+        let finished = self.current_main_widget.as_ref().is_none_or(|widget| widget.is_finished());
+        !finished && descriptor.is_some_and(|game| game.category == Category::Games)
+    }
+
+    /// Throw away the running game and start the same one from scratch.
+    fn restart_main_widget(&mut self) {
+        let descriptor = self.current_game.as_ref()
+            .and_then(|game| self.registry.position(game))
+            .and_then(|index| self.registry.get(index));
+        if let Some(widget) = descriptor.and_then(|game| game.create()) {
+            self.current_main_widget = Some(widget);
+            saves::clear(&saves::AUTOSAVE);
+            self.saves.started();
+            effects::clear();
+            if let Some(game) = &self.current_game {
+                stats::record_session(game);
+            }
+        }
+    }
+
+    fn open_settings_over_game(&mut self) {
+        if let (Some(game), Some(widget)) = (self.current_game.take(), self.current_main_widget.take()) {
+            self.suspended = Some((game, widget));
+            self.current_main_widget = Some(Box::new(SettingsMain::new()));
+            self.announce_music();
+        }
+    }
+
+    fn return_to_suspended_game(&mut self) {
+        if let Some((game, widget)) = self.suspended.take() {
+            self.current_game = Some(game);
+            self.current_main_widget = Some(widget);
+            self.announce_music();
+        }
+    }
+
+    fn close_main_widget(&mut self) {
+        self.paused = None;
+        if let Some(widget) = self.current_main_widget.take() {
+            match &self.current_game {
+                Some(_) if widget.is_finished() => self.saves.finished(),
+                Some(game) => self.saves.snapshot(game, widget.as_ref()),
+                None => saves::clear(&saves::AUTOSAVE),
+            }
+            if self.current_game.as_deref() == Some(training_plan::NAME) {
+                self.training_plan = TrainingPlan::load();
+            }
+            self.refresh_main_menu();
+            self.dirty = true;
+            stats::flush();
+            self.recommendation = self.training_plan.recommend(&self.registry.trainers());
+            effects::clear();
+            self.announce_music();
+        }
+        self.current_game = None;
+    }
+
+    /// Ask the audio thread for the tune of the current screen (the menu tune when no widget is open).
+    fn announce_music(&self) {
+        let tune = match &self.current_main_widget {
+            Some(widget) => widget.music(),
+            None => Some(Tune::Menu),
+        };
+        sound::emit(SoundEvent::Music(tune));
+    }
+
+    fn restore_checkpoint(&mut self, checkpoint: Checkpoint) {
+        let index = self.registry.position(&checkpoint.game);
+        self.current_main_widget = index
+            .and_then(|index| self.registry.get(index))
+            .and_then(|game| game.restore(&checkpoint.state));
+        self.current_game = self.current_main_widget.as_ref().map(|_| checkpoint.game);
+        self.saves.started();
+        self.announce_music();
+    }
+
+    /// Rebuild the top level entries after the saved session changed, keeping the highlighted entry.
+    fn refresh_main_menu(&mut self) {
+        self.main_menu.filter = None;
+        let has_session = self.saves.last_session().is_some_and(|session| self.registry.position(&session.game).is_some());
+        let entries = MainMenuEntry::entries_for(&self.registry, has_session);
+        self.main_menu.searched = MainMenuEntry::all_games(&self.registry);
+        let (items, state) = match self.main_menu.parents.first_mut() {
+            Some((items, state)) => (items, state),
+            None => (&mut self.main_menu.items, &mut self.main_menu.state),
+        };
+        let selected = state.selected().and_then(|index| items.get(index)).cloned();
+        *items = entries;
+        let index = selected.and_then(|entry| items.iter().position(|item| *item == entry));
+        state.select(Some(index.unwrap_or(0)));
     }
 
     fn get_fps(&self) -> f64 {
@@ -257,7 +899,52 @@ impl App {
         }
     }
 
+    /// One column, unless the terminal is wide and the entries do not fit below each other.
+    fn main_menu_columns(&self, area: Rect) -> usize {
+        let rows = area.height.saturating_sub(2).max(1) as usize;
+        let needed = self.main_menu.visible_entries().len().div_ceil(rows);
+        let room = (area.width.saturating_sub(WIDE_LAYOUT / 2) / MENU_COLUMN_WIDTH) as usize;
+        if area.width >= WIDE_LAYOUT && needed > 1 && needed <= room { needed } else { 1 }
+    }
+
+    fn menu_icon(&self, entry: &MainMenuEntry) -> &'static str {
+        let category_icon = |category: Category| match category {
+            Category::Games => "♦",
+            Category::Learn => "✎",
+            Category::Tools => "¤",
+            Category::Screensavers => "☾",
+            Category::Settings => "≡",
+        };
+        match entry {
+            MainMenuEntry::Resume => "»",
+            MainMenuEntry::Category { category, .. } => category_icon(*category),
+            MainMenuEntry::Game { index, .. } => self.registry.get(*index).map_or("·", |game| category_icon(game.category)),
+            MainMenuEntry::Back => "«",
+            MainMenuEntry::Exit => "×",
+        }
+    }
+
+    fn render_menu_grid(&self, block: Block, area: Rect, buf: &mut Buffer) {
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let menu = &self.main_menu;
+        let entries = menu.visible_entries().into_iter().zip(menu.get_lines()).enumerate();
+        for (i, ((index, _), line)) in entries {
+            let (column, row) = (i / menu.rows, i % menu.rows);
+            let cell = Rect::new(inner.x + column as u16 * MENU_COLUMN_WIDTH, inner.y + row as u16, MENU_COLUMN_WIDTH, 1).intersection(inner);
+            let selected = menu.state.selected() == Some(i);
+            let mut spans = vec![
+                Span::raw(if selected { "> " } else { "  " }),
+                Span::raw(format!("{} ", self.menu_icon(&menu.filtered_items()[index]))),
+            ];
+            spans.extend(line.spans);
+            let style = if selected { Style::default().fg(Color::LightCyan).bold() } else { Style::default() };
+            Line::from(spans).style(style).render(cell, buf);
+        }
+    }
+
     pub fn render_main_menu(&mut self, area: Rect, buf: &mut Buffer) {
+        self.main_menu_area = area;
         let highlight_color = Color::LightCyan;
 
         let menu_is_active = self.current_main_widget.is_none();
@@ -265,10 +952,36 @@ impl App {
         let binding = self.main_menu.clone();
         let menu_lines = binding.get_lines();
 
+        let title = match (&self.main_menu.filter, self.main_menu.parents.last()) {
+            (Some(filter), _) => format!("/{}_", filter),
+            // the entry that opened this submenu is still selected in its parent
+            (None, Some((items, state))) => state.selected().and_then(|i| items.get(i))
+                .map(|entry| entry.name().trim_end_matches(" >").to_string())
+                .unwrap_or_else(|| "Main Menu".to_string()),
+            (None, None) => "Main Menu".to_string(),
+        };
+
+        let block = Block::default().borders(Borders::ALL)
+            .title(title).title_alignment(Center)
+            .title_bottom(Line::from(match (self.main_menu.is_filtering(), notifications::unread_count()) {
+                (true, _) => String::new(),
+                (false, 0) => "</> filter".to_string(),
+                (false, unread) => format!("</> filter <N> {} new", unread),
+            }).dark_gray().centered());
+
+        if self.main_menu.columns > 1 {
+            // a List clamps the selection while rendering, the grid has to do it itself
+            let last = self.main_menu.visible_entries().len().saturating_sub(1);
+            self.main_menu.state.select(self.main_menu.state.selected().map(|i| i.min(last)));
+            self.render_menu_grid(block, area, buf);
+            if !menu_is_active {
+                buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+            }
+            return;
+        }
+
         let games_list = List::new(menu_lines)
-            .block(Block::default().borders(Borders::ALL)
-                .title("Main Menu").title_alignment(Center)
-            )
+            .block(block)
             .highlight_style(Style::default().fg(highlight_color).bold())
             .highlight_symbol("> ")
             .when(!menu_is_active, |list| list.dim())
@@ -279,29 +992,91 @@ impl App {
     }
 
     pub fn render_game_details(&mut self, area: Rect, buf: &mut Buffer) {
-        let selected_game_name = self.main_menu.get_selected_entry();
+        let selected_entry = self.main_menu.get_selected_entry();
 
-        let details_content = match selected_game_name {
-            Some(game) => Paragraph::new(game.to_string()),
+        let details_content = match selected_entry {
+            Some(MainMenuEntry::Game { index, .. }) => match self.registry.get(*index) {
+                Some(game) => {
+                    let mut lines = vec![
+                        Line::from(game.name.clone().bold()),
+                        Line::from(game.category.to_string().dark_gray()),
+                        Line::from(""),
+                        Line::from(game.description.clone()),
+                    ];
+                    if let Some(goal) = self.training_plan.goals.get(&game.name) {
+                        let line = format!("This week: {}/{} {}", goal.progress(&game.name), goal.target, goal.metric);
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(line.light_yellow()));
+                    }
+                    lines.extend(stats_lines(&stats::get(&game.name)));
+                    Paragraph::new(lines)
+                }
+                None => Paragraph::new("No game selected."),
+            },
+            Some(MainMenuEntry::Resume) => match self.saves.last_session() {
+                Some(session) => Paragraph::new(vec![
+                    Line::from("Resume last session".bold()),
+                    Line::from(""),
+                    Line::from(format!("{} ({})", session.game, session.summary)),
+                ]),
+                None => Paragraph::new("No saved session."),
+            },
+            Some(MainMenuEntry::Category { category, .. }) => {
+                let mut lines = vec![Line::from(category.to_string().bold()), Line::from("")];
+                lines.extend(self.registry.entries().iter()
+                    .filter(|game| game.category == *category)
+                    .map(|game| Line::from(vec![game.name.clone().white(), "  ".into(), game.description.clone().dark_gray()])));
+                Paragraph::new(lines)
+            }
+            Some(MainMenuEntry::Back) => Paragraph::new("Back to the previous menu."),
+            Some(MainMenuEntry::Exit) => Paragraph::new("Exit"),
             None => Paragraph::new("No game selected."),
         };
 
-        details_content.render(area, buf);
+        // the home screen suggests what to play next below the details
+        let recommendation = self.recommendation.as_ref().filter(|_| self.main_menu.parents.is_empty());
+        let Some(recommendation) = recommendation else { return details_content.render(area, buf) };
+        let [details_area, recommendation_area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(4)]).areas(area);
+        details_content.render(details_area, buf);
+        Paragraph::new(vec![
+            Line::from("Up next".dark_gray()),
+            Line::from(recommendation.game.as_str().light_green().bold()),
+            Line::from(recommendation.reason.as_str().dark_gray()),
+            Line::from(vec!["<P>".light_cyan(), " play it".white()]),
+        ]).render(recommendation_area, buf);
     }
 
     pub fn render_main_widget(&mut self, area: Rect, buf: &mut Buffer) {
         let is_active = self.current_main_widget.is_some();
 
+        let flash = effects::flash_color().filter(|_| is_active);
+
+        // the box grows from its middle row, the widget appears once it is fully open
+        let opening = is_active && self.box_opening.is_animating();
+        let area = if opening {
+            let height = ((area.height as f64 * self.box_opening.value()).round() as u16).max(2);
+            Rect { y: area.y + (area.height - height.min(area.height)) / 2, height: height.min(area.height), ..area }
+        } else {
+            area
+        };
+
         Block::bordered()
             .when(!is_active, |block| block.dim())
+            .border_style(flash.map(|color| Style::new().fg(color).bold()).unwrap_or_default())
             .render(area, buf);
 
         let inner_area = area.inner(Margin {
             horizontal: 1,
             vertical: 1,
         });
+        let inner_area = effects::shaken(inner_area, area);
+        if inspector::is_open() {
+            let name = self.current_main_widget.as_ref().map_or("game details".to_string(), |widget| widget.get_name());
+            inspector::mark(&name, inner_area);
+        }
 
         match &self.current_main_widget {
+            Some(_) if opening => {}
             Some(main_widget) => main_widget.render_ref(inner_area, buf),
             None => self.render_game_details(inner_area, buf),
         }
@@ -313,9 +1088,14 @@ impl App {
         }
 
         let content = format!(
-            "Loop Mode: {}, FPS: {:.0}",
-            if self.refresh_without_inputs { "Real Time" } else { "Performance" },
-            self.get_fps()
+            "Loop Mode: {}, FPS: {:.0}{}",
+            match (self.focused, self.refresh_without_inputs) {
+                (false, _) => "Paused in the background",
+                (true, true) => "Real Time",
+                (true, false) => "Performance",
+            },
+            self.get_fps(),
+            if storage::is_guest() { ", Guest (nothing is saved)" } else { "" }
         );
 
         Paragraph::new(content)
@@ -328,17 +1108,47 @@ impl App {
             return;
         }
 
-        Paragraph::new("<F1> Overview | <F2> Settings | <F4> Debug | <Space> Pause, <Ctrl+C> Quit")
-            .block(Block::bordered().border_style(Style::default().dark_gray()).title("Controls"))
-            .render(area, buf);
+        // Space only pauses in the menu, games use it themselves and get a pause menu on Esc
+        let pause = match &self.current_main_widget {
+            None => "<Space> Pause",
+            Some(_) if self.is_game_running() => "<Esc> Pause menu",
+            Some(_) => "<Esc> Back",
+        };
+        let controls = Line::from(format!("<F1> Help | <F2> Settings | <F4> Debug | <F6> Clean | {}, <Ctrl+C> Quit", pause));
+        let block = Block::bordered().border_style(Style::default().dark_gray()).title("Controls");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [controls_area, _, tips_area] = Layout::horizontal([
+            Constraint::Length(controls.width() as u16),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ]).areas(inner);
+        controls.render(controls_area, buf);
+        self.tips.render(tips_area, buf);
+    }
+
+    pub fn render_help_overlay(&self, area: Rect, buf: &mut Buffer) {
+        let overlay = match &self.current_main_widget {
+            Some(widget) => HelpOverlay::new(Some(widget.get_name()), widget.help_lines()),
+            None => HelpOverlay::new(None, Vec::new()),
+        };
+        overlay.render(area, buf);
     }
 
     pub fn render_middle_area(&mut self, main_area: Rect, buf: &mut Buffer) {
+        let columns = self.main_menu_columns(main_area);
+        self.main_menu.columns = columns;
+        self.main_menu.rows = main_area.height.saturating_sub(2).max(1) as usize;
+        let menu_width = 28 + (columns as u16 - 1) * MENU_COLUMN_WIDTH;
+
         let [left, right] = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Length(28), Constraint::Min(24),])
+            .constraints(vec![Constraint::Length(menu_width), Constraint::Min(24),])
             .areas(main_area);
 
+        inspector::mark("main menu", left);
+        inspector::mark("game box", right);
         self.render_main_menu(left, buf);
         self.render_main_widget(right, buf);
     }
@@ -346,17 +1156,255 @@ impl App {
 
 impl Widget for &mut App {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        inspector::begin_frame();
+        let full_area = area;
+        let config = config::get();
+        BackgroundFill(config.background).render(area, buf);
+
+        // on very wide terminals the UI stays a centered column instead of stretching
+        let area = if config.max_width > 0 && area.width > config.max_width {
+            let [column] = Layout::horizontal([Constraint::Length(config.max_width)]).flex(Flex::Center).areas(area);
+            column
+        } else {
+            area
+        };
+        // kept clear for rounded corners and overlays of the terminal, columns are about half as wide as rows are high
+        let area = area.inner(Margin { horizontal: config.safe_margin as u16 * 2, vertical: config.safe_margin as u16 });
+
+        inspector::mark("app", area);
+        let bar_height = if config.presentation { 0 } else { 3 };
         let [top_area, main_area, bottom_area] = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
-                Constraint::Length(3),
+                Constraint::Length(bar_height),
                 Constraint::Fill(1),
-                Constraint::Length(3),
+                Constraint::Length(bar_height),
             ])
             .areas(area);
 
-        self.render_top_area(top_area, buf);
+        inspector::mark("top bar", top_area);
+        inspector::mark("middle", main_area);
+        inspector::mark("bottom bar", bottom_area);
+        if !config.presentation {
+            self.render_top_area(top_area, buf);
+            self.render_bottom_area(bottom_area, buf);
+        }
         self.render_middle_area(main_area, buf);
-        self.render_bottom_area(bottom_area, buf);
+
+        if self.help_visible {
+            self.render_help_overlay(area, buf);
+        }
+
+        if let Some(menu) = &self.paused {
+            menu.render(main_area, buf);
+        }
+
+        if let Some(modal) = &self.exit_prompt {
+            modal.render(area, buf);
+        }
+
+        if self.notifications_visible {
+            NotificationCenter::new(notifications::recent()).render(main_area, buf);
+        }
+
+        ToastArea.render(main_area, buf);
+
+        if let Some(checkpoint) = &self.recovery {
+            render_recovery_prompt(checkpoint, area, buf);
+        }
+
+        if let Some(summary) = &self.summary {
+            summary.render(area, buf);
+        }
+
+        if let Some(inspector) = &self.inspector {
+            inspector.render(full_area, buf);
+        }
+    }
+}
+
+fn stats_lines(stats: &stats::GameStats) -> Vec<Line<'static>> {
+    if stats.sessions == 0 && stats.rounds == 0 {
+        return Vec::new();
+    }
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from("Your Stats".yellow()),
+        Line::from(format!("Time played: {}   Sessions: {}", stats::format_duration(stats.seconds_played), stats.sessions)),
+    ];
+    if let Some(accuracy) = stats.accuracy() {
+        lines.push(Line::from(format!(
+            "Rounds: {}   Accuracy: {:.0}%   Best streak: {}",
+            stats.rounds, accuracy, stats.best_streak
+        )));
+    }
+    for (mode, mode_stats) in &stats.modes {
+        if let Some(accuracy) = mode_stats.accuracy() {
+            let line = format!("  {:<14} {:>4} rounds  {:>3.0}%", mode, mode_stats.rounds, accuracy);
+            lines.push(Line::from(line.dark_gray()));
+        }
+    }
+    lines
+}
+
+fn exit_prompt() -> Modal {
+    Modal::new("Exit")
+        .body(vec![Line::from("Leave hackerman?"), Line::from("Your stats and saves are kept.".dark_gray())])
+        .buttons(&["Quit", "Cancel"])
+        .accent(Color::LightRed)
+}
+
+fn render_recovery_prompt(checkpoint: &Checkpoint, area: Rect, buf: &mut Buffer) {
+    let question = format!("Recover interrupted {} game ({})?", checkpoint.game, checkpoint.summary);
+    let lines = vec![
+        Line::from(question.clone()),
+        Line::from(""),
+        Line::from(vec!["<Y>".light_cyan(), " recover   ".white(), "<N>".light_cyan(), " discard".white()]),
+    ];
+    let width = question.chars().count() as u16 + 4;
+    let [middle] = Layout::vertical([Constraint::Length(5)]).flex(Flex::Center).areas(area);
+    let [popup] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(middle);
+
+    Clear.render(popup, buf);
+    Paragraph::new(lines)
+        .alignment(Center)
+        .block(Block::bordered().border_type(BorderType::Double).title(" Crash Recovery ").title_alignment(Center))
+        .render(popup, buf);
+}
+
+fn tip_line(index: usize) -> Line<'static> {
+    Line::from(vec![Span::styled("Tip: ", Style::default().fg(Color::Yellow)), Span::styled(TIPS[index], Style::default().fg(Color::Gray))])
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::minesweeper::{Difficulty, MinesweeperGame};
+    use crate::games::tetris::TetrisGame;
+    use crate::games::main_screen_widget::WidgetRef;
+    use crate::training::{Goal, Metric};
+
+    fn app() -> App {
+        storage::install_for_tests();
+        App::new()
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_input(app, KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    #[test]
+    fn space_in_the_menu_toggles_real_time() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char(' '));
+        assert!(!app.refresh_without_inputs);
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.refresh_without_inputs);
+    }
+
+    #[test]
+    fn space_in_a_game_leaves_the_ticks_running() {
+        let mut app = app();
+        app.current_main_widget = Some(Box::new(TetrisGame::new()));
+        app.current_game = Some(crate::games::tetris::NAME.to_string());
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Char(' '));
+        }
+        assert!(app.refresh_without_inputs);
+    }
+
+    #[test]
+    fn revealing_with_space_keeps_the_clock_running() {
+        let mut app = app();
+        app.current_main_widget = Some(Box::new(MinesweeperGame::with_difficulty(Difficulty::Beginner)));
+        app.current_game = Some(crate::games::minesweeper::NAME.to_string());
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.refresh_without_inputs);
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        text.chars().for_each(|ch| press(app, KeyCode::Char(ch)));
+    }
+
+    #[test]
+    fn the_filter_finds_games_of_every_category_from_the_top_level() {
+        let mut app = app();
+        type_text(&mut app, "/tetris");
+        assert!(matches!(app.main_menu.get_selected_entry(), Some(MainMenuEntry::Game { name, .. }) if name == crate::games::tetris::NAME));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_game.as_deref(), Some(crate::games::tetris::NAME));
+    }
+
+    #[test]
+    fn the_filter_of_a_category_stays_in_it() {
+        let mut app = app();
+        app.main_menu.push(MainMenuEntry::category_entries(&app.registry, Category::Tools));
+        type_text(&mut app, "/tetris");
+        assert!(app.main_menu.get_selected_entry().is_none());
+        press(&mut app, KeyCode::Esc);
+        assert!(matches!(app.main_menu.get_selected_entry(), Some(MainMenuEntry::Game { .. })));
+    }
+
+    #[test]
+    fn closing_the_filter_goes_back_to_the_categories() {
+        let mut app = app();
+        type_text(&mut app, "/hanoi");
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.main_menu.is_filtering());
+        assert_eq!(app.main_menu.state.selected(), Some(0));
+        assert!(!matches!(app.main_menu.get_selected_entry(), Some(MainMenuEntry::Game { .. })));
+    }
+
+    #[test]
+    fn the_plan_is_read_again_when_its_screen_closes() {
+        let mut app = app();
+        let game = app.registry.position(training_plan::NAME).and_then(|index| app.registry.get(index)).unwrap();
+        app.current_main_widget = game.create();
+        app.current_game = Some(training_plan::NAME.to_string());
+        let goal = Goal { target: 5, metric: Metric::Sessions };
+        TrainingPlan { goals: [(crate::games::tetris::NAME.to_string(), goal.clone())].into() }.save();
+        app.close_main_widget();
+        assert_eq!(app.training_plan.goals.get(crate::games::tetris::NAME), Some(&goal));
+    }
+
+    #[test]
+    fn the_recommended_game_opens_from_its_category() {
+        let mut app = app();
+        app.recommendation = Some(Recommendation { game: crate::games::tetris::NAME.to_string(), reason: String::new() });
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.current_game.as_deref(), Some(crate::games::tetris::NAME));
+        assert!(app.current_main_widget.is_some());
+        assert!(matches!(app.main_menu.get_selected_entry(), Some(MainMenuEntry::Game { name, .. }) if name == crate::games::tetris::NAME));
     }
-}
\ No newline at end of file
+
+    /// A game that has already ended.
+    struct Finished;
+
+    impl WidgetRef for Finished {
+        fn render_ref(&self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    impl MainScreenWidget for Finished {
+        fn run(&mut self, _dt: f64) {}
+        fn handle_input(&mut self, _input: KeyEvent) {}
+        fn is_exit_intended(&self) -> bool { false }
+        fn is_finished(&self) -> bool { true }
+    }
+
+    #[test]
+    fn leaving_a_finished_game_drops_the_session_to_resume() {
+        let mut app = app();
+        let older = Checkpoint { game: crate::games::tetris::NAME.to_string(), summary: String::new(), state: String::new() };
+        saves::store(&saves::SESSION, &older);
+        app.saves = SaveManager::new();
+        app.current_main_widget = Some(Box::new(Finished));
+        app.current_game = Some(crate::games::tetris::NAME.to_string());
+        press(&mut app, KeyCode::Esc);
+        assert!(app.current_main_widget.is_none() && app.paused.is_none());
+        assert!(app.saves.last_session().is_none());
+        assert_eq!(saves::load(&saves::SESSION), None);
+        assert!(!app.main_menu.items.contains(&MainMenuEntry::Resume));
+    }
+}
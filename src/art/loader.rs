@@ -0,0 +1,224 @@
+use crate::storage;
+use crate::utils::{parse_ansi_art, parse_ascii_art, AnsiCharset, AsciiCells};
+use ratatui::style::Color;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Art shipped with the binary, used when the assets directory has no file of the same name.
+const EMBEDDED: [(&str, &str); 3] = [
+    ("tree", include_str!("../../assets/art/tree.art")),
+    ("settings", include_str!("../../assets/art/settings.art")),
+    ("binbreak", include_str!("../../assets/art/binbreak.art")),
+];
+
+#[derive(Debug)]
+pub enum ArtError {
+    NotFound(String),
+    /// A palette line whose color ratatui cannot parse, e.g. `Y=yelow`.
+    BadColor { line: String },
+    /// Art and color layer differ in size; `row` is 0-based, `None` means the number of rows differs.
+    SizeMismatch { row: Option<usize> },
+}
+
+impl Display for ArtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtError::NotFound(name) => write!(f, "no art called {name}"),
+            ArtError::BadColor { line } => write!(f, "unknown color in palette line `{line}`"),
+            ArtError::SizeMismatch { row: Some(row) } => write!(f, "art and colors differ in width on row {}", row + 1),
+            ArtError::SizeMismatch { row: None } => write!(f, "art and colors differ in height"),
+        }
+    }
+}
+
+/// One `.art` file: the characters, a color layer of the same size and the palette
+/// mapping color-layer characters to colors.
+///
+/// ```text
+/// # comments are allowed before the first section
+/// [palette]
+/// @=lightgreen
+/// default=darkgray
+/// [art]
+///  ,@@,
+/// [colors]
+///  ,@@,
+/// [background]
+///  ..@.
+/// [modifiers]
+///  .bb.
+/// ```
+///
+/// The `[background]` layer (colors from the same palette) and the `[modifiers]` layer
+/// (see [`modifier_for`](crate::utils::modifier_for)) are optional.
+#[derive(Clone, Debug)]
+pub struct ArtSource {
+    pub art: String,
+    pub colors: String,
+    pub background: Option<String>,
+    pub modifiers: Option<String>,
+    pub palette: HashMap<char, Color>,
+    pub default_color: Color,
+}
+
+impl ArtSource {
+    pub fn parse(contents: &str) -> Result<Self, ArtError> {
+        let mut layers: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut palette = HashMap::new();
+        let mut default_color = Color::Reset;
+        let mut section = "";
+        for line in contents.lines() {
+            match line.trim_end() {
+                "[palette]" | "[art]" | "[colors]" | "[background]" | "[modifiers]" => section = line.trim_end(),
+                _ if section != "" && section != "[palette]" => layers.entry(section).or_default().push(line),
+                "" => {}
+                _ if section == "[palette]" => {
+                    let bad_color = || ArtError::BadColor { line: line.to_string() };
+                    if let Some(value) = line.strip_prefix("default=") {
+                        default_color = value.trim().parse().map_err(|_| bad_color())?;
+                    } else {
+                        let mut chars = line.chars();
+                        let (Some(key), Some('=')) = (chars.next(), chars.next()) else { return Err(bad_color()) };
+                        palette.insert(key, chars.as_str().trim().parse().map_err(|_| bad_color())?);
+                    }
+                }
+                _ => {} // comments
+            }
+        }
+
+        // blank lines before the next section are not part of the art
+        for layer in layers.values_mut() {
+            while layer.last().is_some_and(|row| row.trim().is_empty()) {
+                layer.pop();
+            }
+        }
+        let art = layers.remove("[art]").unwrap_or_default();
+        let mut layer = |name: &str| -> Result<Option<String>, ArtError> {
+            let Some(rows) = layers.remove(name) else { return Ok(None) };
+            if rows.len() != art.len() {
+                return Err(ArtError::SizeMismatch { row: None });
+            }
+            if let Some(row) = art.iter().zip(&rows).position(|(a, r)| a.chars().count() != r.chars().count()) {
+                return Err(ArtError::SizeMismatch { row: Some(row) });
+            }
+            Ok(Some(rows.join("\n")))
+        };
+        let colors = layer("[colors]")?.unwrap_or_else(|| art.iter().map(|row| " ".repeat(row.chars().count())).collect::<Vec<_>>().join("\n"));
+        let background = layer("[background]")?;
+        let modifiers = layer("[modifiers]")?;
+        Ok(Self { art: art.join("\n"), colors, background, modifiers, palette, default_color })
+    }
+
+    pub fn cells(&self) -> AsciiCells {
+        let cells = AsciiCells::new(parse_ascii_art(self.art.clone(), self.colors.clone(), &self.palette, self.default_color));
+        let cells = match &self.background {
+            Some(background) => cells.with_background_layer(background, &self.palette),
+            None => cells,
+        };
+        match &self.modifiers {
+            Some(modifiers) => cells.with_modifier_layer(modifiers),
+            None => cells,
+        }
+    }
+}
+
+/// Where users can put `<name>.art` or `<name>.ans` files to replace the built-in art.
+pub fn assets_dir() -> PathBuf {
+    storage::data_dir().join("art")
+}
+
+/// The art called `name`: the user's file from [`assets_dir`] if it is valid, the embedded one otherwise.
+pub fn source(name: &str) -> Result<ArtSource, ArtError> {
+    let user = std::fs::read_to_string(assets_dir().join(format!("{}.art", name))).ok();
+    if let Some(source) = user.and_then(|contents| ArtSource::parse(&contents).ok()) {
+        return Ok(source);
+    }
+    let (_, contents) = EMBEDDED.iter().find(|(embedded, _)| *embedded == name).ok_or_else(|| ArtError::NotFound(name.to_string()))?;
+    ArtSource::parse(contents)
+}
+
+/// Like [`source`], but a valid `.art` file is preferred over ANSI art in `<name>.ans`,
+/// which is read as UTF-8 when possible and as code page 437 otherwise.
+pub fn load(name: &str) -> Result<AsciiCells, ArtError> {
+    let user_art = std::fs::read_to_string(assets_dir().join(format!("{}.art", name))).ok()
+        .and_then(|contents| ArtSource::parse(&contents).ok());
+    if let Some(source) = user_art {
+        return Ok(source.cells());
+    }
+    if let Ok(bytes) = std::fs::read(assets_dir().join(format!("{}.ans", name))) {
+        let charset = if std::str::from_utf8(&bytes).is_ok() { AnsiCharset::Utf8 } else { AnsiCharset::Cp437 };
+        return Ok(parse_ansi_art(&bytes, charset));
+    }
+    source(name).map(|source| source.cells())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    const ROCKET: &str = "\
+# a comment before the first section
+[palette]
+@=lightgreen
+r=red
+default=darkgray
+
+[art]
+/\\/\\
+|@@|
+/__\\
+
+[colors]
+.rrr
+r@@r
+rrrr
+[background]
+....
+.rr.
+....
+[modifiers]
+....
+.bb.
+...k
+";
+
+    #[test]
+    fn every_layer_ends_up_in_the_cells() {
+        let source = ArtSource::parse(ROCKET).unwrap();
+        assert_eq!(source.art, "/\\/\\\n|@@|\n/__\\");
+        assert_eq!(source.default_color, Color::DarkGray);
+        let cells = source.cells().cells;
+        let at = |x, y| cells.iter().find(|cell| (cell.x, cell.y) == (x, y)).unwrap();
+        assert_eq!((at(1, 1).ch, at(1, 1).color, at(1, 1).background, at(1, 1).modifier), ('@', Color::LightGreen, Some(Color::Red), Modifier::BOLD));
+        assert_eq!((at(0, 1).ch, at(0, 1).color, at(0, 1).background), ('|', Color::Red, None));
+        assert_eq!((at(0, 0).color, at(3, 2).modifier), (Color::DarkGray, Modifier::SLOW_BLINK));
+    }
+
+    #[test]
+    fn art_without_colors_takes_the_default_color() {
+        let source = ArtSource::parse("[palette]\ndefault=cyan\n[art]\nab\nc").unwrap();
+        assert_eq!(source.colors, "  \n ");
+        assert!(source.cells().cells.iter().all(|cell| cell.color == Color::Cyan));
+    }
+
+    #[test]
+    fn layers_must_match_the_art() {
+        let error = |contents: &str| ArtSource::parse(contents).unwrap_err().to_string();
+        assert_eq!(error("[art]\nab\ncd\n[colors]\nab"), "art and colors differ in height");
+        assert_eq!(error("[art]\nab\ncd\n[colors]\nab\ncde"), "art and colors differ in width on row 2");
+        assert_eq!(error("[art]\nab\n[modifiers]\nb"), "art and colors differ in width on row 1");
+        assert_eq!(error("[palette]\nY=yelow\n[art]\nY"), "unknown color in palette line `Y=yelow`");
+        assert_eq!(error("[palette]\nlight=green\n[art]\nY"), "unknown color in palette line `light=green`");
+    }
+
+    #[test]
+    fn the_embedded_art_is_valid() {
+        for (name, contents) in EMBEDDED {
+            let source = ArtSource::parse(contents).unwrap_or_else(|error| panic!("{}: {}", name, error));
+            assert!(!source.cells().cells.is_empty(), "{} is empty", name);
+        }
+        assert!(matches!(source("no such art"), Err(ArtError::NotFound(name)) if name == "no such art"));
+    }
+}
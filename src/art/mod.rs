@@ -0,0 +1,3 @@
+//! Ascii art kept outside the source code, see [`loader`].
+
+pub mod loader;
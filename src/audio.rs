@@ -0,0 +1,82 @@
+use crate::config;
+#[cfg(feature = "music")]
+use crate::music::Tune;
+use crate::sound::{self, SoundEvent};
+#[cfg(feature = "music")]
+use rodio::buffer::SamplesBuffer;
+use rodio::source::{SineWave, Source};
+use rodio::{OutputStream, Sink};
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "music")]
+const MUSIC_SAMPLE_RATE: u32 = 22_050;
+
+/// Spawn the audio thread which plays a short beep sequence for every emitted [`SoundEvent`]
+/// and, with the `music` feature, loops the requested background tune.
+///
+/// Fails silently when no output device is available; the games work the same without sound.
+pub fn start() {
+    let events = sound::subscribe();
+    thread::spawn(move || {
+        // the stream has to outlive the sinks, so everything lives on this thread
+        let Ok((_stream, handle)) = OutputStream::try_default() else { return };
+        let Ok(effects) = Sink::try_new(&handle) else { return };
+        #[cfg(feature = "music")]
+        let mut music: Option<Sink> = None;
+
+        loop {
+            // wake up regularly so volume changes in the settings apply to the running tune
+            let event = match events.recv_timeout(Duration::from_millis(250)) {
+                Ok(event) => Some(event),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+            let settings = config::get();
+
+            #[cfg(feature = "music")]
+            {
+                if let Some(SoundEvent::Music(tune)) = event {
+                    // dropping the old sink stops the previous tune
+                    music = tune.and_then(|tune| play_tune(&handle, tune));
+                }
+                if let Some(sink) = &music {
+                    let volume = if settings.muted { 0.0 } else { settings.music_volume as f32 / 100.0 };
+                    sink.set_volume(volume);
+                }
+            }
+
+            let Some(event) = event else { continue };
+            if settings.muted || settings.volume == 0 {
+                continue;
+            }
+            effects.set_volume(settings.volume as f32 / 100.0);
+            for &(frequency, millis) in tones(event) {
+                let tone = SineWave::new(frequency).take_duration(Duration::from_millis(millis)).amplify(0.2);
+                effects.append(tone);
+            }
+        }
+    });
+}
+
+#[cfg(feature = "music")]
+fn play_tune(handle: &rodio::OutputStreamHandle, tune: Tune) -> Option<Sink> {
+    let sink = Sink::try_new(handle).ok()?;
+    let samples = tune.track().render(MUSIC_SAMPLE_RATE);
+    sink.append(SamplesBuffer::new(1, MUSIC_SAMPLE_RATE, samples).amplify(0.08).repeat_infinite());
+    Some(sink)
+}
+
+/// (frequency in Hz, duration in ms) pairs played back to back.
+fn tones(event: SoundEvent) -> &'static [(f32, u64)] {
+    match event {
+        SoundEvent::MenuMove => &[(880.0, 25)],
+        SoundEvent::MenuSelect => &[(660.0, 40), (990.0, 60)],
+        SoundEvent::Correct => &[(784.0, 80), (1046.0, 120)],
+        SoundEvent::Incorrect => &[(220.0, 220)],
+        SoundEvent::Timeout => &[(330.0, 100), (262.0, 160)],
+        SoundEvent::GameOver => &[(392.0, 160), (330.0, 160), (262.0, 320)],
+        SoundEvent::Music(_) => &[],
+    }
+}
@@ -1,18 +1,124 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use hackerman::games::binary_numbers::{BinaryNumbersGame, Bits};
+use clap::Parser;
+use crossterm::event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
+use crossterm::execute;
+use hackerman::art::loader;
+use hackerman::config;
+use hackerman::events::{EventLoop, Message};
+use hackerman::games::binary_numbers::{self, Base, BinaryNumbersGame, Bits, Mode, NumberRange, Quiz, LEADERBOARD_SIZE};
 use hackerman::games::main_screen_widget::MainScreenWidget;
+use hackerman::games::scoring;
+use hackerman::report::{self, SessionResults};
+use hackerman::stats;
+use hackerman::sound::{self, SoundEvent};
+use hackerman::storage::{self, migration::{self, Schema}, profiles::{self, Profile}};
+use hackerman::ui::settings_form::{Field, SettingsForm};
+use hackerman::ui::theme::Theme;
 use hackerman::utils::{AsciiArtWidget, AsciiCells};
-use nice_trim::NiceTrim;
+use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::{List, ListItem, ListState};
-use std::collections::HashMap;
-use std::thread;
-use std::time::Instant;
+use ratatui::widgets::{Block, BorderType, Clear, List, ListItem, ListState, Padding, Paragraph};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+
+/// The first entry of the start menu plays the daily challenge.
+const DAILY_ITEM: usize = 0;
+
+/// The difficulty, answer mode, base, quiz, practice, mode and range chosen last, as `difficulty=<high score key>`,
+/// `answers=typed` or `answers=choose`, `base=<base>`, `quiz=<quiz>`, `practice=true`, `mode=<mode>` and `range=<range>`.
+const MENU: Schema = Schema { namespace: "config", key: "binbreak", steps: &[migration::unversioned] };
+
+/// Convert binary, octal and hex numbers against the clock.
+///
+/// Any of --bits, --mode and --no-timer skips the start menu; the choices not given are the ones
+/// made in the menu last time. During a game, O or F2 opens the options and P pauses.
+#[derive(Parser)]
+#[command(name = "binbreak", version)]
+struct Args {
+    /// Difficulty: 4, 8, 12 or 16 bits, 4*16, 4*256 or 4*4096 for the scaled ones, or WIDTH:SECONDS for a custom one
+    #[arg(long, value_parser = parse_bits)]
+    bits: Option<Bits>,
+    /// How a run ends: lives, time-attack-60, time-attack-120 or career
+    #[arg(long)]
+    mode: Option<Mode>,
+    /// Practice without timer, score or lives
+    #[arg(long)]
+    no_timer: bool,
+    /// Only convert numbers in this range: LOW-HIGH, e.g. 0-63, or powers-of-two for 2ⁿ and 2ⁿ±1
+    #[arg(long)]
+    range: Option<NumberRange>,
+    /// Draw the puzzles from this seed, the same seed asks the same questions
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Colors: classic, phosphor, amber or mono
+    #[arg(long, default_value_t)]
+    theme: Theme,
+    /// Write the results of the session to this file after every round: CSV if it ends in .csv, JSON otherwise
+    #[arg(long, value_name = "PATH")]
+    export: Option<PathBuf>,
+    /// Ask over plain stdin and stdout instead of the full screen: type the answer and press Enter
+    #[arg(long)]
+    plain: bool,
+    /// Keep nothing on disk
+    #[arg(long)]
+    guest: bool,
+    /// Play as this profile instead of the one used last
+    #[arg(long, value_parser = parse_profile)]
+    profile: Option<String>,
+    /// Keep everything in a single database file
+    #[cfg(feature = "sqlite")]
+    #[arg(long)]
+    sqlite: bool,
+}
+
+fn parse_bits(value: &str) -> Result<Bits, String> {
+    let preset = match value {
+        "4" => Some(Bits::Four),
+        "4*16" | "4x16" => Some(Bits::FourShift4),
+        "4*256" | "4x256" => Some(Bits::FourShift8),
+        "4*4096" | "4x4096" => Some(Bits::FourShift12),
+        "8" => Some(Bits::Eight),
+        "12" => Some(Bits::Twelve),
+        "16" => Some(Bits::Sixteen),
+        _ => None,
+    };
+    let custom = || {
+        let (width, seconds) = value.split_once(':')?;
+        Bits::custom(width.trim().parse().ok()?, seconds.trim().parse().ok()?)
+    };
+    preset.or_else(custom).ok_or_else(|| format!(
+        "expected 4, 8, 12, 16, 4*16, 4*256, 4*4096 or WIDTH:SECONDS with a width of {}-{} and {}-{} seconds",
+        Bits::CUSTOM_WIDTHS.start(), Bits::CUSTOM_WIDTHS.end(), Bits::CUSTOM_SECONDS.start(), Bits::CUSTOM_SECONDS.end(),
+    ))
+}
+
+fn parse_profile(value: &str) -> Result<String, String> {
+    if profiles::is_valid_name(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("expected 1-{} letters, digits, spaces, - and _", profiles::MAX_NAME_LENGTH))
+    }
+}
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse();
+    let last = || profiles::last_used().unwrap_or_else(|| profiles::DEFAULT.to_string());
+    let profile = if args.guest { Profile::Guest } else { Profile::Named(args.profile.clone().unwrap_or_else(last)) };
+    if let Err(e) = storage::install_profile(&profile) {
+        eprintln!("{}", e); // still readable once the full screen closes
+    }
+    hackerman::random::install_from_args();
+    if args.plain {
+        return run_plain(&args);
+    }
+    #[cfg(feature = "audio")]
+    hackerman::audio::start();
     let mut terminal = ratatui::init();
-    let result = run_app(&mut terminal);
+    execute!(std::io::stdout(), EnableMouseCapture, EnableFocusChange)?;
+    let result = run_app(&mut terminal, args);
+    let _ = execute!(std::io::stdout(), DisableFocusChange, DisableMouseCapture);
     ratatui::restore();
     result
 }
@@ -21,11 +127,31 @@ fn main() -> color_eyre::Result<()> {
 struct StartMenuState {
     items: Vec<(String, Bits)>,
     list_state: ListState,
+    editor: Option<SettingsForm>, // bit width and time of the custom entry, open after choosing it
+    typed_answers: bool, // type the decimal value instead of choosing it, switched with T
+    base: Base, // how the numbers are written, switched with B
+    quiz: Quiz, // conversions, bitwise operations or signed numbers, switched with Q
+    practice: bool, // no timer, score or lives, switched with Z
+    mode: Mode, // lives, time attack or career, switched with M
+    range: NumberRange, // the numbers conversions ask, switched with R
+    seed: Option<u64>, // from --seed, draws the puzzles of the next game
+    export: Option<PathBuf>, // from --export, the results file of the session
+    results: Option<SessionResults>, // games played so far, carried into the results file of the next
 }
 
 impl StartMenuState {
     fn new() -> Self {
-        let items = vec![
+        let saved = migration::load(&MENU).ok().flatten().unwrap_or_default();
+        let last = saved.lines().find_map(|line| line.strip_prefix("difficulty=")?.trim().parse().ok()).and_then(Bits::from_high_score_key);
+        let typed_answers = saved.lines().any(|line| line.trim() == "answers=typed");
+        let base = saved.lines().find_map(|line| line.strip_prefix("base=")?.trim().parse().ok()).unwrap_or_default();
+        let quiz = saved.lines().find_map(|line| line.strip_prefix("quiz=")?.trim().parse().ok()).unwrap_or_default();
+        let practice = saved.lines().any(|line| line.trim() == "practice=true");
+        let mode = saved.lines().find_map(|line| line.strip_prefix("mode=")?.trim().parse().ok()).unwrap_or_default();
+        let range = saved.lines().find_map(|line| line.strip_prefix("range=")?.trim().parse().ok()).unwrap_or_default();
+        let custom = last.clone().filter(Bits::is_custom).unwrap_or(Bits::Custom { width: 8, seconds: 12 });
+        let mut items = vec![
+            ("daily      (8 bits, same for all)".to_string(), Bits::Eight),
             ("easy       (4 bits)".to_string(), Bits::Four),
             ("easy+16    (4 bits*16)".to_string(), Bits::FourShift4),
             ("easy+256   (4 bits*256)".to_string(), Bits::FourShift8),
@@ -34,7 +160,11 @@ impl StartMenuState {
             ("master     (12 bits)".to_string(), Bits::Twelve),
             ("insane     (16 bits)".to_string(), Bits::Sixteen),
         ];
-        Self { items, list_state: ListState::default().with_selected(Some(4)) } // default to normal (8 bits)
+        items.push((custom_label(&custom), custom));
+        let selected = last
+            .and_then(|last| items.iter().enumerate().skip(DAILY_ITEM + 1).find(|(_, (_, bits))| bits.high_score_key() == last.high_score_key()))
+            .map_or(5, |(index, _)| index); // default to normal (8 bits)
+        Self { items, list_state: ListState::default().with_selected(Some(selected)), editor: None, typed_answers, base, quiz, practice, mode, range, seed: None, export: None, results: None }
     }
     fn selected_index(&self) -> usize {
         self.list_state.selected().unwrap_or(0)
@@ -42,12 +172,71 @@ impl StartMenuState {
     fn selected_bits(&self) -> Bits {
         self.items[self.selected_index()].1.clone()
     }
+    fn daily_selected(&self) -> bool {
+        self.selected_index() == DAILY_ITEM
+    }
     fn select_next(&mut self) {
         self.list_state.select_next();
+        sound::emit(SoundEvent::MenuMove);
     }
     fn select_previous(&mut self) {
         self.list_state.select_previous();
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    /// The difficulty the preview shows: the selected entry, or the custom one being edited.
+    fn previewed_bits(&self) -> Bits {
+        self.editor.as_ref().and_then(edited_bits).unwrap_or_else(|| self.selected_bits())
+    }
+
+    fn open_editor(&mut self) {
+        let Bits::Custom { width, seconds } = self.selected_bits() else { return };
+        self.editor = Some(SettingsForm::new(vec![
+            Field::slider("width", "Bit width", width as i64, *Bits::CUSTOM_WIDTHS.start() as i64, *Bits::CUSTOM_WIDTHS.end() as i64, 1),
+            Field::slider("seconds", "Time per round", seconds as i64, *Bits::CUSTOM_SECONDS.start() as i64, *Bits::CUSTOM_SECONDS.end() as i64, 1).unit(" s"),
+        ]));
+    }
+
+    /// Remember `bits` for the next launch and start a game with it.
+    fn start(&mut self, bits: Bits) -> AppState {
+        sound::emit(SoundEvent::MenuSelect);
+        AppState::Playing(self.new_game(bits))
+    }
+
+    /// A game of `bits` with the choices of the menu, which are remembered for the next launch.
+    fn new_game(&mut self, bits: Bits) -> BinaryNumbersGame {
+        let answers = if self.typed_answers { "typed" } else { "choose" };
+        let _ = migration::save(&MENU, &format!(
+            "difficulty={}\nanswers={}\nbase={}\nquiz={}\npractice={}\nmode={}\nrange={}\n",
+            bits.high_score_key(), answers, self.base, self.quiz, self.practice, self.mode, self.range,
+        ));
+        let mut game = BinaryNumbersGame::new(bits).with_base(self.base).with_quiz(self.quiz).with_untimed(self.practice).with_mode(self.mode).with_range(self.range);
+        if let Some(seed) = self.seed.take() { game = game.with_seed(seed); }
+        self.session(game)
     }
+
+    /// Today's daily challenge: 8 bit binary conversions with lives, whatever else the menu says.
+    fn start_daily(&mut self) -> AppState {
+        sound::emit(SoundEvent::MenuSelect);
+        AppState::Playing(self.session(BinaryNumbersGame::new(Bits::Eight).with_daily(stats::today())))
+    }
+
+    /// The choices that hold for every game of the session: answer mode and results file.
+    fn session(&mut self, mut game: BinaryNumbersGame) -> BinaryNumbersGame {
+        if let Some(path) = &self.export { game = game.with_export(path.clone()); }
+        if let Some(results) = self.results.take() { game = game.with_results(results); }
+        game.with_typed_answers(self.typed_answers)
+    }
+}
+
+/// The custom difficulty set in the editor.
+fn edited_bits(editor: &SettingsForm) -> Option<Bits> {
+    let number = |id| editor.field(id).map(|field| field.number() as u32);
+    Bits::custom(number("width")?, number("seconds")?)
+}
+
+fn custom_label(bits: &Bits) -> String {
+    format!("custom     ({} bits, {} s)", bits.to_int(), bits.base_time())
 }
 
 enum AppState {
@@ -57,12 +246,55 @@ enum AppState {
 }
 
 fn handle_start_input(state: &mut StartMenuState, key: KeyEvent) -> Option<AppState> {
+    if let Some(editor) = &mut state.editor {
+        match key.code {
+            KeyCode::Enter => {
+                let bits = edited_bits(editor)?;
+                state.items.last_mut()?.0 = custom_label(&bits);
+                state.items.last_mut()?.1 = bits.clone();
+                state.editor = None;
+                return Some(state.start(bits));
+            }
+            KeyCode::Esc => state.editor = None,
+            _ => { editor.handle_key(key); }
+        }
+        return None;
+    }
     match key.code {
         KeyCode::Up => state.select_previous(),
         KeyCode::Down => state.select_next(),
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            state.typed_answers = !state.typed_answers;
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            state.base = state.base.next();
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            state.quiz = state.quiz.next();
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Char('z') | KeyCode::Char('Z') => {
+            state.practice = !state.practice;
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            state.mode = state.mode.next();
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            state.range = state.range.next();
+            sound::emit(SoundEvent::MenuMove);
+        }
+        KeyCode::Enter if state.daily_selected() => return Some(state.start_daily()),
+        KeyCode::Enter if state.selected_bits().is_custom() => {
+            sound::emit(SoundEvent::MenuSelect);
+            state.open_editor();
+        }
         KeyCode::Enter => {
             let bits = state.selected_bits();
-            return Some(AppState::Playing(BinaryNumbersGame::new(bits)));
+            return Some(state.start(bits));
         }
         KeyCode::Esc => return Some(AppState::Exit),
         _ => {}
@@ -83,24 +315,39 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
 
     let list_width = 2 + max_len; // marker + space + label
     let list_height = upper_labels.len() as u16;
+    // the preview sits right of the list when it fits
+    let preview_width = if area.width >= list_width + PREVIEW_GAP + PREVIEW_WIDTH { PREVIEW_WIDTH } else { 0 };
+    let menu_width = list_width + if preview_width > 0 { PREVIEW_GAP + preview_width } else { 0 };
+    let menu_height = if preview_width > 0 { list_height.max(PREVIEW_HEIGHT) } else { list_height };
 
     // Vertical spacing between ASCII art and list
     let spacing: u16 = 3;
-    let total_height = ascii_height + spacing + list_height;
+    // the leaderboard goes below the menu when there is room for it
+    let board_height = if area.height >= ascii_height + spacing + menu_height + 1 + LEADERBOARD_HEIGHT { LEADERBOARD_HEIGHT } else { 0 };
+    let total_height = ascii_height + spacing + menu_height + if board_height > 0 { 1 + board_height } else { 0 };
 
     // Center vertically & horizontally
     let start_y = area.y + area.height.saturating_sub(total_height) / 2;
     let ascii_x = area.x + area.width.saturating_sub(ascii_width) / 2;
-    let list_x = area.x + area.width.saturating_sub(list_width) / 2;
+    let list_x = area.x + area.width.saturating_sub(menu_width) / 2;
     let ascii_y = start_y;
     let list_y = ascii_y + ascii_height + spacing;
 
     // Define rects (clamp to area)
     let ascii_area = Rect::new(ascii_x, ascii_y, ascii_width.min(area.width), ascii_height.min(area.height));
     let list_area = Rect::new(list_x, list_y, list_width.min(area.width), list_height.min(area.height.saturating_sub(list_y - area.y)));
+    let preview_area = Rect::new(list_x + list_width + PREVIEW_GAP, list_y, preview_width, PREVIEW_HEIGHT.min(area.height.saturating_sub(list_y - area.y)));
+
+    let board_area = Rect::new(list_x, list_y + menu_height + 1, menu_width.min(area.width), board_height);
 
     // Render ASCII art
     ascii_widget.render(ascii_area, buf);
+    if preview_width > 0 {
+        render_preview(state, preview_area, buf);
+    }
+    if board_height > 0 {
+        render_leaderboard(state, board_area, buf);
+    }
 
     // Palette for menu flair
     let palette = [
@@ -128,35 +375,321 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
     ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut state.list_state);
 }
 
-fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()> {
-    let mut app_state = AppState::Start(StartMenuState::new());
+const PREVIEW_WIDTH: u16 = 40;
+const PREVIEW_HEIGHT: u16 = 13;
+const PREVIEW_GAP: u16 = 4;
+const LEADERBOARD_HEIGHT: u16 = 12;
+
+/// The best runs of the previewed difficulty in the chosen mode.
+fn render_leaderboard(state: &StartMenuState, area: Rect, buf: &mut Buffer) {
+    if state.daily_selected() {
+        let block = Block::bordered().border_type(BorderType::Rounded).border_style(Style::default().fg(Color::DarkGray)).title(format!(" Best {} · 8 bits, lives ", LEADERBOARD_SIZE));
+        let inner = block.inner(area).inner(Margin { horizontal: 1, vertical: 0 });
+        block.render(area, buf);
+        Paragraph::new(binary_numbers::leaderboard_lines(&binary_numbers::leaderboard(Bits::Eight, Base::Binary, Quiz::Convert, Mode::Lives), None)).render(inner, buf);
+        return;
+    }
+    let bits = state.previewed_bits();
+    let mode = if state.practice { "practice".to_string() } else { state.mode.label() };
+    let title = match state.mode {
+        Mode::Career if !state.practice => format!(" Best {} · career ", LEADERBOARD_SIZE),
+        _ => format!(" Best {} · {} bits, {} ", LEADERBOARD_SIZE, bits.to_int(), mode),
+    };
+    let block = Block::bordered().border_type(BorderType::Rounded).border_style(Style::default().fg(Color::DarkGray)).title(title);
+    let inner = block.inner(area).inner(Margin { horizontal: 1, vertical: 0 });
+    block.render(area, buf);
+
+    let lines = match () {
+        _ if state.practice => vec![Line::from(Span::styled("Practice runs are not ranked", Style::default().fg(Color::DarkGray)))],
+        _ if bits.is_custom() && state.mode != Mode::Career => vec![Line::from(Span::styled("Custom difficulties are not ranked", Style::default().fg(Color::DarkGray)))],
+        _ if state.range != NumberRange::Full => vec![Line::from(Span::styled("Number ranges are not ranked", Style::default().fg(Color::DarkGray)))],
+        _ => binary_numbers::leaderboard_lines(&binary_numbers::leaderboard(bits, state.base, state.quiz, state.mode), None),
+    };
+    Paragraph::new(lines).render(inner, buf);
+}
+
+/// What the previewed difficulty asks of you, with the custom editor on top while it is open.
+fn render_preview(state: &StartMenuState, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered().border_type(BorderType::Rounded).border_style(Style::default().fg(Color::DarkGray)).title(" Preview ");
+    let inner = block.inner(area).inner(Margin { horizontal: 1, vertical: 0 });
+    block.render(area, buf);
+    if state.daily_selected() {
+        return render_daily_preview(state, inner, buf);
+    }
+
+    let bits = state.previewed_bits();
+    let best = if state.practice { "not kept in practice".to_string() } else if (bits.is_custom() && state.mode != Mode::Career) || state.range != NumberRange::Full { "unranked".to_string() } else { binary_numbers::get_high_score(bits.clone(), state.base, state.quiz, state.mode).to_string() };
+    let row = |label: &str, value: String| Line::from(vec![Span::styled(format!("{:<14}", label), Style::default().fg(Color::Gray)), Span::styled(value, Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD))]);
+    let mut lines = vec![
+        row("Bit width", if state.mode == Mode::Career { "4 to 16, by streak".to_string() } else { format!("{}", bits.to_int()) }),
+        row("Mode", if state.practice { "practice".to_string() } else { state.mode.label() }),
+        row("Time/round", match state.mode {
+            _ if state.practice => "untimed".to_string(),
+            Mode::TimeAttack(seconds) => format!("{} s for all rounds", seconds),
+            Mode::Career => "by level, less on a streak".to_string(),
+            Mode::Lives => format!("{:.0} s, less on a streak", scoring::rules().round_time(bits.base_time(), 0)),
+        }),
+        row("Quiz", state.quiz.label().to_string()),
+        row("Base", if state.quiz == Quiz::Convert { state.base.label() } else { "binary" }.to_string()),
+        row("Numbers", if state.quiz == Quiz::Convert { state.range.label() } else { NumberRange::Full.label() }),
+        row("Answers", if state.typed_answers && state.quiz.answers_in_decimal(state.base) { "typed".to_string() } else if state.quiz != Quiz::Bitwise { format!("{} choices", bits.suggestion_count()) } else { "choices in binary".to_string() }),
+        row("Best score", best),
+        Line::from(""),
+    ];
+    let hint = match (&state.editor, bits.is_custom()) {
+        (Some(_), _) => "←→ change  Enter play  Esc back",
+        (None, true) => "Enter edit  T typed  Z practice",
+        (None, false) => "Enter play  T typed  Z practice",
+    };
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+    if state.editor.is_none() {
+        lines.push(Line::from(Span::styled("B base  Q quiz  M mode  R range", Style::default().fg(Color::DarkGray))));
+    }
+
+    let editor_height = state.editor.as_ref().map_or(0, |editor| editor.height() + 1);
+    let [editor_area, details_area] = Layout::vertical([Constraint::Length(editor_height), Constraint::Fill(1)]).areas(inner);
+    if let Some(editor) = &state.editor {
+        editor.render(editor_area, buf);
+    }
+    Paragraph::new(lines).render(details_area, buf);
+}
+
+/// Today's challenge, the streak and a calendar of the past weeks.
+fn render_daily_preview(state: &StartMenuState, area: Rect, buf: &mut Buffer) {
+    let (today, results) = (stats::today(), binary_numbers::daily_results());
+    let streak = binary_numbers::daily_streak(&results, today);
+    let row = |label: &str, value: String| Line::from(vec![Span::styled(format!("{:<14}", label), Style::default().fg(Color::Gray)), Span::styled(value, Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD))]);
+    let mut lines = vec![
+        row("Today", report::format_timestamp(today as u64 * 86_400)[..10].to_string()),
+        row("Status", results.get(&today).map_or("not played yet".to_string(), |score| format!("done, best {}", score))),
+        row("Streak", format!("{} day{}", streak, if streak == 1 { "" } else { "s" })),
+        Line::from(""),
+    ];
+    lines.extend(binary_numbers::daily_calendar_lines(&results, today));
+    let hint = if state.typed_answers { "Enter play  T typed (on)" } else { "Enter play  T typed" };
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+    Paragraph::new(lines).render(area, buf);
+}
+
+/// Options of the game in progress, opened with O or F2: the game stands still meanwhile and
+/// goes on with the same score once they close.
+struct OptionsOverlay {
+    form: SettingsForm,
+    widths: Vec<Bits>, // the choices of the bit width field
+}
+
+impl OptionsOverlay {
+    const WIDTH: u16 = 44;
+
+    fn new(game: &BinaryNumbersGame, theme: Theme) -> Self {
+        let mut widths = Bits::PRESETS.to_vec();
+        if game.bits().is_custom() { widths.push(game.bits().clone()); }
+        let mut fields = Vec::new();
+        // a career and the daily challenge decide the width themselves
+        if game.can_change_bits() {
+            let selected = widths.iter().position(|bits| bits.high_score_key() == game.bits().high_score_key()).unwrap_or(0);
+            let labels = widths.iter().map(|bits| if bits.is_custom() { format!("{} bits, {} s", bits.to_int(), bits.base_time()) } else { bits.label().to_string() }).collect();
+            fields.push(Field::choice("bits", "Bit width", labels, selected).hint("unranked"));
+        }
+        fields.push(Field::toggle("hints", "Hints", game.hints()).hint("unranked"));
+        fields.push(Field::toggle("sound", "Sound", !config::get().muted));
+        fields.push(Field::toggle("colorblind", "Colorblind", config::get().colorblind).hint("✓ and ✗"));
+        fields.push(Field::choice("palette", "Palette", Theme::iter().map(|theme| theme.to_string()).collect(), Theme::iter().position(|other| other == theme).unwrap_or(0)));
+        Self { form: SettingsForm::new(fields), widths }
+    }
+
+    /// Returns false once the options close.
+    fn handle_key(&mut self, key: KeyEvent, game: &mut BinaryNumbersGame, theme: &mut Theme) -> bool {
+        match key.code {
+            KeyCode::Esc | KeyCode::F(2) | KeyCode::Char('o') | KeyCode::Char('O') => return false,
+            _ => if let Some(id) = self.form.handle_key(key) { self.apply(id, game, theme); }
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, game: &mut BinaryNumbersGame, theme: &mut Theme) {
+        if let Some(id) = self.form.handle_mouse(mouse) { self.apply(id, game, theme); }
+    }
+
+    fn apply(&self, id: &str, game: &mut BinaryNumbersGame, theme: &mut Theme) {
+        let Some(field) = self.form.field(id) else { return };
+        match id {
+            "bits" => game.change_bits(self.widths[field.selected()].clone()),
+            "hints" => game.set_hints(field.is_on()),
+            "sound" => config::update(|c| c.muted = !field.is_on()),
+            "colorblind" => config::update(|c| c.colorblind = field.is_on()),
+            "palette" => *theme = Theme::iter().nth(field.selected()).unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let height = self.form.height() + 4;
+        let [middle] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(Self::WIDTH)]).flex(Flex::Center).areas(middle);
+        Clear.render(popup, buf);
+        let block = Block::bordered()
+            .border_type(BorderType::Double)
+            .title(" Options ")
+            .title_style(Style::default().fg(Color::LightCyan))
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+        let [form_area, _, hint_area] = Layout::vertical([Constraint::Length(self.form.height()), Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+        self.form.render(form_area, buf);
+        Paragraph::new(Line::from(Span::styled("↑↓ choose  ←→ change  Esc close", Style::default().fg(Color::DarkGray)))).render(hint_area, buf);
+    }
+}
+
+/// Over a paused game: the screen dimmed and a banner saying how to go on.
+fn render_paused(area: Rect, buf: &mut Buffer) {
+    buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+    let lines = vec![
+        Line::from(Span::styled("PAUSED", Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled("P resume", Style::default().fg(Color::DarkGray))),
+    ];
+    let [middle] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)]).flex(Flex::Center).areas(area);
+    let [banner] = Layout::horizontal([Constraint::Length(24)]).flex(Flex::Center).areas(middle);
+    Clear.render(banner, buf);
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(Block::bordered().border_type(BorderType::Double).border_style(Style::default().fg(Color::LightYellow)))
+        .render(banner, buf);
+}
+
+/// The start menu with the choices of the command line applied.
+fn menu_from_args(args: &Args) -> StartMenuState {
+    let mut menu = StartMenuState::new();
+    menu.seed = args.seed;
+    menu.export = args.export.clone();
+    if let Some(mode) = args.mode { menu.mode = mode; }
+    if args.no_timer { menu.practice = true; }
+    if let Some(range) = args.range { menu.range = range; }
+    menu
+}
+
+/// The start menu, or straight into a game when the command line picked one.
+fn first_state(args: &Args) -> AppState {
+    let mut menu = menu_from_args(args);
+    if args.bits.is_none() && args.mode.is_none() && !args.no_timer { return AppState::Start(menu); }
+    let bits = args.bits.clone().unwrap_or_else(|| menu.selected_bits());
+    menu.start(bits)
+}
+
+/// `--plain`: one game over stdin and stdout, one line per question and answer. No raw mode and
+/// no escape codes, so it works in dumb terminals and pipelines. An empty line skips, `q` or the
+/// end of the input quits.
+fn run_plain(args: &Args) -> color_eyre::Result<()> {
+    let mut menu = menu_from_args(args);
+    let bits = args.bits.clone().unwrap_or_else(|| menu.selected_bits());
+    let mut game = menu.new_game(bits);
+    let result = play_plain(&mut game, std::io::stdin().lock(), std::io::stdout().lock());
+    hackerman::stats::flush();
+    match result {
+        // the reading end of a pipe is gone, e.g. `binbreak --plain | head`
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+fn play_plain(game: &mut BinaryNumbersGame, input: impl BufRead, mut out: impl Write) -> std::io::Result<()> {
+    let mut lines = input.lines();
+    writeln!(out, "binbreak: type the answer and press Enter. An empty line skips, q quits.")?;
+    'game: while !game.is_over() {
+        writeln!(out)?;
+        writeln!(out, "{}", game.plain_question())?;
+        let asked = Instant::now();
+        loop {
+            match game.plain_time_left() {
+                Some(left) => write!(out, "[{:.0}s] > ", left)?,
+                None => write!(out, "> ")?,
+            }
+            out.flush()?;
+            let Some(line) = lines.next().transpose()? else { break 'game };
+            let line = line.trim();
+            if line.eq_ignore_ascii_case("q") { break 'game; }
+            match game.plain_answer(line, asked.elapsed().as_secs_f64()) {
+                Ok(outcome) => {
+                    writeln!(out, "{}  ({})", outcome, game.plain_status())?;
+                    break;
+                }
+                Err(problem) => writeln!(out, "{}", problem)?,
+            }
+        }
+    }
+    if game.is_over() {
+        writeln!(out)?;
+        for line in game.plain_summary() {
+            writeln!(out, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_app(terminal: &mut ratatui::DefaultTerminal, args: Args) -> color_eyre::Result<()> {
+    let mut app_state = first_state(&args);
+    let mut theme = args.theme;
+    let mut options: Option<OptionsOverlay> = None; // open over the game in progress
+    let mut paused = false; // the game stands still under a banner until P is pressed again
     let mut last_frame_time = Instant::now();
-    let target_frame_duration = std::time::Duration::from_millis(33); // ~30 FPS
+    let events = EventLoop::start(Duration::from_millis(33)); // ~30 FPS
+    let mut focused = true; // in the background the game stands still and only input wakes the loop
 
     while !matches!(app_state, AppState::Exit) {
         let now = Instant::now();
-        let dt = now - last_frame_time;
+        let dt = if focused { now - last_frame_time } else { Duration::ZERO };
         last_frame_time = now;
 
-        terminal.draw(|f| match &mut app_state {
-            AppState::Start(menu) => render_start_screen(menu, f.area(), f.buffer_mut()),
-            AppState::Playing(game) => f.render_widget(&mut *game, f.area()),
-            AppState::Exit => {}
+        terminal.draw(|f| {
+            match &mut app_state {
+                AppState::Start(menu) => render_start_screen(menu, f.area(), f.buffer_mut()),
+                AppState::Playing(game) => {
+                    f.render_widget(&mut *game, f.area());
+                    if let Some(options) = &options { options.render(f.area(), f.buffer_mut()); }
+                    if paused { render_paused(f.area(), f.buffer_mut()); }
+                }
+                AppState::Exit => {}
+            }
+            theme.apply(f.buffer_mut());
         })?;
 
         // Advance game if playing
         if let AppState::Playing(game) = &mut app_state {
-            game.run(dt.as_secs_f64());
+            if options.is_none() && !paused { game.run(dt.as_secs_f64()); }
             if game.is_exit_intended() {
-                app_state = AppState::Start(StartMenuState::new());
+                hackerman::stats::flush();
+                let mut menu = StartMenuState::new();
+                menu.export = args.export.clone();
+                menu.results = Some(game.results());
+                app_state = AppState::Start(menu);
                 continue;
             }
         }
 
-        // handle input
-        let poll_timeout = std::cmp::min(dt, target_frame_duration);
-        if event::poll(poll_timeout)? {
-            if let Event::Key(key) = event::read()? {
+        // wait for the next tick or input
+        let message = loop {
+            match events.next() {
+                Message::Tick if !focused => {}
+                message => break message,
+            }
+        };
+        if let Message::Input(event) = message {
+            match event {
+                Event::FocusLost => focused = false,
+                Event::FocusGained => {
+                    focused = true;
+                    last_frame_time = Instant::now();
+                }
+                _ => {}
+            }
+            if let (Event::Mouse(mouse), AppState::Playing(game)) = (&event, &mut app_state) {
+                match options.as_mut() {
+                    _ if paused => {}
+                    Some(overlay) => overlay.handle_mouse(*mouse, game, &mut theme),
+                    None => game.handle_game_mouse(*mouse),
+                }
+            }
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
                     match key.code {
                         // global exit via Ctrl+C
@@ -170,7 +703,14 @@ fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()> {
                                 handle_start_input(&mut menu, key).unwrap_or(AppState::Start(menu))
                             }
                             AppState::Playing(mut game) => {
-                                game.handle_game_input(key);
+                                match options.as_mut() {
+                                    // a paused game only takes the key that resumes it
+                                    _ if paused => paused = !matches!(key.code, KeyCode::Char('p') | KeyCode::Char('P')),
+                                    None if matches!(key.code, KeyCode::Char('p') | KeyCode::Char('P')) && !game.is_over() => paused = true,
+                                    Some(overlay) => if !overlay.handle_key(key, &mut game, &mut theme) { options = None; },
+                                    None if matches!(key.code, KeyCode::Char('o') | KeyCode::Char('O') | KeyCode::F(2)) => options = Some(OptionsOverlay::new(&game, theme)),
+                                    None => game.handle_game_input(key),
+                                }
                                 AppState::Playing(game)
                             }
                             AppState::Exit => AppState::Exit,
@@ -179,54 +719,11 @@ fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()> {
                 }
             }
         }
-
-        // cap frame rate
-        let frame_duration = last_frame_time.elapsed();
-        if frame_duration < target_frame_duration {
-            thread::sleep(target_frame_duration - frame_duration);
-        }
     }
+    hackerman::stats::flush();
     Ok(())
 }
 
 fn ascii_art_cells() -> AsciiCells {
-    let art = r#"
- ,,        ,,              ,,
-*MM        db             *MM                                `7MM
- MM                        MM                                  MM
- MM,dMMb.`7MM  `7MMpMMMb.  MM,dMMb.`7Mb,od8 .gP"Ya   ,6"Yb.    MM  ,MP'
- MM    `Mb MM    MM    MM  MM    `Mb MM' "',M'   Yb 8)   MM    MM ;Y
- MM     M8 MM    MM    MM  MM     M8 MM    8M""""""  ,pm9MM    MM;Mm
- MM.   ,M9 MM    MM    MM  MM.   ,M9 MM    YM.    , 8M   MM    MM `Mb.
- P^YbmdP'.JMML..JMML  JMML.P^YbmdP'.JMML.   `Mbmmd' `Moo9^Yo..JMML. YA.
-    "#.nice();
-
-    let colors = r#"
- ,,        ,,              ,,
-*MM        db             *MM                                `7MM
- MM                        MM                                  MM
- MM,dMMb.`7MM  `7MMpMMMb.  MM,dMMb.`7Mb,od8 .gP"Ya   ,6"Yb.    MM  ,MP'
- MM    `Mb MM    MM    MM  MM    `Mb MM' "',M'   Yb 8)   MM    MM ;Y
- MM     M8 MM    MM    MM  MM     M8 MM    8M""""""  ,pm9MM    MM;Mm
- MM.   ,M9 MM    MM    MM  MM.   ,M9 MM    YM.    , 8M   MM    MM `Mb.
- P^YbmdP'.JMML..JMML  JMML.P^YbmdP'.JMML.   `Mbmmd' `Moo9^Yo..JMML. YA.
-    "#.nice();
-
-    let color_map = HashMap::from([
-        ('M', Color::White),
-        ('b', Color::LightYellow),
-        ('d', Color::LightCyan),
-        ('Y', Color::LightGreen),
-        ('8', Color::LightMagenta),
-        ('*', Color::Magenta),
-        ('`', Color::Cyan),
-        ('6', Color::Green),
-        ('9', Color::Red),
-        ('(', Color::Blue),
-        (')', Color::Blue),
-        (' ', Color::Black),
-    ]);
-
-    let default_color = Color::LightBlue;
-    AsciiCells::from(art.to_string(), colors.to_string(), &color_map, default_color)
+    loader::load("binbreak").unwrap_or_else(|_| AsciiCells::new(Vec::new()))
 }
\ No newline at end of file
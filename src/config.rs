@@ -0,0 +1,233 @@
+use crate::games::registry::Category;
+use crate::geocoding::Place;
+use crate::keymap::Preset;
+use crate::storage::migration::{self, Schema};
+use std::sync::Mutex;
+use strum_macros::{Display, EnumIter, EnumString};
+
+const SCHEMA: Schema = Schema { namespace: "config", key: "settings", steps: &[migration::unversioned] };
+
+/// What is drawn behind all widgets.
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Background {
+    /// Nothing, the terminal's own background (and transparency) shows through.
+    None,
+    /// A solid dark fill.
+    Solid,
+    /// A sparse dot pattern on the solid fill.
+    Pattern,
+}
+
+/// How the weather screen writes temperatures.
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn convert(self, celsius: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+
+    /// `14°C`, `57°F`
+    pub fn format(self, celsius: f64) -> String {
+        format!("{:.0}{}", self.convert(celsius), self.symbol())
+    }
+}
+
+/// How the weather screen writes wind speeds.
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum WindUnit {
+    Kmh,
+    Mph,
+    Ms,
+}
+
+impl WindUnit {
+    pub fn convert(self, kmh: f64) -> f64 {
+        match self {
+            WindUnit::Kmh => kmh,
+            WindUnit::Mph => kmh / 1.609_344,
+            WindUnit::Ms => kmh / 3.6,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            WindUnit::Kmh => "km/h",
+            WindUnit::Mph => "mph",
+            WindUnit::Ms => "m/s",
+        }
+    }
+
+    /// `22 km/h`, `6.1 m/s`; metres per second keep a decimal, they are small numbers.
+    pub fn format(self, kmh: f64) -> String {
+        match self {
+            WindUnit::Ms => format!("{:.1} {}", self.convert(kmh), self.symbol()),
+            _ => format!("{:.0} {}", self.convert(kmh), self.symbol()),
+        }
+    }
+}
+
+/// User settings shared by all screens, persisted as `key=value` lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub volume: u8, // percent, 0..=100
+    pub music_volume: u8, // percent, 0..=100
+    pub muted: bool,
+    pub reduce_motion: bool, // no screen shake or flashes
+    pub colorblind: bool, // right and wrong in blue and orange with ✓ and ✗, see [`crate::ui::theme::palette`]
+    pub question_window: u8, // rounds in which a puzzle answer is not repeated, 0 allows repeats
+    pub font: String, // big-text font, see [`crate::fonts::available`]
+    pub background: Background,
+    pub max_width: u16, // columns the UI may use before it is centered, 0 for the whole terminal
+    pub safe_margin: u8, // rows kept free around the UI, and twice as many columns
+    pub presentation: bool, // no debug and control bars, for screenshots and recordings
+    pub do_not_disturb: bool, // notifications skip the toast while a game of `quiet_during` runs
+    pub quiet_during: Vec<Category>,
+    pub session_summary: bool, // what was played is shown for a moment when hackerman quits
+    pub auto_advance_ms: u16, // quizzes move on this long after a correct answer, 0 waits for Enter
+    pub location: Option<Place>, // where the weather is shown for, picked on the weather screen
+    pub saved_locations: Vec<Place>, // every place picked so far, the weather screen cycles through them
+    pub controls: Preset, // extra keys for the arrows and Enter
+    pub temperature_unit: TemperatureUnit,
+    pub wind_unit: WindUnit,
+    pub weather_ttl_minutes: u16, // a cached weather report is shown this long before it is fetched again
+    pub weather_offline: bool, // the weather screen shows cached reports only
+    pub weather_alert_toasts: bool, // a weather alert for the location is shown as a toast on start
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            volume: 80,
+            music_volume: 40,
+            muted: false,
+            reduce_motion: false,
+            colorblind: false,
+            question_window: 3,
+            font: crate::fonts::Font::PIXEL.to_string(),
+            background: Background::None,
+            max_width: 0,
+            safe_margin: 0,
+            presentation: false,
+            do_not_disturb: false,
+            quiet_during: vec![Category::Games],
+            session_summary: true,
+            auto_advance_ms: 0,
+            location: None,
+            saved_locations: Vec::new(),
+            controls: Preset::Arrows,
+            temperature_unit: TemperatureUnit::Celsius,
+            wind_unit: WindUnit::Kmh,
+            weather_ttl_minutes: 30,
+            weather_offline: false,
+            weather_alert_toasts: true,
+        }
+    }
+}
+
+impl Config {
+    pub const MAX_QUESTION_WINDOW: u8 = 8;
+    /// Choices offered for [`Config::max_width`].
+    pub const MAX_WIDTH_STEPS: [u16; 5] = [0, 100, 120, 160, 200];
+    pub const MAX_AUTO_ADVANCE_MS: u16 = 3000;
+    pub const MAX_SAFE_MARGIN: u8 = 4;
+    pub const MAX_WEATHER_TTL_MINUTES: u16 = 240;
+
+    /// Seconds a quiz waits after a correct answer before the next round starts on its own,
+    /// `None` when it waits for Enter. Wrong answers always wait, so they can be looked at.
+    pub fn auto_advance(&self) -> Option<f64> {
+        (self.auto_advance_ms > 0).then(|| self.auto_advance_ms as f64 / 1000.0)
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut config = Self::default();
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let value = value.trim();
+            match key.trim() {
+                "volume" => if let Ok(v) = value.parse::<u8>() { config.volume = v.min(100) },
+                "music_volume" => if let Ok(v) = value.parse::<u8>() { config.music_volume = v.min(100) },
+                "muted" => config.muted = value == "true",
+                "reduce_motion" => config.reduce_motion = value == "true",
+                "colorblind" => config.colorblind = value == "true",
+                "question_window" => if let Ok(v) = value.parse::<u8>() { config.question_window = v.min(Config::MAX_QUESTION_WINDOW) },
+                "font" => config.font = value.to_string(),
+                "background" => if let Ok(v) = value.parse() { config.background = v },
+                "max_width" => if let Ok(v) = value.parse::<u16>() { config.max_width = v },
+                "safe_margin" => if let Ok(v) = value.parse::<u8>() { config.safe_margin = v.min(Config::MAX_SAFE_MARGIN) },
+                "presentation" => config.presentation = value == "true",
+                "do_not_disturb" => config.do_not_disturb = value == "true",
+                "quiet_during" => config.quiet_during = value.split(',').filter_map(|c| c.trim().parse().ok()).collect(),
+                "session_summary" => config.session_summary = value == "true",
+                "location" => config.location = value.parse().ok(),
+                "saved_location" => config.saved_locations.extend(value.parse().ok()),
+                "controls" => if let Ok(v) = value.parse() { config.controls = v },
+                "temperature_unit" => if let Ok(v) = value.parse() { config.temperature_unit = v },
+                "wind_unit" => if let Ok(v) = value.parse() { config.wind_unit = v },
+                "weather_ttl_minutes" => if let Ok(v) = value.parse::<u16>() { config.weather_ttl_minutes = v.min(Config::MAX_WEATHER_TTL_MINUTES) },
+                "weather_offline" => config.weather_offline = value == "true",
+                "weather_alert_toasts" => config.weather_alert_toasts = value == "true",
+                "auto_advance_ms" => if let Ok(v) = value.parse::<u16>() { config.auto_advance_ms = v.min(Config::MAX_AUTO_ADVANCE_MS) },
+                _ => {} // unknown keys are ignored so older builds can read newer files
+            }
+        }
+        // files from before there were several locations only know the one
+        if config.saved_locations.is_empty() {
+            config.saved_locations.extend(config.location.clone());
+        }
+        config
+    }
+
+    fn serialize(&self) -> String {
+        let place_line = |key: &str, place: &Place| format!("{}={}|{}|{}|{}\n", key, place.name, place.country, place.latitude, place.longitude);
+        let location = self.location.as_ref().map_or(String::new(), |place| place_line("location", place))
+            + &self.saved_locations.iter().map(|place| place_line("saved_location", place)).collect::<String>();
+        format!(
+            "volume={}\nmusic_volume={}\nmuted={}\nreduce_motion={}\ncolorblind={}\nquestion_window={}\nfont={}\nbackground={}\nmax_width={}\nsafe_margin={}\npresentation={}\ndo_not_disturb={}\nquiet_during={}\nsession_summary={}\nauto_advance_ms={}\ncontrols={}\ntemperature_unit={}\nwind_unit={}\nweather_ttl_minutes={}\nweather_offline={}\nweather_alert_toasts={}\n{}",
+            self.volume, self.music_volume, self.muted, self.reduce_motion, self.colorblind, self.question_window, self.font, self.background,
+            self.max_width, self.safe_margin, self.presentation, self.do_not_disturb, self.quiet_during.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(","),
+            self.session_summary, self.auto_advance_ms, self.controls, self.temperature_unit, self.wind_unit,
+            self.weather_ttl_minutes, self.weather_offline, self.weather_alert_toasts, location,
+        )
+    }
+
+    fn load() -> Self {
+        // a failed migration leaves a backup behind, the defaults keep the app usable
+        migration::load(&SCHEMA).ok().flatten().map(|contents| Self::parse(&contents)).unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let _ = migration::save(&SCHEMA, &self.serialize());
+    }
+}
+
+static CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+
+/// Current settings, loaded from storage on first use.
+pub fn get() -> Config {
+    CONFIG.lock().unwrap().get_or_insert_with(Config::load).clone()
+}
+
+/// Change settings and persist them right away.
+pub fn update(change: impl FnOnce(&mut Config)) {
+    let mut guard = CONFIG.lock().unwrap();
+    let config = guard.get_or_insert_with(Config::load);
+    change(config);
+    config.save();
+}
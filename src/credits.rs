@@ -0,0 +1,43 @@
+//! Author, license and source of every file embedded from `assets`. `build.rs` checks that
+//! each of them is listed.
+
+const CREDITS: &str = include_str!("../assets/credits.txt");
+
+/// Where one embedded file comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credit {
+    pub path: String, // relative to `assets`, `art/tree.art`
+    pub author: String,
+    pub license: String,
+    pub source: String, // URL
+}
+
+/// One `[path]` section per file with `author`, `license` and `source` lines.
+fn parse(contents: &str) -> Vec<Credit> {
+    let mut credits: Vec<Credit> = Vec::new();
+    for line in contents.lines().map(str::trim).filter(|line| !line.starts_with('#')) {
+        if let Some(path) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            credits.push(Credit { path: path.to_string(), author: String::new(), license: String::new(), source: String::new() });
+            continue;
+        }
+        let (Some(credit), Some((key, value))) = (credits.last_mut(), line.split_once('=')) else { continue };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "author" => credit.author = value,
+            "license" => credit.license = value,
+            "source" => credit.source = value,
+            _ => {}
+        }
+    }
+    credits
+}
+
+/// Every embedded file, in the order of `assets/credits.txt`.
+pub fn all() -> Vec<Credit> {
+    parse(CREDITS)
+}
+
+/// The credit of the file at `path` inside `assets`.
+pub fn of(path: &str) -> Option<Credit> {
+    all().into_iter().find(|credit| credit.path == path)
+}
@@ -0,0 +1,83 @@
+use crate::config;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::mem;
+use std::sync::Mutex;
+
+/// Short visual feedback drawn by the app around the main widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Effect {
+    /// Jiggle the widget area left and right.
+    Shake,
+    /// Tint the widget border.
+    Flash(Color),
+}
+
+impl Effect {
+    fn duration(self) -> f64 {
+        match self {
+            Effect::Shake => 0.3,
+            Effect::Flash(_) => 0.4,
+        }
+    }
+}
+
+struct Active {
+    effect: Effect,
+    remaining: f64,
+}
+
+static ACTIVE: Mutex<Vec<Active>> = Mutex::new(Vec::new());
+
+/// Start an effect, restarting a running one of the same kind. Does nothing with reduce motion enabled.
+pub fn trigger(effect: Effect) {
+    if config::get().reduce_motion {
+        return;
+    }
+    let mut active = ACTIVE.lock().unwrap();
+    active.retain(|a| mem::discriminant(&a.effect) != mem::discriminant(&effect));
+    active.push(Active { effect, remaining: effect.duration() });
+}
+
+pub fn wrong_answer() {
+    trigger(Effect::Shake);
+    trigger(Effect::Flash(Color::Red));
+}
+
+pub fn game_over() {
+    trigger(Effect::Shake);
+    trigger(Effect::Flash(Color::LightMagenta));
+}
+
+pub fn advance(dt: f64) {
+    let mut active = ACTIVE.lock().unwrap();
+    active.iter_mut().for_each(|a| a.remaining -= dt);
+    active.retain(|a| a.remaining > 0.0);
+}
+
+pub fn is_active() -> bool {
+    !ACTIVE.lock().unwrap().is_empty()
+}
+
+pub fn clear() {
+    ACTIVE.lock().unwrap().clear();
+}
+
+/// `area` moved sideways by the running shake, kept inside `bounds`.
+pub fn shaken(area: Rect, bounds: Rect) -> Rect {
+    let offset = ACTIVE.lock().unwrap().iter()
+        .find(|a| a.effect == Effect::Shake)
+        .map(|a| if (a.remaining * 30.0) as i64 % 2 == 0 { 1 } else { -1 })
+        .unwrap_or(0);
+    let max_x = (bounds.x + bounds.width).saturating_sub(area.width);
+    let x = (area.x as i32 + offset).clamp(bounds.x as i32, max_x.max(bounds.x) as i32) as u16;
+    Rect { x, ..area }
+}
+
+/// Color of the running flash, blinking off every other step so it reads as a pulse.
+pub fn flash_color() -> Option<Color> {
+    ACTIVE.lock().unwrap().iter().find_map(|a| match a.effect {
+        Effect::Flash(color) if (a.remaining * 10.0) as i64 % 2 == 0 => Some(color),
+        _ => None,
+    })
+}
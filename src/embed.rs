@@ -0,0 +1,300 @@
+//! Running a single hackerman screen outside the hackerman shell.
+//!
+//! [`WidgetHost`] mounts any [`MainScreenWidget`] inside another ratatui application: the host
+//! application decides where it goes in its layout and forwards input to it.
+//!
+//! ```ignore
+//! let mut game = WidgetHost::from_registry("Binary Numbers").unwrap();
+//! loop {
+//!     game.tick();
+//!     terminal.draw(|frame| {
+//!         let [sidebar, main] = Layout::horizontal([Constraint::Length(30), Constraint::Fill(1)]).areas(frame.area());
+//!         frame.render_widget(&my_sidebar, sidebar);
+//!         frame.render_widget(&game, main);
+//!     })?;
+//!     if event::poll(Duration::from_millis(16))? {
+//!         let event = event::read()?;
+//!         if !game.handle_event(&event) { /* the host's own keys */ }
+//!     }
+//!     if game.is_finished() { break; }
+//! }
+//! ```
+//!
+//! [`run_widget`] does all of that full screen for programs that only want to show one game.
+
+use crate::events::{EventLoop, Message};
+use crate::games::main_screen_widget::MainScreenWidget;
+use crate::games::registry::GameRegistry;
+use crate::utils::ToDuration;
+use crossterm::event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant; // std's clock panics in the browser
+
+/// A [`MainScreenWidget`] with the bookkeeping the hackerman shell normally does: frame timing,
+/// filtering input and knowing when the widget is done.
+pub struct WidgetHost {
+    widget: Box<dyn MainScreenWidget>,
+    last_tick: Option<Instant>,
+    focused: bool, // false after the terminal reported losing focus, the widget stands still meanwhile
+}
+
+impl WidgetHost {
+    pub fn new(widget: Box<dyn MainScreenWidget>) -> Self {
+        Self { widget, last_tick: None, focused: true }
+    }
+
+    /// One of the built-in screens by its menu name, e.g. "Binary Numbers". Placeholders give `None`.
+    pub fn from_registry(name: &str) -> Option<Self> {
+        let registry = GameRegistry::with_builtin_games();
+        let game = registry.position(name).and_then(|index| registry.get(index))?;
+        game.create().map(Self::new)
+    }
+
+    /// Advance by the wall clock time since the previous call; the first call only starts the clock.
+    /// Time the terminal spent unfocused is skipped, see [`WidgetHost::handle_event`].
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let dt = self.last_tick.filter(|_| self.focused).map_or(0.0, |last| (now - last).as_secs_f64());
+        self.last_tick = Some(now);
+        self.advance(dt);
+    }
+
+    /// Advance by `dt` seconds, for hosts that keep their own clock. Finished widgets stand still.
+    pub fn advance(&mut self, dt: f64) {
+        if !self.is_finished() {
+            self.widget.run(dt);
+        }
+    }
+
+    /// Forward a terminal event. Returns whether the widget took it; key releases, resizes
+    /// and everything after the widget finished are left to the host. Focus events are noted to
+    /// pause the widget while the terminal is in the background and left to the host as well.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        match event {
+            Event::FocusLost => { self.focused = false; return false; }
+            Event::FocusGained => { self.focused = true; return false; }
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.widget.handle_input(*key),
+            Event::Mouse(mouse) => self.widget.handle_mouse(*mouse),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The widget asked to be closed, e.g. its own quit key was pressed.
+    pub fn is_finished(&self) -> bool {
+        self.widget.is_exit_intended()
+    }
+
+    /// Whether the widget changes on its own; hosts that only redraw on input should also redraw while this is true.
+    pub fn needs_redraw(&self) -> bool {
+        self.widget.needs_redraw()
+    }
+
+    /// The controls of the widget as (key, description) pairs, for the host's help screen.
+    pub fn help_lines(&self) -> Vec<(String, String)> {
+        self.widget.help_lines()
+    }
+
+    pub fn widget(&self) -> &dyn MainScreenWidget {
+        self.widget.as_ref()
+    }
+
+    pub fn widget_mut(&mut self) -> &mut dyn MainScreenWidget {
+        self.widget.as_mut()
+    }
+
+    pub fn into_inner(self) -> Box<dyn MainScreenWidget> {
+        self.widget
+    }
+}
+
+impl Widget for &WidgetHost {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.widget.render_ref(area, buf);
+    }
+}
+
+/// Settings for [`run_widget`].
+#[derive(Clone, Debug)]
+pub struct EmbedOptions {
+    pub frame_time: Duration,
+    /// Closes the widget in addition to the widget's own way out; `None` leaves it to the widget.
+    pub quit_key: Option<KeyCode>,
+    pub mouse: bool,
+}
+
+impl Default for EmbedOptions {
+    fn default() -> Self {
+        Self { frame_time: 16.milliseconds(), quit_key: Some(KeyCode::Esc), mouse: true }
+    }
+}
+
+impl EmbedOptions {
+    pub fn frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    pub fn quit_key(mut self, quit_key: Option<KeyCode>) -> Self {
+        self.quit_key = quit_key;
+        self
+    }
+
+    pub fn mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+}
+
+/// Show `widget` full screen until it finishes or the quit key is pressed, then restore the terminal.
+///
+/// ```ignore
+/// let game = BinaryNumbersGame::new(Bits::Eight);
+/// hackerman::embed::run_widget(Box::new(game), EmbedOptions::default().quit_key(Some(KeyCode::Char('q'))))?;
+/// ```
+pub fn run_widget(widget: Box<dyn MainScreenWidget>, options: EmbedOptions) -> color_eyre::Result<()> {
+    let mut terminal = ratatui::init();
+    execute!(std::io::stdout(), EnableFocusChange)?;
+    if options.mouse {
+        execute!(std::io::stdout(), EnableMouseCapture)?;
+    }
+    let mut host = WidgetHost::new(widget);
+    let events = EventLoop::start(options.frame_time);
+    let result = (|| -> color_eyre::Result<()> {
+        while !host.is_finished() {
+            match events.next() {
+                Message::Input(Event::Key(key)) if key.kind == KeyEventKind::Press && Some(key.code) == options.quit_key => break,
+                Message::Input(event) => { host.handle_event(&event); }
+                Message::Tick if !host.focused => continue, // in the background only input wakes it
+                Message::Tick | Message::Wake => {}
+            }
+            host.tick();
+            terminal.draw(|frame| frame.render_widget(&host, frame.area()))?;
+        }
+        Ok(())
+    })();
+    if options.mouse {
+        let _ = execute!(std::io::stdout(), DisableMouseCapture);
+    }
+    let _ = execute!(std::io::stdout(), DisableFocusChange);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::main_screen_widget::WidgetRef;
+    use crate::games::hanoi;
+    use crate::storage;
+    use crossterm::event::{KeyEvent, KeyEventState, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::{Constraint, Layout};
+    use ratatui::widgets::Paragraph;
+    use ratatui::Terminal;
+
+    /// Counts the keys and the time it was given and prints them; `q` closes it.
+    #[derive(Default)]
+    struct Counter {
+        keys: String,
+        elapsed: f64,
+        done: bool,
+    }
+
+    impl WidgetRef for Counter {
+        fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+            Paragraph::new(format!("[{}] {:.1}s", self.keys, self.elapsed)).render(area, buf);
+        }
+    }
+
+    impl MainScreenWidget for Counter {
+        fn run(&mut self, dt: f64) { self.elapsed += dt; }
+        fn handle_input(&mut self, input: KeyEvent) {
+            match input.code {
+                KeyCode::Char('q') => self.done = true,
+                KeyCode::Char(ch) => self.keys.push(ch),
+                _ => {}
+            }
+        }
+        fn is_exit_intended(&self) -> bool { self.done }
+    }
+
+    fn key(ch: char, kind: KeyEventKind) -> Event {
+        Event::Key(KeyEvent { code: KeyCode::Char(ch), modifiers: KeyModifiers::NONE, kind, state: KeyEventState::NONE })
+    }
+
+    /// Draw `host` to the right of a sidebar, the way the module docs lay it out.
+    fn draw(host: &WidgetHost) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(24, 2)).unwrap();
+        terminal.draw(|frame| {
+            let [sidebar, main] = Layout::horizontal([Constraint::Length(8), Constraint::Fill(1)]).areas(frame.area());
+            frame.render_widget(Paragraph::new("sidebar"), sidebar);
+            frame.render_widget(host, main);
+        }).unwrap();
+        terminal
+    }
+
+    #[test]
+    fn the_widget_is_drawn_where_the_host_puts_it() {
+        let mut host = WidgetHost::new(Box::new(Counter::default()));
+        assert!(host.handle_event(&key('a', KeyEventKind::Press)));
+        assert!(!host.handle_event(&key('b', KeyEventKind::Release)));
+        assert!(!host.handle_event(&Event::Resize(80, 24)));
+        host.advance(1.5);
+        draw(&host).backend().assert_buffer_lines([
+            "sidebar [a] 1.5s        ",
+            "                        ",
+        ]);
+    }
+
+    #[test]
+    fn the_clock_stands_still_while_the_terminal_is_in_the_background() {
+        let mut host = WidgetHost::new(Box::new(Counter::default()));
+        host.tick();
+        assert!(!host.handle_event(&Event::FocusLost));
+        std::thread::sleep(Duration::from_millis(300));
+        host.tick();
+        draw(&host).backend().assert_buffer_lines(["sidebar [] 0.0s         ", "                        "]);
+        assert!(!host.handle_event(&Event::FocusGained));
+        std::thread::sleep(Duration::from_millis(20));
+        host.tick();
+        // counted from the tick in the background on, not from the first one
+        let drawn: String = draw(&host).backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        let elapsed: f64 = drawn.trim().trim_start_matches("sidebar [] ").trim_end_matches('s').parse().unwrap();
+        assert!(elapsed < 0.3, "{} seconds counted", elapsed);
+    }
+
+    #[test]
+    fn finished_widgets_leave_everything_to_the_host() {
+        let mut host = WidgetHost::new(Box::new(Counter::default()));
+        host.handle_event(&key('q', KeyEventKind::Press));
+        assert!(host.is_finished());
+        assert!(!host.handle_event(&key('a', KeyEventKind::Press)));
+        host.advance(2.0);
+        draw(&host).backend().assert_buffer_lines(["sidebar [] 0.0s         ", "                        "]);
+    }
+
+    #[test]
+    fn built_in_screens_by_their_menu_name() {
+        storage::install_for_tests();
+        assert!(WidgetHost::from_registry("Dino Jump").is_none()); // a placeholder
+        assert!(WidgetHost::from_registry("Chess").is_none());
+        let host = WidgetHost::from_registry(hanoi::NAME).unwrap();
+        assert_eq!(host.widget().get_name(), "HanoiGame");
+        assert!(!host.help_lines().is_empty());
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|frame| frame.render_widget(&host, frame.area())).unwrap();
+        let drawn: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(drawn.trim() != "", "nothing drawn");
+    }
+}
@@ -0,0 +1,63 @@
+use crossterm::event::{self, Event};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Everything that can wake up the UI loop.
+#[derive(Debug)]
+pub enum Message {
+    /// A terminal event, read on the input thread.
+    Input(Event),
+    /// Frame timer, games advance their state on it.
+    Tick,
+    /// Background work (network, timers) finished and wants the screen refreshed.
+    Wake,
+}
+
+/// An input thread and a tick producer feeding one channel, so the UI blocks on a single `recv`
+/// instead of polling crossterm with a timeout.
+pub struct EventLoop {
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    tick_pending: Arc<AtomicBool>,
+}
+
+impl EventLoop {
+    pub fn start(tick_rate: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let tick_pending = Arc::new(AtomicBool::new(false));
+
+        let input = sender.clone();
+        thread::spawn(move || {
+            while let Ok(event) = event::read() {
+                if input.send(Message::Input(event)).is_err() { break; }
+            }
+        });
+
+        // at most one tick waits in the channel, a slow frame must not queue up a burst of catch-up frames
+        let (ticks, pending) = (sender.clone(), tick_pending.clone());
+        thread::spawn(move || loop {
+            if !pending.swap(true, Ordering::AcqRel) && ticks.send(Message::Tick).is_err() { break; }
+            thread::sleep(tick_rate);
+        });
+
+        Self { sender, receiver, tick_pending }
+    }
+
+    /// A handle for background work to push messages into the loop, usually [`Message::Wake`].
+    pub fn sender(&self) -> Sender<Message> {
+        self.sender.clone()
+    }
+
+    /// Block until the next message arrives.
+    pub fn next(&self) -> Message {
+        // the loop keeps a sender itself, so the channel never disconnects
+        let message = self.receiver.recv().expect("event channel closed");
+        if let Message::Tick = message {
+            self.tick_pending.store(false, Ordering::Release);
+        }
+        message
+    }
+}
@@ -0,0 +1,294 @@
+use crate::config;
+use crate::storage;
+use crate::utils::{AsciiCell, AsciiCells};
+use ratatui::style::{Color, Modifier};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Built-in 3x5 pixel glyphs, rows separated by `|`. Lowercase is drawn as uppercase.
+const PIXEL_GLYPHS: [(char, &str); 45] = [
+    ('A', ".#.|#.#|###|#.#|#.#"), ('B', "##.|#.#|##.|#.#|##."), ('C', ".##|#..|#..|#..|.##"),
+    ('D', "##.|#.#|#.#|#.#|##."), ('E', "###|#..|##.|#..|###"), ('F', "###|#..|##.|#..|#.."),
+    ('G', ".##|#..|#.#|#.#|.##"), ('H', "#.#|#.#|###|#.#|#.#"), ('I', "###|.#.|.#.|.#.|###"),
+    ('J', "..#|..#|..#|#.#|.#."), ('K', "#.#|#.#|##.|#.#|#.#"), ('L', "#..|#..|#..|#..|###"),
+    ('M', "#.#|###|###|#.#|#.#"), ('N', "##.|#.#|#.#|#.#|#.#"), ('O', ".#.|#.#|#.#|#.#|.#."),
+    ('P', "##.|#.#|##.|#..|#.."), ('Q', ".#.|#.#|#.#|##.|.##"), ('R', "##.|#.#|##.|#.#|#.#"),
+    ('S', ".##|#..|.#.|..#|##."), ('T', "###|.#.|.#.|.#.|.#."), ('U', "#.#|#.#|#.#|#.#|###"),
+    ('V', "#.#|#.#|#.#|#.#|.#."), ('W', "#.#|#.#|###|###|#.#"), ('X', "#.#|#.#|.#.|#.#|#.#"),
+    ('Y', "#.#|#.#|.#.|.#.|.#."), ('Z', "###|..#|.#.|#..|###"),
+    ('0', "###|#.#|#.#|#.#|###"), ('1', ".#.|##.|.#.|.#.|###"), ('2', "##.|..#|.#.|#..|###"),
+    ('3', "##.|..#|.#.|..#|##."), ('4', "#.#|#.#|###|..#|..#"), ('5', "###|#..|##.|..#|##."),
+    ('6', ".##|#..|###|#.#|###"), ('7', "###|..#|.#.|.#.|.#."), ('8', "###|#.#|###|#.#|###"),
+    ('9', "###|#.#|###|..#|##."),
+    (' ', "...|...|...|...|..."), (':', "...|.#.|...|.#.|..."), ('.', "...|...|...|...|.#."),
+    ('-', "...|...|###|...|..."), ('!', ".#.|.#.|.#.|...|.#."), ('?', "##.|..#|.#.|...|.#."),
+    ('/', "..#|..#|.#.|#..|#.."), ('%', "#.#|..#|.#.|#..|#.#"), ('°', "##.|##.|...|...|..."),
+];
+
+/// How the built-in pixel font maps pixels to terminal cells.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PixelSize {
+    /// One cell per pixel.
+    Full,
+    /// Two pixels stacked in one cell, using half blocks.
+    HalfHeight,
+}
+
+/// A font loaded from a FIGlet `.flf` file.
+///
+/// Fonts asking for kerning or smushing get their letters moved together until they touch;
+/// the smushing rules that merge touching characters are not applied.
+#[derive(Clone, Debug)]
+pub struct FigletFont {
+    height: usize,
+    hardblank: char, // a blank that keeps letters apart, drawn as a space
+    kerning: bool,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl FigletFont {
+    /// Read the printable ASCII glyphs of a FIGlet font; code-tagged extra characters are skipped.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut lines = source.lines();
+        let header = lines.next().ok_or("empty font file")?;
+        let signature = header.strip_prefix("flf2a").ok_or("not a FIGlet font (missing flf2a signature)")?;
+        let hardblank = signature.chars().next().ok_or("missing hardblank character")?;
+        // height, baseline, max length, old layout (-1 for full width), comment lines
+        let fields: Vec<i64> = header.split_whitespace().skip(1).map_while(|field| field.parse().ok()).collect();
+        let (Some(&height), Some(&layout), Some(&comment_lines)) = (fields.first(), fields.get(3), fields.get(4)) else {
+            return Err("incomplete header".to_string());
+        };
+        if height <= 0 {
+            return Err("font height must be at least 1".to_string());
+        }
+        let (height, comment_lines) = (height as usize, comment_lines.max(0) as usize);
+
+        let mut lines = lines.skip(comment_lines);
+        let mut glyphs = HashMap::new();
+        for code in 32u8..=126 {
+            let mut rows = Vec::with_capacity(height);
+            for _ in 0..height {
+                let line = lines.next().ok_or_else(|| format!("glyph {:?} is cut short", code as char))?;
+                let endmark = line.chars().last().unwrap_or(' ');
+                rows.push(line.trim_end_matches(endmark).to_string());
+            }
+            let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+            let rows = rows.into_iter().map(|row| format!("{:<width$}", row)).collect();
+            glyphs.insert(code as char, rows);
+        }
+        Ok(Self { height, hardblank, kerning: layout >= 0, glyphs })
+    }
+}
+
+/// The font big-text banners are drawn with: titles, clocks and countdowns.
+#[derive(Clone, Debug)]
+pub enum Font {
+    Pixel(PixelSize),
+    Figlet { name: String, font: FigletFont },
+}
+
+impl Font {
+    pub const PIXEL: &'static str = "pixel";
+    pub const PIXEL_HALF: &'static str = "pixel-half";
+
+    /// The name stored in the config, see [`available`].
+    pub fn name(&self) -> &str {
+        match self {
+            Font::Pixel(PixelSize::Full) => Font::PIXEL,
+            Font::Pixel(PixelSize::HalfHeight) => Font::PIXEL_HALF,
+            Font::Figlet { name, .. } => name,
+        }
+    }
+
+    /// `text` as rows of equal width, ready to be printed line by line.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        self.layout(text).iter().map(|row| row.iter().map(|(ch, _)| *ch).collect()).collect()
+    }
+
+    /// `text` as cells, letter `i` colored with `colors[i % colors.len()]`. Blanks are cells too,
+    /// so the text covers what is behind it.
+    pub fn cells(&self, text: &str, colors: &[Color]) -> AsciiCells {
+        let cells = self.layout(text).into_iter().enumerate().flat_map(|(y, row)| {
+            row.into_iter().enumerate().map(move |(x, (ch, letter))| AsciiCell {
+                ch,
+                x: x as u16,
+                y: y as u16,
+                color: letter.and_then(|i| colors.get(i % colors.len().max(1))).copied().unwrap_or(Color::Reset),
+                background: None,
+                modifier: Modifier::empty(),
+            })
+        });
+        AsciiCells::new(cells.collect())
+    }
+
+    /// Rows of characters, each with the index of the letter of `text` it was drawn for (`None` for blanks).
+    fn layout(&self, text: &str) -> Vec<Vec<(char, Option<usize>)>> {
+        let (height, hardblank, kerning) = match self {
+            Font::Pixel(PixelSize::Full) => (5, None, false),
+            Font::Pixel(PixelSize::HalfHeight) => (3, None, false),
+            Font::Figlet { font, .. } => (font.height, Some(font.hardblank), font.kerning),
+        };
+        let mut rows: Vec<Vec<(char, Option<usize>)>> = vec![Vec::new(); height];
+        for (index, ch) in text.chars().enumerate() {
+            let glyph = self.glyph(ch);
+            // kerning: move the letter left until it touches the text so far in some row
+            let overlap = if kerning {
+                rows.iter().zip(&glyph)
+                    .map(|(row, line)| leading_blanks(row.iter().rev().map(|(ch, _)| *ch)) + leading_blanks(line.chars()))
+                    .min()
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+            for (row, line) in rows.iter_mut().zip(&glyph) {
+                let from_row = leading_blanks(row.iter().rev().map(|(ch, _)| *ch)).min(overlap);
+                row.truncate(row.len() - from_row);
+                row.extend(line.chars().skip(overlap - from_row).map(|ch| match ch {
+                    ' ' => (' ', None),
+                    ch if Some(ch) == hardblank => (ch, None),
+                    ch => (ch, Some(index)),
+                }));
+            }
+        }
+        // hardblanks stop kerning and are only turned into spaces once all letters are placed
+        for (ch, _) in rows.iter_mut().flatten().filter(|(ch, _)| Some(*ch) == hardblank) {
+            *ch = ' ';
+        }
+        rows
+    }
+
+    /// Rows of one letter; unknown letters are drawn as `?`.
+    fn glyph(&self, ch: char) -> Vec<String> {
+        match self {
+            Font::Pixel(size) => pixel_glyph(ch, *size),
+            Font::Figlet { font, .. } => font.glyphs.get(&ch).or_else(|| font.glyphs.get(&'?')).cloned()
+                .unwrap_or_else(|| vec![String::new(); font.height]),
+        }
+    }
+}
+
+fn leading_blanks(chars: impl Iterator<Item = char>) -> usize {
+    chars.take_while(|ch| *ch == ' ').count()
+}
+
+/// A pixel letter followed by an empty column that separates it from the next one.
+fn pixel_glyph(ch: char, size: PixelSize) -> Vec<String> {
+    let glyphs: HashMap<char, &str> = PIXEL_GLYPHS.into_iter().collect();
+    let glyph = glyphs.get(&ch.to_ascii_uppercase()).or_else(|| glyphs.get(&'?')).unwrap();
+    let pixel_rows: Vec<Vec<bool>> = glyph.split('|')
+        .map(|row| row.chars().map(|pixel| pixel == '#').chain([false]).collect())
+        .collect();
+
+    match size {
+        PixelSize::Full => pixel_rows.iter()
+            .map(|row| row.iter().map(|&on| if on { '█' } else { ' ' }).collect())
+            .collect(),
+        PixelSize::HalfHeight => pixel_rows.chunks(2)
+            .map(|pair| (0..pair[0].len())
+                .map(|x| match (pair[0][x], pair.get(1).is_some_and(|below| below[x])) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect())
+            .collect(),
+    }
+}
+
+/// Where users drop `.flf` files.
+pub fn fonts_dir() -> PathBuf {
+    storage::data_dir().join("fonts")
+}
+
+/// The built-in pixel fonts followed by the FIGlet fonts in [`fonts_dir`], sorted by name.
+pub fn available() -> Vec<String> {
+    let mut figlet: Vec<String> = std::fs::read_dir(fonts_dir()).into_iter().flatten().flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "flf"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .collect();
+    figlet.sort();
+    [Font::PIXEL.to_string(), Font::PIXEL_HALF.to_string()].into_iter().chain(figlet).collect()
+}
+
+/// The font called `name`; unknown or broken fonts fall back to the full size pixel font.
+pub fn load(name: &str) -> Font {
+    match name {
+        Font::PIXEL_HALF => Font::Pixel(PixelSize::HalfHeight),
+        Font::PIXEL | "" => Font::Pixel(PixelSize::Full),
+        _ => std::fs::read_to_string(fonts_dir().join(format!("{}.flf", name))).ok()
+            .and_then(|source| FigletFont::parse(&source).ok())
+            .map(|font| Font::Figlet { name: name.to_string(), font })
+            .unwrap_or(Font::Pixel(PixelSize::Full)),
+    }
+}
+
+/// The font last read for the settings, with the name it was read for.
+static CURRENT: Mutex<Option<(String, Font)>> = Mutex::new(None);
+
+/// The font chosen in the settings. Read from disk again only after the setting changed.
+pub fn current() -> Font {
+    let name = config::get().font;
+    let mut guard = CURRENT.lock().unwrap();
+    match guard.as_ref() {
+        Some((loaded, font)) if *loaded == name => font.clone(),
+        _ => guard.insert((name.clone(), load(&name))).1.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A two rows high FIGlet font with `layout` in its header, drawing `H` and `I` and every other
+    /// letter as a box of `.`.
+    fn font_source(layout: i32) -> String {
+        let mut source = format!("flf2a$ 2 1 6 {} 1\nA test font, this is its comment\n", layout);
+        for code in 32u8..=126 {
+            let rows = match code as char {
+                ' ' => ["$@", "$@@"],
+                'H' => ["| | @", "|-|  @@"],
+                'I' => ["  |@", " $|@@"],
+                _ => ["..@", "..@@"],
+            };
+            source.push_str(&format!("{}\n{}\n", rows[0], rows[1]));
+        }
+        source
+    }
+
+    #[test]
+    fn glyphs_lose_their_endmarks_and_get_one_width() {
+        let font = FigletFont::parse(&font_source(0)).unwrap();
+        assert_eq!((font.height, font.hardblank, font.kerning), (2, '$', true));
+        assert_eq!(font.glyphs.len(), 95);
+        assert_eq!(font.glyphs[&'H'], ["| | ", "|-|  "].map(|row| format!("{:<5}", row)));
+        assert_eq!(font.glyphs[&'I'], ["  |", " $|"]);
+    }
+
+    #[test]
+    fn kerning_moves_letters_together_until_they_touch() {
+        let font = |layout| Font::Figlet { name: "test".to_string(), font: FigletFont::parse(&font_source(layout)).unwrap() };
+        // the hardblank of the I keeps it from moving further into the H
+        assert_eq!(font(0).render("HI"), ["| | |", "|-| |"]);
+        assert_eq!(font(-1).render("HI"), ["| |    |", "|-|    |"]);
+        assert_eq!(font(0).render("H~"), ["| |..", "|-|.."]);
+    }
+
+    #[test]
+    fn broken_fonts_are_refused() {
+        assert_eq!(FigletFont::parse("").unwrap_err(), "empty font file");
+        assert_eq!(FigletFont::parse("tlf2a$ 2 1 6 0 1").unwrap_err(), "not a FIGlet font (missing flf2a signature)");
+        assert_eq!(FigletFont::parse("flf2a$ 2 1").unwrap_err(), "incomplete header");
+        assert_eq!(FigletFont::parse("flf2a$ 0 1 6 0 0").unwrap_err(), "font height must be at least 1");
+        let cut = font_source(0).lines().take(100).collect::<Vec<_>>().join("\n");
+        assert_eq!(FigletFont::parse(&cut).unwrap_err(), "glyph 'Q' is cut short");
+    }
+
+    #[test]
+    fn pixel_letters_are_drawn_in_blocks() {
+        assert_eq!(Font::Pixel(PixelSize::Full).render("1"), [" █  ", "██  ", " █  ", " █  ", "███ "]);
+        assert_eq!(Font::Pixel(PixelSize::HalfHeight).render("i"), ["▀█▀ ", " █  ", "▀▀▀ "]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::credits::{self, Credit};
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+
+/// Name, version and license of hackerman, and who made the art and word lists it ships with.
+pub struct AboutMain {
+    exit_intended: bool,
+    credits: Vec<Credit>,
+    scroll: u16, // lines scrolled down
+}
+
+impl AboutMain {
+    pub fn new() -> Self {
+        Self { exit_intended: false, credits: credits::all(), scroll: 0 }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(format!("hackerman {}", env!("CARGO_PKG_VERSION")).light_green().bold()),
+            Line::from(env!("CARGO_PKG_DESCRIPTION").white()),
+            Line::from(format!("by {}, {} license", env!("CARGO_PKG_AUTHORS").replace(':', ", "), env!("CARGO_PKG_LICENSE")).gray()),
+            Line::default(),
+            Line::from("Bundled assets".light_cyan().bold()),
+        ];
+        for credit in &self.credits {
+            lines.push(Line::default());
+            lines.push(Line::from(credit.path.clone().white()));
+            lines.push(Line::from(vec![format!("  {}", credit.author).gray(), format!(", {}", credit.license).light_yellow()]));
+            lines.push(Line::from(format!("  {}", credit.source).dark_gray()));
+        }
+        lines
+    }
+}
+
+impl MainScreenWidget for AboutMain {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        let last = self.lines().len().saturating_sub(1) as u16;
+        match input.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        vec![("Up Down PgUp PgDn".to_string(), "scroll".to_string())]
+    }
+}
+
+impl WidgetRef for AboutMain {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title("About").title_alignment(Alignment::Center);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(self.lines()).scroll((self.scroll, 0)).render(inner.inner(Margin { horizontal: 1, vertical: 0 }), buf);
+    }
+}
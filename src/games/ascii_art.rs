@@ -1,41 +1,43 @@
+use crate::art::loader::{self, ArtSource};
+use crate::credits::{self, Credit};
 use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
-use crate::utils::{AsciiArtWidget, AsciiCells};
+use crate::utils::{AsciiAnimation, AsciiCells, LoopMode};
 use crossterm::event::KeyEvent;
-use nice_trim::NiceTrim;
 use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
-use ratatui::prelude::{Color, Widget};
-use std::collections::HashMap;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Line, Stylize, Widget};
 
 pub struct AsciiArtMain {
     exit_intended: bool,
-    timer: f64,
+    animation: AsciiAnimation,
+    credit: Option<Credit>, // of the art shown, below it
 }
 
 impl AsciiArtMain {
     pub fn new() -> Self {
-        Self { exit_intended: false, timer: 0.0 }
+        let mut animation = AsciiAnimation::new(LoopMode::PingPong);
+        if let Ok(tree) = loader::source("tree") {
+            animation = animation.frame(tree.cells(), 1.5).frame(shimmer(tree), 0.4);
+        }
+        Self { exit_intended: false, animation, credit: credits::of("art/tree.art") }
     }
 }
 
 impl WidgetRef for AsciiArtMain {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        let time_per_frame = 0.25;
-
-        let frame = get_frame_1();
-        let centered = frame.get_centered_area(area);
-
-        AsciiArtWidget::new(frame).render(centered, buf);
+        let [art, caption] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        if let Some(frame) = self.animation.current_frame() {
+            frame.render(frame.get_centered_area(art), buf);
+        }
+        if let Some(credit) = &self.credit {
+            Line::from(format!("{} by {}, {}", credit.path, credit.author, credit.license).dark_gray()).centered().render(caption, buf);
+        }
     }
 }
 
 impl MainScreenWidget for AsciiArtMain {
     fn run(&mut self, dt: f64) {
-        self.timer += dt;
-
-        if self.timer > 10.0 {
-            self.timer -= 10.0;
-        }
+        self.animation.tick(dt);
     }
 
     fn handle_input(&mut self, _input: KeyEvent) -> () {}
@@ -43,46 +45,13 @@ impl MainScreenWidget for AsciiArtMain {
     fn is_exit_intended(&self) -> bool { self.exit_intended }
 }
 
-fn get_frame_1() -> AsciiCells {
-    let art = r"
-                          ,@@@@@@@,
-                  ,,,.   ,@@@@@@/@@,  .oo8888o.
-               ,&%%&%&&%,@@@@@/@@@@@@,:8888\88/8o
-              ,%&\%&&%&&%,@@@\@@/@@@88\88888/88'
-              %&&%&%&/%&&%@@\@@/ /@@@88888\88888'
-              %&&%/ %&%%&&@@\ V /@@' `88\8 `/88'
-              `&%\ ` /%&'    |.|        \ '|8'
-                  |o|        | |         | |
-                  |.|        | |         | |
-            ___ \/ ._\//_/__/  ,\_\//__\/.  \_//__
-        ".nice();
-
-    let foreground_colors = r"
-                          ,@@@@@@@,
-                  ,,,.   ,@@@@@@/@@,  .oo8888o.
-               ,&%%&%&&%,@@@@@/@Y@@@@,:8888\88/8o
-              ,%&\%&&%&&%,@@@\@Y/@@@88\88888/88'
-              %&&%&%&/%&&%@@\@Y/ /@@@88888\88888'
-              %&&%/ %&%%&&@@\ V /@@' `88\8 `/88'
-              `&%\ ` /%&'    |.|        \ '|8'
-                  |W|        | |         | |
-                  |.|        | |         | |
-            ___ B/ ._\BG_B__/  G\_BGG__B/.  \_BG__
-        ".nice();
-
-    let color_map = HashMap::from([
-        ('@', Color::LightGreen),
-        ('&', Color::Green),
-        ('%', Color::Green),
-        ('8', Color::Green),
-        ('o', Color::Green),
-        ('G', Color::Green),
-        ('B', Color::LightGreen),
-        ('W', Color::White),
-        ('Y', Color::Yellow),
-    ]);
-
-    let default_color = Color::DarkGray;
-
-    AsciiCells::from(art, foreground_colors, &color_map, default_color)
-}
\ No newline at end of file
+/// The tree with the colors of its lit and shaded leaves swapped, so the leaves shimmer.
+fn shimmer(mut tree: ArtSource) -> AsciiCells {
+    let (light, shade) = (tree.palette.get(&'@').copied(), tree.palette.get(&'&').copied());
+    for (key, color) in [('@', shade), ('&', light), ('%', light)] {
+        if let Some(color) = color {
+            tree.palette.insert(key, color);
+        }
+    }
+    tree.cells()
+}
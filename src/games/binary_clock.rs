@@ -0,0 +1,255 @@
+use crate::config;
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::keymap;
+use crate::random::{self, GameRng};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage::{self, migration::{self, Schema}};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::Rng;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::cell::Cell;
+use strum_macros::Display;
+
+/// Name under which the clock is registered; also the key of its quiz stats.
+pub const NAME: &str = "Binary Clock";
+
+const SCHEMA: Schema = Schema { namespace: "tools", key: "binary_clock", steps: &[migration::unversioned] };
+
+/// Minutes the offset from UTC changes by per key press; some time zones are half an hour off.
+const OFFSET_STEP: i64 = 30;
+
+#[derive(Display, Clone, Copy, PartialEq, Debug)]
+enum ClockMode {
+    /// One column per decimal digit, like most binary wrist watches.
+    #[strum(to_string = "BCD")]
+    Bcd,
+    /// One column each for hours, minutes and seconds.
+    Binary,
+}
+
+impl ClockMode {
+    /// Columns as (value, bits), most significant bit on top.
+    fn columns(self, (hours, minutes, seconds): (u32, u32, u32)) -> Vec<(u32, u32)> {
+        match self {
+            ClockMode::Bcd => vec![
+                (hours / 10, 2), (hours % 10, 4),
+                (minutes / 10, 3), (minutes % 10, 4),
+                (seconds / 10, 3), (seconds % 10, 4),
+            ],
+            ClockMode::Binary => vec![(hours, 5), (minutes, 6), (seconds, 6)],
+        }
+    }
+
+    /// Columns that belong together: hours, minutes and seconds.
+    fn group_size(self) -> usize {
+        match self { ClockMode::Bcd => 2, ClockMode::Binary => 1 }
+    }
+}
+
+struct Quiz {
+    time: (u32, u32, u32),
+    typed: String, // digits of hhmmss
+    result: Option<bool>,
+}
+
+/// Hours, minutes and seconds as columns of lit and dark bits. The quiz shows a random time
+/// to read and type in.
+pub struct BinaryClock {
+    mode: ClockMode,
+    offset_minutes: i64, // local time minus UTC, the clock has no time zone database
+    quiz: Option<Quiz>,
+    streak: u32,
+    advance_in: Option<f64>, // seconds until the next time comes on its own after a correct reading
+    shown_second: Cell<u64>, // of the last render, the live clock redraws once a second
+    rng: GameRng, // draws the times to read
+    exit_intended: bool,
+}
+
+impl BinaryClock {
+    pub fn new() -> Self {
+        let contents = migration::load(&SCHEMA).ok().flatten().unwrap_or_default();
+        let offset_minutes = contents.lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(key, _)| *key == "offset_minutes")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        Self { mode: ClockMode::Bcd, offset_minutes, quiz: None, streak: 0, advance_in: None, shown_second: Cell::new(0), rng: random::rng(), exit_intended: false }
+    }
+
+    /// Draw the times of the quiz from `rng`, e.g. a [`random::seeded`] one.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    fn now(&self) -> (u32, u32, u32) {
+        let seconds = (storage::unix_now() as i64 + self.offset_minutes * 60).rem_euclid(86_400) as u32;
+        (seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    fn shift_offset(&mut self, minutes: i64) {
+        self.offset_minutes = (self.offset_minutes + minutes).clamp(-12 * 60, 14 * 60);
+        let _ = migration::save(&SCHEMA, &format!("offset_minutes={}\n", self.offset_minutes));
+    }
+
+    fn new_question(&mut self) {
+        let time = (self.rng.random_range(0..24), self.rng.random_range(0..60), self.rng.random_range(0..60));
+        self.quiz = Some(Quiz { time, typed: String::new(), result: None });
+        self.advance_in = None;
+    }
+
+    fn check(&mut self) {
+        let Some(quiz) = self.quiz.as_mut().filter(|quiz| quiz.result.is_none() && quiz.typed.len() == 6) else { return };
+        let (hours, minutes, seconds) = quiz.time;
+        let correct = quiz.typed == format!("{:02}{:02}{:02}", hours, minutes, seconds);
+        quiz.result = Some(correct);
+        self.streak = if correct { self.streak + 1 } else { 0 };
+        self.advance_in = if correct { config::get().auto_advance() } else { None };
+        stats::record_round(NAME, &self.mode.to_string(), correct, self.streak);
+        sound::emit(if correct { SoundEvent::Correct } else { SoundEvent::Incorrect });
+    }
+
+    fn handle_quiz_input(&mut self, input: KeyEvent) {
+        let Some(quiz) = self.quiz.as_mut() else { return };
+        if let Some(digit) = keymap::digit(input) {
+            if quiz.result.is_none() && quiz.typed.len() < 6 { quiz.typed.push(digit) }
+            return;
+        }
+        match input.code {
+            KeyCode::Backspace if quiz.result.is_none() => { quiz.typed.pop(); }
+            KeyCode::Enter if quiz.result.is_some() => self.new_question(),
+            KeyCode::Enter => self.check(),
+            _ => {}
+        }
+    }
+}
+
+impl MainScreenWidget for BinaryClock {
+    fn run(&mut self, dt: f64) {
+        if let Some(left) = self.advance_in.as_mut().filter(|_| self.quiz.is_some()) {
+            *left -= dt;
+            if *left <= 0.0 { self.new_question(); }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Char('m') | KeyCode::Char('M') => self.mode = match self.mode { ClockMode::Bcd => ClockMode::Binary, ClockMode::Binary => ClockMode::Bcd },
+            KeyCode::Char('q') | KeyCode::Char('Q') if self.quiz.is_some() => self.quiz = None,
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.new_question(),
+            KeyCode::Char('[') if self.quiz.is_none() => self.shift_offset(-OFFSET_STEP),
+            KeyCode::Char(']') if self.quiz.is_none() => self.shift_offset(OFFSET_STEP),
+            _ => self.handle_quiz_input(input),
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    /// The live clock changes once a second; the quiz on input and when the next time comes on its own.
+    fn needs_redraw(&self) -> bool {
+        (self.quiz.is_none() && self.shown_second.get() != storage::unix_now()) || (self.quiz.is_some() && self.advance_in.is_some())
+    }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("M", "switch between BCD and pure binary"),
+            ("Q", "quiz: read a random time, or back to the clock"),
+            ("0-9 Enter", "type the time as hhmmss and check it"),
+            ("[ ]", "move the clock half an hour back / forward, for your time zone"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for BinaryClock {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        self.shown_second.set(storage::unix_now());
+        let time = self.quiz.as_ref().map_or_else(|| self.now(), |quiz| quiz.time);
+        let columns = self.mode.columns(time);
+        let rows = columns.iter().map(|(_, bits)| *bits).max().unwrap_or(0) as u16;
+
+        let [header, _, bits_area, labels_area, _, answer_area, _, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(rows),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+
+        let title = if self.quiz.is_some() { "Read the clock" } else { "Binary Clock" };
+        Paragraph::new(Line::from(vec![
+            title.yellow().bold(),
+            format!("  {}", self.mode).light_cyan(),
+            if self.quiz.is_some() { format!("  streak {}", self.streak).dark_gray() } else { format!("  UTC{}{}:{:02}", if self.offset_minutes < 0 { '-' } else { '+' }, self.offset_minutes.abs() / 60, self.offset_minutes.abs() % 60).dark_gray() },
+        ])).centered().render(header, buf);
+
+        // bit weights on the left, then the columns with a wider gap between hours, minutes and seconds
+        const COLUMN: u16 = 3;
+        const GROUP_GAP: u16 = 2;
+        let groups = columns.len() / self.mode.group_size();
+        let width = 4 + columns.len() as u16 * COLUMN + (groups as u16 - 1) * GROUP_GAP;
+        let x0 = area.x + area.width.saturating_sub(width) / 2;
+        let column_x = |index: usize| x0 + 4 + index as u16 * COLUMN + (index / self.mode.group_size()) as u16 * GROUP_GAP;
+        for row in 0..rows.min(bits_area.height) {
+            let bit = rows - 1 - row;
+            buf.set_string(x0, bits_area.y + row, format!("{:>2}", 1u32 << bit), Style::default().fg(Color::DarkGray));
+            for (index, (value, bits)) in columns.iter().enumerate() {
+                if bit >= *bits as u16 { continue; }
+                let (symbol, style) = if value >> bit & 1 == 1 { ("●", Style::default().fg(Color::LightGreen)) } else { ("○", Style::default().fg(Color::DarkGray)) };
+                buf.set_string(column_x(index), bits_area.y + row, symbol, style);
+            }
+        }
+        for (group, label) in ["h", "m", "s"].iter().enumerate() {
+            let x = column_x(group * self.mode.group_size()) + (self.mode.group_size() as u16 - 1) * COLUMN / 2;
+            if x < labels_area.right() {
+                buf.set_string(x, labels_area.y, *label, Style::default().fg(Color::DarkGray));
+            }
+        }
+
+        let (hours, minutes, seconds) = time;
+        let decimal = format!("{:02}:{:02}:{:02}", hours, minutes, seconds);
+        let answer: Vec<Line> = match &self.quiz {
+            None => vec![Line::from(decimal.white().bold())],
+            Some(quiz) => {
+                let mut typed: Vec<char> = quiz.typed.chars().chain(std::iter::repeat('_')).take(6).collect();
+                typed.insert(4, ':');
+                typed.insert(2, ':');
+                let typed: String = typed.into_iter().collect();
+                match quiz.result {
+                    None => vec![Line::from(typed.light_cyan().bold())],
+                    Some(true) => vec![Line::from(typed.light_green().bold()), Line::from("Correct!".light_green())],
+                    Some(false) => vec![Line::from(typed.light_red().crossed_out()), Line::from(format!("It was {}", decimal).white())],
+                }
+            }
+        };
+        Paragraph::new(answer).centered().render(answer_area, buf);
+
+        let keys = match &self.quiz {
+            None => "<M> BCD / binary  <Q> quiz  <[ ]> time zone",
+            Some(quiz) if quiz.result.is_some() => "<Enter> next time  <Q> back to the clock",
+            Some(_) => "<0-9> type hhmmss  <Enter> check  <Q> back to the clock",
+        };
+        Paragraph::new(keys.dark_gray()).centered().render(footer, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_seeded_quiz_asks_the_same_times() {
+        storage::install_for_tests();
+        let times = |seed| {
+            let mut clock = BinaryClock::new().with_rng(random::seeded(seed));
+            (0..5).map(|_| { clock.new_question(); clock.quiz.as_ref().unwrap().time }).collect::<Vec<_>>()
+        };
+        assert_eq!(times(3), times(3));
+        assert_ne!(times(3), times(4));
+    }
+}
@@ -1,33 +1,69 @@
-use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::games::question_bank::QuestionBank;
+use crate::games::scoring::{self, ScoringRules};
+use crate::games::session_clock::SessionClock;
+use crate::ui::countdown::CountdownWidget;
+use crate::ui::finale::Finale;
+use crate::ui::inspector;
+use crate::ui::keypad::{Keypad, KeypadKey};
+use crate::ui::mistake_map::{self, MistakeMap};
+use crate::ui::modal::{Modal, ModalResult};
+use crate::ui::stat_bar::StatBar;
+use crate::ui::theme::{self, Palette};
 use crate::utils::{center, When};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use rand::prelude::SliceRandom;
 use rand::Rng;
 use ratatui::buffer::Buffer;
-use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Margin, Position, Rect};
 use ratatui::prelude::Alignment::Center;
 use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
 use ratatui::style::Modifier; // added for bold high score marker
 use ratatui::text::Span;
 use ratatui::widgets::BorderType::Double;
-use ratatui::widgets::{Block, BorderType, Paragraph};
-use std::collections::HashMap;
-use std::fs::{File};
-use std::io::{Read, Write};
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Block, BorderType, Chart, Clear, Dataset, GraphType, Paragraph};
+use crate::config;
+use crate::music::Tune;
+use crate::particles::{Emitter, ParticleSystem};
+use crate::random::{self, GameRng};
+use crate::tween::{Animator, Easing};
+use crate::effects;
+use crate::keymap;
+use crate::sound::{self, SoundEvent};
+use crate::report::{self, RunResults, SessionReport, SessionResults};
+use crate::stats;
+use crate::storage;
+use crate::storage::migration::{self, Schema};
+use crate::storage::scores::{Ranked, ScoreStore};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Binary Numbers";
 
 // NEW: snapshot of game stats passed into puzzle for integrated layout
 struct StatsSnapshot {
     score: u32,
+    counted_score: u32, // the score counter, catching up with `score`
     streak: u32,
     max_streak: u32,
     rounds: u32,
-    lives: u32,
     max_lives: u32,
     bits: Bits,
-    hearts: String,
-    game_state: GameState, // NEW: overall game state replaces old boolean flags
+    base: Base,
+    quiz: Quiz,
+    untimed: bool, // practice: accuracy instead of score and lives
+    time_attack: bool, // one clock for the whole run instead of lives
+    career: bool, // the bit width follows the streak
+    correct: u32,
+    hearts: Vec<Span<'static>>,
     prev_high_score: u32,      // NEW: previous high score for this mode
     new_high_score: bool,      // NEW: whether current score is a new high score
+    unranked_reason: Option<String>, // why this run cannot set a high score
 }
 
 impl WidgetRef for BinaryNumbersGame {
@@ -38,27 +74,48 @@ impl WidgetRef for BinaryNumbersGame {
             .areas(area);
         // puzzle holds latest stats snapshot updated during run()
         self.puzzle.render_ref(game_column, buf);
+        if let Some(banner) = &self.level_banner {
+            banner.render(game_column, buf);
+        }
+        if let Some(scroll) = self.review_scroll {
+            self.render_review(scroll, game_column, buf);
+        } else if self.stats_open {
+            self.render_stats(game_column, buf);
+        } else if let Some(prompt) = &self.game_over {
+            prompt.render(game_column, buf);
+        }
+        self.confetti.render(game_column, buf);
+        if let Some(finale) = &self.finale {
+            finale.render(area, buf);
+        }
     }
 }
 
 impl WidgetRef for BinaryNumbersPuzzle {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         // Unified vertical layout: stats + current number + suggestions + status/time + result/instructions (or game over)
+        let palette = theme::palette();
         let [middle] = Layout::horizontal([Constraint::Percentage(100)])
             .flex(Flex::Center)
             .areas(area);
 
-        let [stats_area, current_number_area, suggestions_area, progress_bar_area, result_area] =
+        let keypad_height = if self.keypad.is_some() { Keypad::HEIGHT + 1 } else { 0 };
+        let [stats_area, current_number_area, suggestions_area, keypad_area, progress_bar_area, result_area] =
             Layout::vertical([
                 Constraint::Length(4),  // stats row expanded for two lines
                 Constraint::Length(5),  // current number area
                 Constraint::Length(3),  // suggestion area
+                Constraint::Length(keypad_height), // on-screen keypad below a typed answer
                 Constraint::Length(4),  // status + time area
                 Constraint::Length(5),  // result / instructions area OR game over block
             ])
             .flex(Flex::Center)
             .horizontal_margin(0)
             .areas(middle);
+        for (name, marked) in [("stats", stats_area), ("current number", current_number_area), ("suggestions", suggestions_area),
+            ("keypad", keypad_area), ("progress", progress_bar_area), ("result", result_area)] {
+            inspector::mark(name, marked);
+        }
 
         // Render stats bar integrated at top
         Block::bordered()
@@ -68,7 +125,9 @@ impl WidgetRef for BinaryNumbersPuzzle {
 
         // Use snapshot if present
         if let Some(stats) = &self.stats_snapshot {
-            let high_label = if stats.new_high_score {
+            let high_label = if stats.untimed {
+                Span::styled("Practice  ", Style::default().fg(Color::LightGreen))
+            } else if stats.new_high_score {
                 let style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
                 Span::styled(format!("Hi-Score: {}*  ", stats.score), style)
             } else {
@@ -76,51 +135,46 @@ impl WidgetRef for BinaryNumbersPuzzle {
                 Span::styled(format!("Hi-Score: {}  ", stats.prev_high_score), style)
             };
 
-            let line1 = Line::from(vec![
-                Span::styled(format!("Mode: {}  ", stats.bits.label()), Style::default().fg(Color::Yellow)),
+            let mode = if stats.career { format!("Level {}: {}  ", career_level(stats.bits.to_int()), stats.bits.label()) } else { format!("Mode: {}  ", stats.bits.label()) };
+            let mut line1 = Line::from(vec![
+                Span::styled(mode, Style::default().fg(Color::Yellow)),
+                match stats.quiz {
+                    Quiz::Convert => Span::styled(format!("Base: {}  ", stats.base.label()), Style::default().fg(Color::LightYellow)),
+                    quiz => Span::styled(format!("Quiz: {}  ", quiz.label()), Style::default().fg(Color::LightYellow)),
+                },
                 high_label,
             ]);
+            if stats.unranked_reason.is_some() && !stats.untimed {
+                line1.push_span(Span::styled("Unranked", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)));
+            }
 
-            let line2 = Line::from(vec![
-                Span::styled(format!("Score: {}  ", stats.score), Style::default().fg(Color::Green)),
-                Span::styled(format!("Streak: {}  ", stats.streak), Style::default().fg(Color::Cyan)),
-                Span::styled(format!("Max: {}  ", stats.max_streak), Style::default().fg(Color::Blue)),
-                Span::styled(format!("Rounds: {}  ", stats.rounds), Style::default().fg(Color::Magenta)),
-                Span::styled(format!("Lives: {}  ", stats.hearts), Style::default().fg(Color::Red)),
-            ]);
+            let line2 = if stats.untimed {
+                Line::from(vec![
+                    Span::styled(format!("Correct: {}/{}  ", stats.correct, stats.rounds), Style::default().fg(Color::Green)),
+                    Span::styled(format!("Accuracy: {}  ", accuracy_label(stats.correct, stats.rounds)), Style::default().fg(Color::Magenta)),
+                    Span::styled(format!("Streak: {}  ", stats.streak), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("Max: {}", stats.max_streak), Style::default().fg(Color::Blue)),
+                ])
+            } else if stats.time_attack {
+                Line::from(vec![
+                    Span::styled(format!("Score: {}  ", stats.counted_score), Style::default().fg(Color::Green)),
+                    Span::styled(format!("Streak: {}  ", stats.streak), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("Max: {}  ", stats.max_streak), Style::default().fg(Color::Blue)),
+                    Span::styled(format!("Answers: {}", stats.rounds), Style::default().fg(Color::Magenta)),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled(format!("Score: {}  ", stats.counted_score), Style::default().fg(Color::Green)),
+                    Span::styled(format!("Streak: {}  ", stats.streak), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("Max: {}  ", stats.max_streak), Style::default().fg(Color::Blue)),
+                    Span::styled(format!("Rounds: {}  ", stats.rounds), Style::default().fg(Color::Magenta)),
+                ].into_iter().chain(stats.hearts.iter().cloned()).collect::<Vec<_>>())
+            };
 
             let widest = line1.width().max(line2.width()) as u16;
             Paragraph::new(vec![line1, line2])
                 .alignment(Center)
                 .render(center(stats_area, Constraint::Length(widest)), buf);
-
-            // If game over, render game over block occupying the remaining area and return early
-            if stats.game_state == GameState::GameOver {
-                let combined_rect = Rect { x: current_number_area.x, y: current_number_area.y, width: current_number_area.width, height: current_number_area.height + suggestions_area.height + progress_bar_area.height + result_area.height };
-                let block = Block::bordered()
-                    .title("Game Over")
-                    .title_alignment(Center)
-                    .border_type(Double)
-                    .title_style(Style::default().fg(Color::Red));
-                block.render(combined_rect, buf);
-                let mut lines = vec![
-                    Line::from(Span::styled(format!("Final Score: {}", stats.score), Style::default().fg(Color::Green))),
-                    Line::from(Span::styled(format!("Previous High: {}", stats.prev_high_score), Style::default().fg(Color::Yellow))),
-                    Line::from(Span::styled(format!("Rounds Played: {}", stats.rounds), Style::default().fg(Color::Magenta))),
-                    Line::from(Span::styled(format!("Max Streak: {}", stats.max_streak), Style::default().fg(Color::Cyan))),
-                ];
-                if stats.new_high_score {
-                    lines.insert(1, Line::from(Span::styled("NEW HIGH SCORE!", Style::default().fg(Color::LightGreen).bold())));
-                }
-                if stats.lives == 0 {
-                    lines.push(Line::from(Span::styled("You lost all your lives.", Style::default().fg(Color::Red))));
-                }
-                lines.push(Line::from(Span::styled("Press Enter to restart or Esc to exit", Style::default().fg(Color::Yellow))));
-                Paragraph::new(lines)
-                    .alignment(Center)
-                    .render(center(combined_rect, Constraint::Length(48)), buf);
-                return;
-            }
         }
 
         // Existing puzzle rendering now uses updated area references
@@ -133,19 +187,40 @@ impl WidgetRef for BinaryNumbersPuzzle {
             .border_style(Style::default().dark_gray())
             .render(inner, buf);
 
-        let binary_string = self.current_to_binary_string();
+        if let Some(operation) = &self.operation {
+            self.render_operation(operation, inner, buf);
+        }
         let scale_suffix = match self.bits { Bits::FourShift4 => Some(" x16"), Bits::FourShift8 => Some(" x256"), Bits::FourShift12 => Some(" x4096"), _ => None };
-        let mut spans = vec![Span::raw(binary_string.clone())];
-        if let Some(sfx) = scale_suffix { spans.push(Span::styled(sfx, Style::default().fg(Color::DarkGray))); }
+        // the `To` bases show the decimal value and ask for its written form
+        let mut spans = if self.base.answers_in_decimal() { self.digit_spans() } else { vec![Span::raw(self.current_number.to_string())] };
+        if let Some(sfx) = scale_suffix.filter(|_| self.quiz == Quiz::Convert && self.base == Base::Binary) { spans.push(Span::styled(sfx, Style::default().fg(Color::DarkGray))); }
         let total_width = spans.iter().map(|s| s.width()).sum::<usize>() as u16;
         let lines: Vec<Line> = vec![Line::from(spans)];
-        Paragraph::new(lines).alignment(Center).render(center(inner, Constraint::Length(total_width)), buf);
+        let digits_area = center(inner, Constraint::Length(total_width));
+        if self.operation.is_none() { Paragraph::new(lines).alignment(Center).render(digits_area, buf); }
+
+        // while revealing, the lit bits add up to the answer one line below the digits
+        if self.operation.is_none() && self.guess_result.is_some() && digits_area.bottom() < inner.bottom().saturating_sub(1) {
+            let sum = self.revealed_sum_line();
+            let sum_area = Rect { y: digits_area.y + 1, height: 1, ..center(inner, Constraint::Length(sum.width() as u16)) };
+            Paragraph::new(sum).render(sum_area, buf);
+        } else if let Some(hint) = self.hint_line().filter(|_| digits_area.bottom() < inner.bottom().saturating_sub(1)) {
+            Paragraph::new(hint).render(Rect { y: digits_area.y + 1, height: 1, ..digits_area }, buf);
+        }
 
-        let suggestions = self.suggestions();
+        if let Some(answer) = &self.answer_input {
+            self.render_answer_input(answer, suggestions_area, buf);
+            if let Some(keypad) = &self.keypad {
+                keypad.render(Rect { y: keypad_area.y + 1, height: Keypad::HEIGHT, ..keypad_area }, buf);
+            }
+        }
+
+        let suggestions = if self.answer_input.is_some() { &[][..] } else { self.suggestions() };
         let suggestions_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![Constraint::Min(6); suggestions.len()])
             .split(suggestions_area);
+        *self.suggestion_areas.borrow_mut() = suggestions_layout.to_vec();
         for (i, suggestion) in suggestions.iter().enumerate() {
             let item_is_selected = self.selected_suggestion == Some(*suggestion);
             let show_correct_number = self.guess_result.is_some();
@@ -155,22 +230,23 @@ impl WidgetRef for BinaryNumbersPuzzle {
             let border_type = if item_is_selected { BorderType::Double } else { BorderType::Plain };
 
             let border_color = if item_is_selected {
-                match self.guess_result {
-                    Some(GuessResult::Correct) => Color::Green,
-                    Some(GuessResult::Incorrect) => Color::Red,
-                    Some(GuessResult::Timeout) => Color::Yellow,
-                    None => Color::LightCyan,
-                }
+                self.guess_result.map_or(Color::LightCyan, |result| result.color(&palette))
             } else {
                 Color::DarkGray
             };
+            // with the colorblind palette the right answer and a wrong pick are marked in the border
+            let mark = match self.guess_result {
+                Some(_) if is_correct_number => palette.mark(true),
+                Some(_) if item_is_selected => palette.mark(false),
+                _ => "",
+            };
 
-            Block::bordered().border_type(border_type).fg(border_color).render(area, buf);
+            Block::bordered().border_type(border_type).fg(border_color).title(Line::from(mark.trim()).centered()).render(area, buf);
 
-            let suggestion_str = format!("{suggestion}");
+            let suggestion_str = self.format_answer(*suggestion);
             Paragraph::new(format!("{}", suggestion_str))
                 .white()
-                .when(show_correct_number && is_correct_number, |p| p.light_green().underlined())
+                .when(show_correct_number && is_correct_number, |p| p.fg(palette.good_light).underlined())
                 .alignment(Center)
                 .render(center(area, Constraint::Length(suggestion_str.len() as u16)), buf);
         }
@@ -183,13 +259,16 @@ impl WidgetRef for BinaryNumbersPuzzle {
         Block::bordered().dark_gray().title("Status").title_alignment(Center).title_style(Style::default().white()).render(left, buf);
 
         if let Some(result) = &self.guess_result {
-            let (icon, line1_text, color) = match result {
-                GuessResult::Correct => (":)", "success", Color::Green),
-                GuessResult::Incorrect => (":(", "incorrect", Color::Red),
-                GuessResult::Timeout => (":(", "time's up", Color::Yellow),
+            let right = *result == GuessResult::Correct;
+            let icon = match palette.mark(right) { "" if right => ":)", "" => ":(", mark => mark.trim() };
+            let (line1_text, color) = match result {
+                GuessResult::Correct => ("success", result.color(&palette)),
+                GuessResult::Incorrect => ("incorrect", result.color(&palette)),
+                GuessResult::Timeout => ("time's up", result.color(&palette)),
             };
 
             let gained_line = match result {
+                _ if self.untimed => format!("answer: {}", self.format_answer(self.current_number)),
                 GuessResult::Correct => format!("gained {} points", self.last_points_awarded),
                 GuessResult::Incorrect => "lost a life".to_string(),
                 GuessResult::Timeout => "timeout".to_string(),
@@ -206,47 +285,56 @@ impl WidgetRef for BinaryNumbersPuzzle {
                 .render(center(left, Constraint::Length(widest)), buf);
         }
 
-        let ratio = self.time_left / self.time_total;
-        let gauge_color = if ratio > 0.6 {
-            Color::Green
-        } else if ratio > 0.3 {
-            Color::Yellow
+        if self.untimed {
+            let accuracy_block = Block::bordered().dark_gray().title("Accuracy").title_style(Style::default().white()).title_alignment(Center);
+            let accuracy = self.stats_snapshot.as_ref().map_or("-".to_string(), |stats| accuracy_label(stats.correct, stats.rounds));
+            Paragraph::new(accuracy).light_magenta().alignment(Center).render(center(accuracy_block.inner(right), Constraint::Percentage(100)), buf);
+            accuracy_block.render(right, buf);
         } else {
-            Color::Red
-        };
-
-        // Replace previous split layout: keep everything inside a single bordered block and remove percent label
-        let time_block = Block::bordered()
-            .dark_gray()
-            .title("Time Remaining")
-            .title_style(Style::default().white())
-            .title_alignment(Center);
-        let inner_time = time_block.inner(right);
-        time_block.render(right, buf);
-
-        // Vertical layout inside the time block interior: gauge line + text line (2 lines total)
-        let [gauge_line, time_line] = Layout::vertical([
-            Constraint::Length(1), // gauge occupies one row
-            Constraint::Length(1), // time text occupies one row
-        ]).areas(inner_time);
-
-        render_ascii_gauge(gauge_line, buf, ratio, gauge_color);
-
-        Paragraph::new(Line::from(Span::styled(
-            format!("{:.2} seconds left", self.time_left),
-            Style::default().fg(gauge_color),
-        )))
-        .alignment(Center)
-        .render(time_line, buf);
+            let time_block = Block::bordered()
+                .dark_gray()
+                .title("Time Remaining")
+                .title_style(Style::default().white())
+                .title_alignment(Center);
+            CountdownWidget::new(self.time_left, self.time_total)
+                .warn_below(3.0)
+                .block(time_block)
+                .render(right, buf);
+        }
 
         Block::bordered().dark_gray().render(result_area, buf);
 
-        let instruction_spans: Vec<Span> = vec![
-            hotkey_span("Left Right", "select  "),
-            hotkey_span("Enter", "confirm  "),
-            hotkey_span("S", "skip  "),
-            hotkey_span("Esc", "exit"),
-        ].iter().flatten().cloned().collect();
+        if self.guess_result.is_some() && !self.reveal_done() {
+            Paragraph::new(Line::from(hotkey_span("Enter", "skip animation")))
+                .alignment(Center)
+                .render(center(result_area, Constraint::Length(65)), buf);
+            return;
+        }
+
+        let instruction_spans: Vec<Span> = if self.answer_input.is_some() {
+            vec![
+                hotkey_span("0-9", "type  "),
+                hotkey_span("Enter", "confirm  "),
+                hotkey_span("K", "keypad  "),
+                hotkey_span("T", "choices  "),
+                hotkey_span("S", "skip"),
+            ]
+        } else if self.answers_in_decimal() {
+            vec![
+                hotkey_span("Left Right", "select  "),
+                hotkey_span("Enter", "confirm  "),
+                hotkey_span("T", "type  "),
+                hotkey_span("S", "skip  "),
+                hotkey_span("Esc", "exit"),
+            ]
+        } else {
+            vec![
+                hotkey_span("Left Right", "select  "),
+                hotkey_span("Enter", "confirm  "),
+                hotkey_span("S", "skip  "),
+                hotkey_span("Esc", "exit"),
+            ]
+        }.iter().flatten().cloned().collect();
 
         Paragraph::new(vec![Line::from(instruction_spans)])
             .alignment(Center)
@@ -254,6 +342,73 @@ impl WidgetRef for BinaryNumbersPuzzle {
     }
 }
 
+impl BinaryNumbersPuzzle {
+    /// Both operands right-aligned above each other with the operator in front, and after a
+    /// guess the result below them, lighting up like the digits of a conversion.
+    fn render_operation(&self, operation: &Operation, area: Rect, buf: &mut Buffer) {
+        let width = self.bits.to_int() as usize;
+        let grouped = |value: u32| {
+            let digits = format!("{:0width$b}", value);
+            digits.as_bytes().chunks(4).map(|chunk| String::from_utf8_lossy(chunk).into_owned()).collect::<Vec<_>>().join(" ")
+        };
+        let digits_width = width + (width - 1) / 4;
+        let right = if operation.operator.is_shift() { format!("{:>digits_width$}", operation.right) } else { grouped(operation.right) };
+        let mut lines = vec![
+            Line::from(format!("{:<4}{}", "", grouped(operation.left))),
+            Line::from(vec![Span::styled(format!("{:<4}", operation.operator.symbol()), Style::default().fg(Color::LightCyan)), Span::raw(right)]),
+        ];
+        if self.guess_result.is_some() {
+            lines.push(Line::from([Span::styled("=   ", Style::default().fg(Color::DarkGray))].into_iter().chain(self.digit_spans()).collect::<Vec<_>>()));
+        }
+        let [column] = Layout::horizontal([Constraint::Length(4 + digits_width as u16)]).flex(Flex::Center).areas(area);
+        Paragraph::new(lines).render(Rect { y: column.y + 1, height: column.height.saturating_sub(2), ..column }, buf);
+    }
+
+    fn render_answer_input(&self, answer: &str, area: Rect, buf: &mut Buffer) {
+        let palette = theme::palette();
+        let border_color = self.guess_result.map_or(Color::LightCyan, |result| result.color(&palette));
+        let [input_area] = Layout::horizontal([Constraint::Length(24)]).flex(Flex::Center).areas(area);
+        let cursor = if self.guess_result.is_none() { "_" } else { "" };
+        // checked as it is typed, only while it can still be changed
+        let (border_color, hint) = match self.answer_problem(answer) {
+            _ if self.guess_result.is_some() => (border_color, Line::default()),
+            Some(problem) => (palette.bad, Line::from(format!(" {} ", problem).fg(palette.bad_light))),
+            None if answer.is_empty() && self.quiz == Quiz::Signed => (border_color, Line::from(" signed, - for minus ".dark_gray())),
+            None if answer.is_empty() => (border_color, Line::from(" decimal value ".dark_gray())),
+            None => (border_color, Line::from(" ✓ Enter to confirm ".fg(palette.good_light))),
+        };
+        Paragraph::new(format!("{}{}", answer, cursor))
+            .white()
+            .alignment(Center)
+            .block(Block::bordered().border_type(BorderType::Double).fg(border_color).title("Your answer").title_bottom(hint.centered()))
+            .render(input_area, buf);
+    }
+
+    /// Why a typed answer cannot be right whatever the bits: out of range, or not a multiple of the
+    /// scale of the shifted modes.
+    fn answer_problem(&self, answer: &str) -> Option<String> {
+        if self.quiz == Quiz::Signed {
+            let (min, max) = self.signed_range();
+            let value = answer.parse::<i64>().ok()?;
+            return (value < min || value > max).then(|| format!("from {} to {}", min, max));
+        }
+        let value = answer.parse::<u32>().ok()?;
+        let scale = self.scale();
+        if value > self.bits.upper_bound() {
+            Some(format!("at most {}", self.bits.upper_bound()))
+        } else if value % scale != 0 {
+            Some(format!("a multiple of {}", scale))
+        } else {
+            None
+        }
+    }
+}
+
+/// `75%`, or `-` before the first round.
+fn accuracy_label(correct: u32, rounds: u32) -> String {
+    if rounds == 0 { "-".to_string() } else { format!("{:.0}%", correct as f64 * 100.0 / rounds as f64) }
+}
+
 fn hotkey_span<'a>(key: &'a str, description: &str) -> Vec<Span<'a>> {
     vec![
         Span::styled("<", Style::default().fg(Color::White)),
@@ -265,6 +420,20 @@ fn hotkey_span<'a>(key: &'a str, description: &str) -> Vec<Span<'a>> {
 pub struct BinaryNumbersGame {
     puzzle: BinaryNumbersPuzzle,
     bits: Bits,
+    base: Base, // how the numbers are written, high scores are kept per base
+    quiz: Quiz, // what the rounds ask, high scores are kept per quiz
+    untimed: bool, // practice without timer, score or lives, only accuracy counts
+    mode: Mode, // how the run ends
+    time_attack_left: f64, // seconds on the clock of a time attack, carried from puzzle to puzzle
+    leaderboards: ScoreStore<Run>, // best runs per difficulty and mode
+    leaderboard_place: Option<usize>, // where the finished run landed on its leaderboard
+    career_progress: u32, // career: position on the adaptive bit width curve, grows with every correct answer
+    career_misses: u32, // career: misses in a row, a level is lost after `CAREER_DROP_AFTER`
+    level_banner: Option<LevelBanner>, // career: announces a new level between rounds
+    daily: Option<i64>, // the day of the daily challenge this run plays, see `with_daily`
+    hints: bool, // the value of each group of four bits under the digits, see `set_hints`
+    range: NumberRange, // the numbers conversions ask, see `with_range`
+    correct: u32, // rounds answered right, for the accuracy
     exit_intended: bool,
     score: u32,
     streak: u32,
@@ -277,6 +446,26 @@ pub struct BinaryNumbersGame {
     high_scores: HighScores,           // NEW: persistent high scores
     prev_high_score_for_display: u32,  // NEW: previous high score captured at game over
     new_high_score_reached: bool,      // NEW: flag if new high score achieved
+    questions: QuestionBank, // recent answers of this session, avoided in new puzzles
+    rng: GameRng, // draws every puzzle, seeded for a reproducible run
+    typed_answers: bool, // type the decimal answer instead of choosing from suggestions
+    keypad_visible: bool, // on-screen keypad below the typed answer
+    clock: SessionClock, // wall clock of this run, decides whether it may set a high score
+    round_log: Vec<storage::RoundRecord>, // rounds of this run, for the exported report
+    mistakes: MistakeMap, // rounds of this run by bit pattern, shown in the final summary
+    misses: Vec<Miss>, // every round of this run answered wrong or left to run out
+    review_scroll: Option<u16>, // lines scrolled in the review of the misses, `None` while it is closed
+    round_stats: Vec<RoundStat>, // every round of this run, for the statistics after the game
+    stats_open: bool, // the statistics page is shown over the summary, see `render_stats`
+    export_message: Option<String>,
+    export_path: Option<PathBuf>, // results file kept up to date after every round, see `with_export`
+    results: SessionResults, // the finished runs of this session, for the results file
+    game_over: Option<Modal>, // final summary, open while the game is over
+    finale: Option<Finale>, // fireworks over the summary after a new high score
+    confetti: ParticleSystem, // celebrates a new high score
+    score_counter: Animator,
+    lives_bar: StatBar,
+    advance_in: Option<f64>, // seconds until the next puzzle starts on its own after a correct answer
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -285,24 +474,286 @@ enum GameState { Active, Result, PendingGameOver, GameOver }
 impl MainScreenWidget for BinaryNumbersGame {
     fn run(&mut self, dt: f64) {
         self.refresh_stats_snapshot();
+        self.confetti.tick(dt);
+        self.score_counter.tick(dt);
+        self.lives_bar.set(self.lives as f64);
+        self.lives_bar.tick(dt);
+        if let Some(finale) = self.finale.as_mut() {
+            finale.tick(dt);
+            if finale.is_done() { self.finale = None; }
+        }
+        if let Some(banner) = self.level_banner.as_mut() {
+            banner.tick(dt);
+            if banner.is_done() { self.level_banner = None; }
+        }
         if self.game_state == GameState::GameOver { return; }
+        let timed = self.game_state == GameState::Active && self.puzzle.guess_result.is_none() && !self.untimed;
+        self.clock.observe(dt, timed);
         self.puzzle.run(dt);
         if self.puzzle.guess_result.is_some() && !self.puzzle_resolved { self.finalize_round(); }
+        // the wait starts once the reveal is over
+        if let Some(left) = self.advance_in.as_mut().filter(|_| self.game_state == GameState::Result && self.puzzle.reveal_done()) {
+            *left -= dt;
+            if *left <= 0.0 { self.handle_result_available(KeyEvent::from(KeyCode::Enter)); }
+        }
         self.refresh_stats_snapshot();
     }
 
     fn handle_input(&mut self, input: KeyEvent) -> () { self.handle_game_input(input); }
+    fn handle_mouse(&mut self, event: MouseEvent) -> () { self.handle_game_mouse(event); }
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Left Right", "select a suggestion"),
+            ("Enter", "confirm / next round"),
+            ("S", "skip the puzzle (counts as timeout)"),
+            ("T", "switch between choosing and typing the answer"),
+            ("Z", "practice without timer and lives, or back (starts over)"),
+            ("0-9 Backspace", "type the answer"),
+            ("-", "minus, for signed answers"),
+            ("K", "on-screen keypad for typing (arrows move, Space presses)"),
+            ("Click", "select, click again to confirm"),
+            ("Up Down", "scroll the mistakes after the game"),
+            ("Esc", "exit the game"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
     fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    /// The countdown runs while a puzzle is open, the reveal animates after a guess, a new career level is announced and confetti falls and fireworks go off after a new high score.
+    fn needs_redraw(&self) -> bool {
+        self.game_state == GameState::Active || !self.puzzle.reveal_done() || !self.confetti.is_empty() || self.score_counter.is_animating() || self.lives_bar.is_animating() || self.finale.is_some() || self.level_banner.is_some()
+    }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn is_finished(&self) -> bool {
+        self.lives == 0 || self.game_state == GameState::GameOver
+    }
+
+    fn save_state(&self) -> Option<SavedState> {
+        // a finished game has nothing left to recover
+        if self.is_finished() { return None; }
+        let lives = if self.lives == 1 { "1 life".to_string() } else { format!("{} lives", self.lives) };
+        let summary = match self.time_attack() {
+            _ if self.untimed => format!("practice, {}/{} correct", self.correct, self.rounds),
+            Some(_) => format!("time attack, score {}, {:.0}s left", self.score, self.time_attack_left),
+            None if self.mode == Mode::Career => format!("career level {}, score {}, {}", career_level(self.bits.to_int()), self.score, lives),
+            None => format!("score {}, {}", self.score, lives),
+        };
+        Some(SavedState {
+            summary,
+            data: self.serialize_state(),
+        })
+    }
 }
 
 impl BinaryNumbersGame {
     pub fn new(bits: Bits) -> Self { Self::new_with_max_lives(bits, 3) }
+
+    /// Start with the answer typed instead of chosen from suggestions, T still switches.
+    pub fn with_typed_answers(mut self, typed: bool) -> Self {
+        self.typed_answers = typed;
+        self.apply_answer_mode();
+        self
+    }
+
+    /// Write the numbers in `base` instead of binary, with the high scores of that base.
+    pub fn with_base(mut self, base: Base) -> Self {
+        self.base = base;
+        self.restart_variant();
+        self
+    }
+
+    /// Ask `quiz` instead of conversions, with the high scores of that quiz.
+    pub fn with_quiz(mut self, quiz: Quiz) -> Self {
+        self.quiz = quiz;
+        self.restart_variant();
+        self
+    }
+
+    /// Practice without timer, score or lives, or back to a normal game.
+    pub fn with_untimed(mut self, untimed: bool) -> Self {
+        self.untimed = untimed;
+        self.restart_variant();
+        self
+    }
+
+    /// Show the hex value of every group of four bits under a binary number. Makes the run unranked.
+    pub fn set_hints(&mut self, on: bool) {
+        self.hints = on;
+        self.puzzle.hints = on;
+        if on { self.clock.flag("hints"); }
+        self.refresh_stats_snapshot();
+    }
+
+    /// Ask conversions only about the numbers in `range`, to drill the ones that are hard. Any
+    /// range but the full one makes the run unranked.
+    pub fn with_range(mut self, range: NumberRange) -> Self {
+        self.range = range;
+        self.restart_variant();
+        if range != NumberRange::Full { self.clock.flag("number range"); }
+        self
+    }
+
+    pub fn hints(&self) -> bool {
+        self.hints
+    }
+
+    pub fn bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    /// Whether [`Self::change_bits`] may be used: a career and the daily challenge pick the width themselves.
+    pub fn can_change_bits(&self) -> bool {
+        self.mode != Mode::Career && self.daily.is_none()
+    }
+
+    /// Go on with `bits`, keeping score, lives and streak. A puzzle still open is replaced by one
+    /// of the new width; the run is no longer ranked.
+    pub fn change_bits(&mut self, bits: Bits) {
+        if !self.can_change_bits() || bits.high_score_key() == self.bits.high_score_key() { return; }
+        self.bits = bits;
+        self.clock.flag("bit width changed during the run");
+        if self.game_state == GameState::Active && self.puzzle.guess_result.is_none() {
+            self.start_puzzle(self.streak);
+        }
+        self.refresh_stats_snapshot();
+    }
+
+    /// Draw the puzzles from `seed`: the same seed and settings ask the same questions.
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.with_rng(random::seeded(seed))
+    }
+
+    /// Draw the puzzles from `rng` instead of one from [`random::rng`], starting with a new one.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self.questions = QuestionBank::new(config::get().question_window as usize);
+        self.restart_variant();
+        self
+    }
+
+    /// The daily challenge of `day` (see [`stats::today`]): the puzzles are drawn from a seed of
+    /// the date, so everyone gets the same ones, and finishing the run marks the day as done.
+    /// Every restart asks them again from the start.
+    pub fn with_daily(mut self, day: i64) -> Self {
+        self.daily = Some(day);
+        self.with_seed(daily_seed(day))
+    }
+
+    /// End the run by lives or by a single clock, see [`Mode`].
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self.restart_variant();
+        self
+    }
+
+    /// Keep a results file of the session at `path`, JSON or CSV by its extension, rewritten after
+    /// every round so it holds everything played even if the session ends abruptly.
+    pub fn with_export(mut self, path: impl Into<PathBuf>) -> Self {
+        self.export_path = Some(path.into());
+        self
+    }
+
+    /// Carry on the results of an earlier game, so one file covers a session of several games.
+    pub fn with_results(mut self, results: SessionResults) -> Self {
+        self.results = results;
+        self
+    }
+
+    /// The finished runs of the session and the current one, see [`Self::with_export`].
+    pub fn results(&self) -> SessionResults {
+        let mut results = self.results.clone();
+        if !self.round_log.is_empty() { results.runs.push(self.run_results()); }
+        results
+    }
+
+    fn restart_variant(&mut self) {
+        if self.mode == Mode::Career { self.start_career(); }
+        self.prev_high_score_for_display = self.best_score();
+        self.time_attack_left = self.time_attack().unwrap_or(0) as f64;
+        self.start_puzzle(self.streak);
+        self.refresh_stats_snapshot();
+    }
+
+    fn score_key(&self) -> String {
+        HighScores::key(&self.bits, self.base, self.quiz)
+    }
+
+    fn leaderboard_key(&self) -> String {
+        leaderboard_key(&self.bits, self.base, self.quiz, self.mode)
+    }
+
+    /// Seconds of a time attack; practice has no clock at all.
+    fn time_attack(&self) -> Option<u32> {
+        match self.mode {
+            Mode::TimeAttack(seconds) if !self.untimed => Some(seconds),
+            _ => None,
+        }
+    }
+
+    /// The score to beat: the high score of the lives mode or the top of the leaderboard of the other modes.
+    fn best_score(&self) -> u32 {
+        match self.mode {
+            Mode::Lives => self.high_scores.get(&self.score_key()),
+            _ => self.leaderboards.best(&self.leaderboard_key()).map_or(0, |run| run.score),
+        }
+    }
+
+    /// A career always starts at the lowest level.
+    fn start_career(&mut self) {
+        self.bits = Bits::Four;
+        self.clock = Self::start_clock(&self.bits);
+        self.career_progress = 0;
+        self.career_misses = 0;
+        self.level_banner = None;
+    }
+
+    /// Career: the bit width follows the adaptive curve of the scoring rules, climbing as correct
+    /// answers add up and falling back to the start of the level below after `CAREER_DROP_AFTER`
+    /// misses in a row.
+    fn advance_career(&mut self, correct: bool) {
+        let rules = scoring::rules();
+        let width = self.bits.to_int();
+        if correct {
+            self.career_misses = 0;
+            self.career_progress += 1;
+        } else {
+            self.career_misses += 1;
+            if self.career_misses < CAREER_DROP_AFTER || width <= CAREER_WIDTHS[0] { return; }
+            self.career_misses = 0;
+            while self.career_progress > 0 && rules.adaptive_bit_width(self.career_progress) >= width { self.career_progress -= 1; }
+            let lower = rules.adaptive_bit_width(self.career_progress);
+            while self.career_progress > 0 && rules.adaptive_bit_width(self.career_progress - 1) == lower { self.career_progress -= 1; }
+        }
+        let new_width = rules.adaptive_bit_width(self.career_progress);
+        if new_width != width {
+            self.bits = career_bits(new_width);
+            self.level_banner = Some(LevelBanner::new(new_width, new_width > width));
+        }
+    }
+
     pub fn new_with_max_lives(bits: Bits, max_lives: u32) -> Self {
         let hs = HighScores::load();
-        let starting_prev = hs.get(bits.high_score_key());
+        let starting_prev = hs.get(&HighScores::key(&bits, Base::Binary, Quiz::Convert));
+        let mut questions = QuestionBank::new(config::get().question_window as usize);
+        let mut rng = random::rng();
         Self {
             bits: bits.clone(),
-            puzzle: Self::init_puzzle(bits.clone(), 0),
+            base: Base::Binary,
+            quiz: Quiz::Convert,
+            untimed: false,
+            mode: Mode::Lives,
+            time_attack_left: 0.0,
+            leaderboards: ScoreStore::load(&LEADERBOARDS, LEADERBOARD_SIZE),
+            leaderboard_place: None,
+            career_progress: 0,
+            career_misses: 0,
+            level_banner: None,
+            daily: None,
+            hints: false,
+            range: NumberRange::Full,
+            correct: 0,
+            puzzle: Self::init_puzzle(bits.clone(), Base::Binary, Quiz::Convert, NumberRange::Full, 0, &mut questions, &mut rng),
             exit_intended: false,
             score: 0,
             streak: 0,
@@ -315,63 +766,216 @@ impl BinaryNumbersGame {
             high_scores: hs,
             prev_high_score_for_display: starting_prev,
             new_high_score_reached: false,
+            questions,
+            rng,
+            typed_answers: false,
+            keypad_visible: false,
+            clock: Self::start_clock(&bits),
+            round_log: Vec::new(),
+            mistakes: MistakeMap::default(),
+            misses: Vec::new(),
+            review_scroll: None,
+            round_stats: Vec::new(),
+            stats_open: false,
+            export_message: None,
+            export_path: None,
+            results: SessionResults::new(NAME),
+            game_over: None,
+            finale: None,
+            confetti: ParticleSystem::new(),
+            score_counter: Animator::new(0.0, 0.6, Easing::EaseOut),
+            lives_bar: StatBar::hearts(max_lives).label("Lives: ").starting_at(max_lives.min(3) as f64),
+            advance_in: None,
         }
     }
 
-    pub fn init_puzzle(bits: Bits, streak: u32) -> BinaryNumbersPuzzle {
-        BinaryNumbersPuzzle::new(bits, streak)
+    /// High scores are only comparable under the default rules and difficulties.
+    fn start_clock(bits: &Bits) -> SessionClock {
+        let mut clock = SessionClock::new();
+        if scoring::rules() != ScoringRules::default() { clock.flag("custom scoring rules"); }
+        if bits.is_custom() { clock.flag("custom difficulty"); }
+        clock
     }
-}
 
-impl BinaryNumbersGame {
-    pub fn lives_hearts(&self) -> String {
-        let full_count = self.lives.min(self.max_lives) as usize;
-        let full = "♥".repeat(full_count);
-        let empty_count = self.max_lives.saturating_sub(self.lives) as usize;
-        let empty = "·".repeat(empty_count);
-        format!("{}{}", full, empty)
+    pub fn init_puzzle(bits: Bits, base: Base, quiz: Quiz, range: NumberRange, streak: u32, questions: &mut QuestionBank, rng: &mut impl Rng) -> BinaryNumbersPuzzle {
+        BinaryNumbersPuzzle::new(bits, base, quiz, range, streak, questions, rng)
+    }
+
+    fn start_puzzle(&mut self, streak: u32) {
+        self.puzzle = Self::init_puzzle(self.bits.clone(), self.base, self.quiz, self.range, streak, &mut self.questions, &mut self.rng);
+        self.puzzle.untimed = self.untimed;
+        self.puzzle.hints = self.hints;
+        // a time attack shows its one clock in place of the round's
+        if let Some(seconds) = self.time_attack() {
+            self.puzzle.time_total = seconds as f64;
+            self.puzzle.time_left = self.time_attack_left;
+        }
+        self.apply_answer_mode();
+        self.advance_in = None;
+    }
+
+    /// Only decimal answers can be typed, the `To` bases and bitwise results are always chosen.
+    fn apply_answer_mode(&mut self) {
+        let typed = self.typed_answers && self.puzzle.answers_in_decimal();
+        self.puzzle.answer_input = typed.then(|| self.puzzle.answer_input.take().unwrap_or_default());
+        self.puzzle.keypad = (typed && self.keypad_visible).then(Keypad::numeric);
+    }
+
+    /// Stats and history name the base or quiz next to the bit width, except for binary conversions.
+    fn mode_label(&self) -> String {
+        match (self.quiz, self.base) {
+            (Quiz::Convert, Base::Binary) => self.bits.label().to_string(),
+            (Quiz::Convert, base) => format!("{} {}", self.bits.label(), base.label()),
+            (quiz, _) => format!("{} {}", self.bits.label(), quiz.label()),
+        }
+    }
+
+    /// Score, lives and streaks as `key=value` lines. The current puzzle is not kept,
+    /// a restored game continues with a fresh one.
+    pub fn serialize_state(&self) -> String {
+        format!(
+            "bits={}\nbase={}\nquiz={}\nrange={}\nuntimed={}\nmode={}\ntime_left={}\ncareer_progress={}\ncareer_misses={}\ncorrect={}\nscore={}\nstreak={}\nmax_streak={}\nrounds={}\nlives={}\nmax_lives={}\n",
+            self.bits.high_score_key(), self.base, self.quiz, self.range, self.untimed as u32, self.mode, self.time_attack_left.ceil() as u32, self.career_progress, self.career_misses, self.correct, self.score, self.streak, self.max_streak, self.rounds, self.lives, self.max_lives,
+        )
     }
 
+    pub fn restore(data: &str) -> Option<Self> {
+        let values: HashMap<&str, u32> = data.lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(k, v)| v.trim().parse().ok().map(|v| (k.trim(), v)))
+            .collect();
+        let bits = Bits::from_high_score_key(*values.get("bits")?)?;
+        // saves from before bases and quizzes existed are binary conversions
+        let base = data.lines().find_map(|line| line.strip_prefix("base=")).and_then(|base| base.trim().parse().ok()).unwrap_or_default();
+        let quiz = data.lines().find_map(|line| line.strip_prefix("quiz=")).and_then(|quiz| quiz.trim().parse().ok()).unwrap_or_default();
+        let range = data.lines().find_map(|line| line.strip_prefix("range=")).and_then(|range| range.trim().parse().ok()).unwrap_or_default();
+        let untimed = values.get("untimed").is_some_and(|untimed| *untimed == 1);
+        let mode = data.lines().find_map(|line| line.strip_prefix("mode=")).and_then(|mode| mode.trim().parse().ok()).unwrap_or_default();
+        let mut game = Self::new_with_max_lives(bits.clone(), *values.get("max_lives")?).with_base(base).with_quiz(quiz).with_untimed(untimed).with_mode(mode).with_range(range);
+        game.correct = values.get("correct").copied().unwrap_or(0);
+        if let Some(left) = values.get("time_left").filter(|_| game.time_attack().is_some()) { game.time_attack_left = *left as f64; }
+        if game.mode == Mode::Career {
+            game.bits = bits;
+            game.career_progress = values.get("career_progress").copied().unwrap_or(0);
+            game.career_misses = values.get("career_misses").copied().unwrap_or(0);
+        }
+        game.score = *values.get("score")?;
+        game.score_counter.jump_to(game.score as f64);
+        game.streak = *values.get("streak")?;
+        game.max_streak = *values.get("max_streak")?;
+        game.rounds = *values.get("rounds")?;
+        game.lives = *values.get("lives")?;
+        game.lives_bar.jump(game.lives as f64);
+        game.start_puzzle(game.streak);
+        game.refresh_stats_snapshot();
+        Some(game)
+    }
+}
+
+impl BinaryNumbersGame {
     fn finalize_round(&mut self) {
         if let Some(result) = self.puzzle.guess_result {
+            if self.time_attack().is_some() {
+                self.time_attack_left = self.puzzle.time_left;
+                // the puzzle open when the clock ran out was never answered
+                if result == GuessResult::Timeout && self.time_attack_left <= 0.0 { return self.end_time_attack(); }
+            }
             self.rounds += 1;
             match result {
+                GuessResult::Correct if self.untimed => {
+                    self.correct += 1;
+                    self.streak += 1;
+                    self.max_streak = self.max_streak.max(self.streak);
+                }
+                GuessResult::Incorrect | GuessResult::Timeout if self.untimed => self.streak = 0,
                 GuessResult::Correct => {
+                    let rules = scoring::rules();
+                    self.correct += 1;
                     self.streak += 1;
                     if self.streak > self.max_streak { self.max_streak = self.streak; }
-                    let streak_bonus = (self.streak - 1) * 2;
-                    let points = 10 + streak_bonus;
+                    let points = rules.points(self.streak);
                     self.score += points;
+                    self.score_counter.animate_to(self.score as f64);
                     self.puzzle.last_points_awarded = points;
-                    if self.streak % 5 == 0 && self.lives < self.max_lives { self.lives += 1; }
+                    if rules.restores_life(self.streak) && self.lives < self.max_lives { self.lives += 1; }
                 }
                 GuessResult::Incorrect | GuessResult::Timeout => {
                     self.streak = 0;
                     self.puzzle.last_points_awarded = 0;
-                    if self.lives > 0 { self.lives -= 1; }
+                    if self.lives > 0 && self.time_attack().is_none() { self.lives -= 1; }
                 }
             }
-            // high score update
-            let bits_key = self.bits.high_score_key();
-            let prev = self.high_scores.get(bits_key);
-            if self.score > prev {
-                if !self.new_high_score_reached { self.prev_high_score_for_display = prev; }
-                self.high_scores.update(bits_key, self.score);
+            if self.mode == Mode::Career { self.advance_career(result == GuessResult::Correct); }
+            // high score update; the other modes only keep their leaderboard, filled once the run is over
+            let prev = self.best_score();
+            if self.score > prev && self.clock.is_ranked() && !self.untimed {
+                if !self.new_high_score_reached {
+                    self.prev_high_score_for_display = prev;
+                    self.confetti.burst(&Emitter::confetti(65.0), 60);
+                }
                 self.new_high_score_reached = true;
-                let _ = self.high_scores.save();
+                if self.mode == Mode::Lives {
+                    self.high_scores.update(&self.score_key(), self.score);
+                    let _ = self.high_scores.save();
+                }
             }
+            let record = storage::RoundRecord {
+                game: "binary_numbers".to_string(),
+                mode: self.mode_label(),
+                played_at: storage::unix_now(),
+                correct: result == GuessResult::Correct,
+                answer_time_ms: (self.puzzle.answer_elapsed * 1000.0) as u64,
+                expected: self.puzzle.format_answer(self.puzzle.current_number),
+                given: match (result, self.puzzle.selected_suggestion) {
+                    (GuessResult::Timeout, _) | (_, None) => "-".to_string(),
+                    (_, Some(selected)) => self.puzzle.format_answer(selected),
+                },
+            };
+            storage::record_round(&record);
+            if result != GuessResult::Correct {
+                self.misses.push(Miss { question: self.puzzle.question(), expected: record.expected.clone(), given: record.given.clone(), seconds: self.puzzle.answer_elapsed });
+            }
+            self.round_log.push(record);
+            self.write_results();
+            stats::record_round(NAME, &self.mode_label(), result == GuessResult::Correct, self.streak);
+            let (raw, width) = (self.puzzle.raw_current_number, self.bits.to_int());
+            self.mistakes.record(raw, width, result == GuessResult::Correct);
+            let scale = self.puzzle.scale();
+            self.round_stats.push(RoundStat {
+                score: self.score,
+                seconds: self.puzzle.answer_elapsed,
+                shift: scale.trailing_zeros(),
+                width,
+                wrong_bits: match (result, self.puzzle.selected_suggestion) {
+                    (GuessResult::Timeout, _) | (_, None) => None,
+                    (_, Some(selected)) => Some((selected / scale ^ raw) & (u32::pow(2, width) - 1)),
+                },
+            });
+            stats::record_pattern(NAME, &mistake_map::key(raw, width), result == GuessResult::Correct);
+            sound::emit(match result {
+                GuessResult::Correct => SoundEvent::Correct,
+                GuessResult::Incorrect => SoundEvent::Incorrect,
+                GuessResult::Timeout => SoundEvent::Timeout,
+            });
             // set state after round resolution
             if self.lives == 0 {
                 self.game_state = GameState::PendingGameOver; // defer summary until Enter
+                sound::emit(SoundEvent::GameOver);
+                effects::game_over();
             } else {
+                if result != GuessResult::Correct { effects::wrong_answer(); }
                 self.game_state = GameState::Result;
+                if result == GuessResult::Correct { self.advance_in = config::get().auto_advance(); }
             }
             self.puzzle_resolved = true;
         }
     }
 
     pub fn handle_game_input(&mut self, input: KeyEvent) {
+        if self.review_scroll.is_some() { return self.handle_review_input(input); }
+        if self.stats_open { return self.handle_stats_input(input); }
         if input.code == KeyCode::Esc { self.exit_intended = true; return; }
+        if let Some(finale) = self.finale.as_mut() { finale.skip(); return; }
         if self.game_state == GameState::GameOver { self.handle_game_over_input(input); return; }
         match self.puzzle.guess_result {
             None => self.handle_no_result_yet(input),
@@ -380,28 +984,337 @@ impl BinaryNumbersGame {
     }
 
     fn handle_game_over_input(&mut self, input: KeyEvent) {
+        let result = self.game_over.as_mut().and_then(|prompt| prompt.handle_key(input));
+        self.answer_game_over(result);
+    }
+
+    /// Reviewing the misses is only offered when there are some, the statistics once a round was played.
+    fn game_over_buttons(&self) -> Vec<&'static str> {
+        let stats = (!self.round_stats.is_empty()).then_some("Stats");
+        let review = (!self.misses.is_empty()).then_some("Mistakes");
+        ["Restart"].into_iter().chain(stats).chain(review).chain(["Export report", "Quit"]).collect()
+    }
+
+    fn answer_game_over(&mut self, result: Option<ModalResult>) {
+        let button = match result {
+            Some(ModalResult::Pressed(index)) => self.game_over_buttons().get(index).copied(),
+            Some(ModalResult::Dismissed) => Some("Quit"),
+            None => None,
+        };
+        match button {
+            Some("Restart") => self.reset_game_state(),
+            Some("Stats") => self.stats_open = true,
+            Some("Mistakes") => self.review_scroll = Some(0),
+            Some("Export report") => {
+                self.export_report();
+                self.game_over = self.game_over.take().map(|prompt| prompt.body(self.game_over_lines()));
+            }
+            Some(_) => self.exit_intended = true,
+            None => {}
+        }
+    }
+
+    /// Two lines per miss: the question, then the answer, the one given and how long it took.
+    fn review_lines(&self) -> Vec<Line<'static>> {
+        let palette = theme::palette();
+        self.misses.iter().enumerate().flat_map(|(index, miss)| {
+            let given = if miss.given == "-" { "timed out".to_string() } else { format!("yours {}", miss.given) };
+            [
+                Line::from(vec![Span::styled(format!("{:>3}. ", index + 1), Style::default().fg(Color::DarkGray)), Span::styled(miss.question.clone(), Style::default().fg(Color::White).bold())]),
+                Line::from(vec![
+                    Span::raw("     "),
+                    Span::styled(format!("{}answer {}", palette.mark(true), miss.expected), Style::default().fg(palette.good)),
+                    Span::styled(format!("  {}{}", palette.mark(false), given), Style::default().fg(palette.bad)),
+                    Span::styled(format!("  {:.1} s", miss.seconds), Style::default().fg(Color::DarkGray)),
+                ]),
+            ]
+        }).collect()
+    }
+
+    fn last_review_scroll(&self) -> u16 {
+        (self.misses.len() * 2).saturating_sub(1) as u16
+    }
+
+    /// Up/Down and PgUp/PgDn scroll the review, Esc or Enter go back to the summary.
+    fn handle_review_input(&mut self, input: KeyEvent) {
+        let last = self.last_review_scroll();
+        let Some(scroll) = self.review_scroll.as_mut() else { return };
         match input.code {
-            KeyCode::Enter => { self.reset_game_state(); }
-            KeyCode::Esc => { self.exit_intended = true; }
+            KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(last),
+            KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+            KeyCode::PageDown => *scroll = (*scroll + 10).min(last),
+            KeyCode::Home => *scroll = 0,
+            KeyCode::End => *scroll = last,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace => self.review_scroll = None,
             _ => {}
         }
     }
 
+    fn render_review(&self, scroll: u16, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Red))
+            .title(format!(" Mistakes · {} ", self.misses.len()))
+            .title_bottom(Line::from(" ↑↓ scroll  Esc back ").centered());
+        let inner = block.inner(area).inner(Margin { horizontal: 1, vertical: 1 });
+        block.render(area, buf);
+        Paragraph::new(self.review_lines()).scroll((scroll, 0)).render(inner, buf);
+    }
+
+    fn handle_stats_input(&mut self, input: KeyEvent) {
+        if matches!(input.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace) { self.stats_open = false; }
+    }
+
+    /// How often the answers had each bit right, by its position in the number. Rounds that ran
+    /// out of time are left out, they say nothing about which bits get mixed up.
+    fn bit_accuracy(&self) -> BTreeMap<u32, (u32, u32)> {
+        let mut positions: BTreeMap<u32, (u32, u32)> = BTreeMap::new();
+        for round in &self.round_stats {
+            let Some(wrong_bits) = round.wrong_bits else { continue };
+            for bit in 0..round.width {
+                let (answered, right) = positions.entry(round.shift + bit).or_default();
+                *answered += 1;
+                if wrong_bits & (1 << bit) == 0 { *right += 1; }
+            }
+        }
+        positions
+    }
+
+    /// The highest bit on the left, like in the puzzle, each with the share of answers that had it right.
+    fn bit_accuracy_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let positions = self.bit_accuracy();
+        if positions.is_empty() {
+            return vec![Line::from(Span::styled("Every round ran out of time.", Style::default().fg(Color::DarkGray)))];
+        }
+        let palette = theme::palette();
+        let column = (width.saturating_sub(4) as usize / positions.len()).clamp(3, 5);
+        let mut bits = vec![Span::styled("bit ", Style::default().fg(Color::DarkGray))];
+        let mut shares = vec![Span::styled("  % ", Style::default().fg(Color::DarkGray))];
+        for (position, (answered, right)) in positions.iter().rev() {
+            let share = *right as f64 * 100.0 / *answered as f64;
+            let color = if share >= 90.0 { palette.good } else if share >= 70.0 { palette.warn } else { palette.bad };
+            bits.push(Span::styled(format!("{:>column$}", position), Style::default().fg(Color::White)));
+            shares.push(Span::styled(format!("{:>column$.0}", share), Style::default().fg(color)));
+        }
+        let worst = positions.iter()
+            .map(|(position, (answered, right))| (*position, *right as f64 / *answered as f64))
+            .filter(|(_, share)| *share < 1.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let verdict = match worst {
+            Some((position, share)) => format!("Mixed up most: bit {} ({:.0}% right)", position, share * 100.0),
+            None => "Every bit answered right.".to_string(),
+        };
+        vec![Line::from(bits), Line::from(shares), Line::from(Span::styled(verdict, Style::default().fg(Color::Yellow)))]
+    }
+
+    /// Accuracy by bit position, answer time and streak of the run, above its score after every round.
+    fn render_stats(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Stats · {} rounds ", self.round_stats.len()))
+            .title_bottom(Line::from(" Esc back ").centered());
+        let inner = block.inner(area).inner(Margin { horizontal: 1, vertical: 1 });
+        block.render(area, buf);
+
+        let seconds: f64 = self.round_stats.iter().map(|round| round.seconds).sum();
+        let average = seconds / self.round_stats.len().max(1) as f64;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Accuracy ", Style::default().fg(Color::DarkGray)),
+                Span::styled(accuracy_label(self.correct, self.rounds), Style::default().fg(Color::White).bold()),
+                Span::styled("   Avg. answer ", Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{:.1} s", average), Style::default().fg(Color::White).bold()),
+                Span::styled("   Longest streak ", Style::default().fg(Color::DarkGray)),
+                Span::styled(self.max_streak.to_string(), Style::default().fg(Color::White).bold()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Accuracy by bit", Style::default().fg(Color::White).bold())),
+        ];
+        lines.extend(self.bit_accuracy_lines(inner.width));
+        let [text_area, chart_area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 1), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(lines).render(text_area, buf);
+
+        let points: Vec<(f64, f64)> = std::iter::once((0.0, 0.0))
+            .chain(self.round_stats.iter().enumerate().map(|(index, round)| ((index + 1) as f64, round.score as f64)))
+            .collect();
+        let (rounds, top) = (self.round_stats.len().max(1), self.round_stats.iter().map(|round| round.score).max().unwrap_or(0).max(1));
+        Chart::new(vec![Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).light_green().data(&points)])
+            .block(Block::bordered().title("Score by round").title_alignment(Center).dark_gray())
+            .x_axis(Axis::default().title("round").gray().bounds([0.0, rounds as f64]).labels(["0".to_string(), rounds.to_string()]))
+            .y_axis(Axis::default().gray().bounds([0.0, top as f64]).labels(["0".to_string(), top.to_string()]))
+            .render(chart_area, buf);
+    }
+
+    /// The clock of a time attack ran out, the summary opens.
+    fn end_time_attack(&mut self) {
+        self.puzzle_resolved = true;
+        sound::emit(SoundEvent::GameOver);
+        self.open_game_over();
+    }
+
+    fn current_run(&self) -> Run {
+        let seconds = self.time_attack().unwrap_or_else(|| self.clock.elapsed() as u32);
+        Run { score: self.score, answers: self.rounds, correct: self.correct, seconds, played_at: storage::unix_now() }
+    }
+
+    /// Put the finished run on the leaderboard of its difficulty and mode. Practice and unranked runs are left off.
+    fn record_run(&mut self) {
+        if self.untimed || !self.clock.is_ranked() { return; }
+        self.leaderboard_place = self.leaderboards.insert(&self.leaderboard_key(), self.current_run());
+        if self.leaderboard_place.is_some() { let _ = self.leaderboards.save(); }
+    }
+
+    fn open_game_over(&mut self) {
+        self.game_state = GameState::GameOver;
+        self.record_run();
+        if let Some(day) = self.daily.filter(|_| !self.untimed) { record_daily(day, self.score); }
+        self.write_results();
+        let (title, banner, accent) = if self.time_attack().is_some() { ("Time's Up", "TIME UP", Color::Yellow) } else { ("Game Over", "GAME OVER", Color::Red) };
+        self.game_over = Some(Modal::new(title)
+            .banner(banner)
+            .body(self.game_over_lines())
+            .buttons(&self.game_over_buttons())
+            .accent(accent));
+        if self.new_high_score_reached {
+            self.finale = Some(Finale::new("NEW BEST", &self.score.to_string()));
+        }
+    }
+
+    fn game_over_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(Span::styled(format!("Final Score: {}", self.score), Style::default().fg(Color::Green))),
+            Line::from(Span::styled(format!("Previous High: {}", self.prev_high_score_for_display), Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(format!("Rounds Played: {}", self.rounds), Style::default().fg(Color::Magenta))),
+            Line::from(Span::styled(format!("Max Streak: {}", self.max_streak), Style::default().fg(Color::Cyan))),
+            Line::from(Span::styled(format!("Session Time: {}", stats::format_duration(self.clock.elapsed())), Style::default().fg(Color::Blue))),
+        ];
+        if let Some(seconds) = self.time_attack() {
+            let run = self.current_run();
+            lines[2] = Line::from(Span::styled(format!("Answers: {} in {} s, {:.1} per minute", run.answers, seconds, run.answers_per_minute()), Style::default().fg(Color::Magenta)));
+            lines[4] = Line::from(Span::styled(format!("Accuracy: {}", accuracy_label(self.correct, self.rounds)), Style::default().fg(Color::Blue)));
+        }
+        if self.clock.is_ranked() && !self.untimed {
+            let place = match self.leaderboard_place {
+                Some(place) => format!("Place {} of the best {}", place + 1, LEADERBOARD_SIZE),
+                None => format!("Not among the best {}", LEADERBOARD_SIZE),
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(format!("Best {} ({}, {})", LEADERBOARD_SIZE, self.mode_label(), self.mode.label()), Style::default().fg(Color::White).bold())));
+            lines.extend(leaderboard_lines(self.leaderboards.board(&self.leaderboard_key()), self.leaderboard_place));
+            lines.push(Line::from(Span::styled(place, Style::default().fg(Color::Yellow))));
+        }
+        if let Some(day) = self.daily.filter(|_| !self.untimed) {
+            let date = report::format_timestamp(day as u64 * 86_400);
+            let streak = daily_streak(&daily_results(), stats::today());
+            lines.push(Line::from(Span::styled(format!("Daily challenge {} done, {} day{} in a row", &date[..10], streak, if streak == 1 { "" } else { "s" }), Style::default().fg(Color::LightGreen))));
+        }
+        if let Some(reason) = self.clock.unranked_reason() {
+            lines.push(Line::from(Span::styled(format!("Unranked: {}", reason), Style::default().fg(Color::DarkGray))));
+        }
+        if self.new_high_score_reached {
+            lines.insert(1, Line::from(Span::styled("NEW HIGH SCORE!", Style::default().fg(Color::LightGreen).bold())));
+        }
+        if self.lives == 0 {
+            lines.push(Line::from(Span::styled("You lost all your lives.", Style::default().fg(Color::Red))));
+        }
+        if !self.mistakes.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(self.mistakes.lines());
+        }
+        if let Some(message) = &self.export_message {
+            lines.push(Line::from(Span::styled(message.clone(), Style::default().fg(Color::DarkGray))));
+        }
+        lines
+    }
+
+    fn export_report(&mut self) {
+        let report = SessionReport { game: NAME, rounds: &self.round_log, score: self.score, max_streak: self.max_streak };
+        self.export_message = Some(match report.export() {
+            Ok(path) => format!("Report saved to {}", path.display()),
+            Err(error) => format!("Export failed: {}", error),
+        });
+    }
+
+    /// The run so far, for the results file.
+    fn run_results(&self) -> RunResults {
+        RunResults {
+            mode: format!("{}, {}", self.mode_label(), self.mode),
+            score: self.score,
+            max_streak: self.max_streak,
+            finished: self.game_state == GameState::GameOver,
+            rounds: self.round_log.clone(),
+        }
+    }
+
+    /// Rewrite the results file with the finished runs and the current one. A failure is shown on the summary.
+    fn write_results(&mut self) {
+        let Some(path) = &self.export_path else { return };
+        if let Err(error) = self.results().write(path) {
+            self.export_message = Some(format!("Writing {} failed: {}", path.display(), error));
+        }
+    }
+
     fn reset_game_state(&mut self) {
+        if self.export_path.is_some() && !self.round_log.is_empty() {
+            let run = self.run_results();
+            self.results.runs.push(run);
+        }
         self.score = 0;
+        self.correct = 0;
+        self.time_attack_left = self.time_attack().unwrap_or(0) as f64;
+        self.leaderboard_place = None;
+        if self.mode == Mode::Career { self.start_career(); }
         self.streak = 0;
         self.rounds = 0;
         self.lives = self.max_lives.min(3);
         self.game_state = GameState::Active;
         self.max_streak = 0;
-        self.prev_high_score_for_display = self.high_scores.get(self.bits.high_score_key());
+        self.prev_high_score_for_display = self.best_score();
         self.new_high_score_reached = false;
-        self.puzzle = Self::init_puzzle(self.bits.clone(), 0);
+        self.clock = Self::start_clock(&self.bits);
+        if self.hints { self.clock.flag("hints"); }
+        if self.range != NumberRange::Full { self.clock.flag("number range"); }
+        if let Some(day) = self.daily {
+            self.rng = random::seeded(daily_seed(day));
+            self.questions = QuestionBank::new(config::get().question_window as usize);
+        }
+        self.round_log.clear();
+        self.mistakes = MistakeMap::default();
+        self.misses.clear();
+        self.review_scroll = None;
+        self.round_stats.clear();
+        self.stats_open = false;
+        self.export_message = None;
+        self.game_over = None;
+        self.finale = None;
+        self.confetti.clear();
+        self.score_counter.jump_to(0.0);
+        self.start_puzzle(0);
         self.puzzle_resolved = false;
         self.refresh_stats_snapshot();
     }
 
     fn handle_no_result_yet(&mut self, input: KeyEvent) {
+        match input.code {
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.typed_answers = !self.typed_answers;
+                return self.apply_answer_mode();
+            }
+            KeyCode::Char('k') | KeyCode::Char('K') if self.typed_answers => {
+                self.keypad_visible = !self.keypad_visible;
+                return self.apply_answer_mode();
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                self.untimed = !self.untimed;
+                return self.reset_game_state();
+            }
+            _ => {}
+        }
+        if self.puzzle.answer_input.is_some() { return self.handle_typed_input(input); }
         match input.code {
             KeyCode::Right => {
                 // select the next suggestion
@@ -430,36 +1343,211 @@ impl BinaryNumbersGame {
                     }
                 }
             }
-            KeyCode::Enter => {
-                if let Some(selected) = self.puzzle.selected_suggestion {
-                    if self.puzzle.is_correct_guess(selected) {
-                        self.puzzle.guess_result = Some(GuessResult::Correct);
-                    } else {
-                        self.puzzle.guess_result = Some(GuessResult::Incorrect);
-                    }
-                    self.finalize_round();
+            KeyCode::Enter => self.confirm_selection(),
+            KeyCode::Char('s') | KeyCode::Char('S') => self.skip_puzzle(),
+            _ => {}
+        }
+    }
+
+    fn handle_typed_input(&mut self, input: KeyEvent) {
+        if let Some(key) = self.puzzle.keypad.as_mut().and_then(|keypad| keypad.handle_key(input)) {
+            return self.press_answer_key(key);
+        }
+        if let Some(digit) = keymap::digit(input) {
+            return self.press_answer_key(KeypadKey::Digit(digit));
+        }
+        match input.code {
+            KeyCode::Backspace => self.press_answer_key(KeypadKey::Backspace),
+            KeyCode::Enter => self.press_answer_key(KeypadKey::Enter),
+            KeyCode::Char('-') if self.quiz == Quiz::Signed => {
+                if let Some(answer) = self.puzzle.answer_input.as_mut() {
+                    if answer.starts_with('-') { answer.remove(0); } else { answer.insert(0, '-'); }
+                }
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => self.skip_puzzle(),
+            _ => {}
+        }
+    }
+
+    fn press_answer_key(&mut self, key: KeypadKey) {
+        let sign = self.puzzle.answer_input.as_deref().is_some_and(|answer| answer.starts_with('-'));
+        let max_len = self.bits.upper_bound().to_string().len() + sign as usize;
+        let invalid = self.puzzle.answer_input.as_deref().is_some_and(|answer| self.puzzle.answer_problem(answer).is_some());
+        let parsed = self.puzzle.answer_input.as_deref().and_then(|answer| self.puzzle.parse_answer(answer));
+        let Some(answer) = self.puzzle.answer_input.as_mut() else { return };
+        match key {
+            KeypadKey::Digit(digit) if answer.len() < max_len => answer.push(digit),
+            KeypadKey::Digit(_) => {}
+            KeypadKey::Backspace => { answer.pop(); }
+            KeypadKey::Enter if invalid => {} // the input box says why
+            KeypadKey::Enter => {
+                if let Some(value) = parsed {
+                    self.puzzle.selected_suggestion = Some(value);
+                    self.confirm_selection();
+                }
+            }
+        }
+    }
+
+    /// A skipped puzzle counts as a timeout, in practice as a miss since nothing ever times out.
+    fn skip_puzzle(&mut self) {
+        self.puzzle.guess_result = Some(if self.untimed { GuessResult::Incorrect } else { GuessResult::Timeout });
+        if self.untimed { self.puzzle.selected_suggestion = None; }
+        self.finalize_round();
+    }
+
+    fn confirm_selection(&mut self) {
+        if let Some(selected) = self.puzzle.selected_suggestion {
+            if self.puzzle.is_correct_guess(selected) {
+                self.puzzle.guess_result = Some(GuessResult::Correct);
+            } else {
+                self.puzzle.guess_result = Some(GuessResult::Incorrect);
+            }
+            self.finalize_round();
+        }
+    }
+
+    /// The open puzzle as plain text for `binbreak --plain`: the question, and on a second line
+    /// the numbered suggestions when the answer cannot be typed as a number.
+    pub fn plain_question(&self) -> String {
+        let puzzle = &self.puzzle;
+        let scale = puzzle.scale();
+        let question = if scale > 1 { format!("{} x{}", puzzle.question(), scale) } else { puzzle.question() };
+        let ask = match (self.quiz, puzzle.base) {
+            (Quiz::Bitwise, _) => "= ?",
+            (Quiz::Signed, _) => "= ? as a signed number",
+            (_, Base::ToOctal) => "= ? in octal",
+            (_, Base::ToHex) => "= ? in hex",
+            _ => "= ? in decimal",
+        };
+        let mut text = format!("Round {}: {} {}", self.rounds + 1, question, ask);
+        if !puzzle.answers_in_decimal() {
+            let choices: Vec<String> = puzzle.suggestions.iter().enumerate().map(|(index, value)| format!("{}) {}", index + 1, puzzle.format_answer(*value))).collect();
+            text.push_str(&format!("\n   {}", choices.join("   ")));
+        }
+        text
+    }
+
+    /// Seconds left for the open puzzle, `None` in practice.
+    pub fn plain_time_left(&self) -> Option<f64> {
+        (!self.untimed).then_some(self.puzzle.time_left)
+    }
+
+    /// Answer the open puzzle `seconds` after it was asked: the value, the number of a suggestion,
+    /// or nothing to skip. Returns what happened, then the next puzzle is open unless the game is over.
+    /// An answer that cannot be read leaves the puzzle open and returns why.
+    pub fn plain_answer(&mut self, answer: &str, seconds: f64) -> Result<String, String> {
+        let choice = match answer {
+            "" => None,
+            _ if self.puzzle.answers_in_decimal() => {
+                if let Some(problem) = self.puzzle.answer_problem(answer) { return Err(format!("The answer is {}.", problem)); }
+                match self.puzzle.parse_answer(answer) {
+                    Some(value) => Some(value),
+                    None => return Err("Type a number, or nothing to skip.".to_string()),
                 }
             }
-            KeyCode::Char('s') | KeyCode::Char('S') => {
-                // Skip puzzle counts as timeout
-                self.puzzle.guess_result = Some(GuessResult::Timeout);
-                self.finalize_round();
+            _ => match answer.parse::<usize>().ok().and_then(|index| self.puzzle.suggestions.get(index.wrapping_sub(1))) {
+                Some(value) => Some(*value),
+                None => return Err(format!("Pick 1 to {}.", self.puzzle.suggestions.len())),
+            },
+        };
+
+        self.clock.observe(seconds, !self.untimed);
+        self.puzzle.run(seconds);
+        if self.puzzle.guess_result.is_none() {
+            self.puzzle.selected_suggestion = choice;
+            match choice {
+                Some(_) => self.confirm_selection(),
+                None => self.skip_puzzle(),
+            }
+        } else {
+            self.finalize_round();
+        }
+
+        let expected = self.puzzle.format_answer(self.puzzle.current_number);
+        let outcome = match self.puzzle.guess_result {
+            Some(GuessResult::Correct) if self.untimed => "Correct!".to_string(),
+            Some(GuessResult::Correct) => format!("Correct! +{}", self.puzzle.last_points_awarded),
+            Some(GuessResult::Incorrect) => format!("Wrong, it is {}.", expected),
+            _ => format!("Time's up, it is {}.", expected),
+        };
+        match self.game_state {
+            GameState::PendingGameOver => self.open_game_over(),
+            GameState::Result => {
+                self.puzzle.finish_reveal();
+                self.handle_result_available(KeyEvent::from(KeyCode::Enter));
             }
             _ => {}
         }
+        Ok(outcome)
+    }
+
+    /// Score, streak and lives or accuracy after the last answer.
+    pub fn plain_status(&self) -> String {
+        match self.time_attack() {
+            _ if self.untimed => format!("{}/{} correct, {}  streak {}", self.correct, self.rounds, accuracy_label(self.correct, self.rounds), self.streak),
+            Some(_) => format!("score {}  streak {}  {:.0}s left", self.score, self.streak, self.time_attack_left),
+            None => format!("score {}  streak {}  lives {}/{}", self.score, self.streak, self.lives, self.max_lives),
+        }
+    }
+
+    pub fn is_over(&self) -> bool { self.game_state == GameState::GameOver }
+
+    /// The final summary as plain text lines.
+    pub fn plain_summary(&self) -> Vec<String> {
+        self.game_over_lines().iter().map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect()).collect()
+    }
+
+    /// Clicking a suggestion selects it, clicking the selected one confirms it.
+    /// While a result is shown, any click continues like Enter.
+    pub fn handle_game_mouse(&mut self, event: MouseEvent) {
+        if self.review_scroll.is_some() {
+            let code = match event.kind {
+                MouseEventKind::ScrollUp => KeyCode::Up,
+                MouseEventKind::ScrollDown => KeyCode::Down,
+                _ => return,
+            };
+            return self.handle_review_input(KeyEvent::from(code));
+        }
+        if self.stats_open {
+            if event.kind == MouseEventKind::Down(MouseButton::Left) { self.stats_open = false; }
+            return;
+        }
+        if event.kind != MouseEventKind::Down(MouseButton::Left) { return; }
+        if let Some(finale) = self.finale.as_mut() { return finale.skip(); }
+        if self.game_state == GameState::GameOver {
+            let result = self.game_over.as_mut().and_then(|prompt| prompt.handle_mouse(event));
+            return self.answer_game_over(result);
+        }
+        if self.puzzle.guess_result.is_some() {
+            self.handle_result_available(KeyEvent::from(KeyCode::Enter));
+            return;
+        }
+
+        if let Some(key) = self.puzzle.keypad.as_mut().and_then(|keypad| keypad.handle_mouse(event)) {
+            return self.press_answer_key(key);
+        }
+
+        let position = Position::new(event.column, event.row);
+        let clicked = self.puzzle.suggestion_areas.borrow().iter().position(|area| area.contains(position));
+        if let Some(suggestion) = clicked.and_then(|index| self.puzzle.suggestions.get(index).copied()) {
+            if self.puzzle.selected_suggestion == Some(suggestion) {
+                self.confirm_selection();
+            } else {
+                self.puzzle.selected_suggestion = Some(suggestion);
+            }
+        }
     }
 
     fn handle_result_available(&mut self, input: KeyEvent) {
         match input.code {
+            KeyCode::Enter if !self.puzzle.reveal_done() => self.puzzle.finish_reveal(),
             KeyCode::Enter => {
                 match self.game_state {
-                    GameState::PendingGameOver => {
-                        // reveal summary
-                        self.game_state = GameState::GameOver;
-                    }
+                    GameState::PendingGameOver => self.open_game_over(),
                     GameState::Result => {
                         // start next puzzle
-                        self.puzzle = Self::init_puzzle(self.bits.clone(), self.streak);
+                        self.start_puzzle(self.streak);
                         self.puzzle_resolved = false;
                         self.game_state = GameState::Active;
                     }
@@ -475,16 +1563,22 @@ impl BinaryNumbersGame {
     fn refresh_stats_snapshot(&mut self) {
         self.puzzle.stats_snapshot = Some(StatsSnapshot {
             score: self.score,
+            counted_score: self.score_counter.value().round() as u32,
             streak: self.streak,
             max_streak: self.max_streak,
             rounds: self.rounds,
-            lives: self.lives,
             max_lives: self.max_lives,
             bits: self.bits.clone(),
-            hearts: self.lives_hearts(),
-            game_state: self.game_state,
+            base: self.base,
+            quiz: self.quiz,
+            untimed: self.untimed,
+            time_attack: self.time_attack().is_some(),
+            career: self.mode == Mode::Career,
+            correct: self.correct,
+            hearts: self.lives_bar.spans(),
             prev_high_score: self.prev_high_score_for_display,
             new_high_score: self.new_high_score_reached,
+            unranked_reason: self.clock.unranked_reason().map(str::to_string),
         });
     }
 }
@@ -496,20 +1590,357 @@ enum GuessResult {
     Timeout,
 }
 
+impl GuessResult {
+    fn color(self, palette: &Palette) -> Color {
+        match self {
+            GuessResult::Correct => palette.good,
+            GuessResult::Incorrect => palette.bad,
+            GuessResult::Timeout => palette.warn,
+        }
+    }
+}
+
 #[derive(Clone)]
-pub enum Bits { Four, FourShift4, FourShift8, FourShift12, Eight, Twelve, Sixteen, }
+pub enum Bits { Four, FourShift4, FourShift8, FourShift12, Eight, Twelve, Sixteen, Custom { width: u32, seconds: u32 } }
 
 impl Bits {
-    pub fn to_int(&self) -> u32 { match self { Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 4, Bits::Eight => 8, Bits::Twelve => 12, Bits::Sixteen => 16 } }
-    pub fn scale_factor(&self) -> u32 { match self { Bits::Four => 1, Bits::FourShift4 => 16, Bits::FourShift8 => 256, Bits::FourShift12 => 4096, Bits::Eight => 1, Bits::Twelve => 1, Bits::Sixteen => 1 } }
-    pub fn high_score_key(&self) -> u32 { match self { Bits::Four => 4, Bits::FourShift4 => 44, Bits::FourShift8 => 48, Bits::FourShift12 => 412, Bits::Eight => 8, Bits::Twelve => 12, Bits::Sixteen => 16 } }
+    /// The difficulties that are not custom, easiest first.
+    pub const PRESETS: [Bits; 7] = [Bits::Four, Bits::FourShift4, Bits::FourShift8, Bits::FourShift12, Bits::Eight, Bits::Twelve, Bits::Sixteen];
+    /// Range of [`Bits::Custom`] widths; two bits still leave room for three suggestions.
+    pub const CUSTOM_WIDTHS: std::ops::RangeInclusive<u32> = 2..=16;
+    /// Range of [`Bits::Custom`] seconds per round.
+    pub const CUSTOM_SECONDS: std::ops::RangeInclusive<u32> = 3..=60;
+
+    pub fn to_int(&self) -> u32 { match self { Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 4, Bits::Eight => 8, Bits::Twelve => 12, Bits::Sixteen => 16, Bits::Custom { width, .. } => *width } }
+    pub fn scale_factor(&self) -> u32 { match self { Bits::Four => 1, Bits::FourShift4 => 16, Bits::FourShift8 => 256, Bits::FourShift12 => 4096, Bits::Eight => 1, Bits::Twelve => 1, Bits::Sixteen => 1, Bits::Custom { .. } => 1 } }
+    /// Custom keys carry their width and time, `1000 + width * 100 + seconds`; their scores are never kept.
+    pub fn high_score_key(&self) -> u32 { match self { Bits::Four => 4, Bits::FourShift4 => 44, Bits::FourShift8 => 48, Bits::FourShift12 => 412, Bits::Eight => 8, Bits::Twelve => 12, Bits::Sixteen => 16, Bits::Custom { width, seconds } => 1000 + width * 100 + seconds } }
+    pub fn from_high_score_key(key: u32) -> Option<Bits> { match key { 4 => Some(Bits::Four), 44 => Some(Bits::FourShift4), 48 => Some(Bits::FourShift8), 412 => Some(Bits::FourShift12), 8 => Some(Bits::Eight), 12 => Some(Bits::Twelve), 16 => Some(Bits::Sixteen), 1000.. => Bits::custom((key - 1000) / 100, key % 100), _ => None } }
+    /// A custom difficulty, if `width` and `seconds` are in range.
+    pub fn custom(width: u32, seconds: u32) -> Option<Bits> { (Self::CUSTOM_WIDTHS.contains(&width) && Self::CUSTOM_SECONDS.contains(&seconds)).then_some(Bits::Custom { width, seconds }) }
+    pub fn is_custom(&self) -> bool { matches!(self, Bits::Custom { .. }) }
     pub fn upper_bound(&self) -> u32 { (u32::pow(2, self.to_int()) - 1) * self.scale_factor() }
-    pub fn suggestion_count(&self) -> usize { match self { Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 3, Bits::Eight => 4, Bits::Twelve => 5, Bits::Sixteen => 6 } }
-    pub fn label(&self) -> &'static str { match self { Bits::Four => "4 bits", Bits::FourShift4 => "4 bits*16", Bits::FourShift8 => "4 bits*256", Bits::FourShift12 => "4 bits*4096", Bits::Eight => "8 bits", Bits::Twelve => "12 bits", Bits::Sixteen => "16 bits" } }
+    /// Bits of the scaled value, e.g. 12 for 4 bits*256.
+    pub fn total_width(&self) -> u32 { self.to_int() + self.scale_factor().trailing_zeros() }
+    /// Seconds per round without a streak, before the difficulty curve applies.
+    pub fn base_time(&self) -> f64 { match self { Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 8.0, Bits::Eight => 12.0, Bits::Twelve => 16.0, Bits::Sixteen => 20.0, Bits::Custom { seconds, .. } => *seconds as f64 } }
+    pub fn suggestion_count(&self) -> usize { match self { Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 3, Bits::Eight => 4, Bits::Twelve => 5, Bits::Sixteen => 6, Bits::Custom { width, .. } => match width { ..=4 => 3, 5..=8 => 4, 9..=12 => 5, _ => 6 } } }
+    pub fn label(&self) -> &'static str { match self { Bits::Four => "4 bits", Bits::FourShift4 => "4 bits*16", Bits::FourShift8 => "4 bits*256", Bits::FourShift12 => "4 bits*4096", Bits::Eight => "8 bits", Bits::Twelve => "12 bits", Bits::Sixteen => "16 bits", Bits::Custom { .. } => "custom" } }
+}
+
+/// How the number of a puzzle is written, and which way it is converted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Base {
+    /// Binary digits to decimal, the original puzzle.
+    #[default]
+    Binary,
+    Octal,
+    Hex,
+    /// A decimal value to find among octal numbers.
+    ToOctal,
+    /// A decimal value to find among hexadecimal numbers.
+    ToHex,
+}
+
+impl Base {
+    pub fn radix(self) -> u32 { match self { Base::Binary => 2, Base::Octal | Base::ToOctal => 8, Base::Hex | Base::ToHex => 16 } }
+    pub fn bits_per_digit(self) -> u32 { match self { Base::Binary => 1, Base::Octal | Base::ToOctal => 3, Base::Hex | Base::ToHex => 4 } }
+    /// Whether the answer is a decimal number; the `To` bases ask for the written form instead.
+    pub fn answers_in_decimal(self) -> bool { matches!(self, Base::Binary | Base::Octal | Base::Hex) }
+    pub fn label(self) -> &'static str { match self { Base::Binary => "binary", Base::Octal => "octal", Base::Hex => "hex", Base::ToOctal => "dec → octal", Base::ToHex => "dec → hex" } }
+    pub fn prefix(self) -> &'static str { match self { Base::Binary => "0b", Base::Octal | Base::ToOctal => "0o", Base::Hex | Base::ToHex => "0x" } }
+    /// The base after this one, back to binary after the last.
+    pub fn next(self) -> Base { Base::iter().skip_while(|base| *base != self).nth(1).unwrap_or_default() }
+    /// Digits needed for every value of `bits`, including the scale of the shifted modes.
+    pub fn digits(self, bits: &Bits) -> usize { bits.total_width().div_ceil(self.bits_per_digit()) as usize }
+
+    /// `value` with `digits` digits, without prefix, e.g. `0F`.
+    pub fn format(self, value: u32, digits: usize) -> String {
+        match self.radix() {
+            2 => format!("{:0digits$b}", value),
+            8 => format!("{:0digits$o}", value),
+            _ => format!("{:0digits$X}", value),
+        }
+    }
+}
+
+/// What a round asks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Quiz {
+    /// A number to convert, written in the game's [`Base`].
+    #[default]
+    Convert,
+    /// The result of AND, OR, XOR or a shift on binary operands, chosen in binary.
+    Bitwise,
+    /// Bits read as a two's complement number, so the highest bit counts negative.
+    Signed,
+}
+
+impl Quiz {
+    pub fn label(self) -> &'static str { match self { Quiz::Convert => "convert", Quiz::Bitwise => "bitwise", Quiz::Signed => "signed" } }
+    /// Whether answers are decimal numbers, the only kind that can be typed.
+    pub fn answers_in_decimal(self, base: Base) -> bool { match self { Quiz::Convert => base.answers_in_decimal(), Quiz::Bitwise => false, Quiz::Signed => true } }
+    /// The quiz after this one, back to converting after the last.
+    pub fn next(self) -> Quiz { Quiz::iter().skip_while(|quiz| *quiz != self).nth(1).unwrap_or_default() }
+}
+
+/// How a run ends.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Mode {
+    /// When the lives run out.
+    #[default]
+    Lives,
+    /// When a single clock of this many seconds runs out; misses cost no lives.
+    TimeAttack(u32),
+    /// When the lives run out, with the bit width going up and down with the player's form.
+    Career,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 4] = [Mode::Lives, Mode::TimeAttack(60), Mode::TimeAttack(120), Mode::Career];
+
+    pub fn label(self) -> String {
+        match self {
+            Mode::Lives => "lives".to_string(),
+            Mode::TimeAttack(seconds) => format!("time attack {} s", seconds),
+            Mode::Career => "career".to_string(),
+        }
+    }
+
+    /// The mode after this one in [`Mode::ALL`], back to lives after the last.
+    pub fn next(self) -> Mode {
+        Mode::ALL.iter().skip_while(|mode| **mode != self).nth(1).copied().unwrap_or_default()
+    }
+}
+
+/// The numbers a conversion may ask, to drill part of the span of a bit width.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NumberRange {
+    /// Every number of the bit width.
+    #[default]
+    Full,
+    /// The numbers from the first to the second, both included, e.g. 0 to 63.
+    Between(u32, u32),
+    /// The powers of two and their neighbours, 2ⁿ-1 and 2ⁿ+1, as bit patterns.
+    PowersOfTwo,
+}
+
+impl NumberRange {
+    /// The ranges the start menu goes through.
+    pub const PRESETS: [NumberRange; 5] = [NumberRange::Full, NumberRange::Between(0, 15), NumberRange::Between(0, 63), NumberRange::Between(0, 255), NumberRange::PowersOfTwo];
+
+    pub fn label(self) -> String {
+        match self {
+            NumberRange::Full => "all".to_string(),
+            NumberRange::Between(low, high) => format!("{} to {}", low, high),
+            NumberRange::PowersOfTwo => "2ⁿ and 2ⁿ±1".to_string(),
+        }
+    }
+
+    /// The range after this one in [`NumberRange::PRESETS`], back to all numbers after the last.
+    pub fn next(self) -> NumberRange {
+        NumberRange::PRESETS.iter().skip_while(|range| **range != self).nth(1).copied().unwrap_or_default()
+    }
+
+    /// The bit patterns of `width` bits whose number, times `scale`, lies in the range. `None`
+    /// when that is all of them, or none, and the puzzle draws from the whole width.
+    fn raw_values(self, width: u32, scale: u32) -> Option<Vec<u32>> {
+        let max = u32::pow(2, width) - 1;
+        let values: Vec<u32> = match self {
+            NumberRange::Full => return None,
+            NumberRange::Between(low, high) => (low.div_ceil(scale)..=(high / scale).min(max)).collect(),
+            NumberRange::PowersOfTwo => {
+                let mut values: Vec<u32> = (0..=width).flat_map(|n| [(1 << n) - 1, 1 << n, (1 << n) + 1]).filter(|value| *value <= max).collect();
+                values.sort_unstable();
+                values.dedup();
+                values
+            }
+        };
+        (!values.is_empty() && values.len() <= max as usize).then_some(values)
+    }
+}
+
+/// `full`, `0-63` or `powers-of-two`, the form of `--range` and the saves.
+impl std::fmt::Display for NumberRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberRange::Full => write!(f, "full"),
+            NumberRange::Between(low, high) => write!(f, "{}-{}", low, high),
+            NumberRange::PowersOfTwo => write!(f, "powers-of-two"),
+        }
+    }
+}
+
+impl std::str::FromStr for NumberRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('-') {
+            _ if s == "full" => Ok(NumberRange::Full),
+            _ if s == "powers-of-two" => Ok(NumberRange::PowersOfTwo),
+            Some((low, high)) => match (low.trim().parse(), high.trim().parse()) {
+                (Ok(low), Ok(high)) if low <= high => Ok(NumberRange::Between(low, high)),
+                _ => Err(format!("expected LOW-HIGH with LOW at most HIGH: {}", s)),
+            },
+            None => Err(format!("unknown range: {}", s)),
+        }
+    }
+}
+
+/// `lives`, `time-attack-60` or `career`, the form kept in saves and leaderboard keys.
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Lives => write!(f, "lives"),
+            Mode::TimeAttack(seconds) => write!(f, "time-attack-{}", seconds),
+            Mode::Career => write!(f, "career"),
+        }
+    }
+}
+
+impl std::str::FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("time-attack-") {
+            _ if s == "lives" => Ok(Mode::Lives),
+            _ if s == "career" => Ok(Mode::Career),
+            Some(seconds) => seconds.parse().map(Mode::TimeAttack).map_err(|_| format!("not a number of seconds: {}", seconds)),
+            None => Err(format!("unknown mode: {}", s)),
+        }
+    }
+}
+
+/// Bit widths of the career levels, lowest first.
+const CAREER_WIDTHS: [u32; 4] = [4, 8, 12, 16];
+/// Misses in a row that cost a career level.
+const CAREER_DROP_AFTER: u32 = 3;
+
+/// The level of a career at `width` bits, counting from 1.
+fn career_level(width: u32) -> usize {
+    CAREER_WIDTHS.iter().position(|level| *level == width).map_or(1, |index| index + 1)
+}
+
+fn career_bits(width: u32) -> Bits {
+    match width {
+        ..=4 => Bits::Four,
+        5..=8 => Bits::Eight,
+        9..=12 => Bits::Twelve,
+        _ => Bits::Sixteen,
+    }
+}
+
+/// Announces a new career level between rounds: a box opening from the middle, blinking for a moment.
+struct LevelBanner {
+    text: String,
+    up: bool,
+    opening: Animator,
+    elapsed: f64,
+}
+
+impl LevelBanner {
+    const SECONDS: f64 = 1.6;
+
+    fn new(width: u32, up: bool) -> Self {
+        let arrow = if up { "▲ LEVEL UP" } else { "▼ LEVEL DOWN" };
+        let mut opening = Animator::new(0.0, 0.3, Easing::EaseOut);
+        opening.animate_to(1.0);
+        Self { text: format!("{}  {} · {} BITS", arrow, career_level(width), width), up, opening, elapsed: 0.0 }
+    }
+
+    fn tick(&mut self, dt: f64) {
+        self.opening.tick(dt);
+        self.elapsed += dt;
+    }
+
+    fn is_done(&self) -> bool { self.elapsed >= Self::SECONDS }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let full = self.text.chars().count() as u16 + 4;
+        let width = ((full as f64 * self.opening.value()).round() as u16).clamp(2, full);
+        let box_area = Rect { height: 3, ..center(area, Constraint::Length(width)) };
+        let box_area = Rect { y: box_area.y.saturating_sub(1), ..box_area }.intersection(area);
+        let palette = theme::palette();
+        let colors = if self.up { [palette.good_light, Color::Yellow] } else { [palette.bad_light, Color::Gray] };
+        let color = colors[(self.elapsed / 0.2) as usize % 2];
+        Clear.render(box_area, buf);
+        let block = Block::bordered().border_type(BorderType::Double).border_style(Style::default().fg(color));
+        let inner = block.inner(box_area);
+        block.render(box_area, buf);
+        Paragraph::new(Span::styled(self.text.clone(), Style::default().fg(color).bold())).alignment(Center).render(inner, buf);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Operator { And, Or, Xor, ShiftLeft, ShiftRight }
+
+impl Operator {
+    const ALL: [Operator; 5] = [Operator::And, Operator::Or, Operator::Xor, Operator::ShiftLeft, Operator::ShiftRight];
+
+    fn symbol(self) -> &'static str { match self { Operator::And => "AND", Operator::Or => "OR", Operator::Xor => "XOR", Operator::ShiftLeft => "<<", Operator::ShiftRight => ">>" } }
+    fn is_shift(self) -> bool { matches!(self, Operator::ShiftLeft | Operator::ShiftRight) }
+}
+
+/// The question of a [`Quiz::Bitwise`] round. For shifts `right` is the distance.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Operation { left: u32, operator: Operator, right: u32 }
+
+impl Operation {
+    /// Operands of `width` bits; shifts move by one to three places.
+    fn random(width: u32, questions: &mut QuestionBank, rng: &mut impl Rng) -> Self {
+        let max = u32::pow(2, width) - 1;
+        let operator = Operator::ALL[rng.random_range(0..Operator::ALL.len())];
+        let right = if operator.is_shift() { rng.random_range(1..=(width - 1).clamp(1, 3)) } else { rng.random_range(0..=max) };
+        Self { left: questions.pick(max, rng), operator, right }
+    }
+
+    /// The result, with bits shifted out of `width` dropped.
+    fn result(&self, width: u32) -> u32 {
+        let mask = u32::pow(2, width) - 1;
+        match self.operator {
+            Operator::And => self.left & self.right,
+            Operator::Or => self.left | self.right,
+            Operator::Xor => self.left ^ self.right,
+            Operator::ShiftLeft => (self.left << self.right) & mask,
+            Operator::ShiftRight => self.left >> self.right,
+        }
+    }
+
+    /// Results of the operations this one is easily mistaken for: the other logic operators,
+    /// or the other direction and neighbouring distances of a shift.
+    fn near_misses(&self, width: u32) -> Vec<u32> {
+        let others: Vec<Operation> = if self.operator.is_shift() {
+            [Operator::ShiftLeft, Operator::ShiftRight].into_iter()
+                .flat_map(|operator| [self.right.saturating_sub(1), self.right, self.right + 1].map(|right| Operation { operator, right, ..*self }))
+                .filter(|other| other.right > 0 && other.right < width)
+                .collect()
+        } else {
+            [Operator::And, Operator::Or, Operator::Xor].map(|operator| Operation { operator, ..*self }).to_vec()
+        };
+        others.iter().map(|other| other.result(width)).collect()
+    }
+}
+
+/// `raw` read as a two's complement number of `width` bits.
+fn signed_value(raw: u32, width: u32) -> i64 {
+    let half = 1i64 << (width - 1);
+    if raw as i64 >= half { raw as i64 - 2 * half } else { raw as i64 }
+}
+
+/// Bit patterns of the values a two's complement reading of `raw` is easily mistaken for:
+/// the other sign, and one off for forgetting the +1 after inverting.
+fn signed_near_misses(raw: u32, width: u32) -> Vec<u32> {
+    let mask = u32::pow(2, width) - 1;
+    vec![raw.wrapping_neg() & mask, raw.wrapping_add(1) & mask, raw.wrapping_sub(1) & mask]
 }
 
 pub struct BinaryNumbersPuzzle {
     bits: Bits,
+    base: Base,
+    quiz: Quiz,
+    operation: Option<Operation>, // the question of a bitwise round, `None` when converting
     current_number: u32, // scaled value used for suggestions matching
     raw_current_number: u32, // raw bit value (unscaled) for display
     suggestions: Vec<u32>,
@@ -519,33 +1950,64 @@ pub struct BinaryNumbersPuzzle {
     guess_result: Option<GuessResult>,
     last_points_awarded: u32,
     stats_snapshot: Option<StatsSnapshot>, // NEW: integrated stats
+    suggestion_areas: RefCell<Vec<Rect>>, // last rendered suggestion boxes, used for mouse hit testing
+    reveal_elapsed: f64, // seconds since the guess, drives the bit-by-bit reveal of the answer
+    answer_input: Option<String>, // typed answer, `None` when choosing from the suggestions
+    keypad: Option<Keypad>,
+    untimed: bool, // practice: the clock never runs out
+    answer_elapsed: f64, // seconds spent on the question so far, also in practice and time attacks
+    hints: bool, // show what each group of four bits is worth in hex
 }
 
 impl BinaryNumbersPuzzle {
-    pub fn new(bits: Bits, streak: u32) -> Self {
-        let mut rng = rand::rng();
-
-        let mut suggestions = Vec::new();
-        let scale = bits.scale_factor();
-        while suggestions.len() < bits.suggestion_count() {
-            let raw = rng.random_range(0..=u32::pow(2, bits.to_int()) - 1);
+    /// Bitwise results are offered in binary, as many as fit next to each other.
+    const SUGGESTION_ROW: u32 = 63;
+
+    /// `range` limits the numbers of conversions, the other quizzes always use all of them.
+    pub fn new(bits: Bits, base: Base, quiz: Quiz, range: NumberRange, streak: u32, questions: &mut QuestionBank, rng: &mut impl Rng) -> Self {
+
+        // bitwise and signed rounds are plain binary, without the scale of the shifted modes
+        let width = bits.to_int();
+        let (base, scale, operation) = match quiz {
+            Quiz::Convert => (base, bits.scale_factor(), None),
+            Quiz::Bitwise => (Base::Binary, 1, Some(Operation::random(width, questions, rng))),
+            Quiz::Signed => (Base::Binary, 1, None),
+        };
+        let range = if quiz == Quiz::Convert { range.raw_values(width, scale) } else { None };
+        let mut suggestions = match (operation, &range) {
+            (Some(operation), _) => vec![operation.result(width)],
+            (None, Some(values)) => vec![questions.pick_by(rng, |rng| values[rng.random_range(0..values.len())]) * scale],
+            (None, None) => vec![questions.pick(u32::pow(2, width) - 1, rng) * scale],
+        };
+        let count = match operation {
+            Some(_) => bits.suggestion_count().min((Self::SUGGESTION_ROW / (width + 3)) as usize).max(2),
+            None => bits.suggestion_count(),
+        };
+        // the other choices come from the range too while it has enough numbers, or they give the answer away
+        let range = range.filter(|values| values.len() >= count);
+        let near_misses = match (quiz, operation) {
+            (_, Some(operation)) => operation.near_misses(width),
+            (Quiz::Signed, None) => signed_near_misses(suggestions[0], width),
+            _ => Vec::new(),
+        };
+        for near_miss in near_misses {
+            if suggestions.len() < count && !suggestions.contains(&near_miss) { suggestions.push(near_miss); }
+        }
+        while suggestions.len() < count {
+            let raw = match &range {
+                Some(values) => values[rng.random_range(0..values.len())],
+                None => rng.random_range(0..=u32::pow(2, width) - 1),
+            };
             let num = raw * scale;
             if !suggestions.contains(&num) { suggestions.push(num); }
         }
 
         let current_number = suggestions[0]; // scaled value
         let raw_current_number = current_number / scale; // back-calculate raw bits
-        suggestions.shuffle(&mut rng);
-
-        // Base time by bits + difficulty scaling (shorter as streak increases)
-        let base_time = match bits {
-            Bits::Four | Bits::FourShift4 | Bits::FourShift8 | Bits::FourShift12 => 8.0,
-            Bits::Eight => 12.0,
-            Bits::Twelve => 16.0,
-            Bits::Sixteen => 20.0,
-        };
-        let penalty = (streak as f64) * 0.5; // 0.5s less per streak
-        let time_total = (base_time - penalty).max(5.0);
+        suggestions.shuffle(rng);
+
+        // Base time by bits + difficulty curve (shorter as streak increases)
+        let time_total = scoring::rules().round_time(bits.base_time(), streak);
         let time_left = time_total;
         let selected_suggestion = Some(suggestions[0]);
         let guess_result = None;
@@ -553,6 +2015,9 @@ impl BinaryNumbersPuzzle {
 
         Self {
             bits,
+            base,
+            quiz,
+            operation,
             current_number,
             raw_current_number,
             suggestions,
@@ -562,12 +2027,84 @@ impl BinaryNumbersPuzzle {
             guess_result,
             last_points_awarded,
             stats_snapshot: None,
+            suggestion_areas: RefCell::new(Vec::new()),
+            reveal_elapsed: 0.0,
+            answer_input: None,
+            keypad: None,
+            untimed: false,
+            answer_elapsed: 0.0,
+            hints: false,
         }
     }
 
     pub fn suggestions(&self) -> &[u32] { &self.suggestions }
     pub fn is_correct_guess(&self, guess: u32) -> bool { guess == self.current_number }
 
+    fn answers_in_decimal(&self) -> bool { self.quiz.answers_in_decimal(self.base) }
+
+    /// Only conversions scale the shifted modes, the other quizzes use their bits as they are.
+    fn scale(&self) -> u32 { if self.quiz == Quiz::Convert { self.bits.scale_factor() } else { 1 } }
+
+    /// Smallest and largest two's complement value of the puzzle's width.
+    fn signed_range(&self) -> (i64, i64) {
+        let half = 1i64 << (self.bits.to_int() - 1);
+        (-half, half - 1)
+    }
+
+    /// A suggestion as the player answers it: decimal, signed for two's complement, in binary for
+    /// a bitwise result, or written in the base of a `To` puzzle.
+    fn format_answer(&self, value: u32) -> String {
+        match self.quiz {
+            Quiz::Bitwise => format!("{:0w$b}", value, w = self.bits.to_int() as usize),
+            Quiz::Signed => signed_value(value, self.bits.to_int()).to_string(),
+            Quiz::Convert if self.base.answers_in_decimal() => value.to_string(),
+            Quiz::Convert => format!("{}{}", self.base.prefix(), self.base.format(value, self.base.digits(&self.bits))),
+        }
+    }
+
+    /// The suggestion a typed answer stands for; in signed rounds the bit pattern of the value.
+    fn parse_answer(&self, answer: &str) -> Option<u32> {
+        match self.quiz {
+            Quiz::Signed => {
+                let value = answer.parse::<i64>().ok()?;
+                Some((value & ((1i64 << self.bits.to_int()) - 1)) as u32)
+            }
+            _ => answer.parse().ok(),
+        }
+    }
+
+    /// The digits the reveal steps through, highest first: the raw bits in binary, otherwise
+    /// the scaled value in the puzzle's base.
+    fn written_digits(&self) -> Vec<u32> {
+        let text = match self.base {
+            Base::Binary => self.current_to_binary_string().replace(' ', ""),
+            base => base.format(self.current_number, base.digits(&self.bits)),
+        };
+        text.chars().filter_map(|c| c.to_digit(16)).collect()
+    }
+
+    /// What a digit at `position` (0 for the lowest) is worth; the highest bit of a signed
+    /// round counts negative.
+    fn place_value(&self, position: usize) -> i64 {
+        match self.base {
+            Base::Binary if self.quiz == Quiz::Signed && position as u32 == self.bits.to_int() - 1 => -(1 << position),
+            Base::Binary => (1i64 << position) * self.scale() as i64,
+            base => (base.radix() as i64).pow(position as u32),
+        }
+    }
+
+    /// The question as one line of text, written the way the puzzle shows it.
+    fn question(&self) -> String {
+        let width = self.bits.to_int() as usize;
+        match (self.operation, self.base) {
+            (Some(operation), _) if operation.operator.is_shift() => format!("{:0width$b} {} {}", operation.left, operation.operator.symbol(), operation.right),
+            (Some(operation), _) => format!("{:0width$b} {} {:0width$b}", operation.left, operation.operator.symbol(), operation.right),
+            (None, Base::Binary) => self.current_to_binary_string(),
+            (None, Base::Octal | Base::Hex) => format!("{}{}", self.base.prefix(), self.base.format(self.current_number, self.base.digits(&self.bits))),
+            (None, Base::ToOctal | Base::ToHex) => self.current_number.to_string(),
+        }
+    }
+
     pub fn current_to_binary_string(&self) -> String {
         let width = self.bits.to_int() as usize;
         let raw = format!("{:0width$b}", self.raw_current_number, width = width);
@@ -579,11 +2116,75 @@ impl BinaryNumbersPuzzle {
             .join(" ")
     }
 
+    /// Seconds between two bits lighting up during the reveal.
+    const REVEAL_STEP: f64 = 0.15;
+
+    fn revealed_digits(&self) -> usize {
+        if self.guess_result.is_none() { return 0; }
+        ((self.reveal_elapsed / Self::REVEAL_STEP) as usize).min(self.written_digits().len())
+    }
+
+    pub fn reveal_done(&self) -> bool { self.revealed_digits() == self.written_digits().len() }
+
+    pub fn finish_reveal(&mut self) { self.reveal_elapsed = Self::REVEAL_STEP * self.written_digits().len() as f64; }
+
+    /// The written digits, binary in groups of four and the other bases behind their prefix;
+    /// after a guess they light up from the highest digit down.
+    fn digit_spans(&self) -> Vec<Span<'static>> {
+        let revealed = self.revealed_digits();
+        let mut spans = Vec::new();
+        if self.base != Base::Binary { spans.push(Span::styled(self.base.prefix(), Style::default().fg(Color::DarkGray))); }
+        for (i, digit) in self.written_digits().into_iter().enumerate() {
+            if self.base == Base::Binary && i > 0 && i % 4 == 0 { spans.push(Span::raw(" ")); }
+            let style = match (i < revealed, digit) {
+                (false, _) => Style::default(),
+                (true, 1..) if i + 1 == revealed => Style::default().fg(Color::Black).bg(Color::LightGreen).bold(),
+                (true, 1..) => Style::default().fg(Color::LightGreen).bold(),
+                (true, _) => Style::default().fg(Color::DarkGray),
+            };
+            spans.push(Span::styled(char::from_digit(digit, 16).unwrap_or('?').to_ascii_uppercase().to_string(), style));
+        }
+        spans
+    }
+
+    /// With hints on, the hex digit of every group of four bits, centered under the group.
+    /// Only for binary numbers that split into whole groups.
+    fn hint_line(&self) -> Option<Line<'static>> {
+        let digits = self.written_digits();
+        if !self.hints || self.guess_result.is_some() || self.operation.is_some() || self.base != Base::Binary || digits.len() % 4 != 0 { return None; }
+        let groups: Vec<String> = digits.chunks(4)
+            .map(|group| format!("{:^4}", format!("{:X}", group.iter().fold(0, |value, digit| value * 2 + digit))))
+            .collect();
+        Some(Line::from(Span::styled(groups.join(" "), Style::default().fg(Color::DarkGray))))
+    }
+
+    /// What the revealed digits are worth and their running total, e.g. `8 + 2 = 10` in binary,
+    /// `-8 + 2 = -6` in a signed round or `3×16 + 15 = 63` in hex.
+    fn revealed_sum_line(&self) -> Line<'static> {
+        let digits = self.written_digits();
+        let terms: Vec<(String, i64)> = digits.iter().enumerate().take(self.revealed_digits())
+            .filter(|(_, digit)| **digit != 0)
+            .map(|(i, digit)| {
+                let (digit, place) = (*digit as i64, self.place_value(digits.len() - 1 - i));
+                let text = if self.base == Base::Binary || place == 1 { (digit * place).to_string() } else { format!("{}×{}", digit, place) };
+                (text, digit * place)
+            })
+            .collect();
+        let total: i64 = terms.iter().map(|(_, value)| value).sum();
+        let color = if self.reveal_done() { Color::LightGreen } else { Color::Gray };
+        let sum = terms.iter().map(|(text, _)| text.as_str()).collect::<Vec<_>>().join(" + ");
+        let text = if terms.len() > 1 || terms.first().is_some_and(|(text, _)| text.contains('×')) { format!("{} = {}", sum, total) } else { total.to_string() };
+        Line::from(Span::styled(text, Style::default().fg(color)))
+    }
+
     pub fn run(&mut self, dt: f64) {
         if self.guess_result.is_some() {
-            // If a guess has been made, we don't need to run the game logic anymore.
+            // If a guess has been made, only the reveal animation keeps running.
+            if config::get().reduce_motion { self.finish_reveal(); } else { self.reveal_elapsed += dt; }
             return;
         }
+        self.answer_elapsed += dt;
+        if self.untimed { return; }
 
         self.time_left = (self.time_left - dt).max(0.0);
 
@@ -600,38 +2201,38 @@ impl Widget for &mut BinaryNumbersGame {
 }
 
 // Simple ASCII gauge renderer to avoid variable glyph heights from Unicode block elements
-fn render_ascii_gauge(area: Rect, buf: &mut Buffer, ratio: f64, color: Color) {
-    let clamped = if ratio < 0.0 { 0.0 } else if ratio > 1.0 { 1.0 } else { ratio };
-    let fill_width = ((area.width as f64) * clamped).round().min(area.width as f64) as u16;
-    if area.height == 0 { return; }
-    for x in 0..area.width {
-        let filled = x < fill_width;
-        let symbol = if filled { "=" } else { " " };
-        let style = if filled { Style::default().fg(color) } else { Style::default().fg(Color::DarkGray) };
-        let cell = buf.get_mut(area.x + x, area.y);
-        cell.set_symbol(symbol);
-        cell.set_style(style);
-    }
-}
-
 // NEW: HighScores management
-struct HighScores { scores: HashMap<u32, u32>, }
+struct HighScores { scores: HashMap<String, u32>, }
 
 impl HighScores {
-    const FILE: &'static str = "binbreak_highscores.txt";
+    const SCHEMA: Schema = Schema { namespace: storage::SCORES, key: "binary_numbers", steps: &[migration::unversioned] };
+    const LEGACY_FILE: &'static str = "binbreak_highscores.txt"; // pre-storage location in the working directory
 
     fn empty() -> Self { Self { scores: HashMap::new() } }
 
+    /// Binary scores keep the bare [`Bits::high_score_key`] they had before bases existed,
+    /// the other bases and quizzes prefix it, e.g. `hex.8` or `bitwise.8`.
+    fn key(bits: &Bits, base: Base, quiz: Quiz) -> String {
+        match (quiz, base) {
+            (Quiz::Convert, Base::Binary) => bits.high_score_key().to_string(),
+            (Quiz::Convert, base) => format!("{}.{}", base, bits.high_score_key()),
+            (quiz, _) => format!("{}.{}", quiz, bits.high_score_key()),
+        }
+    }
+
     fn load() -> Self {
         let mut hs = Self::empty();
-        if let Ok(mut file) = File::open(Self::FILE) {
-            let mut contents = String::new();
-            if file.read_to_string(&mut contents).is_ok() {
-                for line in contents.lines() {
-                    if let Some((k,v)) = line.split_once('=') {
-                        if let (Ok(bits), Ok(score)) = (k.trim().parse::<u32>(), v.trim().parse::<u32>()) {
-                            hs.scores.insert(bits, score);
-                        }
+        let contents = match migration::load(&Self::SCHEMA) {
+            Ok(Some(contents)) => Some(contents),
+            Ok(None) if !storage::is_guest() => std::fs::read_to_string(Self::LEGACY_FILE).ok()
+                .and_then(|legacy| migration::upgrade(&Self::SCHEMA, &legacy).ok()),
+            _ => None,
+        };
+        if let Some(contents) = contents {
+            for line in contents.lines() {
+                if let Some((k,v)) = line.split_once('=') {
+                    if let Ok(score) = v.trim().parse::<u32>() {
+                        hs.scores.insert(k.trim().to_string(), score);
                     }
                 }
             }
@@ -642,21 +2243,198 @@ impl HighScores {
     fn save(&self) -> std::io::Result<()> {
         let mut data = String::new();
         for key in [4u32,44u32,48u32,412u32,8u32,12u32,16u32] { // include new shifted variant key
-            let val = self.get(key);
+            let val = self.get(&key.to_string());
+            data.push_str(&format!("{}={}\n", key, val));
+        }
+        let mut other_bases: Vec<_> = self.scores.iter().filter(|(key, _)| key.contains('.')).collect();
+        other_bases.sort();
+        for (key, val) in other_bases {
             data.push_str(&format!("{}={}\n", key, val));
         }
-        let mut file = File::create(Self::FILE)?;
-        file.write_all(data.as_bytes())
+        migration::save(&Self::SCHEMA, &data)
     }
 
-    fn get(&self, bits: u32) -> u32 {
-        *self.scores.get(&bits).unwrap_or(&0)
+    fn get(&self, key: &str) -> u32 {
+        *self.scores.get(key).unwrap_or(&0)
     }
 
-    fn update(&mut self, bits: u32, score: u32) {
-        self.scores.insert(bits, score);
+    fn update(&mut self, key: &str, score: u32) {
+        self.scores.insert(key.to_string(), score);
     }
 }
 
+/// A round answered wrong or left to run out, listed in the review after the game.
+struct Miss {
+    question: String,
+    expected: String,
+    given: String, // `-` when the time ran out
+    seconds: f64,
+}
+
+/// What the statistics after the game keep of every round.
+struct RoundStat {
+    score: u32, // the score once the round was answered
+    seconds: f64,
+    shift: u32, // bits below the lowest one asked, in the shifted modes
+    width: u32,
+    wrong_bits: Option<u32>, // the bits the answer got wrong, `None` when the time ran out
+}
+
+/// A finished run, kept on the leaderboard of its difficulty and mode.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub score: u32,
+    pub answers: u32,
+    pub correct: u32,
+    pub seconds: u32,
+    pub played_at: u64,
+}
+
+impl Run {
+    pub fn answers_per_minute(&self) -> f64 {
+        if self.seconds == 0 { 0.0 } else { self.answers as f64 * 60.0 / self.seconds as f64 }
+    }
+}
+
+impl Ranked for Run {
+    /// `score,answers,correct,seconds,played_at`
+    fn serialize(&self) -> String {
+        format!("{},{},{},{},{}", self.score, self.answers, self.correct, self.seconds, self.played_at)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut fields = value.split(',').map(|field| field.trim().parse::<u64>().ok());
+        let mut next = || fields.next().flatten();
+        Some(Self { score: next()? as u32, answers: next()? as u32, correct: next()? as u32, seconds: next()? as u32, played_at: next()? })
+    }
+
+    fn beats(&self, other: &Self) -> bool { self.score > other.score }
+}
+
+/// The best runs per difficulty and mode, on boards named `<mode>.<high score key>`.
+const LEADERBOARDS: Schema = Schema { namespace: storage::SCORES, key: "binary_numbers_leaderboards", steps: &[migration::unversioned] };
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// A career climbs through the widths, its board is kept under the one it starts at.
+fn leaderboard_key(bits: &Bits, base: Base, quiz: Quiz, mode: Mode) -> String {
+    let bits = if mode == Mode::Career { &Bits::Four } else { bits };
+    format!("{}.{}", mode, HighScores::key(bits, base, quiz))
+}
+
+/// The best runs for a difficulty and mode, best first.
+pub fn leaderboard(bits: Bits, base: Base, quiz: Quiz, mode: Mode) -> Vec<Run> {
+    ScoreStore::<Run>::load(&LEADERBOARDS, LEADERBOARD_SIZE).board(&leaderboard_key(&bits, base, quiz, mode)).to_vec()
+}
+
+/// One line per run, `place  score  answers  accuracy  date`, with the run at `highlight` picked out.
+pub fn leaderboard_lines(runs: &[Run], highlight: Option<usize>) -> Vec<Line<'static>> {
+    if runs.is_empty() {
+        return vec![Line::from(Span::styled("No runs yet", Style::default().fg(Color::DarkGray)))];
+    }
+    runs.iter().enumerate().map(|(place, run)| {
+        let date = report::format_timestamp(run.played_at);
+        let text = format!("{:>2}. {:>6}  {:>4} ans  {:>4}  {}", place + 1, run.score, run.answers, accuracy_label(run.correct, run.answers), &date[..10]);
+        let style = if highlight == Some(place) { Style::default().fg(Color::LightGreen).bold() } else if place == 0 { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::Gray) };
+        Line::from(Span::styled(text, style))
+    }).collect()
+}
+
 // NEW: public helper for external modules (e.g., start screen) to read current high score for a bits mode
-pub fn get_high_score(bits: Bits) -> u32 { HighScores::load().get(bits.high_score_key()) }
+pub fn get_high_score(bits: Bits, base: Base, quiz: Quiz, mode: Mode) -> u32 {
+    match mode {
+        Mode::Lives => HighScores::load().get(&HighScores::key(&bits, base, quiz)),
+        mode => leaderboard(bits, base, quiz, mode).first().map_or(0, |run| run.score),
+    }
+}
+
+/// Finished daily challenges as `<day>=<best score>` lines, days counted as in [`stats::today`].
+const DAILY: Schema = Schema { namespace: storage::SCORES, key: "binary_numbers_daily", steps: &[migration::unversioned] };
+
+/// The seed of the daily challenge of `day`, the same on every machine.
+pub fn daily_seed(day: i64) -> u64 {
+    (day as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ 0x6269_6E62_7265_616B
+}
+
+/// The days a daily challenge was finished with the best score of each.
+pub fn daily_results() -> BTreeMap<i64, u32> {
+    migration::load(&DAILY).ok().flatten().unwrap_or_default().lines()
+        .filter_map(|line| {
+            let (day, score) = line.split_once('=')?;
+            Some((day.trim().parse().ok()?, score.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+fn record_daily(day: i64, score: u32) {
+    let mut results = daily_results();
+    let best = results.entry(day).or_insert(score);
+    *best = (*best).max(score);
+    let data: String = results.iter().map(|(day, score)| format!("{}={}\n", day, score)).collect();
+    let _ = migration::save(&DAILY, &data);
+}
+
+/// Days in a row with a finished daily challenge, up to today. A streak lasts through today
+/// while today's challenge is still open.
+pub fn daily_streak(results: &BTreeMap<i64, u32>, today: i64) -> u32 {
+    let mut day = if results.contains_key(&today) { today } else { today - 1 };
+    let mut streak = 0;
+    while results.contains_key(&day) {
+        streak += 1;
+        day -= 1;
+    }
+    streak
+}
+
+/// The last five weeks as a calendar, one row per week from Monday: finished days in green,
+/// missed ones dimmed and today underlined.
+pub fn daily_calendar_lines(results: &BTreeMap<i64, u32>, today: i64) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![Line::from(Span::styled("Mo Tu We Th Fr Sa Su", label))];
+    let first_monday = today - stats::weekday(today) as i64 - 28;
+    for week in 0..5 {
+        let spans: Vec<Span<'static>> = (0..7).map(|weekday| {
+            let day = first_monday + week * 7 + weekday;
+            let (_, _, date) = stats::civil_from_days(day);
+            let mut style = match () {
+                _ if day > today => return Span::raw("   "),
+                _ if results.contains_key(&day) => Style::default().fg(theme::palette().good_light).bold(),
+                _ => Style::default().fg(Color::DarkGray),
+            };
+            if day == today { style = style.add_modifier(Modifier::UNDERLINED); }
+            Span::styled(format!("{:>2} ", date), style)
+        }).collect();
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Everything of a puzzle its generator decides.
+    fn drawn(puzzle: &BinaryNumbersPuzzle) -> (Option<Operation>, u32, Vec<u32>) {
+        (puzzle.operation, puzzle.current_number, puzzle.suggestions.clone())
+    }
+
+    #[test]
+    fn the_same_seed_draws_the_same_puzzles() {
+        for quiz in Quiz::iter() {
+            let puzzles = |seed| {
+                let (mut questions, mut rng) = (QuestionBank::new(8), random::seeded(seed));
+                (0..5)
+                    .map(|streak| drawn(&BinaryNumbersPuzzle::new(Bits::Eight, Base::Hex, quiz, NumberRange::Full, streak, &mut questions, &mut rng)))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(puzzles(7), puzzles(7));
+            assert_ne!(puzzles(7), puzzles(8));
+        }
+    }
+
+    #[test]
+    fn games_with_the_same_seed_start_with_the_same_puzzle() {
+        storage::install_for_tests();
+        let first = |seed| drawn(&BinaryNumbersGame::new(Bits::Twelve).with_seed(seed).puzzle);
+        assert_eq!(first(42), first(42));
+    }
+}
@@ -0,0 +1,542 @@
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Crossword";
+
+/// Boards shipped with the binary, listed after the ones in [`crosswords_dir`].
+const EMBEDDED: [&str; 3] = [
+    include_str!("../../assets/crosswords/bits.txt"),
+    include_str!("../../assets/crosswords/network.txt"),
+    include_str!("../../assets/crosswords/code.txt"),
+];
+
+/// Columns per grid cell: the clue number in superscript, the letter and a gap.
+const CELL_WIDTH: u16 = 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+impl Direction {
+    fn step(self) -> (usize, usize) {
+        match self {
+            Direction::Across => (0, 1),
+            Direction::Down => (1, 0),
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Direction::Across => Direction::Down,
+            Direction::Down => Direction::Across,
+        }
+    }
+}
+
+/// One word of the puzzle and its clue.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub number: u32,
+    pub direction: Direction,
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+    pub clue: String,
+}
+
+impl Entry {
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let (dr, dc) = self.direction.step();
+        (0..self.len).map(move |i| (self.row + dr * i, self.col + dc * i))
+    }
+
+    fn contains(&self, row: usize, col: usize) -> bool {
+        self.cells().any(|cell| cell == (row, col))
+    }
+}
+
+/// A solved grid with its clues, read from a text file:
+///
+/// ```text
+/// [title]
+/// Bits and Bytes
+/// [grid]
+/// BYTE
+/// I##X
+/// [across]
+/// 1 Eight bits
+/// [down]
+/// 1 Smallest unit of data
+/// ```
+///
+/// `#` marks a block. Words are numbered like in print, every word of two or more letters needs a clue.
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub title: String,
+    solution: Vec<Vec<Option<char>>>,
+    pub entries: Vec<Entry>,
+}
+
+impl Puzzle {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let (mut title, mut grid, mut across, mut down) = (String::new(), Vec::new(), Vec::new(), Vec::new());
+        let mut section = "";
+        for line in contents.lines().map(str::trim_end) {
+            match line {
+                "[title]" | "[grid]" | "[across]" | "[down]" => section = line,
+                "" => {}
+                _ if line.starts_with('#') && section != "[grid]" => {} // comments
+                _ => match section {
+                    "[title]" => title = line.trim().to_string(),
+                    "[grid]" => grid.push(line.trim()),
+                    "[across]" => across.push(line),
+                    "[down]" => down.push(line),
+                    _ => {}
+                },
+            }
+        }
+
+        let width = grid.first().map_or(0, |row| row.chars().count());
+        if width == 0 {
+            return Err("the grid is empty".to_string());
+        }
+        if let Some(row) = grid.iter().position(|row| row.chars().count() != width) {
+            return Err(format!("grid row {} is not {} letters wide", row + 1, width));
+        }
+        let solution: Vec<Vec<Option<char>>> = grid.iter()
+            .map(|row| row.chars().map(|ch| (ch != '#').then(|| ch.to_ascii_uppercase())).collect())
+            .collect();
+
+        let clues = |lines: &[&str], direction| -> Result<Vec<(u32, String)>, String> {
+            lines.iter().map(|line| {
+                let (number, clue) = line.trim().split_once(' ').ok_or_else(|| format!("clue without a number: {}", line))?;
+                let number = number.trim_end_matches('.').parse().map_err(|_| format!("bad {:?} clue number: {}", direction, line))?;
+                Ok((number, clue.trim().to_string()))
+            }).collect()
+        };
+        let (across, down) = (clues(&across, Direction::Across)?, clues(&down, Direction::Down)?);
+
+        let mut entries = Vec::new();
+        let mut number = 0;
+        for row in 0..solution.len() {
+            for col in 0..width {
+                let mut numbered = false;
+                for (direction, clues) in [(Direction::Across, &across), (Direction::Down, &down)] {
+                    let len = word_length(&solution, row, col, direction);
+                    if len < 2 {
+                        continue;
+                    }
+                    if !numbered {
+                        number += 1;
+                        numbered = true;
+                    }
+                    let clue = clues.iter().find(|(n, _)| *n == number)
+                        .ok_or_else(|| format!("no clue for {} {:?}", number, direction))?;
+                    entries.push(Entry { number, direction, row, col, len, clue: clue.1.clone() });
+                }
+            }
+        }
+        let title = if title.is_empty() { "Crossword".to_string() } else { title };
+        Ok(Self { title, solution, entries })
+    }
+
+    fn height(&self) -> usize {
+        self.solution.len()
+    }
+
+    fn width(&self) -> usize {
+        self.solution[0].len()
+    }
+
+    fn is_open(&self, row: usize, col: usize) -> bool {
+        self.solution.get(row).and_then(|r| r.get(col)).is_some_and(|cell| cell.is_some())
+    }
+
+    fn number_at(&self, row: usize, col: usize) -> Option<u32> {
+        self.entries.iter().find(|entry| entry.row == row && entry.col == col).map(|entry| entry.number)
+    }
+
+    /// The word through a cell in a direction, if the cell is part of one.
+    fn entry_at(&self, row: usize, col: usize, direction: Direction) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.direction == direction && entry.contains(row, col))
+    }
+}
+
+/// Length of the word starting at `(row, col)`, 0 if no word starts there.
+fn word_length(solution: &[Vec<Option<char>>], row: usize, col: usize, direction: Direction) -> usize {
+    let open = |r: usize, c: usize| solution.get(r).and_then(|cells| cells.get(c)).is_some_and(|cell| cell.is_some());
+    let (dr, dc) = direction.step();
+    let starts = open(row, col) && (row < dr || col < dc || !open(row - dr, col - dc));
+    if !starts {
+        return 0;
+    }
+    (0..).take_while(|i| open(row + dr * i, col + dc * i)).count()
+}
+
+/// Where users can add their own puzzles as `.txt` files.
+pub fn crosswords_dir() -> PathBuf {
+    storage::data_dir().join("crosswords")
+}
+
+/// The user's valid puzzles, sorted by file name, then the built-in ones.
+pub fn puzzles() -> Vec<Puzzle> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(crosswords_dir()).into_iter().flatten().flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    files.sort();
+    let user = files.iter().filter_map(|path| std::fs::read_to_string(path).ok());
+    user.chain(EMBEDDED.iter().map(|contents| contents.to_string()))
+        .filter_map(|contents| Puzzle::parse(&contents).ok())
+        .collect()
+}
+
+pub struct CrosswordGame {
+    puzzles: Vec<Puzzle>,
+    index: usize,
+    letters: Vec<Vec<Option<char>>>, // what the player typed
+    revealed: HashSet<(usize, usize)>,
+    cursor: (usize, usize),
+    direction: Direction,
+    checking: bool, // wrong letters are marked
+    solved: bool,
+    exit_intended: bool,
+}
+
+impl CrosswordGame {
+    pub fn new() -> Self {
+        let mut game = Self {
+            puzzles: puzzles(),
+            index: 0,
+            letters: Vec::new(),
+            revealed: HashSet::new(),
+            cursor: (0, 0),
+            direction: Direction::Across,
+            checking: false,
+            solved: false,
+            exit_intended: false,
+        };
+        game.open(0);
+        game
+    }
+
+    /// Continue a game saved by [`MainScreenWidget::save_state`]; `None` if its puzzle is gone.
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut game = Self::new();
+        let mut fill = None;
+        for (key, value) in data.lines().filter_map(|line| line.split_once('=')) {
+            match key {
+                "puzzle" => game.open(game.puzzles.iter().position(|puzzle| puzzle.title == value)?),
+                "fill" => fill = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        for (row, line) in fill?.split('|').enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if game.puzzle()?.is_open(row, col) && ch != '.' {
+                    game.letters[row][col] = Some(ch);
+                }
+            }
+        }
+        Some(game)
+    }
+
+    fn puzzle(&self) -> Option<&Puzzle> {
+        self.puzzles.get(self.index)
+    }
+
+    fn open(&mut self, index: usize) {
+        self.index = index;
+        let Some(puzzle) = self.puzzles.get(index) else { return };
+        self.letters = vec![vec![None; puzzle.width()]; puzzle.height()];
+        self.revealed.clear();
+        self.checking = false;
+        self.solved = false;
+        self.direction = Direction::Across;
+        self.cursor = puzzle.entries.first().map_or((0, 0), |entry| (entry.row, entry.col));
+    }
+
+    fn current_entry(&self) -> Option<&Entry> {
+        let puzzle = self.puzzle()?;
+        let (row, col) = self.cursor;
+        puzzle.entry_at(row, col, self.direction).or_else(|| puzzle.entry_at(row, col, self.direction.other()))
+    }
+
+    /// Move one cell, jumping over blocks; stays put at the edge.
+    fn move_cursor(&mut self, dr: isize, dc: isize) {
+        let Some(puzzle) = self.puzzle() else { return };
+        let (mut row, mut col) = (self.cursor.0 as isize, self.cursor.1 as isize);
+        loop {
+            row += dr;
+            col += dc;
+            if row < 0 || col < 0 || row >= puzzle.height() as isize || col >= puzzle.width() as isize {
+                return;
+            }
+            if puzzle.is_open(row as usize, col as usize) {
+                self.cursor = (row as usize, col as usize);
+                return;
+            }
+        }
+    }
+
+    /// Arrow keys along the current direction move, across it they switch direction first.
+    fn arrow(&mut self, direction: Direction, dr: isize, dc: isize) {
+        let crosses_word = self.puzzle().is_some_and(|p| p.entry_at(self.cursor.0, self.cursor.1, direction).is_some());
+        if self.direction != direction && crosses_word {
+            self.direction = direction;
+        } else {
+            self.move_cursor(dr, dc);
+        }
+    }
+
+    /// Jump to the next (or previous) word in clue order, across words first.
+    fn next_entry(&mut self, forward: bool) {
+        let Some(puzzle) = self.puzzle() else { return };
+        let mut order: Vec<&Entry> = puzzle.entries.iter().collect();
+        order.sort_by_key(|entry| (entry.direction == Direction::Down, entry.number));
+        let current = self.current_entry().and_then(|entry| order.iter().position(|e| e.number == entry.number && e.direction == entry.direction));
+        let next = match current {
+            Some(i) if forward => (i + 1) % order.len(),
+            Some(i) => (i + order.len() - 1) % order.len(),
+            None => 0,
+        };
+        if let Some(entry) = order.get(next) {
+            let (cursor, direction) = ((entry.row, entry.col), entry.direction);
+            self.cursor = cursor;
+            self.direction = direction;
+        }
+    }
+
+    fn type_letter(&mut self, letter: char) {
+        if self.solved || self.revealed.contains(&self.cursor) {
+            return self.advance();
+        }
+        self.letters[self.cursor.0][self.cursor.1] = Some(letter.to_ascii_uppercase());
+        self.advance();
+        self.check_solved();
+    }
+
+    fn advance(&mut self) {
+        let (dr, dc) = self.direction.step();
+        let next = (self.cursor.0 + dr, self.cursor.1 + dc);
+        if self.puzzle().is_some_and(|puzzle| puzzle.is_open(next.0, next.1)) {
+            self.cursor = next;
+        }
+    }
+
+    fn erase(&mut self) {
+        let (row, col) = self.cursor;
+        if self.letters[row][col].is_some() && !self.revealed.contains(&self.cursor) {
+            self.letters[row][col] = None;
+            return;
+        }
+        let (dr, dc) = self.direction.step();
+        if row >= dr && col >= dc && self.puzzle().is_some_and(|puzzle| puzzle.is_open(row - dr, col - dc)) {
+            self.cursor = (row - dr, col - dc);
+            if !self.revealed.contains(&self.cursor) {
+                self.letters[self.cursor.0][self.cursor.1] = None;
+            }
+        }
+    }
+
+    fn reveal_word(&mut self) {
+        let Some(entry) = self.current_entry().cloned() else { return };
+        let Some(puzzle) = self.puzzle() else { return };
+        let solution: Vec<((usize, usize), Option<char>)> = entry.cells().map(|(r, c)| ((r, c), puzzle.solution[r][c])).collect();
+        for ((row, col), letter) in solution {
+            self.letters[row][col] = letter;
+            self.revealed.insert((row, col));
+        }
+        self.check_solved();
+    }
+
+    fn check_solved(&mut self) {
+        let Some(puzzle) = self.puzzle() else { return };
+        if self.solved || puzzle.solution != self.letters {
+            return;
+        }
+        // a round counts as correct when it was solved without revealing anything
+        stats::record_round(NAME, &puzzle.title, self.revealed.is_empty(), 0);
+        self.solved = true;
+        sound::emit(SoundEvent::Correct);
+    }
+
+    fn is_wrong(&self, row: usize, col: usize) -> bool {
+        let Some(puzzle) = self.puzzle() else { return false };
+        self.letters[row][col].is_some_and(|letter| Some(letter) != puzzle.solution[row][col])
+    }
+}
+
+impl MainScreenWidget for CrosswordGame {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        if self.puzzle().is_none() {
+            return;
+        }
+        let ctrl = input.modifiers.contains(KeyModifiers::CONTROL);
+        match input.code {
+            KeyCode::Char('r') | KeyCode::Char('R') if ctrl => self.reveal_word(),
+            KeyCode::Char('q') | KeyCode::Char('Q') if ctrl => self.exit_intended = true,
+            KeyCode::Char(' ') => self.direction = self.direction.other(),
+            KeyCode::Char(letter) if letter.is_ascii_alphanumeric() && !ctrl => self.type_letter(letter),
+            KeyCode::Left => self.arrow(Direction::Across, 0, -1),
+            KeyCode::Right => self.arrow(Direction::Across, 0, 1),
+            KeyCode::Up => self.arrow(Direction::Down, -1, 0),
+            KeyCode::Down => self.arrow(Direction::Down, 1, 0),
+            KeyCode::Tab => self.next_entry(true),
+            KeyCode::BackTab => self.next_entry(false),
+            KeyCode::Backspace | KeyCode::Delete => self.erase(),
+            KeyCode::Enter => self.checking = !self.checking,
+            KeyCode::PageDown => self.open((self.index + 1) % self.puzzles.len()),
+            KeyCode::PageUp => self.open((self.index + self.puzzles.len() - 1) % self.puzzles.len()),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("A-Z 0-9", "fill in a letter"),
+            ("Arrows", "move, or turn to that direction"),
+            ("Space", "switch between across and down"),
+            ("Tab Shift+Tab", "next / previous word"),
+            ("Backspace", "erase"),
+            ("Enter", "check: mark wrong letters"),
+            ("Ctrl+R", "reveal the current word"),
+            ("PgUp PgDn", "previous / next puzzle"),
+            ("Ctrl+Q", "quit"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    fn is_finished(&self) -> bool { self.solved }
+
+    fn save_state(&self) -> Option<SavedState> {
+        let puzzle = self.puzzle()?;
+        let filled = self.letters.iter().flatten().filter(|letter| letter.is_some()).count();
+        if self.solved || filled == 0 {
+            return None;
+        }
+        let fill: Vec<String> = self.letters.iter()
+            .map(|row| row.iter().map(|letter| letter.unwrap_or('.')).collect())
+            .collect();
+        Some(SavedState {
+            summary: format!("{}, {} letters filled in", puzzle.title, filled),
+            data: format!("puzzle={}\nfill={}\n", puzzle.title, fill.join("|")),
+        })
+    }
+}
+
+impl WidgetRef for CrosswordGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let Some(puzzle) = self.puzzle() else {
+            let message = format!("No valid puzzles. Add some to {}", crosswords_dir().display());
+            return Paragraph::new(message).centered().render(area, buf);
+        };
+
+        let [header, body, footer] = Layout::vertical([Constraint::Length(2), Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let current = self.current_entry();
+        let clue_line = match (self.solved, current) {
+            (true, _) => Line::from("Solved! PgDn for the next puzzle".light_green().bold()),
+            (false, Some(entry)) => Line::from(vec![
+                format!("{} {:?}: ", entry.number, entry.direction).light_cyan(),
+                entry.clue.clone().white(),
+            ]),
+            (false, None) => Line::from(""),
+        };
+        Paragraph::new(vec![
+            Line::from(format!("{} ({}/{})", puzzle.title, self.index + 1, self.puzzles.len()).yellow().bold()),
+            clue_line,
+        ]).centered().render(header, buf);
+
+        let grid_width = puzzle.width() as u16 * CELL_WIDTH + 2;
+        let [grid_area, clues_area] = Layout::horizontal([Constraint::Length(grid_width), Constraint::Max(44)])
+            .spacing(2)
+            .flex(Flex::Center)
+            .areas(body);
+        let [grid_area] = Layout::vertical([Constraint::Length(puzzle.height() as u16 + 2)]).areas(grid_area);
+        self.render_grid(puzzle, current, grid_area, buf);
+        render_clues(puzzle, current, clues_area, buf);
+
+        let check = if self.checking { "<Enter> hide mistakes" } else { "<Enter> check" };
+        Paragraph::new(format!("<Space> turn  <Tab> next word  {}  <Ctrl+R> reveal  <PgDn> next puzzle", check))
+            .dark_gray()
+            .centered()
+            .render(footer, buf);
+    }
+}
+
+impl CrosswordGame {
+    fn render_grid(&self, puzzle: &Puzzle, current: Option<&Entry>, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().border_style(Style::new().dark_gray());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        for row in 0..puzzle.height() {
+            for col in 0..puzzle.width() {
+                let cell = Rect::new(inner.x + col as u16 * CELL_WIDTH, inner.y + row as u16, CELL_WIDTH, 1).intersection(inner);
+                if !puzzle.is_open(row, col) {
+                    Line::from("█".repeat(CELL_WIDTH as usize).dark_gray()).render(cell, buf);
+                    continue;
+                }
+                let number = puzzle.number_at(row, col).map(superscript).unwrap_or_default();
+                let letter = self.letters[row][col].unwrap_or('·');
+                let mut style = match () {
+                    _ if self.revealed.contains(&(row, col)) => Style::new().yellow(),
+                    _ if self.checking && self.is_wrong(row, col) => Style::new().light_red(),
+                    _ if self.solved => Style::new().light_green(),
+                    _ => Style::new().white(),
+                };
+                if current.is_some_and(|entry| entry.contains(row, col)) {
+                    style = style.bg(Color::DarkGray);
+                }
+                if self.cursor == (row, col) {
+                    style = style.bg(Color::LightCyan).fg(Color::Black);
+                }
+                let text = format!("{:<2}{} ", number, letter);
+                Line::from(vec![
+                    Span::styled(text[..number.len()].to_string(), style.dark_gray()),
+                    Span::styled(text[number.len()..].to_string(), style.bold()),
+                ]).render(cell, buf);
+            }
+        }
+    }
+}
+
+fn render_clues(puzzle: &Puzzle, current: Option<&Entry>, area: Rect, buf: &mut Buffer) {
+    let mut lines = Vec::new();
+    for (direction, heading) in [(Direction::Across, "Across"), (Direction::Down, "Down")] {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(heading.yellow().bold()));
+        for entry in puzzle.entries.iter().filter(|entry| entry.direction == direction) {
+            let is_current = current.is_some_and(|c| c.number == entry.number && c.direction == entry.direction);
+            let style = if is_current { Style::new().light_cyan().bold() } else { Style::new().white() };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>2} ", entry.number), style.dark_gray()),
+                Span::styled(format!("{} ({})", entry.clue, entry.len), style),
+            ]));
+        }
+    }
+    Paragraph::new(lines).wrap(Wrap { trim: false }).render(area, buf);
+}
+
+/// Clue numbers as small digits in the corner of a cell.
+fn superscript(number: u32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    number.to_string().chars().map(|digit| DIGITS[digit.to_digit(10).unwrap_or(0) as usize]).collect()
+}
@@ -0,0 +1,184 @@
+use crate::games::binary_numbers::Bits;
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::games::scoring::{self, Curve, ScoringRules};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph};
+
+/// Streaks shown on the x axis.
+const MAX_STREAK: f64 = 50.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditedCurve {
+    RoundTime,
+    AdaptiveBits,
+}
+
+impl EditedCurve {
+    fn title(self) -> &'static str {
+        match self {
+            EditedCurve::RoundTime => "Time per round (8 bits)",
+            EditedCurve::AdaptiveBits => "Bit width in adaptive mode",
+        }
+    }
+
+    fn curve(self, rules: &ScoringRules) -> &Curve {
+        match self {
+            EditedCurve::RoundTime => &rules.time_penalty,
+            EditedCurve::AdaptiveBits => &rules.adaptive_bits,
+        }
+    }
+
+    fn curve_mut(self, rules: &mut ScoringRules) -> &mut Curve {
+        match self {
+            EditedCurve::RoundTime => &mut rules.time_penalty,
+            EditedCurve::AdaptiveBits => &mut rules.adaptive_bits,
+        }
+    }
+
+    /// Amount Up/Down changes a point by.
+    fn step(self) -> f64 {
+        match self {
+            EditedCurve::RoundTime => 0.5,
+            EditedCurve::AdaptiveBits => 4.0,
+        }
+    }
+}
+
+/// Shows the difficulty curves of [`ScoringRules`] as charts and lets the player move their points.
+pub struct DifficultyEditor {
+    exit_intended: bool,
+    edited: EditedCurve,
+    selected: usize,
+}
+
+impl DifficultyEditor {
+    pub fn new() -> Self {
+        Self { exit_intended: false, edited: EditedCurve::RoundTime, selected: 0 }
+    }
+
+    fn edit(&mut self, change: impl FnOnce(&mut Curve, usize) -> usize) {
+        let (edited, selected) = (self.edited, self.selected);
+        let mut new_selected = selected;
+        scoring::update(|rules| new_selected = change(edited.curve_mut(rules), selected));
+        self.selected = new_selected;
+    }
+
+    fn move_point(&mut self, dx: i64, dy: f64) {
+        let edited = self.edited;
+        self.edit(|curve, index| {
+            let Some(&(x, y)) = curve.points().get(index) else { return index };
+            let y = match edited {
+                // the curve stores seconds taken off, Up should give more time
+                EditedCurve::RoundTime => (y - dy).max(0.0),
+                EditedCurve::AdaptiveBits => (y + dy).clamp(4.0, 16.0),
+            };
+            curve.set_point(index, (x as i64 + dx).max(0) as u32, y);
+            index
+        });
+    }
+}
+
+impl MainScreenWidget for DifficultyEditor {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        let point_count = self.edited.curve(&scoring::rules()).points().len();
+        match input.code {
+            KeyCode::Tab => {
+                self.edited = match self.edited {
+                    EditedCurve::RoundTime => EditedCurve::AdaptiveBits,
+                    EditedCurve::AdaptiveBits => EditedCurve::RoundTime,
+                };
+                self.selected = 0;
+            }
+            KeyCode::Left => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right => self.selected = (self.selected + 1).min(point_count.saturating_sub(1)),
+            KeyCode::Up => self.move_point(0, self.edited.step()),
+            KeyCode::Down => self.move_point(0, -self.edited.step()),
+            KeyCode::Char('<') | KeyCode::Char(',') => self.move_point(-1, 0.0),
+            KeyCode::Char('>') | KeyCode::Char('.') => self.move_point(1, 0.0),
+            KeyCode::Char('a') | KeyCode::Char('A') => self.edit(|curve, index| curve.insert_after(index)),
+            KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => self.edit(|curve, index| {
+                curve.remove(index);
+                index.min(curve.points().len() - 1)
+            }),
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                let edited = self.edited;
+                self.edit(|curve, _| {
+                    *curve = match edited {
+                        EditedCurve::RoundTime => ScoringRules::default_time_penalty(),
+                        EditedCurve::AdaptiveBits => ScoringRules::default_adaptive_bits(),
+                    };
+                    0
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Tab", "switch between round time and adaptive bit width"),
+            ("Left Right", "select a point"),
+            ("Up Down", "raise / lower the selected point"),
+            ("< >", "move the selected point along the streak axis"),
+            ("A D", "add a point after / delete the selected one"),
+            ("R", "reset this curve to the defaults"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for DifficultyEditor {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let rules = scoring::rules();
+        let [chart_area, points_area, keys_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ]).areas(area);
+
+        // the round time curve is shown as it is played, not as the stored penalty
+        let base_time = Bits::Eight.base_time();
+        let value_at = |streak: f64| match self.edited {
+            EditedCurve::RoundTime => rules.round_time(base_time, streak as u32),
+            EditedCurve::AdaptiveBits => rules.adaptive_bit_width(streak as u32) as f64,
+        };
+        let line: Vec<(f64, f64)> = (0..=MAX_STREAK as u32).map(|streak| (streak as f64, value_at(streak as f64))).collect();
+        let points: Vec<(f64, f64)> = self.edited.curve(&rules).points().iter()
+            .map(|&(x, _)| (x as f64, value_at(x as f64)))
+            .collect();
+        let selected: Vec<(f64, f64)> = points.get(self.selected).copied().into_iter().collect();
+
+        let y_max = match self.edited {
+            EditedCurve::RoundTime => base_time,
+            EditedCurve::AdaptiveBits => 16.0,
+        };
+        let chart = Chart::new(vec![
+            Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).light_cyan().data(&line),
+            Dataset::default().marker(Marker::Block).graph_type(GraphType::Scatter).dark_gray().data(&points),
+            Dataset::default().marker(Marker::Block).graph_type(GraphType::Scatter).light_green().data(&selected),
+        ])
+            .block(Block::bordered().title(self.edited.title()).title_alignment(Alignment::Center))
+            .x_axis(Axis::default().title("streak").gray().bounds([0.0, MAX_STREAK]).labels(["0", "25", "50"]))
+            .y_axis(Axis::default().gray().bounds([0.0, y_max]).labels(["0".to_string(), format!("{}", y_max)]));
+        chart.render(chart_area, buf);
+
+        let spans: Vec<Span> = points.iter().enumerate().map(|(i, (x, y))| {
+            let text = format!(" {}:{} ", x, y);
+            if i == self.selected { text.black().on_light_green() } else { text.white() }
+        }).collect();
+        Paragraph::new(Line::from(spans))
+            .block(Block::bordered().title("Points (streak:value)").dark_gray())
+            .render(points_area, buf);
+
+        Line::from("<Tab> curve  <Left Right> select  <Up Down> value  <, .> streak  <A/D> add/delete  <R> reset".dark_gray())
+            .centered()
+            .render(keys_area, buf);
+    }
+}
@@ -0,0 +1,298 @@
+//! A small but complete game to copy from when adding a new one.
+//!
+//! A dot runs back and forth along a track; press Space while it is inside the target zone.
+//! Hits score and shrink the zone, misses cost a life. Everything a game in the main menu
+//! deals with is here, in the order the app calls it:
+//!
+//! 1. the registry calls the constructor ([`ExampleGame::new`]) or, after a crash or quit,
+//!    the restorer ([`ExampleGame::restore`]) with the data of [`MainScreenWidget::save_state`],
+//! 2. every frame the app calls [`MainScreenWidget::run`] with the seconds since the last frame,
+//! 3. key presses arrive in [`MainScreenWidget::handle_input`] (Esc is handled by the app and
+//!    opens the pause menu, the game does not advance while it is open),
+//! 4. [`WidgetRef::render_ref`] draws the current state; it takes `&self`, so all changes happen in 2 and 3,
+//! 5. [`MainScreenWidget::is_exit_intended`] tells the app to close the game.
+//!
+//! To add your own game, copy this file, rename the type, add it to `games/mod.rs`
+//! and register it in [`GameRegistry::with_builtin_games`](crate::games::registry::GameRegistry::with_builtin_games).
+//! This one is only registered in debug builds started with `--example-game`. The tests at the
+//! bottom show how to check what a game draws with a [`TestBackend`](ratatui::backend::TestBackend).
+
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Example Game";
+
+/// Width of the track in cells. Positions are kept as `f64` so the dot moves smoothly
+/// at any frame rate and is only rounded to a cell when drawn.
+const TRACK: f64 = 40.0;
+const START_LIVES: u32 = 3;
+
+pub struct ExampleGame {
+    // state that survives a restore
+    score: u32,
+    lives: u32,
+    // state of the current round, recreated on restore
+    position: f64, // of the dot, 0..TRACK
+    direction: f64, // +1 or -1
+    speed: f64, // cells per second
+    zone: (f64, f64), // target zone, start and end
+    message: &'static str,
+    exit_intended: bool,
+}
+
+impl ExampleGame {
+    pub fn new() -> Self {
+        Self {
+            score: 0,
+            lives: START_LIVES,
+            position: 0.0,
+            direction: 1.0,
+            speed: 12.0,
+            zone: (16.0, 24.0),
+            message: "press Space inside the zone",
+            exit_intended: false,
+        }
+    }
+
+    /// Counterpart of [`MainScreenWidget::save_state`]. Returning `None` for data we cannot
+    /// read makes the app drop the checkpoint instead of starting a broken game.
+    pub fn restore(data: &str) -> Option<Self> {
+        let (score, lives) = data.split_once(',')?;
+        let mut game = Self::new();
+        game.score = score.trim().parse().ok()?;
+        game.lives = lives.trim().parse().ok()?;
+        game.tighten();
+        Some(game)
+    }
+
+    /// Difficulty is derived from the score, so a restored game continues at the same level.
+    fn tighten(&mut self) {
+        let width = (8.0 - self.score as f64 * 0.5).max(2.0);
+        let start = (TRACK - width) / 2.0;
+        self.zone = (start, start + width);
+        self.speed = 12.0 + self.score as f64 * 1.5;
+    }
+
+    fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+
+    fn press(&mut self) {
+        let hit = self.position >= self.zone.0 && self.position <= self.zone.1;
+        if hit {
+            self.score += 1;
+            self.message = "hit!";
+            self.tighten();
+        } else {
+            self.lives -= 1;
+            self.message = if self.is_over() { "game over, Enter to play again" } else { "missed" };
+        }
+        // shared services: sounds, and stats that feed the profile and training plan
+        sound::emit(if hit { SoundEvent::Correct } else { SoundEvent::Incorrect });
+        stats::record_round(NAME, "default", hit, self.score);
+        if self.is_over() {
+            sound::emit(SoundEvent::GameOver);
+        }
+    }
+}
+
+impl MainScreenWidget for ExampleGame {
+    /// Advance by `dt` seconds. Multiply every speed by `dt` instead of moving a fixed
+    /// amount per call, the frame rate changes with the terminal and the performance mode.
+    fn run(&mut self, dt: f64) {
+        if self.is_over() {
+            return;
+        }
+        self.position += self.direction * self.speed * dt;
+        if self.position >= TRACK {
+            self.position = TRACK;
+            self.direction = -1.0;
+        } else if self.position <= 0.0 {
+            self.position = 0.0;
+            self.direction = 1.0;
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Char(' ') if !self.is_over() => self.press(),
+            KeyCode::Enter if self.is_over() => *self = Self::new(),
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit_intended = true,
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    /// The dot moves on its own, so keep redrawing while the game runs. Screens that only
+    /// change on input return false and save the CPU.
+    fn needs_redraw(&self) -> bool { !self.is_over() }
+
+    /// Shown in the F1 overlay below the global keys.
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Space", "stop the dot, inside the zone to score"),
+            ("Enter", "play again after game over"),
+            ("Q", "quit"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    /// Once the game is over, leaving it also drops the session offered in "Resume last session".
+    fn is_finished(&self) -> bool { self.is_over() }
+
+    /// Called regularly for the autosave and when the app quits. Keep `data` small and
+    /// line based, it is stored in a text file; the summary is shown in "Resume last session".
+    fn save_state(&self) -> Option<SavedState> {
+        if self.is_over() { return None; }
+        Some(SavedState {
+            summary: format!("score {}, {} lives", self.score, self.lives),
+            data: format!("{},{}", self.score, self.lives),
+        })
+    }
+}
+
+impl WidgetRef for ExampleGame {
+    /// Draw everything from the state, nothing is remembered between frames.
+    /// `area` is the inside of the game box; center fixed-size content in it.
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let [column] = Layout::horizontal([Constraint::Length(TRACK as u16 + 3)]).flex(Flex::Center).areas(area);
+        let [status, track, message] = Layout::vertical([Constraint::Length(1), Constraint::Length(3), Constraint::Length(1)])
+            .flex(Flex::Center)
+            .areas(column);
+
+        let hearts = "♥".repeat(self.lives as usize);
+        Paragraph::new(Line::from(vec![
+            format!("Score: {}  ", self.score).green(),
+            format!("Lives: {}", hearts).red(),
+        ])).centered().render(status, buf);
+
+        let block = Block::bordered().dark_gray();
+        let inner = block.inner(track);
+        block.render(track, buf);
+        // draw cell by cell: the zone as a background, the dot on top of it
+        for x in 0..inner.width {
+            let cell_x = x as f64;
+            let in_zone = cell_x >= self.zone.0.floor() && cell_x <= self.zone.1.ceil();
+            let is_dot = x == self.position.round() as u16;
+            let (symbol, style) = match (is_dot, in_zone) {
+                (true, _) => ("●", Style::default().fg(Color::LightCyan).bold()),
+                (false, true) => ("░", Style::default().fg(Color::Green)),
+                (false, false) => ("·", Style::default().fg(Color::DarkGray)),
+            };
+            if let Some(cell) = buf.cell_mut((inner.x + x, inner.y)) {
+                cell.set_symbol(symbol).set_style(style);
+            }
+        }
+
+        Paragraph::new(self.message.white()).centered().render(message, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage;
+    use crossterm::event::KeyModifiers;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    /// Draw the game into a terminal of 45×7 cells, like the app draws it into the game box.
+    fn draw(game: &ExampleGame) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(45, 7)).unwrap();
+        terminal.draw(|frame| game.render_ref(frame.area(), frame.buffer_mut())).unwrap();
+        terminal
+    }
+
+    /// The expected screen: its text, and the styles of its areas applied in order.
+    fn snapshot(lines: [&str; 7], styles: &[((u16, u16, u16), Style)]) -> Buffer {
+        let mut buffer = Buffer::with_lines(lines);
+        buffer.set_style(Rect::new(1, 2, 43, 3), Style::default().fg(Color::DarkGray)); // the track
+        for &((x, y, width), style) in styles {
+            buffer.set_style(Rect::new(x, y, width, 1), style);
+        }
+        buffer
+    }
+
+    fn press(game: &mut ExampleGame, code: KeyCode) {
+        storage::install_for_tests();
+        game.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    const DOT: Style = Style::new().fg(Color::LightCyan).add_modifier(Modifier::BOLD);
+    const ZONE: Style = Style::new().fg(Color::Green);
+
+    #[test]
+    fn snapshot_at_the_start() {
+        let expected = snapshot([
+            "                                             ",
+            "            Score: 0  Lives: ♥♥♥             ",
+            " ┌─────────────────────────────────────────┐ ",
+            " │●···············░░░░░░░░░················│ ",
+            " └─────────────────────────────────────────┘ ",
+            "         press Space inside the zone         ",
+            "                                             ",
+        ], &[
+            ((12, 1, 10), Style::new().fg(Color::Green)),
+            ((22, 1, 10), Style::new().fg(Color::Red)),
+            ((2, 3, 1), DOT),
+            ((18, 3, 9), ZONE),
+            ((9, 5, 27), Style::new().fg(Color::White)),
+        ]);
+        draw(&ExampleGame::new()).backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn snapshot_after_a_hit() {
+        let mut game = ExampleGame::new();
+        game.run(1.5); // 18 cells to the right, inside the zone
+        press(&mut game, KeyCode::Char(' '));
+        let expected = snapshot([
+            "                                             ",
+            "            Score: 1  Lives: ♥♥♥             ",
+            " ┌─────────────────────────────────────────┐ ",
+            " │················░░●░░░░░░················│ ",
+            " └─────────────────────────────────────────┘ ",
+            "                    hit!                     ",
+            "                                             ",
+        ], &[
+            ((12, 1, 10), Style::new().fg(Color::Green)),
+            ((22, 1, 10), Style::new().fg(Color::Red)),
+            ((18, 3, 9), ZONE),
+            ((20, 3, 1), DOT),
+            ((20, 5, 4), Style::new().fg(Color::White)),
+        ]);
+        draw(&game).backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn snapshot_of_the_game_over() {
+        let mut game = ExampleGame::new();
+        for _ in 0..START_LIVES {
+            press(&mut game, KeyCode::Char(' ')); // the dot is still at the left end
+        }
+        game.run(1.0); // stands still once the game is over
+        let expected = snapshot([
+            "                                             ",
+            "              Score: 0  Lives:               ",
+            " ┌─────────────────────────────────────────┐ ",
+            " │●···············░░░░░░░░░················│ ",
+            " └─────────────────────────────────────────┘ ",
+            "       game over, Enter to play again        ",
+            "                                             ",
+        ], &[
+            ((14, 1, 10), Style::new().fg(Color::Green)),
+            ((24, 1, 7), Style::new().fg(Color::Red)),
+            ((2, 3, 1), DOT),
+            ((18, 3, 9), ZONE),
+            ((7, 5, 30), Style::new().fg(Color::White)),
+        ]);
+        draw(&game).backend().assert_buffer(&expected);
+        assert!(game.save_state().is_none());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::random;
+use crate::review::ReviewSchedule;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph, Wrap};
+use std::collections::VecDeque;
+
+const BUILTIN: [&str; 3] = [
+    include_str!("../../assets/flashcards/http_status.txt"),
+    include_str!("../../assets/flashcards/ports.txt"),
+    include_str!("../../assets/flashcards/shortcuts.txt"),
+];
+
+/// Cards never seen before that one session introduces, on top of the ones due for review.
+const NEW_PER_SESSION: usize = 10;
+
+/// A titled list of question and answer cards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deck {
+    pub title: String,
+    pub cards: Vec<(String, String)>, // front, back
+}
+
+impl Deck {
+    /// Read a deck in the format of `assets/flashcards`: a `[title]` section and a `[cards]` section
+    /// of `front | back` lines.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let (mut title, mut cards) = (String::new(), Vec::new());
+        let mut section = "";
+        for line in contents.lines().map(str::trim) {
+            match line {
+                "[title]" | "[cards]" => section = line,
+                "" => {}
+                _ if line.starts_with('#') => {} // comments
+                _ => match section {
+                    "[title]" => title = line.to_string(),
+                    "[cards]" => {
+                        let (front, back) = line.split_once('|').ok_or_else(|| format!("card without a | between its sides: {}", line))?;
+                        cards.push((front.trim().to_string(), back.trim().to_string()));
+                    }
+                    _ => {}
+                },
+            }
+        }
+        if title.is_empty() {
+            return Err("the deck has no title".to_string());
+        }
+        if cards.is_empty() {
+            return Err(format!("{} has no cards", title));
+        }
+        Ok(Self { title, cards })
+    }
+}
+
+/// The decks that come with hackerman, each listed on its own in the Learn menu.
+pub fn decks() -> Vec<Deck> {
+    BUILTIN.iter().filter_map(|contents| Deck::parse(contents).ok()).collect()
+}
+
+/// One pass over the cards of a deck that are due: read the front, recall the back, then reveal it
+/// and say whether you knew it. The answer decides when [`ReviewSchedule`] asks the card again.
+pub struct FlashcardsGame {
+    deck: Deck,
+    schedule: ReviewSchedule,
+    queue: VecDeque<usize>, // cards left this session, forgotten ones come around again
+    revealed: bool,
+    reviewed: u32,
+    recalled: u32,
+    streak: u32,
+    exit_intended: bool,
+}
+
+impl FlashcardsGame {
+    pub fn new(deck: Deck) -> Self {
+        Self::new_with_rng(deck, &mut random::rng())
+    }
+
+    /// The due cards come in an order drawn from `rng`.
+    pub fn new_with_rng(deck: Deck, rng: &mut impl Rng) -> Self {
+        let schedule = ReviewSchedule::load();
+        let (mut due, new): (Vec<usize>, Vec<usize>) = (0..deck.cards.len())
+            .filter(|&card| schedule.is_due(&deck.title, &deck.cards[card].0))
+            .partition(|&card| schedule.get(&deck.title, &deck.cards[card].0).is_some());
+        due.shuffle(rng);
+        let queue = due.into_iter().chain(new.into_iter().take(NEW_PER_SESSION)).collect();
+        Self { deck, schedule, queue, revealed: false, reviewed: 0, recalled: 0, streak: 0, exit_intended: false }
+    }
+
+    fn current(&self) -> Option<&(String, String)> {
+        self.queue.front().map(|&card| &self.deck.cards[card])
+    }
+
+    fn answer(&mut self, recalled: bool) {
+        let Some(card) = self.queue.pop_front() else { return };
+        let front = &self.deck.cards[card].0;
+        self.schedule.answer(&self.deck.title, front, recalled);
+        self.schedule.save();
+        self.streak = if recalled { self.streak + 1 } else { 0 };
+        stats::record_round(&self.deck.title, "review", recalled, self.streak);
+        self.reviewed += 1;
+        if recalled {
+            self.recalled += 1;
+            sound::emit(SoundEvent::Correct);
+        } else {
+            self.queue.push_back(card);
+            sound::emit(SoundEvent::Incorrect);
+        }
+        self.revealed = false;
+    }
+
+    /// Cards never answered, still being learned (back within a week) and known.
+    fn progress(&self) -> [usize; 3] {
+        let mut progress = [0; 3];
+        for (front, _) in &self.deck.cards {
+            let index = match self.schedule.get(&self.deck.title, front) {
+                None => 0,
+                Some(card) if card.interval_days < 7 => 1,
+                Some(_) => 2,
+            };
+            progress[index] += 1;
+        }
+        progress
+    }
+}
+
+impl MainScreenWidget for FlashcardsGame {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Down if !self.revealed && self.current().is_some() => self.revealed = true,
+            KeyCode::Right | KeyCode::Char('y') | KeyCode::Char('Y') if self.revealed => self.answer(true),
+            KeyCode::Left | KeyCode::Char('n') | KeyCode::Char('N') if self.revealed => self.answer(false),
+            KeyCode::Enter if self.current().is_none() => self.exit_intended = true,
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Enter", "turn the card over"),
+            ("Right Y", "I knew it: the card comes back later"),
+            ("Left N", "I did not: the card comes back this session"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for FlashcardsGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let [header, _, card_area, _, footer] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(9),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+        let [card_area] = Layout::horizontal([Constraint::Max(60)]).flex(Flex::Center).areas(card_area);
+
+        let [new, learning, known] = self.progress();
+        Paragraph::new(vec![
+            Line::from(vec![
+                self.deck.title.clone().yellow().bold(),
+                format!("  {} left  {}/{} recalled", self.queue.len(), self.recalled, self.reviewed).white(),
+            ]),
+            Line::from(vec![format!("new {}", new).dark_gray(), format!("  learning {}", learning).yellow(), format!("  known {}", known).light_green()]),
+        ]).centered().render(header, buf);
+
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray();
+        let Some((front, back)) = self.current() else {
+            let next = match self.schedule.next_due(&self.deck.title, &self.deck.cards.iter().map(|(front, _)| front.as_str()).collect::<Vec<_>>()) {
+                Some(seconds) if seconds >= 2 * 86_400 => format!("Next review in {} days.", seconds / 86_400),
+                Some(seconds) => format!("Next review in {}.", stats::format_duration(seconds as f64)),
+                None => "More new cards wait: open the deck again to learn them.".to_string(),
+            };
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from("Done for today".light_green().bold()),
+                Line::from(""),
+                Line::from(format!("{} of {} answers recalled", self.recalled, self.reviewed).white()),
+                Line::from(next.dark_gray()),
+            ]).centered().block(block).render(card_area, buf);
+            Paragraph::new("<Enter> back to the menu".dark_gray()).centered().render(footer, buf);
+            return;
+        };
+
+        let mut lines = vec![Line::from(""), Line::from(front.clone().white().bold()), Line::from("")];
+        if self.revealed {
+            lines.push(Line::from("─".repeat(card_area.width.saturating_sub(8) as usize).dark_gray()));
+            lines.push(Line::from(""));
+            lines.push(Line::from(back.clone().light_cyan()));
+        }
+        Paragraph::new(lines).centered().wrap(Wrap { trim: true }).block(block).render(card_area, buf);
+
+        let keys = if self.revealed { "<Right/Y> knew it  <Left/N> forgot" } else { "<Enter> show the answer" };
+        Paragraph::new(keys.dark_gray()).centered().render(footer, buf);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::img2ascii::{self, Palette};
+use crate::storage;
+use crate::utils::AsciiCells;
+use crossterm::event::{KeyCode, KeyEvent};
+use image::DynamicImage;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+/// Browse the PNG and JPEG files in the gallery directory as ascii art.
+pub struct GalleryMain {
+    exit_intended: bool,
+    paths: Vec<PathBuf>,
+    selected: usize,
+    palette: Palette,
+    image: Option<Result<DynamicImage, String>>,
+    converted: RefCell<Option<(Rect, AsciiCells)>>, // last conversion and the area it was made for
+}
+
+/// Where the gallery looks for images.
+pub fn gallery_dir() -> PathBuf {
+    storage::data_dir().join("gallery")
+}
+
+impl GalleryMain {
+    pub fn new() -> Self {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(gallery_dir()).into_iter().flatten().flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+                matches!(extension.as_str(), "png" | "jpg" | "jpeg")
+            })
+            .collect();
+        paths.sort();
+        let mut gallery = Self {
+            exit_intended: false,
+            paths,
+            selected: 0,
+            palette: Palette::Ansi16,
+            image: None,
+            converted: RefCell::new(None),
+        };
+        gallery.open_selected();
+        gallery
+    }
+
+    fn open_selected(&mut self) {
+        self.image = self.paths.get(self.selected).map(|path| img2ascii::open(path).map_err(|e| e.to_string()));
+        self.converted.replace(None);
+    }
+
+    fn step(&mut self, forward: bool) {
+        if self.paths.is_empty() {
+            return;
+        }
+        let count = self.paths.len();
+        self.selected = if forward { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+        self.open_selected();
+    }
+}
+
+impl MainScreenWidget for GalleryMain {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Right => self.step(true),
+            KeyCode::Left => self.step(false),
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.palette = match self.palette {
+                    Palette::Monochrome => Palette::Ansi16,
+                    Palette::Ansi16 => Palette::TrueColor,
+                    Palette::TrueColor => Palette::Monochrome,
+                };
+                self.converted.replace(None);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn follows_controls_preset(&self) -> bool { true }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Left Right", "previous / next image"),
+            ("C", "monochrome, 16 colors or true color"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for GalleryMain {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let [picture_area, caption_area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        let caption = match (&self.image, self.paths.get(self.selected)) {
+            (Some(Ok(image)), Some(path)) => {
+                let mut converted = self.converted.borrow_mut();
+                if converted.as_ref().is_none_or(|(made_for, _)| *made_for != picture_area) {
+                    // as wide as possible while the rows still fit
+                    let mut width = picture_area.width;
+                    while width > 1 && img2ascii::rows_for(image, width) > picture_area.height {
+                        width -= 1;
+                    }
+                    *converted = Some((picture_area, img2ascii::convert(image, width, self.palette)));
+                }
+                if let Some((_, cells)) = converted.as_ref() {
+                    cells.render(cells.get_centered_area(picture_area), buf);
+                }
+                let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                format!("{} ({}/{})  <Left Right> browse  <C> colors", name, self.selected + 1, self.paths.len())
+            }
+            (Some(Err(error)), Some(path)) => format!("Could not open {}: {}", path.display(), error),
+            _ => format!("Put PNG or JPEG files into {} to see them here.", gallery_dir().display()),
+        };
+        Paragraph::new(caption.dark_gray()).centered().render(caption_area, buf);
+    }
+}
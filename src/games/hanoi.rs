@@ -0,0 +1,337 @@
+use crate::effects;
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::music::Tune;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::tween::{lerp, Animator, Easing};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::collections::VecDeque;
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Tower of Hanoi";
+
+const MIN_DISCS: usize = 3;
+const MAX_DISCS: usize = 8;
+const DISC_COLORS: [Color; MAX_DISCS] = [
+    Color::LightRed, Color::LightYellow, Color::LightGreen, Color::LightCyan,
+    Color::LightBlue, Color::LightMagenta, Color::Red, Color::Yellow,
+];
+/// Seconds for one move when played by hand, and in the demonstration.
+const MOVE_TIME: f64 = 0.45;
+const DEMO_MOVE_TIME: f64 = 0.3;
+
+/// A disc on its way from one peg to another: lifted off the top, carried across and dropped.
+struct Flight {
+    disc: usize,
+    from: (usize, usize), // peg, height in discs
+    to: (usize, usize),
+    progress: Animator, // 0 to 1 over the whole move
+}
+
+pub struct HanoiGame {
+    discs: usize,
+    pegs: [Vec<usize>; 3], // disc sizes from bottom to top, 1 is the smallest
+    cursor: usize,
+    held: Option<usize>, // peg whose top disc is picked up
+    flight: Option<Flight>,
+    moves: u32,
+    demo: VecDeque<(usize, usize)>, // remaining moves of the auto-solve demonstration
+    demonstrating: bool,
+    demonstrated: bool, // the tower was moved by the demonstration, not the player
+    solved: bool,
+    exit_intended: bool,
+}
+
+impl HanoiGame {
+    pub fn new() -> Self {
+        Self::with_discs(4)
+    }
+
+    pub fn with_discs(discs: usize) -> Self {
+        let discs = discs.clamp(MIN_DISCS, MAX_DISCS);
+        Self {
+            discs,
+            pegs: [(1..=discs).rev().collect(), Vec::new(), Vec::new()],
+            cursor: 0,
+            held: None,
+            flight: None,
+            moves: 0,
+            demo: VecDeque::new(),
+            demonstrating: false,
+            demonstrated: false,
+            solved: false,
+            exit_intended: false,
+        }
+    }
+
+    /// Continue a game saved by [`MainScreenWidget::save_state`].
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut game = Self::new();
+        for (key, value) in data.lines().filter_map(|line| line.split_once('=')) {
+            match key {
+                "discs" => game = Self::with_discs(value.parse().ok()?),
+                "moves" => game.moves = value.parse().ok()?,
+                "pegs" => {
+                    let pegs: Vec<Vec<usize>> = value.split('|')
+                        .map(|peg| peg.split(',').filter(|disc| !disc.is_empty()).map(|disc| disc.parse().ok()).collect())
+                        .collect::<Option<_>>()?;
+                    game.pegs = pegs.try_into().ok()?;
+                }
+                _ => {}
+            }
+        }
+        let mut all: Vec<usize> = game.pegs.iter().flatten().copied().collect();
+        all.sort();
+        let stacked = game.pegs.iter().all(|peg| peg.windows(2).all(|pair| pair[0] > pair[1]));
+        (all == (1..=game.discs).collect::<Vec<_>>() && stacked).then_some(game)
+    }
+
+    /// Fewest moves that solve the puzzle.
+    pub fn optimal_moves(&self) -> u32 {
+        (1 << self.discs) - 1
+    }
+
+    /// Every move of the optimal solution for `discs` discs from `from` to `to`.
+    fn solution(discs: usize, from: usize, to: usize, moves: &mut VecDeque<(usize, usize)>) {
+        if discs == 0 {
+            return;
+        }
+        let spare = 3 - from - to;
+        Self::solution(discs - 1, from, spare, moves);
+        moves.push_back((from, to));
+        Self::solution(discs - 1, spare, to, moves);
+    }
+
+    fn restart(&mut self, discs: usize) {
+        *self = Self { exit_intended: self.exit_intended, ..Self::with_discs(discs) };
+    }
+
+    /// Start over and let the game solve itself.
+    fn start_demo(&mut self) {
+        self.restart(self.discs);
+        Self::solution(self.discs, 0, 2, &mut self.demo);
+        self.demonstrating = true;
+        self.demonstrated = true;
+    }
+
+    fn can_move(&self, from: usize, to: usize) -> bool {
+        match (self.pegs[from].last(), self.pegs[to].last()) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(disc), Some(top)) => disc < top,
+        }
+    }
+
+    /// Take the top disc of `from` off its peg and send it flying; it lands on `to` when the flight ends.
+    fn launch(&mut self, from: usize, to: usize, duration: f64) {
+        let Some(disc) = self.pegs[from].pop() else { return };
+        let mut progress = Animator::new(0.0, duration, Easing::EaseInOut);
+        progress.animate_to(1.0);
+        let (from_height, to_height) = (self.pegs[from].len(), self.pegs[to].len());
+        self.flight = Some(Flight { disc, from: (from, from_height), to: (to, to_height), progress });
+        if !self.demonstrating {
+            self.moves += 1;
+        }
+    }
+
+    fn land(&mut self) {
+        let Some(flight) = self.flight.take() else { return };
+        self.pegs[flight.to.0].push(flight.disc);
+        if self.demonstrating {
+            self.moves += 1;
+        }
+        if self.pegs[2].len() == self.discs && !self.solved {
+            self.solved = true;
+            sound::emit(SoundEvent::Correct);
+            if !self.demonstrated {
+                stats::record_round(NAME, &format!("{} discs", self.discs), self.moves == self.optimal_moves(), 0);
+            }
+        }
+    }
+
+    /// Space on a peg: pick up its top disc, or put the held disc down there.
+    fn select(&mut self, peg: usize) {
+        self.cursor = peg;
+        match self.held.take() {
+            None if self.pegs[peg].is_empty() => {}
+            None => {
+                self.held = Some(peg);
+                sound::emit(SoundEvent::MenuSelect);
+            }
+            Some(from) if from == peg => {}
+            Some(from) if self.can_move(from, peg) => self.launch(from, peg, MOVE_TIME),
+            Some(_) => {
+                sound::emit(SoundEvent::Incorrect);
+                effects::wrong_answer();
+            }
+        }
+    }
+}
+
+impl MainScreenWidget for HanoiGame {
+    fn run(&mut self, dt: f64) {
+        if let Some(flight) = &mut self.flight {
+            flight.progress.tick(dt);
+            if !flight.progress.is_animating() {
+                self.land();
+            }
+        }
+        if self.flight.is_none() && self.demonstrating {
+            match self.demo.pop_front() {
+                Some((from, to)) => self.launch(from, to, DEMO_MOVE_TIME),
+                None => self.demonstrating = false,
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        if self.demonstrating {
+            // any key takes the controls back, from the start
+            return self.restart(self.discs);
+        }
+        if self.demonstrated {
+            self.restart(self.discs);
+        }
+        if self.flight.is_some() {
+            self.land();
+        }
+        match input.code {
+            KeyCode::Left => self.cursor = (self.cursor + 2) % 3,
+            KeyCode::Right => self.cursor = (self.cursor + 1) % 3,
+            KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Up | KeyCode::Down => self.select(self.cursor),
+            KeyCode::Char(peg @ '1'..='3') => self.select(peg as usize - '1' as usize),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.restart(self.discs + 1),
+            KeyCode::Char('-') => self.restart(self.discs.saturating_sub(1)),
+            KeyCode::Char('r') | KeyCode::Char('R') => self.restart(self.discs),
+            KeyCode::Char('s') | KeyCode::Char('S') => self.start_demo(),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    /// A disc is in the air or the demonstration is running.
+    fn needs_redraw(&self) -> bool {
+        self.flight.is_some() || self.demonstrating
+    }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Left Right", "choose a peg"),
+            ("Space", "pick up / put down a disc"),
+            ("1 2 3", "pick up / put down on that peg"),
+            ("+ -", "more or fewer discs, starts over"),
+            ("R", "start over"),
+            ("S", "watch the solution, any key stops it"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    fn is_finished(&self) -> bool { self.solved || self.demonstrated }
+
+    fn save_state(&self) -> Option<SavedState> {
+        if self.moves == 0 || self.solved || self.demonstrated {
+            return None;
+        }
+        let mut pegs = self.pegs.clone();
+        if let Some(flight) = &self.flight {
+            pegs[flight.to.0].push(flight.disc);
+        }
+        let pegs: Vec<String> = pegs.iter()
+            .map(|peg| peg.iter().map(|disc| disc.to_string()).collect::<Vec<_>>().join(","))
+            .collect();
+        Some(SavedState {
+            summary: format!("{} discs, {} moves", self.discs, self.moves),
+            data: format!("discs={}\nmoves={}\npegs={}\n", self.discs, self.moves, pegs.join("|")),
+        })
+    }
+}
+
+impl WidgetRef for HanoiGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let peg_width = self.discs as u16 * 2 + 3;
+        let tower_height = self.discs as u16 + 2;
+        let [header, _, towers, labels, _, footer] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2), // room to lift discs over the pegs
+            Constraint::Length(tower_height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+        let [towers] = Layout::horizontal([Constraint::Length(peg_width * 3 + 4)]).flex(Flex::Center).areas(towers);
+        if towers.height < tower_height || towers.width < peg_width * 3 + 4 {
+            return Paragraph::new("Make the window bigger to see the towers").centered().render(area, buf);
+        }
+
+        let (status, color) = match () {
+            _ if self.demonstrating => ("Watch the solution, any key takes over".to_string(), Color::LightCyan),
+            _ if self.demonstrated => ("That is the optimal solution, now try it yourself".to_string(), Color::LightCyan),
+            _ if self.solved && self.moves == self.optimal_moves() => ("Solved in the fewest moves possible!".to_string(), Color::LightGreen),
+            _ if self.solved => (format!("Solved! {} moves more than needed", self.moves - self.optimal_moves()), Color::Yellow),
+            _ => ("Move the tower to the right peg, never a bigger disc on a smaller one".to_string(), Color::White),
+        };
+        Paragraph::new(vec![
+            Line::from(vec![
+                format!("Moves: {}", self.moves).light_cyan(),
+                format!("  Optimal: {}", self.optimal_moves()).dark_gray(),
+                format!("  Discs: {}", self.discs).yellow(),
+            ]),
+            Line::from(status.fg(color)),
+        ]).centered().render(header, buf);
+
+        let center_x = |peg: usize| towers.x + 2 + peg as u16 * (peg_width + 1) + peg_width / 2;
+        let base_y = towers.bottom() - 1;
+        for peg in 0..3 {
+            let x = center_x(peg);
+            for y in towers.y + 1..base_y {
+                buf.set_string(x, y, "│", Style::default().dark_gray());
+            }
+            let base = "▀".repeat(peg_width as usize);
+            buf.set_string(x - peg_width / 2, base_y, base, Style::default().dark_gray());
+            for (height, disc) in self.pegs[peg].iter().enumerate() {
+                let lifted = self.held == Some(peg) && height + 1 == self.pegs[peg].len();
+                let y = base_y - 1 - height as u16 - if lifted { 1 } else { 0 };
+                draw_disc(*disc, x, y, buf);
+            }
+            let label_style = if self.cursor == peg && !self.demonstrating {
+                Style::default().fg(Color::Black).bg(Color::LightCyan).bold()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            buf.set_string(x - 1, labels.y, format!(" {} ", peg + 1), label_style);
+        }
+
+        if let Some(flight) = &self.flight {
+            // lift to above the pegs, carry across, drop: each a third of the move
+            let t = flight.progress.value();
+            let top = towers.y as f64 - 1.0;
+            let from_y = (base_y - 1 - flight.from.1 as u16) as f64;
+            let to_y = (base_y - 1 - flight.to.1 as u16) as f64;
+            let (from_x, to_x) = (center_x(flight.from.0) as f64, center_x(flight.to.0) as f64);
+            let (x, y) = match t {
+                t if t < 1.0 / 3.0 => (from_x, lerp(from_y, top, t * 3.0)),
+                t if t < 2.0 / 3.0 => (lerp(from_x, to_x, t * 3.0 - 1.0), top),
+                t => (to_x, lerp(top, to_y, t * 3.0 - 2.0)),
+            };
+            draw_disc(flight.disc, x.round() as u16, y.round() as u16, buf);
+        }
+
+        Paragraph::new(Line::from(vec![
+            "<←/→> peg  <Space> pick up / put down  <+/-> discs  <S> solve".dark_gray(),
+        ])).centered().render(footer, buf);
+    }
+}
+
+fn draw_disc(disc: usize, center_x: u16, y: u16, buf: &mut Buffer) {
+    let width = disc as u16 * 2 + 1;
+    if !buf.area.contains(Position::new(center_x, y)) {
+        return;
+    }
+    let style = Style::default().fg(DISC_COLORS[(disc - 1) % DISC_COLORS.len()]);
+    buf.set_string(center_x.saturating_sub(width / 2), y, "█".repeat(width as usize), style);
+}
@@ -0,0 +1,387 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::random::{self, GameRng};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage::migration::{self, Schema};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rand::Rng;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::collections::BTreeMap;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Name under which the trainer is registered; also the key of its stats.
+pub const NAME: &str = "Keyboard Layouts";
+
+const SCHEMA: Schema = Schema { namespace: "training", key: "keyboard_layouts", steps: &[migration::unversioned] };
+
+/// The keys a US QWERTY keyboard types, by physical position. Typed characters are looked up here
+/// and replaced by the character at the same position of the layout being learned.
+const QWERTY: [&str; 3] = ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"];
+
+/// Characters per drill line.
+const LINE_LENGTH: usize = 36;
+/// Lines of at least this accuracy in a row that finish a lesson.
+const LESSON_ACCURACY: f64 = 0.95;
+const LINES_PER_LESSON: u32 = 3;
+
+#[derive(EnumIter, EnumString, Display, Clone, Copy, PartialEq, Debug)]
+pub enum KeyboardLayout {
+    Colemak,
+    Dvorak,
+}
+
+impl KeyboardLayout {
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            KeyboardLayout::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+        }
+    }
+
+    /// What the key at the position of the QWERTY character `typed` produces in this layout.
+    fn remap(self, typed: char) -> Option<char> {
+        let typed = typed.to_ascii_lowercase();
+        QWERTY.iter().zip(self.rows()).find_map(|(qwerty, row)| {
+            qwerty.chars().position(|ch| ch == typed).and_then(|column| row.chars().nth(column))
+        })
+    }
+}
+
+/// Keys are added row by row, starting with the strongest fingers on the home row.
+struct Lesson {
+    name: &'static str,
+    keys: &'static [(usize, usize)], // row, column of the keys added by this lesson
+}
+
+const LESSONS: [Lesson; 5] = [
+    Lesson { name: "Home row, index fingers", keys: &[(1, 3), (1, 4), (1, 5), (1, 6)] },
+    Lesson { name: "Home row", keys: &[(1, 0), (1, 1), (1, 2), (1, 7), (1, 8), (1, 9)] },
+    Lesson { name: "Top row", keys: &[(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6), (0, 7), (0, 8), (0, 9)] },
+    Lesson { name: "Bottom row", keys: &[(2, 0), (2, 1), (2, 2), (2, 3), (2, 4), (2, 5), (2, 6), (2, 7), (2, 8), (2, 9)] },
+    Lesson { name: "Everything", keys: &[] },
+];
+
+/// Hits and misses per key and layout, stored as `Colemak:a=40/3` lines.
+#[derive(Clone, Debug, Default)]
+struct KeyAccuracy {
+    keys: BTreeMap<(String, char), (u32, u32)>,
+}
+
+impl KeyAccuracy {
+    fn load() -> Self {
+        let contents = migration::load(&SCHEMA).ok().flatten().unwrap_or_default();
+        let keys = contents.lines()
+            .filter_map(|line| {
+                let (layout, rest) = line.split_once(':')?;
+                let (key, counts) = rest.rsplit_once('=')?;
+                let (hits, misses) = counts.split_once('/')?;
+                let key = key.chars().next().filter(|_| key.chars().count() == 1)?;
+                Some(((layout.to_string(), key), (hits.parse().ok()?, misses.parse().ok()?)))
+            })
+            .collect();
+        Self { keys }
+    }
+
+    fn save(&self) {
+        let data: String = self.keys.iter()
+            .map(|((layout, key), (hits, misses))| format!("{}:{}={}/{}\n", layout, key, hits, misses))
+            .collect();
+        let _ = migration::save(&SCHEMA, &data);
+    }
+
+    fn record(&mut self, layout: KeyboardLayout, key: char, hit: bool) {
+        let counts = self.keys.entry((layout.to_string(), key)).or_default();
+        if hit { counts.0 += 1 } else { counts.1 += 1 }
+    }
+
+    /// Share of presses that were right, `None` for keys never drilled.
+    fn accuracy(&self, layout: KeyboardLayout, key: char) -> Option<f64> {
+        let (hits, misses) = self.keys.get(&(layout.to_string(), key)).copied().unwrap_or_default();
+        (hits + misses > 0).then(|| hits as f64 / (hits + misses) as f64)
+    }
+}
+
+pub struct LayoutTrainer {
+    layout: KeyboardLayout,
+    lesson: usize,
+    line: Vec<char>,
+    typed: Vec<bool>, // for each typed character of the line, whether it was right the first time
+    missed_current: bool,
+    last_wrong: Option<char>, // what the last wrong key produced, shown on the keyboard
+    good_lines: u32, // accurate lines in a row in this lesson
+    accuracy: KeyAccuracy,
+    message: Option<String>,
+    rng: GameRng, // makes up the words
+    exit_intended: bool,
+}
+
+impl LayoutTrainer {
+    pub fn new() -> Self {
+        let mut trainer = Self {
+            layout: KeyboardLayout::Colemak,
+            lesson: 0,
+            line: Vec::new(),
+            typed: Vec::new(),
+            missed_current: false,
+            last_wrong: None,
+            good_lines: 0,
+            accuracy: KeyAccuracy::load(),
+            message: None,
+            rng: random::rng(),
+            exit_intended: false,
+        };
+        trainer.new_line();
+        trainer
+    }
+
+    /// Make up the words with `rng`, starting with a new line.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self.new_line();
+        self
+    }
+
+    /// Letters of the current lesson and all before it, in the current layout.
+    fn unlocked_keys(&self) -> Vec<char> {
+        let rows = self.layout.rows();
+        let positions: Vec<(usize, usize)> = if LESSONS[self.lesson].keys.is_empty() {
+            (0..3).flat_map(|row| (0..10).map(move |column| (row, column))).collect()
+        } else {
+            LESSONS[..=self.lesson].iter().flat_map(|lesson| lesson.keys.iter().copied()).collect()
+        };
+        positions.iter()
+            .filter_map(|(row, column)| rows[*row].chars().nth(*column))
+            .filter(|key| key.is_ascii_alphabetic())
+            .collect()
+    }
+
+    /// Short made-up words of the unlocked letters; the keys of this lesson and keys often missed come up more.
+    fn new_line(&mut self) {
+        let keys = self.unlocked_keys();
+        let rows = self.layout.rows();
+        let new_keys: Vec<char> = LESSONS[self.lesson].keys.iter().filter_map(|(row, column)| rows[*row].chars().nth(*column)).collect();
+        let weights: Vec<f64> = keys.iter().map(|key| {
+            let weak = 1.0 + 3.0 * (1.0 - self.accuracy.accuracy(self.layout, *key).unwrap_or(1.0));
+            if new_keys.contains(key) { weak * 2.0 } else { weak }
+        }).collect();
+        let total: f64 = weights.iter().sum();
+        let rng = &mut self.rng;
+        let mut line = Vec::new();
+        while line.len() < LINE_LENGTH {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            for _ in 0..rng.random_range(2..=5) {
+                let mut pick = rng.random_range(0.0..total);
+                let index = weights.iter().position(|weight| { pick -= weight; pick < 0.0 }).unwrap_or(0);
+                line.push(keys[index]);
+            }
+        }
+        self.line = line;
+        self.typed.clear();
+        self.missed_current = false;
+        self.last_wrong = None;
+    }
+
+    fn type_char(&mut self, typed: char) {
+        let Some(&expected) = self.line.get(self.typed.len()) else { return };
+        let produced = if typed == ' ' { Some(' ') } else { self.layout.remap(typed) };
+        let Some(produced) = produced else { return };
+        let hit = produced == expected;
+        if expected != ' ' && !(self.missed_current && hit) {
+            self.accuracy.record(self.layout, expected, hit);
+        }
+        if !hit {
+            self.missed_current = true;
+            self.last_wrong = Some(produced);
+            sound::emit(SoundEvent::Incorrect);
+            return;
+        }
+        self.typed.push(!self.missed_current);
+        self.missed_current = false;
+        self.last_wrong = None;
+        if self.typed.len() == self.line.len() {
+            self.finish_line();
+        }
+    }
+
+    fn finish_line(&mut self) {
+        let accuracy = self.line_accuracy();
+        let accurate = accuracy >= LESSON_ACCURACY;
+        stats::record_round(NAME, &self.layout.to_string(), accurate, self.good_lines);
+        self.accuracy.save();
+        self.good_lines = if accurate { self.good_lines + 1 } else { 0 };
+        self.message = Some(format!("Line done with {:.0}% accuracy", accuracy * 100.0));
+        if self.good_lines >= LINES_PER_LESSON && self.lesson + 1 < LESSONS.len() {
+            self.lesson += 1;
+            self.good_lines = 0;
+            self.message = Some(format!("Next lesson: {}", LESSONS[self.lesson].name));
+            sound::emit(SoundEvent::Correct);
+        }
+        self.new_line();
+    }
+
+    fn line_accuracy(&self) -> f64 {
+        if self.typed.is_empty() {
+            return 1.0;
+        }
+        self.typed.iter().filter(|first_try| **first_try).count() as f64 / self.typed.len() as f64
+    }
+
+    fn switch_lesson(&mut self, lesson: usize) {
+        self.lesson = lesson;
+        self.good_lines = 0;
+        self.message = Some(format!("Lesson: {}", LESSONS[self.lesson].name));
+        self.new_line();
+    }
+}
+
+impl MainScreenWidget for LayoutTrainer {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Tab => {
+                self.layout = KeyboardLayout::iter().cycle().skip_while(|layout| *layout != self.layout).nth(1).unwrap();
+                self.switch_lesson(self.lesson);
+            }
+            KeyCode::PageUp => self.switch_lesson(self.lesson.saturating_sub(1)),
+            KeyCode::PageDown => self.switch_lesson((self.lesson + 1).min(LESSONS.len() - 1)),
+            KeyCode::Char(ch) if !input.modifiers.contains(KeyModifiers::CONTROL) => self.type_char(ch),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Type", "the keys as if the layout were set, no need to change your system layout"),
+            ("Tab", "switch between Colemak and Dvorak"),
+            ("PgUp PgDn", "previous / next lesson"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for LayoutTrainer {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let [header, _, line_area, _, keyboard_area, legend, _, footer] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+
+        Paragraph::new(vec![
+            Line::from(vec![
+                format!("{}", self.layout).yellow().bold(),
+                format!("  Lesson {}/{}: {}", self.lesson + 1, LESSONS.len(), LESSONS[self.lesson].name).white(),
+            ]),
+            Line::from(vec![
+                format!("Accuracy {:.0}%", self.line_accuracy() * 100.0).light_cyan(),
+                format!("  {}/{} good lines", self.good_lines, LINES_PER_LESSON).dark_gray(),
+                self.message.as_ref().map_or("".into(), |message| format!("  {}", message).dark_gray()),
+            ]),
+        ]).centered().render(header, buf);
+
+        let spans: Vec<Span> = self.line.iter().enumerate().map(|(index, ch)| {
+            let style = match self.typed.get(index) {
+                Some(true) => Style::default().fg(Color::Green),
+                Some(false) => Style::default().fg(Color::Yellow),
+                None if index == self.typed.len() && self.missed_current => Style::default().fg(Color::Black).bg(Color::LightRed),
+                None if index == self.typed.len() => Style::default().fg(Color::Black).bg(Color::LightCyan),
+                None => Style::default().fg(Color::White),
+            };
+            Span::styled(ch.to_string(), style)
+        }).collect();
+        Paragraph::new(Line::from(spans)).centered().render(line_area, buf);
+
+        self.render_keyboard(keyboard_area, buf);
+
+        Paragraph::new(Line::from(vec![
+            "accuracy ".dark_gray(),
+            " <80% ".fg(Color::Black).bg(Color::Red),
+            " <95% ".fg(Color::Black).bg(Color::Yellow),
+            " 95%+ ".fg(Color::Black).bg(Color::Green),
+            " not practiced ".fg(Color::White).bg(Color::DarkGray),
+        ])).centered().render(legend, buf);
+
+        Paragraph::new("<Tab> switch layout  <PgUp/PgDn> lesson  type on your usual keyboard".dark_gray())
+            .centered()
+            .render(footer, buf);
+    }
+}
+
+impl LayoutTrainer {
+    const KEY_WIDTH: u16 = 4;
+
+    /// The layout being learned, each key shaded by its accuracy; the next key to press is highlighted.
+    fn render_keyboard(&self, area: Rect, buf: &mut Buffer) {
+        let unlocked = self.unlocked_keys();
+        let next = self.line.get(self.typed.len()).copied();
+        for (row, keys) in self.layout.rows().iter().enumerate() {
+            // the rows of a real keyboard are staggered
+            let indent = [0, 1, 3][row];
+            let width = 10 * Self::KEY_WIDTH + indent;
+            let x0 = area.x + area.width.saturating_sub(width + 3) / 2 + indent;
+            let y = area.y + row as u16;
+            if y >= area.bottom() {
+                break;
+            }
+            for (column, key) in keys.chars().enumerate() {
+                let x = x0 + column as u16 * Self::KEY_WIDTH;
+                if x + Self::KEY_WIDTH > area.right() {
+                    break;
+                }
+                let background = match self.accuracy.accuracy(self.layout, key) {
+                    _ if !unlocked.contains(&key) => Color::Reset,
+                    None => Color::DarkGray,
+                    Some(accuracy) if accuracy < 0.8 => Color::Red,
+                    Some(accuracy) if accuracy < LESSON_ACCURACY => Color::Yellow,
+                    Some(_) => Color::Green,
+                };
+                let mut style = if background == Color::Reset {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(if background == Color::DarkGray { Color::White } else { Color::Black }).bg(background)
+                };
+                if Some(key) == next {
+                    style = Style::default().fg(Color::Black).bg(Color::LightCyan).bold();
+                } else if Some(key) == self.last_wrong {
+                    style = style.add_modifier(Modifier::CROSSED_OUT | Modifier::BOLD);
+                }
+                buf.set_string(x, y, format!(" {} ", key), style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_seeded_trainer_makes_up_the_same_lines() {
+        crate::storage::install_for_tests();
+        let lines = |seed| {
+            let mut trainer = LayoutTrainer::new().with_rng(random::seeded(seed));
+            let mut lines = vec![trainer.line.clone()];
+            for _ in 0..3 {
+                trainer.new_line();
+                lines.push(trainer.line.clone());
+            }
+            lines
+        };
+        assert_eq!(lines(5), lines(5));
+        assert_ne!(lines(5), lines(6));
+    }
+}
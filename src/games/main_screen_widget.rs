@@ -1,4 +1,5 @@
-use crossterm::event::KeyEvent;
+use crate::music::Tune;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 
@@ -6,16 +7,65 @@ pub trait WidgetRef {
     fn render_ref(&self, area: Rect, buf: &mut Buffer);
 }
 
+/// Serialized game state together with a short human readable description of it.
+pub struct SavedState {
+    pub summary: String,
+    pub data: String,
+}
+
 pub trait MainScreenWidget: WidgetRef {
     fn run(&mut self, dt: f64) -> ();
     fn handle_input(&mut self, input: KeyEvent) -> ();
+
+    /// Mouse events carry absolute terminal coordinates, matching the areas passed to `render_ref`.
+    fn handle_mouse(&mut self, _event: MouseEvent) -> () {}
     fn is_exit_intended(&self) -> bool;
 
+    /// Whether the app should turn the keys of the chosen [controls preset](crate::keymap::Preset)
+    /// into arrows and Enter before they reach this screen. Screens with letter hotkeys or text
+    /// entry keep the default, their keys would be taken otherwise.
+    fn follows_controls_preset(&self) -> bool {
+        false
+    }
+
+    /// Whether keys reach this screen exactly as the terminal sends them, without
+    /// [`keymap::normalize`](crate::keymap::normalize). Only for showing what arrives.
+    fn wants_raw_keys(&self) -> bool {
+        false
+    }
+
+    /// Whether the screen changes on its own and must be redrawn on the next tick.
+    /// Screens that only change on input return false, the app then redraws them after input only.
+    fn needs_redraw(&self) -> bool {
+        true
+    }
+
     fn get_name(&self) -> String {
         let type_name = std::any::type_name::<Self>();
         type_name.split("::").last().unwrap_or("Unknown").to_string()
     }
 
+    /// Controls of this screen as (key, description) pairs, listed in the F1 help overlay.
+    fn help_lines(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Snapshot used for autosave; `None` when there is nothing worth restoring.
+    /// Restoring is done by the constructor registered in the game registry.
+    fn save_state(&self) -> Option<SavedState> {
+        None
+    }
+
+    /// Whether the game has ended, lost or won. Leaving a finished game leaves nothing to resume.
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// Background tune while this screen is open (only audible with the `music` feature).
+    fn music(&self) -> Option<Tune> {
+        None
+    }
+
     fn get_overview(&self) -> String {
         format!("You are here: {}. The overview is not implemented.", self.get_name())
     }
@@ -0,0 +1,623 @@
+use crate::effects;
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::music::Tune;
+use crate::random::{self, GameRng};
+use crate::report;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage;
+use crate::storage::migration::{self, Schema};
+use crate::storage::scores::{Ranked, ScoreStore};
+use crate::ui::settings_form::{Field, SettingsForm};
+use crate::ui::theme;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use rand::prelude::SliceRandom;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+use std::cell::RefCell;
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Minesweeper";
+
+/// The fastest clears, on one board per difficulty.
+const BEST: Schema = Schema { namespace: storage::SCORES, key: "minesweeper", steps: &[migration::unversioned] };
+pub const LEADERBOARD_SIZE: usize = 5;
+
+/// Colors of the counts 1 to 8, after the classic game.
+const COUNT_COLORS: [Color; 8] = [
+    Color::LightBlue, Color::Green, Color::LightRed, Color::Blue,
+    Color::Red, Color::Cyan, Color::Magenta, Color::Gray,
+];
+
+/// The size of the field and how many mines it hides.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+    Custom { width: usize, height: usize, mines: usize },
+}
+
+impl Difficulty {
+    pub const PRESETS: [Difficulty; 3] = [Difficulty::Beginner, Difficulty::Intermediate, Difficulty::Expert];
+    pub const CUSTOM_WIDTHS: std::ops::RangeInclusive<usize> = 5..=30;
+    pub const CUSTOM_HEIGHTS: std::ops::RangeInclusive<usize> = 5..=20;
+
+    /// A custom field, `None` when it is out of range or leaves no room for a safe first click.
+    pub fn custom(width: usize, height: usize, mines: usize) -> Option<Difficulty> {
+        let fits = Self::CUSTOM_WIDTHS.contains(&width) && Self::CUSTOM_HEIGHTS.contains(&height) && (1..=Self::max_mines(width, height)).contains(&mines);
+        fits.then_some(Difficulty::Custom { width, height, mines })
+    }
+
+    /// The first click and its neighbours stay clear of mines.
+    fn max_mines(width: usize, height: usize) -> usize {
+        width * height - 9
+    }
+
+    pub fn width(self) -> usize { self.size().0 }
+    pub fn height(self) -> usize { self.size().1 }
+    pub fn mines(self) -> usize { self.size().2 }
+
+    fn size(self) -> (usize, usize, usize) {
+        match self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (30, 16, 99),
+            Difficulty::Custom { width, height, mines } => (width, height, mines),
+        }
+    }
+
+    pub fn label(self) -> String {
+        let name = match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Expert => "expert",
+            Difficulty::Custom { .. } => "custom",
+        };
+        format!("{} ({}×{}, {} mines)", name, self.width(), self.height(), self.mines())
+    }
+
+    /// The name of its leaderboard and its form in saves, e.g. `expert` or `custom-12x10-20`.
+    fn key(self) -> String {
+        match self {
+            Difficulty::Beginner => "beginner".to_string(),
+            Difficulty::Intermediate => "intermediate".to_string(),
+            Difficulty::Expert => "expert".to_string(),
+            Difficulty::Custom { width, height, mines } => format!("custom-{}x{}-{}", width, height, mines),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Difficulty> {
+        if let Some(preset) = Self::PRESETS.into_iter().find(|preset| preset.key() == key) { return Some(preset); }
+        let (width, rest) = key.strip_prefix("custom-")?.split_once('x')?;
+        let (height, mines) = rest.split_once('-')?;
+        Self::custom(width.parse().ok()?, height.parse().ok()?, mines.parse().ok()?)
+    }
+}
+
+/// A cleared field, ranked by the time it took; the faster the better.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub tenths: u32, // of a second
+    pub played_at: u64,
+}
+
+impl Ranked for Run {
+    /// `tenths,played_at`
+    fn serialize(&self) -> String {
+        format!("{},{}", self.tenths, self.played_at)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let (tenths, played_at) = value.split_once(',')?;
+        Some(Self { tenths: tenths.trim().parse().ok()?, played_at: played_at.trim().parse().ok()? })
+    }
+
+    fn beats(&self, other: &Self) -> bool { self.tenths < other.tenths }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Square {
+    mine: bool,
+    open: bool,
+    flagged: bool,
+    around: u8, // mines in the eight squares around
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Outcome { Playing, Won, Lost }
+
+pub struct MinesweeperGame {
+    difficulty: Difficulty,
+    squares: Vec<Square>, // row by row
+    mined: bool, // the mines are laid on the first reveal, away from it
+    cursor: (usize, usize),
+    outcome: Outcome,
+    exploded: Option<(usize, usize)>,
+    seconds: f64, // since the first reveal
+    setup: Option<SettingsForm>, // choosing the difficulty, open before the first game and with S
+    field_area: RefCell<Rect>, // last rendered squares, used for mouse hit testing
+    rng: GameRng, // lays the mines
+    best: ScoreStore<Run>,
+    place: Option<usize>, // of the cleared field on its board
+    exit_intended: bool,
+}
+
+impl MinesweeperGame {
+    pub fn new() -> Self {
+        let mut game = Self::with_difficulty(Difficulty::Beginner);
+        game.setup = Some(setup_form(game.difficulty, 0));
+        game
+    }
+
+    /// A field of `difficulty`, ready to play without the setup.
+    pub fn with_difficulty(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            squares: vec![Square::default(); difficulty.width() * difficulty.height()],
+            mined: false,
+            cursor: (difficulty.width() / 2, difficulty.height() / 2),
+            outcome: Outcome::Playing,
+            exploded: None,
+            seconds: 0.0,
+            setup: None,
+            field_area: RefCell::new(Rect::default()),
+            rng: random::rng(),
+            best: ScoreStore::load(&BEST, LEADERBOARD_SIZE),
+            place: None,
+            exit_intended: false,
+        }
+    }
+
+    /// Lay the mines with `rng`, e.g. a [`random::seeded`] one for the same field every time.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Counterpart of [`MainScreenWidget::save_state`]: `<difficulty>,<tenths>` on the first
+    /// line, then the rows of the field: `.` hidden, `*` a hidden mine, `f` and `F` flags on a safe
+    /// square and on a mine, `o` open.
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut lines = data.lines();
+        let (difficulty, tenths) = lines.next()?.split_once(',')?;
+        let mut game = Self::with_difficulty(Difficulty::from_key(difficulty.trim())?);
+        game.seconds = tenths.trim().parse::<u32>().ok()? as f64 / 10.0;
+        let symbols: Vec<char> = lines.flat_map(str::chars).collect();
+        if symbols.len() != game.squares.len() { return None; }
+        for (square, symbol) in game.squares.iter_mut().zip(symbols) {
+            square.mine = matches!(symbol, '*' | 'F');
+            square.flagged = matches!(symbol, 'f' | 'F');
+            square.open = symbol == 'o';
+        }
+        game.mined = true;
+        game.count_around();
+        Some(game)
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.difficulty.width() + x
+    }
+
+    fn neighbours(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let (width, height) = (self.difficulty.width() as isize, self.difficulty.height() as isize);
+        (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0))
+            .map(|(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(|&(x, y)| (0..width).contains(&x) && (0..height).contains(&y))
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect()
+    }
+
+    /// The mines go anywhere but `first` and the squares around it, so the first reveal opens an area.
+    fn lay_mines(&mut self, first: (usize, usize)) {
+        let mut kept_clear = self.neighbours(first);
+        kept_clear.push(first);
+        let kept_clear: Vec<usize> = kept_clear.into_iter().map(|square| self.index(square)).collect();
+        let mut candidates: Vec<usize> = (0..self.squares.len()).filter(|index| !kept_clear.contains(index)).collect();
+        candidates.shuffle(&mut self.rng);
+        for index in candidates.into_iter().take(self.difficulty.mines()) {
+            self.squares[index].mine = true;
+        }
+        self.mined = true;
+        self.count_around();
+    }
+
+    fn count_around(&mut self) {
+        let width = self.difficulty.width();
+        for index in 0..self.squares.len() {
+            let around = self.neighbours((index % width, index / width)).iter().filter(|square| self.squares[self.index(**square)].mine).count();
+            self.squares[index].around = around as u8;
+        }
+    }
+
+    /// Open a hidden square, or chord on an open one.
+    fn reveal(&mut self, square: (usize, usize)) {
+        if self.outcome != Outcome::Playing { return; }
+        if !self.mined { self.lay_mines(square); }
+        let current = self.squares[self.index(square)];
+        if current.flagged { return; }
+        if current.open { return self.chord(square); }
+        self.open(square);
+        self.check_outcome();
+    }
+
+    /// An open count with as many flags around it opens the other squares around it.
+    fn chord(&mut self, square: (usize, usize)) {
+        let neighbours = self.neighbours(square);
+        let flags = neighbours.iter().filter(|n| self.squares[self.index(**n)].flagged).count();
+        if flags != self.squares[self.index(square)].around as usize { return; }
+        for neighbour in neighbours {
+            let around = self.squares[self.index(neighbour)];
+            if !around.open && !around.flagged { self.open(neighbour); }
+        }
+        self.check_outcome();
+    }
+
+    /// Open a square and, if no mines are around it, everything connected up to the counts.
+    fn open(&mut self, square: (usize, usize)) {
+        let mut pending = vec![square];
+        while let Some(square) = pending.pop() {
+            let index = self.index(square);
+            if self.squares[index].open || self.squares[index].flagged { continue; }
+            self.squares[index].open = true;
+            if self.squares[index].mine {
+                self.exploded = Some(square);
+                return;
+            }
+            if self.squares[index].around == 0 { pending.extend(self.neighbours(square)); }
+        }
+    }
+
+    fn toggle_flag(&mut self, square: (usize, usize)) {
+        if self.outcome != Outcome::Playing { return; }
+        let index = self.index(square);
+        if self.squares[index].open { return; }
+        self.squares[index].flagged = !self.squares[index].flagged;
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    fn check_outcome(&mut self) {
+        if self.exploded.is_some() {
+            self.outcome = Outcome::Lost;
+            sound::emit(SoundEvent::GameOver);
+            effects::game_over();
+            stats::record_round(NAME, &self.difficulty.key(), false, 0);
+        } else if self.squares.iter().all(|square| square.open || square.mine) {
+            self.outcome = Outcome::Won;
+            for square in self.squares.iter_mut().filter(|square| square.mine) { square.flagged = true; }
+            sound::emit(SoundEvent::Correct);
+            stats::record_round(NAME, &self.difficulty.key(), true, 0);
+            let run = Run { tenths: (self.seconds * 10.0).round() as u32, played_at: storage::unix_now() };
+            self.place = self.best.insert(&self.difficulty.key(), run);
+            if self.place.is_some() { let _ = self.best.save(); }
+        }
+    }
+
+    fn flags(&self) -> usize {
+        self.squares.iter().filter(|square| square.flagged).count()
+    }
+
+    /// A new field of the same difficulty; the setup keeps its choices too.
+    fn restart(&mut self, difficulty: Difficulty) {
+        let rng = std::mem::replace(&mut self.rng, random::rng());
+        *self = Self::with_difficulty(difficulty).with_rng(rng);
+    }
+
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let (width, height) = (self.difficulty.width() as isize, self.difficulty.height() as isize);
+        let (x, y) = self.cursor;
+        self.cursor = ((x as isize + dx).clamp(0, width - 1) as usize, (y as isize + dy).clamp(0, height - 1) as usize);
+    }
+
+    fn handle_setup_input(&mut self, input: KeyEvent) {
+        let Some(form) = self.setup.as_mut() else { return };
+        match input.code {
+            KeyCode::Enter => {
+                let difficulty = form_difficulty(form);
+                self.restart(difficulty);
+                sound::emit(SoundEvent::MenuSelect);
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit_intended = true,
+            _ => {
+                if let Some(changed) = form.handle_key(input) { self.setup_changed(changed); }
+            }
+        }
+    }
+
+    /// Picking a preset shows its size; changing the size makes it a custom field.
+    fn setup_changed(&mut self, changed: &str) {
+        let Some(form) = self.setup.as_ref() else { return };
+        let difficulty = if changed == "difficulty" { form_difficulty(form) } else { form_custom(form) };
+        self.setup = Some(setup_form(difficulty, form.focused()));
+    }
+
+    /// The square under the mouse, if it is on the field.
+    fn square_at(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let area = *self.field_area.borrow();
+        if !area.contains(Position::new(column, row)) { return None; }
+        let square = (((column - area.x) / 2) as usize, (row - area.y) as usize);
+        (square.0 < self.difficulty.width() && square.1 < self.difficulty.height()).then_some(square)
+    }
+
+    fn board_lines(&self, difficulty: Difficulty) -> Vec<Line<'static>> {
+        let runs = self.best.board(&difficulty.key());
+        if runs.is_empty() {
+            return vec![Line::from(Span::styled("No cleared fields yet", Style::default().fg(Color::DarkGray)))];
+        }
+        runs.iter().enumerate().map(|(place, run)| {
+            let date = report::format_timestamp(run.played_at);
+            let text = format!("{:>2}. {:>6.1} s  {}", place + 1, run.tenths as f64 / 10.0, &date[..10]);
+            let style = if self.place == Some(place) { Style::default().fg(Color::LightGreen).bold() } else if place == 0 { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::Gray) };
+            Line::from(Span::styled(text, style))
+        }).collect()
+    }
+
+    /// What a square shows: hidden, flagged or open, and after the game where the mines were.
+    fn square_cell(&self, square: Square, at: (usize, usize)) -> (String, Style) {
+        let palette = theme::palette();
+        let hidden = Style::default().bg(Color::DarkGray);
+        let over = self.outcome != Outcome::Playing;
+        match square {
+            _ if self.exploded == Some(at) => ("✱ ".to_string(), Style::default().fg(Color::White).bg(palette.bad).bold()),
+            Square { mine: true, flagged: false, .. } if over => ("✱ ".to_string(), Style::default().fg(palette.bad)),
+            Square { mine: false, flagged: true, .. } if over => ("✗ ".to_string(), hidden.fg(palette.bad)),
+            Square { flagged: true, .. } => ("⚑ ".to_string(), hidden.fg(palette.warn).bold()),
+            Square { open: false, .. } => ("  ".to_string(), hidden),
+            Square { around: 0, .. } => (" ·".to_string(), Style::default().fg(Color::DarkGray)),
+            Square { around, .. } => (format!(" {}", around), Style::default().fg(COUNT_COLORS[around as usize - 1]).bold()),
+        }
+    }
+
+    fn render_setup(&self, form: &SettingsForm, area: Rect, buf: &mut Buffer) {
+        let difficulty = form_difficulty(form);
+        let [column] = Layout::horizontal([Constraint::Length(48)]).flex(Flex::Center).areas(area);
+        let [title, form_area, _, keys, _, board] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(form.height()),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(LEADERBOARD_SIZE as u16 + 2),
+        ])
+            .flex(Flex::Center)
+            .areas(column);
+        Paragraph::new(Line::from(NAME).bold().light_cyan()).centered().render(title, buf);
+        form.render(form_area, buf);
+        Paragraph::new(Line::from("↑↓ choose  ←→ change  Enter play").dark_gray()).centered().render(keys, buf);
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray().title(format!(" Fastest {} · {} ", LEADERBOARD_SIZE, difficulty.label()));
+        let inner = block.inner(board);
+        block.render(board, buf);
+        Paragraph::new(self.board_lines(difficulty)).render(inner.inner(Margin { horizontal: 1, vertical: 0 }), buf);
+    }
+}
+
+/// Difficulty first, then the size, which picks a custom field when changed.
+fn setup_form(difficulty: Difficulty, focus: usize) -> SettingsForm {
+    let mut options: Vec<String> = Difficulty::PRESETS.iter().map(|preset| preset.label()).collect();
+    options.push("custom".to_string());
+    let selected = Difficulty::PRESETS.iter().position(|preset| *preset == difficulty).unwrap_or(Difficulty::PRESETS.len());
+    let (width, height) = (difficulty.width(), difficulty.height());
+    SettingsForm::new(vec![
+        Field::choice("difficulty", "Difficulty", options, selected),
+        Field::slider("width", "Width", width as i64, *Difficulty::CUSTOM_WIDTHS.start() as i64, *Difficulty::CUSTOM_WIDTHS.end() as i64, 1),
+        Field::slider("height", "Height", height as i64, *Difficulty::CUSTOM_HEIGHTS.start() as i64, *Difficulty::CUSTOM_HEIGHTS.end() as i64, 1),
+        Field::slider("mines", "Mines", difficulty.mines() as i64, 1, Difficulty::max_mines(width, height) as i64, 1),
+    ]).with_focus(focus)
+}
+
+/// The difficulty the setup shows: the chosen preset, or a custom field of the size set there.
+fn form_difficulty(form: &SettingsForm) -> Difficulty {
+    let selected = form.field("difficulty").map_or(0, Field::selected);
+    Difficulty::PRESETS.get(selected).copied().unwrap_or_else(|| form_custom(form))
+}
+
+/// A custom field of the size in the setup, with the mines capped to what fits.
+fn form_custom(form: &SettingsForm) -> Difficulty {
+    let number = |id| form.field(id).map_or(1, |field| field.number() as usize);
+    let (width, height) = (number("width"), number("height"));
+    let mines = number("mines").min(Difficulty::max_mines(width, height));
+    Difficulty::custom(width, height, mines).unwrap_or(Difficulty::Beginner)
+}
+
+impl MainScreenWidget for MinesweeperGame {
+    fn run(&mut self, dt: f64) {
+        if self.setup.is_none() && self.mined && self.outcome == Outcome::Playing { self.seconds += dt; }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if self.setup.is_some() { return self.handle_setup_input(input); }
+        match input.code {
+            KeyCode::Up => self.move_cursor(0, -1),
+            KeyCode::Down => self.move_cursor(0, 1),
+            KeyCode::Left => self.move_cursor(-1, 0),
+            KeyCode::Right => self.move_cursor(1, 0),
+            KeyCode::Enter | KeyCode::Char(' ') if self.outcome == Outcome::Playing => self.reveal(self.cursor),
+            KeyCode::Enter | KeyCode::Char('n') | KeyCode::Char('N') => self.restart(self.difficulty),
+            KeyCode::Char('f') | KeyCode::Char('F') => self.toggle_flag(self.cursor),
+            KeyCode::Char('s') | KeyCode::Char('S') => self.setup = Some(setup_form(self.difficulty, 0)),
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit_intended = true,
+            _ => {}
+        }
+    }
+
+    /// Left click opens or chords, right click flags, both move the cursor there.
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        if let Some(form) = self.setup.as_mut() {
+            if let Some(changed) = form.handle_mouse(event) { self.setup_changed(changed); }
+            return;
+        }
+        let Some(square) = self.square_at(event.column, event.row) else { return };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.cursor = square;
+                self.reveal(square);
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                self.cursor = square;
+                self.toggle_flag(square);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn follows_controls_preset(&self) -> bool { true }
+
+    /// The clock only runs while a field is being cleared.
+    fn needs_redraw(&self) -> bool { self.setup.is_none() && self.mined && self.outcome == Outcome::Playing }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Arrows", "move"),
+            ("Space / Enter", "open, or open around a count with all its flags"),
+            ("F", "flag or unflag"),
+            ("Left click", "open or open around"),
+            ("Right click", "flag"),
+            ("N", "new field"),
+            ("S", "choose the difficulty"),
+            ("Q", "quit"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    fn is_finished(&self) -> bool { self.outcome != Outcome::Playing }
+
+    fn save_state(&self) -> Option<SavedState> {
+        if self.setup.is_some() || !self.mined || self.outcome != Outcome::Playing { return None; }
+        let width = self.difficulty.width();
+        let rows: String = self.squares.chunks(width)
+            .map(|row| row.iter().map(|square| match square {
+                Square { open: true, .. } => 'o',
+                Square { flagged: true, mine, .. } => if *mine { 'F' } else { 'f' },
+                Square { mine, .. } => if *mine { '*' } else { '.' },
+            }).collect::<String>() + "\n")
+            .collect();
+        Some(SavedState {
+            summary: format!("{}, {:.0} s", self.difficulty.label(), self.seconds),
+            data: format!("{},{}\n{}", self.difficulty.key(), (self.seconds * 10.0) as u32, rows),
+        })
+    }
+
+    fn get_overview(&self) -> String {
+        "Minesweeper: open every square that hides no mine. The counts say how many mines touch a square.".to_string()
+    }
+}
+
+impl WidgetRef for MinesweeperGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(form) = &self.setup {
+            return self.render_setup(form, area, buf);
+        }
+        let (width, height) = (self.difficulty.width() as u16, self.difficulty.height() as u16);
+        let over = self.outcome != Outcome::Playing;
+        let board_height = if over { LEADERBOARD_SIZE as u16 + 2 } else { 0 };
+        let [column] = Layout::horizontal([Constraint::Length((width * 2 + 2).max(56))]).flex(Flex::Center).areas(area);
+        let [status, field, message, board] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(height + 2),
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+        ])
+            .flex(Flex::Center)
+            .areas(column);
+        let [field] = Layout::horizontal([Constraint::Length(width * 2 + 2)]).flex(Flex::Center).areas(field);
+
+        let mines_left = self.difficulty.mines() as isize - self.flags() as isize;
+        Paragraph::new(Line::from(vec![
+            format!("Mines: {}  ", mines_left).light_red(),
+            format!("Time: {:.1} s  ", self.seconds).light_cyan(),
+            self.difficulty.label().gray(),
+        ])).centered().render(status, buf);
+
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray();
+        let inner = block.inner(field);
+        block.render(field, buf);
+        *self.field_area.borrow_mut() = inner;
+        for (index, square) in self.squares.iter().enumerate() {
+            let at = (index % self.difficulty.width(), index / self.difficulty.width());
+            let (symbol, mut style) = self.square_cell(*square, at);
+            if at == self.cursor && !over { style = style.bg(Color::LightYellow).fg(Color::Black); }
+            let position = (inner.x + at.0 as u16 * 2, inner.y + at.1 as u16);
+            if inner.contains(position.into()) { buf.set_string(position.0, position.1, symbol, style); }
+        }
+
+        let text = match (self.outcome, self.place) {
+            (Outcome::Playing, _) if !self.mined => "Open a square to start, the first one is safe".to_string(),
+            (Outcome::Playing, _) => String::new(),
+            (Outcome::Lost, _) => "Boom! N for a new field, S to change the size".to_string(),
+            (Outcome::Won, Some(0)) => format!("Cleared in {:.1} s, a new best! N for a new field", self.seconds),
+            (Outcome::Won, Some(place)) => format!("Cleared in {:.1} s, place {}. N for a new field", self.seconds, place + 1),
+            (Outcome::Won, None) => format!("Cleared in {:.1} s. N for a new field", self.seconds),
+        };
+        Paragraph::new(text.white()).centered().render(message, buf);
+        if over {
+            let block = Block::bordered().border_type(BorderType::Rounded).dark_gray().title(format!(" Fastest {} ", LEADERBOARD_SIZE));
+            let inner = block.inner(board);
+            block.render(board, buf);
+            Paragraph::new(self.board_lines(self.difficulty)).render(inner.inner(Margin { horizontal: 1, vertical: 0 }), buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn game(seed: u64) -> MinesweeperGame {
+        storage::install_for_tests();
+        MinesweeperGame::with_difficulty(Difficulty::Beginner).with_rng(random::seeded(seed))
+    }
+
+    fn press(game: &mut MinesweeperGame, code: KeyCode) {
+        game.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn the_first_reveal_is_safe_and_opens_an_area() {
+        for seed in 0..20 {
+            let mut game = game(seed);
+            press(&mut game, KeyCode::Char(' '));
+            assert!(game.outcome != Outcome::Lost);
+            let around = game.neighbours(game.cursor);
+            assert!(around.iter().all(|square| !game.squares[game.index(*square)].mine));
+            assert_eq!(game.squares.iter().filter(|square| square.mine).count(), 10);
+        }
+    }
+
+    #[test]
+    fn chording_opens_the_unflagged_neighbours() {
+        let mut game = MinesweeperGame::restore("beginner,0\n*........\n.........\n.........\n.........\n.........\n.........\n.........\n.........\n.........\n").unwrap();
+        game.reveal((1, 1));
+        assert!(game.squares[game.index((1, 1))].open);
+        game.reveal((1, 1)); // no flag yet, nothing happens
+        assert!(!game.squares[game.index((0, 1))].open);
+        game.toggle_flag((0, 0));
+        game.reveal((1, 1));
+        assert!(game.neighbours((1, 1)).iter().all(|square| *square == (0, 0) || game.squares[game.index(*square)].open));
+        assert!(game.outcome == Outcome::Won);
+    }
+
+    #[test]
+    fn saves_restore_the_field() {
+        let mut game = game(7);
+        press(&mut game, KeyCode::Enter);
+        game.run(2.5);
+        let saved = game.save_state().unwrap();
+        let restored = MinesweeperGame::restore(&saved.data).unwrap();
+        assert_eq!(restored.save_state().unwrap().data, saved.data);
+    }
+
+    #[test]
+    fn custom_fields_leave_room_for_the_first_click() {
+        assert_eq!(Difficulty::custom(5, 5, 16), Some(Difficulty::Custom { width: 5, height: 5, mines: 16 }));
+        assert_eq!(Difficulty::custom(5, 5, 17), None);
+        assert_eq!(Difficulty::custom(31, 5, 1), None);
+        assert_eq!(Difficulty::from_key("custom-12x10-20"), Difficulty::custom(12, 10, 20));
+        assert_eq!(Difficulty::from_key("expert"), Some(Difficulty::Expert));
+    }
+}
@@ -1,5 +1,28 @@
+pub mod about;
+pub mod binary_clock;
 pub mod binary_numbers;
+pub mod crossword;
+pub mod hanoi;
+pub mod layout_trainer;
 pub mod main_screen_widget;
 pub mod ascii_art;
 pub mod settings;
-pub mod weather_main;
\ No newline at end of file
+pub mod minesweeper;
+pub mod snake;
+pub mod tetris;
+pub mod weather_main;
+pub mod weather_scene;
+pub mod registry;
+pub mod question_bank;
+pub mod scoring;
+pub mod difficulty_editor;
+pub mod flashcards;
+pub mod session_clock;
+pub mod training_plan;
+pub mod trivia;
+pub mod profile;
+pub mod profile_chooser;
+#[cfg(debug_assertions)]
+pub mod example_game;
+#[cfg(feature = "images")]
+pub mod gallery;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::stats::{self, GameStats};
+use crate::games::binary_numbers;
+use crate::ui::heatmap::Heatmap;
+use crate::ui::mistake_map::MistakeMap;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+use std::collections::BTreeMap;
+
+/// Lifetime totals over all games, a calendar of the days played and the kinds of binary numbers
+/// most often missed.
+pub struct ProfileMain {
+    exit_intended: bool,
+    games: BTreeMap<String, GameStats>,
+    activity: BTreeMap<i64, u32>,
+}
+
+impl ProfileMain {
+    pub fn new() -> Self {
+        Self { exit_intended: false, games: stats::all(), activity: stats::activity() }
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'static>> {
+        let seconds: f64 = self.games.values().map(|game| game.seconds_played).sum();
+        let sessions: u32 = self.games.values().map(|game| game.sessions).sum();
+        let rounds: u32 = self.games.values().map(|game| game.rounds).sum();
+        let correct: u32 = self.games.values().map(|game| game.correct).sum();
+        let best_streak = self.games.values().map(|game| game.best_streak).max().unwrap_or(0);
+
+        let mut lines = vec![
+            Line::from(format!("Time played: {}   Sessions: {}", stats::format_duration(seconds), sessions)),
+        ];
+        if rounds > 0 {
+            lines.push(Line::from(format!(
+                "Rounds: {}   Accuracy: {:.0}%   Best streak: {}",
+                rounds, correct as f64 * 100.0 / rounds as f64, best_streak
+            )));
+        }
+        lines
+    }
+
+    /// Binary Numbers misses by bit width and by pattern, empty before the first round.
+    fn mistake_lines(&self) -> Vec<Line<'static>> {
+        let Some(game) = self.games.get(binary_numbers::NAME).filter(|game| !game.patterns.is_empty()) else { return Vec::new() };
+        let mut widths = vec![Span::styled("Binary Numbers missed by width ", Style::default().fg(Color::White))];
+        for (mode, stats) in &game.modes {
+            let missed = 100.0 - stats.accuracy().unwrap_or(100.0);
+            widths.push(Span::styled(format!(" {} ", mode), Style::default().fg(Color::DarkGray)));
+            widths.push(Span::styled(format!("{:.0}%", missed), Style::default().fg(if missed >= 25.0 { Color::LightRed } else { Color::Yellow })));
+        }
+        let mut lines = vec![Line::from(widths), Line::from("")];
+        lines.extend(MistakeMap::from_patterns(&game.patterns).lines());
+        lines
+    }
+}
+
+impl MainScreenWidget for ProfileMain {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, _input: KeyEvent) -> () {}
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+}
+
+impl WidgetRef for ProfileMain {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title("Profile").title_alignment(Alignment::Center);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [summary, _, calendar, _, mistakes] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(Heatmap::HEIGHT),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ]).areas(inner.inner(Margin { horizontal: 1, vertical: 0 }));
+
+        Paragraph::new(self.summary_lines()).render(summary, buf);
+        Heatmap::new(&self.activity, stats::today()).render(calendar, buf);
+        Paragraph::new(self.mistake_lines()).render(mistakes, buf);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::storage::{self, profiles::{self, Profile}};
+use crate::ui::toast;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+
+/// Shown before the main menu: who is playing, a guest who keeps nothing, or a new profile.
+///
+/// Choosing installs the storage backend of the profile, so nothing may read user data before.
+pub struct ProfileChooser {
+    profiles: Vec<String>,
+    selected: usize, // the profiles, then the guest entry, then the new profile entry
+    new_name: Option<String>, // typed after picking the new profile entry
+    chosen: bool,
+}
+
+impl ProfileChooser {
+    /// Starts on the profile picked last time.
+    pub fn new() -> Self {
+        Self::with_profiles(profiles::list(), profiles::last_used())
+    }
+
+    /// Offers `profiles`, starting on `last`.
+    fn with_profiles(profiles: Vec<String>, last: Option<String>) -> Self {
+        let selected = last.and_then(|last| profiles.iter().position(|name| *name == last)).unwrap_or(0);
+        Self { profiles, selected, new_name: None, chosen: false }
+    }
+
+    fn entries(&self) -> usize {
+        self.profiles.len() + 2
+    }
+
+    /// Errors only show once the app runs, as a toast.
+    fn choose(&mut self, profile: Profile) {
+        if let Err(e) = storage::install_profile(&profile) {
+            toast::show(e.to_string());
+        }
+        self.chosen = true;
+    }
+
+    fn confirm(&mut self) {
+        match self.profiles.get(self.selected) {
+            Some(name) => self.choose(Profile::Named(name.clone())),
+            None if self.selected == self.profiles.len() => self.choose(Profile::Guest),
+            None => self.new_name = Some(String::new()),
+        }
+    }
+
+    /// A name that cannot be a profile stays in the field to be fixed.
+    fn confirm_new_name(&mut self) {
+        let Some(name) = self.new_name.take() else { return };
+        if !profiles::is_valid_name(&name) {
+            self.new_name = Some(name);
+            return;
+        }
+        if let Err(e) = profiles::create(&name) {
+            toast::show(format!("Could not create the profile: {}", e));
+        }
+        self.choose(Profile::Named(name));
+    }
+}
+
+impl MainScreenWidget for ProfileChooser {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        if let Some(name) = &mut self.new_name {
+            match input.code {
+                KeyCode::Char(ch) if name.chars().count() < profiles::MAX_NAME_LENGTH => name.push(ch),
+                KeyCode::Backspace => { name.pop(); }
+                KeyCode::Enter => self.confirm_new_name(),
+                KeyCode::Esc => self.new_name = None,
+                _ => {}
+            }
+            return;
+        }
+        match input.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.entries() - 1),
+            KeyCode::Enter => self.confirm(),
+            // keeps whoever played last time
+            KeyCode::Esc => {
+                let last = profiles::last_used().unwrap_or_else(|| profiles::DEFAULT.to_string());
+                self.choose(Profile::Named(last));
+            }
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.chosen }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Up Down", "select a profile"),
+            ("Enter", "play as the selected profile"),
+            ("Esc", "play as last time"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for ProfileChooser {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let entry = |index: usize, text: String, note: &'static str| {
+            let marker = if index == self.selected { "> " } else { "  " };
+            let text = format!("{}{:<width$}", marker, text, width = profiles::MAX_NAME_LENGTH + 2);
+            let text = if index == self.selected { text.light_cyan().bold() } else { text.white() };
+            Line::from(vec![text, note.dark_gray()])
+        };
+        let mut lines: Vec<Line> = self.profiles.iter().enumerate()
+            .map(|(index, name)| entry(index, name.clone(), ""))
+            .collect();
+        lines.push(entry(self.profiles.len(), "Guest".to_string(), "nothing is kept"));
+        lines.push(match &self.new_name {
+            Some(name) => {
+                let hint = if name.is_empty() || profiles::is_valid_name(name) { "".dark_gray() } else { " letters, digits, spaces, - and _".light_red() };
+                Line::from(vec!["> New profile: ".light_cyan().bold(), name.clone().white(), "_".light_cyan(), hint])
+            }
+            None => entry(self.profiles.len() + 1, "New profile...".to_string(), ""),
+        });
+
+        let [column] = Layout::horizontal([Constraint::Length(50)]).flex(Flex::Center).areas(area);
+        let [content, keys] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2), Constraint::Length(1)])
+            .flex(Flex::Center)
+            .areas(column);
+        Paragraph::new(lines)
+            .block(Block::bordered().border_type(BorderType::Rounded).title(" Who is playing? ").title_alignment(Alignment::Center))
+            .render(content, buf);
+        let keys_line = if self.new_name.is_some() { "<Enter> create  <Esc> back" } else { "<Up Down> select  <Enter> play  <Esc> as last time" };
+        Line::from(keys_line.dark_gray()).centered().render(keys, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(chooser: &mut ProfileChooser, code: KeyCode) {
+        chooser.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn chooser() -> ProfileChooser {
+        ProfileChooser::with_profiles(vec![profiles::DEFAULT.to_string(), "ada".to_string()], Some("ada".to_string()))
+    }
+
+    #[test]
+    fn it_starts_on_the_profile_picked_last_time() {
+        assert_eq!(chooser().selected, 1);
+        assert_eq!(ProfileChooser::with_profiles(vec![profiles::DEFAULT.to_string()], Some("gone".to_string())).selected, 0);
+    }
+
+    #[test]
+    fn a_new_profile_needs_a_name_that_can_be_a_directory() {
+        let mut chooser = chooser();
+        for _ in 0..chooser.entries() {
+            press(&mut chooser, KeyCode::Down);
+        }
+        assert_eq!(chooser.selected, chooser.entries() - 1);
+        press(&mut chooser, KeyCode::Enter);
+        for ch in "../x".chars() {
+            press(&mut chooser, KeyCode::Char(ch));
+        }
+        press(&mut chooser, KeyCode::Enter);
+        assert_eq!(chooser.new_name.as_deref(), Some("../x"));
+        assert!(!chooser.is_exit_intended());
+        press(&mut chooser, KeyCode::Esc);
+        assert_eq!(chooser.new_name, None);
+        assert!(!chooser.is_exit_intended());
+    }
+
+    #[test]
+    fn names_stop_at_the_longest_a_profile_can_have() {
+        let mut chooser = ProfileChooser { new_name: Some(String::new()), ..chooser() };
+        for _ in 0..profiles::MAX_NAME_LENGTH + 5 {
+            press(&mut chooser, KeyCode::Char('a'));
+        }
+        assert_eq!(chooser.new_name.map(|name| name.len()), Some(profiles::MAX_NAME_LENGTH));
+    }
+}
@@ -0,0 +1,51 @@
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Remembers the last answers of a session and steers new questions away from them,
+/// so the same number does not come up twice within a few rounds.
+pub struct QuestionBank {
+    recent: VecDeque<u32>,
+    window: usize,
+}
+
+impl QuestionBank {
+    /// Random draws tried before settling for a value that is merely not an exact repeat.
+    const ATTEMPTS: usize = 24;
+
+    /// `window` is the number of past answers to avoid; 0 disables the memory.
+    pub fn new(window: usize) -> Self {
+        Self { recent: VecDeque::with_capacity(window), window }
+    }
+
+    /// Draw a value in `0..=max`, preferring values unlike the recent ones, and remember it.
+    pub fn pick(&mut self, max: u32, rng: &mut impl Rng) -> u32 {
+        self.pick_by(rng, |rng| rng.random_range(0..=max))
+    }
+
+    /// Like [`Self::pick`], with the values drawn by `draw`, e.g. from a list.
+    pub fn pick_by<R: Rng>(&mut self, rng: &mut R, mut draw: impl FnMut(&mut R) -> u32) -> u32 {
+        let mut fallback = None;
+        let mut value = draw(rng);
+        for _ in 0..Self::ATTEMPTS {
+            if !self.recent.contains(&value) {
+                if !self.recent.iter().any(|&recent| Self::is_similar(recent, value)) { break; }
+                fallback.get_or_insert(value);
+            }
+            value = draw(rng);
+        }
+        let value = if self.recent.contains(&value) { fallback.unwrap_or(value) } else { value };
+        self.remember(value);
+        value
+    }
+
+    fn remember(&mut self, value: u32) {
+        if self.window == 0 { return; }
+        if self.recent.len() == self.window { self.recent.pop_front(); }
+        self.recent.push_back(value);
+    }
+
+    /// One flipped bit away, e.g. 1010 and 1011: solved by looking at the last answer.
+    fn is_similar(a: u32, b: u32) -> bool {
+        (a ^ b).count_ones() == 1
+    }
+}
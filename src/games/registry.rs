@@ -0,0 +1,215 @@
+use crate::games::about::AboutMain;
+use crate::games::ascii_art::AsciiArtMain;
+use crate::games::binary_clock::{self, BinaryClock};
+use crate::games::binary_numbers::{self, BinaryNumbersGame, Bits};
+use crate::games::crossword::{self, CrosswordGame};
+use crate::games::difficulty_editor::DifficultyEditor;
+use crate::games::flashcards::{self, FlashcardsGame};
+use crate::games::hanoi::{self, HanoiGame};
+use crate::games::layout_trainer::{self, LayoutTrainer};
+#[cfg(debug_assertions)]
+use crate::games::example_game::{self, ExampleGame};
+#[cfg(feature = "images")]
+use crate::games::gallery::GalleryMain;
+use crate::games::main_screen_widget::MainScreenWidget;
+use crate::games::profile::ProfileMain;
+use crate::games::settings::SettingsMain;
+use crate::games::minesweeper::{self, MinesweeperGame};
+use crate::games::snake::{self, SnakeGame};
+use crate::games::tetris::{self, TetrisGame};
+use crate::games::training_plan::{self, TrainingPlanScreen};
+use crate::games::trivia::{self, TriviaGame};
+use crate::games::weather_main::WeatherMain;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+#[derive(EnumIter, EnumString, IntoStaticStr, Display, Clone, Copy, PartialEq, Debug)]
+pub enum Category {
+    Games,
+    Learn,
+    Tools,
+    Screensavers,
+    Settings,
+}
+
+pub type Constructor = Box<dyn Fn() -> Box<dyn MainScreenWidget>>;
+pub type Restorer = Box<dyn Fn(&str) -> Option<Box<dyn MainScreenWidget>>>;
+
+/// Everything the shell needs to list a game in the main menu and start it.
+pub struct GameDescriptor {
+    pub name: String,
+    pub description: String,
+    pub category: Category,
+    constructor: Option<Constructor>,
+    restorer: Option<Restorer>,
+}
+
+impl GameDescriptor {
+    pub fn new(
+        name: &str,
+        description: &str,
+        category: Category,
+        constructor: impl Fn() -> Box<dyn MainScreenWidget> + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            category,
+            constructor: Some(Box::new(constructor)),
+            restorer: None,
+        }
+    }
+
+    /// Rebuild the game from the data of [`MainScreenWidget::save_state`].
+    pub fn with_restore(mut self, restorer: impl Fn(&str) -> Option<Box<dyn MainScreenWidget>> + 'static) -> Self {
+        self.restorer = Some(Box::new(restorer));
+        self
+    }
+
+    /// Listed in the menu, but cannot be started yet.
+    pub fn placeholder(name: &str, description: &str, category: Category) -> Self {
+        Self { name: name.to_string(), description: description.to_string(), category, constructor: None, restorer: None }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.constructor.is_some()
+    }
+
+    pub fn create(&self) -> Option<Box<dyn MainScreenWidget>> {
+        self.constructor.as_ref().map(|constructor| constructor())
+    }
+
+    pub fn restore(&self, data: &str) -> Option<Box<dyn MainScreenWidget>> {
+        self.restorer.as_ref().and_then(|restorer| restorer(data))
+    }
+}
+
+/// The games shown in the main menu, in registration order.
+///
+/// Crates embedding the shell can add their own screens:
+///
+/// ```ignore
+/// let mut registry = GameRegistry::with_builtin_games();
+/// registry.register(GameDescriptor::new("My Game", "Something new", Category::Games, || Box::new(MyGame::new())));
+/// App::with_registry(registry).run(terminal)
+/// ```
+pub struct GameRegistry {
+    entries: Vec<GameDescriptor>,
+}
+
+impl GameRegistry {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn with_builtin_games() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register(GameDescriptor::new("Settings", "Sound and other preferences.", Category::Settings, || Box::new(SettingsMain::new())))
+            .register(GameDescriptor::new(
+                "Difficulty Curve",
+                "Tune how fast rounds get shorter and how bit widths grow in adaptive mode.",
+                Category::Settings,
+                || Box::new(DifficultyEditor::new()),
+            ))
+            .register(GameDescriptor::new("Weather", "Current conditions at a glance.", Category::Tools, || Box::new(WeatherMain::new())))
+            .register(GameDescriptor::new(binary_clock::NAME, "The time in bits, and a quiz on reading it.", Category::Tools, || Box::new(BinaryClock::new())))
+            .register(GameDescriptor::new("Profile", "Your totals and a calendar of the days you played.", Category::Tools, || Box::new(ProfileMain::new())))
+            .register(GameDescriptor::new("About", "Version, license and who made the bundled art and word lists.", Category::Tools, || Box::new(AboutMain::new())))
+            .register(GameDescriptor::new("Ascii Art", "A quiet tree to look at.", Category::Screensavers, || Box::new(AsciiArtMain::new())))
+            .register(GameDescriptor::new(
+                binary_numbers::NAME,
+                "Convert binary numbers to decimal against the clock.",
+                Category::Games,
+                || Box::new(BinaryNumbersGame::new(Bits::Eight)),
+            ).with_restore(|data| BinaryNumbersGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::new(
+                crossword::NAME,
+                "Tech-themed crosswords: bits, networks and code.",
+                Category::Games,
+                || Box::new(CrosswordGame::new()),
+            ).with_restore(|data| CrosswordGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::new(
+                hanoi::NAME,
+                "Move the tower one disc at a time, or watch it solve itself.",
+                Category::Games,
+                || Box::new(HanoiGame::new()),
+            ).with_restore(|data| HanoiGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::new(trivia::NAME, "Multiple choice questions on computers, networks and their history.", Category::Games, || Box::new(TriviaGame::new())))
+            .register(GameDescriptor::new(
+                layout_trainer::NAME,
+                "Learn Colemak or Dvorak row by row without changing your system layout.",
+                Category::Games,
+                || Box::new(LayoutTrainer::new()),
+            ))
+            .register(GameDescriptor::new(
+                snake::NAME,
+                "Eat, grow and don't bite your own tail. Faster the longer you get.",
+                Category::Games,
+                || Box::new(SnakeGame::new()),
+            ).with_restore(|data| SnakeGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::new(
+                tetris::NAME,
+                "Turn and drop the falling pieces to clear whole rows.",
+                Category::Games,
+                || Box::new(TetrisGame::new()),
+            ).with_restore(|data| TetrisGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::new(
+                minesweeper::NAME,
+                "Open every square without a mine; the numbers count the mines around them.",
+                Category::Games,
+                || Box::new(MinesweeperGame::new()),
+            ).with_restore(|data| MinesweeperGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)))
+            .register(GameDescriptor::placeholder("Dino Jump", "Jump over cacti. Coming soon.", Category::Games));
+        for deck in flashcards::decks() {
+            let (name, description) = (deck.title.clone(), format!("{} flashcards, asked again just before you would forget them.", deck.cards.len()));
+            registry.register(GameDescriptor::new(&name, &description, Category::Learn, move || Box::new(FlashcardsGame::new(deck.clone()))));
+        }
+        // the template stays out of the menu, the trainers and the plan unless asked for
+        #[cfg(debug_assertions)]
+        if std::env::args().any(|arg| arg == "--example-game") {
+            registry.register(GameDescriptor::new(
+                example_game::NAME,
+                "Template for new games: stop the dot inside the zone.",
+                Category::Games,
+                || Box::new(ExampleGame::new()),
+            ).with_restore(|data| ExampleGame::restore(data).map(|game| Box::new(game) as Box<dyn MainScreenWidget>)));
+        }
+        #[cfg(feature = "images")]
+        registry.register(GameDescriptor::new("Gallery", "Your pictures as ascii art.", Category::Screensavers, || Box::new(GalleryMain::new())));
+
+        // the plan covers every playable game registered above
+        let trainers = registry.trainers();
+        registry.register(GameDescriptor::new(
+            training_plan::NAME,
+            "Weekly goals per trainer, with reminders when the week runs out.",
+            Category::Tools,
+            move || Box::new(TrainingPlanScreen::new(trainers.clone())),
+        ));
+        registry
+    }
+
+    pub fn register(&mut self, descriptor: GameDescriptor) -> &mut Self {
+        self.entries.push(descriptor);
+        self
+    }
+
+    pub fn entries(&self) -> &[GameDescriptor] {
+        &self.entries
+    }
+
+    pub fn get(&self, index: usize) -> Option<&GameDescriptor> {
+        self.entries.get(index)
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|game| game.name == name)
+    }
+
+    /// Names of the playable games, the ones training goals and suggestions are about.
+    pub fn trainers(&self) -> Vec<String> {
+        self.entries.iter()
+            .filter(|game| game.category == Category::Games && game.is_available())
+            .map(|game| game.name.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,172 @@
+use crate::storage::migration::{self, Schema};
+use std::sync::Mutex;
+
+const SCHEMA: Schema = Schema { namespace: "config", key: "scoring", steps: &[migration::unversioned] };
+
+/// A piecewise linear curve through `(x, y)` points, flat before the first and after the last point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curve {
+    points: Vec<(u32, f64)>,
+}
+
+impl Curve {
+    pub fn new(mut points: Vec<(u32, f64)>) -> Self {
+        points.sort_by_key(|(x, _)| *x);
+        points.dedup_by_key(|(x, _)| *x);
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[(u32, f64)] {
+        &self.points
+    }
+
+    pub fn value_at(&self, x: f64) -> f64 {
+        let Some(&(first_x, first_y)) = self.points.first() else { return 0.0 };
+        if x <= first_x as f64 { return first_y; }
+        for pair in self.points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            if x <= x1 as f64 {
+                let t = (x - x0 as f64) / (x1 - x0) as f64;
+                return y0 + (y1 - y0) * t;
+            }
+        }
+        self.points.last().map(|(_, y)| *y).unwrap_or(0.0)
+    }
+
+    /// Change the point at `index`, keeping it between its neighbours.
+    pub fn set_point(&mut self, index: usize, x: u32, y: f64) {
+        let lower = if index == 0 { 0 } else { self.points[index - 1].0 + 1 };
+        let upper = self.points.get(index + 1).map(|(x, _)| x - 1).unwrap_or(u32::MAX);
+        if let Some(point) = self.points.get_mut(index) {
+            *point = (x.clamp(lower, upper.max(lower)), y);
+        }
+    }
+
+    /// Insert a point after `index`, halfway to the next one. Returns the index of the new point.
+    pub fn insert_after(&mut self, index: usize) -> usize {
+        let Some(&(x, y)) = self.points.get(index) else { return index };
+        let (new_x, new_y) = match self.points.get(index + 1) {
+            Some(&(next_x, _)) if next_x - x < 2 => return index, // no room in between
+            Some(&(next_x, _)) => ((x + next_x) / 2, self.value_at(((x + next_x) / 2) as f64)),
+            None => (x + 5, y),
+        };
+        self.points.insert(index + 1, (new_x, new_y));
+        index + 1
+    }
+
+    /// Remove the point at `index`; the last remaining point is kept.
+    pub fn remove(&mut self, index: usize) {
+        if self.points.len() > 1 && index < self.points.len() {
+            self.points.remove(index);
+        }
+    }
+
+    /// `0:0,30:15`
+    fn serialize(&self) -> String {
+        self.points.iter().map(|(x, y)| format!("{}:{}", x, y)).collect::<Vec<_>>().join(",")
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let points: Option<Vec<(u32, f64)>> = value.split(',')
+            .map(|point| point.split_once(':').and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?))))
+            .collect();
+        points.filter(|points| !points.is_empty()).map(Self::new)
+    }
+}
+
+/// How rounds are timed and scored, tweakable in the difficulty editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoringRules {
+    pub base_points: u32,
+    pub streak_bonus: u32, // extra points per streak step
+    pub life_every: u32, // streak length that restores a life, 0 never does
+    pub min_round_time: f64, // seconds
+    pub time_penalty: Curve, // seconds taken off the base round time, by streak
+    pub adaptive_bits: Curve, // bit width by streak in adaptive mode, rounded down to 4, 8, 12 or 16
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            base_points: 10,
+            streak_bonus: 2,
+            life_every: 5,
+            min_round_time: 5.0,
+            time_penalty: Self::default_time_penalty(),
+            adaptive_bits: Self::default_adaptive_bits(),
+        }
+    }
+}
+
+impl ScoringRules {
+    pub fn default_time_penalty() -> Curve {
+        Curve::new(vec![(0, 0.0), (30, 15.0)]) // half a second less per streak step
+    }
+
+    pub fn default_adaptive_bits() -> Curve {
+        Curve::new(vec![(0, 4.0), (10, 8.0), (25, 12.0), (45, 16.0)])
+    }
+
+    /// Seconds for a round that takes `base_time` without a streak.
+    pub fn round_time(&self, base_time: f64, streak: u32) -> f64 {
+        (base_time - self.time_penalty.value_at(streak as f64)).max(self.min_round_time)
+    }
+
+    /// Points for a correct answer that brought the streak to `streak`.
+    pub fn points(&self, streak: u32) -> u32 {
+        self.base_points + streak.saturating_sub(1) * self.streak_bonus
+    }
+
+    pub fn restores_life(&self, streak: u32) -> bool {
+        self.life_every > 0 && streak > 0 && streak % self.life_every == 0
+    }
+
+    pub fn adaptive_bit_width(&self, streak: u32) -> u32 {
+        ((self.adaptive_bits.value_at(streak as f64) as u32) / 4 * 4).clamp(4, 16)
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut rules = Self::default();
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let value = value.trim();
+            match key.trim() {
+                "base_points" => if let Ok(v) = value.parse() { rules.base_points = v },
+                "streak_bonus" => if let Ok(v) = value.parse() { rules.streak_bonus = v },
+                "life_every" => if let Ok(v) = value.parse() { rules.life_every = v },
+                "min_round_time" => if let Ok(v) = value.parse() { rules.min_round_time = v },
+                "time_penalty" => if let Some(curve) = Curve::parse(value) { rules.time_penalty = curve },
+                "adaptive_bits" => if let Some(curve) = Curve::parse(value) { rules.adaptive_bits = curve },
+                _ => {}
+            }
+        }
+        rules
+    }
+
+    fn serialize(&self) -> String {
+        format!(
+            "base_points={}\nstreak_bonus={}\nlife_every={}\nmin_round_time={}\ntime_penalty={}\nadaptive_bits={}\n",
+            self.base_points, self.streak_bonus, self.life_every, self.min_round_time,
+            self.time_penalty.serialize(), self.adaptive_bits.serialize(),
+        )
+    }
+
+    fn load() -> Self {
+        migration::load(&SCHEMA).ok().flatten().map(|contents| Self::parse(&contents)).unwrap_or_default()
+    }
+}
+
+static RULES: Mutex<Option<ScoringRules>> = Mutex::new(None);
+
+/// Current rules, loaded from storage on first use.
+pub fn rules() -> ScoringRules {
+    RULES.lock().unwrap().get_or_insert_with(ScoringRules::load).clone()
+}
+
+/// Change the rules and persist them right away.
+pub fn update(change: impl FnOnce(&mut ScoringRules)) {
+    let mut guard = RULES.lock().unwrap();
+    let rules = guard.get_or_insert_with(ScoringRules::load);
+    change(rules);
+    let _ = migration::save(&SCHEMA, &rules.serialize());
+}
@@ -0,0 +1,54 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant; // std's clock panics in the browser
+
+/// Monotonic wall clock running next to a game's own timers.
+///
+/// Games count down with the `dt` the app hands them, which stops while the game is paused or the
+/// process is suspended. A round whose timer stood still for a while gave the player free thinking
+/// time, so the run is flagged as unranked and kept off the leaderboards.
+pub struct SessionClock {
+    started: Instant,
+    last_timed_frame: Option<Instant>,
+    unranked_reason: Option<String>,
+}
+
+impl SessionClock {
+    /// Seconds a running round may lag behind the wall clock before it counts as paused.
+    const TOLERANCE: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self { started: Instant::now(), last_timed_frame: None, unranked_reason: None }
+    }
+
+    /// Called on every frame with the `dt` the game advanced by; `timed` while a round clock is ticking.
+    pub fn observe(&mut self, dt: f64, timed: bool) {
+        let now = Instant::now();
+        if let (true, Some(last)) = (timed, self.last_timed_frame) {
+            let stalled = now.duration_since(last).as_secs_f64() - dt;
+            if stalled > Self::TOLERANCE {
+                self.flag(format!("round clock stopped for {:.0}s", stalled));
+            }
+        }
+        self.last_timed_frame = timed.then_some(now);
+    }
+
+    /// Mark the run unranked; the first reason is kept.
+    pub fn flag(&mut self, reason: impl Into<String>) {
+        self.unranked_reason.get_or_insert_with(|| reason.into());
+    }
+
+    pub fn is_ranked(&self) -> bool {
+        self.unranked_reason.is_none()
+    }
+
+    pub fn unranked_reason(&self) -> Option<&str> {
+        self.unranked_reason.as_deref()
+    }
+
+    /// Wall-clock seconds since the run started.
+    pub fn elapsed(&self) -> f64 {
+        self.started.elapsed().as_secs_f64()
+    }
+}
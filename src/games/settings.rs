@@ -1,27 +1,175 @@
+use crate::art::loader;
+use crate::config::{self, Background};
+use crate::fonts;
 use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
-use crate::utils::{AsciiArtWidget, AsciiCells};
-use crossterm::event::KeyEvent;
+use crate::games::registry::Category;
+use crate::keymap::{self, Preset};
+use crate::ui::key_test::KeyTest;
+use crate::ui::modal::{Modal, ModalResult};
+use crate::ui::settings_form::{Field, SettingsForm};
+use crate::utils::AsciiArtWidget;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::layout::Flex::Center;
 use ratatui::prelude::*;
-use std::collections::HashMap;
-use nice_trim::NiceTrim;
+use ratatui::widgets::Paragraph;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 pub struct SettingsMain {
     exit_intended: bool,
+    form: SettingsForm, // rebuilt from the config after every change
+    reset_prompt: Option<Modal>, // asks before every setting goes back to its default
+    key_test: Option<KeyTest>, // shows what the terminal sends, gets every key while open
 }
 
 impl SettingsMain {
     pub fn new() -> Self {
-        Self { exit_intended: false }
+        Self { exit_intended: false, form: build_form(), reset_prompt: None, key_test: None }
+    }
+
+    /// Show the config as it is now, e.g. after a hotkey changed it.
+    fn refresh(&mut self) {
+        self.form = build_form().with_focus(self.form.focused());
+    }
+
+    /// Store the field the form reports as changed.
+    fn apply(&mut self, id: &str) {
+        let Some(field) = self.form.field(id) else { return };
+        let (on, selected, number) = (field.is_on(), field.selected(), field.number() as u8);
+        match id {
+            "sound" => config::update(|c| c.muted = !on),
+            "volume" => config::update(|c| c.volume = number),
+            "music" => config::update(|c| c.music_volume = number),
+            "motion" => config::update(|c| c.reduce_motion = on),
+            "colorblind" => config::update(|c| c.colorblind = on),
+            "repeats" => config::update(|c| c.question_window = number),
+            "font" => if let Some(font) = fonts::available().get(selected) { config::update(|c| c.font = font.clone()) },
+            "backdrop" => if let Some(background) = Background::iter().nth(selected) { config::update(|c| c.background = background) },
+            "width" => config::update(|c| c.max_width = config::Config::MAX_WIDTH_STEPS[selected]),
+            "margin" => config::update(|c| c.safe_margin = number),
+            "presentation" => config::update(|c| c.presentation = on),
+            "quiet" => config::update(|c| c.do_not_disturb = on),
+            "summary" => config::update(|c| c.session_summary = on),
+            "controls" => if let Some(preset) = Preset::iter().nth(selected) { config::update(|c| c.controls = preset) },
+            "advance" => config::update(|c| c.auto_advance_ms = field.number() as u16),
+            "weather" => config::update(|c| c.weather_ttl_minutes = field.number() as u16),
+            "offline" => config::update(|c| c.weather_offline = on),
+            "alerts" => config::update(|c| c.weather_alert_toasts = on),
+            _ => if let Ok(category) = Category::from_str(id) {
+                config::update(|c| {
+                    c.quiet_during.retain(|quiet| *quiet != category);
+                    if on { c.quiet_during.push(category) }
+                })
+            },
+        }
+        self.refresh();
+    }
+
+    fn answer_reset_prompt(&mut self, result: Option<ModalResult>) {
+        match result {
+            Some(ModalResult::Pressed(0)) => {
+                config::update(|c| *c = config::Config::default());
+                self.reset_prompt = None;
+            }
+            Some(_) => self.reset_prompt = None,
+            None => {}
+        }
     }
 }
 
 impl MainScreenWidget for SettingsMain {
     fn run(&mut self, _dt: f64) {}
 
-    fn handle_input(&mut self, _input: KeyEvent) -> () {}
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        if let Some(test) = &mut self.key_test {
+            if test.handle_key(input) { self.key_test = None }
+            return;
+        }
+        if let Some(prompt) = &mut self.reset_prompt {
+            let result = prompt.handle_key(input);
+            self.answer_reset_prompt(result);
+            return self.refresh();
+        }
+        if self.form.is_capturing() {
+            if let Some(id) = self.form.handle_key(input) { self.apply(id) }
+            return;
+        }
+        match input.code {
+            KeyCode::Char('m') | KeyCode::Char('M') => config::update(|c| c.muted = !c.muted),
+            KeyCode::Char('+') | KeyCode::Char('=') => config::update(|c| c.volume = (c.volume + 10).min(100)),
+            KeyCode::Char('-') => config::update(|c| c.volume = c.volume.saturating_sub(10)),
+            KeyCode::Char(']') => config::update(|c| c.music_volume = (c.music_volume + 10).min(100)),
+            KeyCode::Char('[') => config::update(|c| c.music_volume = c.music_volume.saturating_sub(10)),
+            KeyCode::Char('r') | KeyCode::Char('R') => config::update(|c| c.reduce_motion = !c.reduce_motion),
+            KeyCode::Char('w') | KeyCode::Char('W') => config::update(|c| {
+                c.question_window = (c.question_window + 1) % (config::Config::MAX_QUESTION_WINDOW + 1)
+            }),
+            KeyCode::Char('b') | KeyCode::Char('B') => config::update(|c| {
+                c.background = Background::iter().cycle().skip_while(|b| *b != c.background).nth(1).unwrap()
+            }),
+            KeyCode::Char('l') | KeyCode::Char('L') => config::update(|c| {
+                let steps = config::Config::MAX_WIDTH_STEPS;
+                c.max_width = steps.iter().position(|w| *w == c.max_width).map_or(steps[0], |i| steps[(i + 1) % steps.len()])
+            }),
+            KeyCode::Char('d') | KeyCode::Char('D') => config::update(|c| c.do_not_disturb = !c.do_not_disturb),
+            KeyCode::Char(digit @ '1'..='9') => if let Some(category) = Category::iter().nth(digit as usize - '1' as usize) {
+                config::update(|c| match c.quiet_during.iter().position(|quiet| *quiet == category) {
+                    Some(i) => { c.quiet_during.remove(i); }
+                    None => c.quiet_during.push(category),
+                })
+            },
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                let available = fonts::available();
+                let current = fonts::current();
+                let next = available.iter().position(|name| name == current.name()).map_or(0, |i| (i + 1) % available.len());
+                config::update(|c| c.font = available[next].clone());
+            }
+            KeyCode::Char('k') | KeyCode::Char('K') => self.key_test = Some(KeyTest::new()),
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                self.reset_prompt = Some(Modal::new("Reset Settings")
+                    .body(vec![Line::from("Put every setting back to its default?"), Line::from("Stats and saves are not touched.".dark_gray())])
+                    .buttons(&["Reset", "Cancel"])
+                    .accent(Color::Yellow));
+            }
+            _ => if let Some(id) = self.form.handle_key(input) { self.apply(id) },
+        }
+        self.refresh();
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> () {
+        if let Some(prompt) = &mut self.reset_prompt {
+            let result = prompt.handle_mouse(event);
+            self.answer_reset_prompt(result);
+            self.refresh();
+        } else if let Some(id) = self.form.handle_mouse(event) {
+            self.apply(id);
+        }
+    }
 
     fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn wants_raw_keys(&self) -> bool { self.key_test.is_some() }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("↑ ↓", "choose a setting"),
+            ("← → Enter", "change it, or click it"),
+            ("M", "mute / unmute sounds"),
+            ("+ -", "change the effects volume"),
+            ("[ ]", "change the music volume"),
+            ("R", "reduce motion: no screen shake or flashes"),
+            ("W", "rounds before a puzzle answer may repeat"),
+            ("F", "font for big text, add your own .flf fonts"),
+            ("B", "background: none, solid or pattern"),
+            ("L", "widest layout before the screen is centered"),
+            ("D", "do not disturb: no toasts while playing"),
+            ("1-5", "categories do not disturb applies to"),
+            ("K", "test your keys: what the terminal sends, remembered per terminal"),
+            ("X", "reset all settings to their defaults"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
 }
 
 impl WidgetRef for SettingsMain {
@@ -34,50 +182,86 @@ impl WidgetRef for SettingsMain {
         //Block::default().borders(Borders::ALL).render(bottom, buf);
 
         render_big_text(top, buf);
+        render_values(&self.form, bottom, buf);
+        if let Some(prompt) = &self.reset_prompt {
+            prompt.render(area, buf);
+        }
+        if let Some(test) = &self.key_test {
+            test.render(area, buf);
+        }
+    }
+}
+
+/// The form, a value only the config knows how to change all at once, and notes.
+fn render_values(form: &SettingsForm, area: Rect, buf: &mut Buffer) {
+    let mut notes = vec![
+        Line::from(vec!["  Controls".white(), format!("  {}", config::get().controls.hint()).light_cyan(), "  in menus".dark_gray()]),
+        Line::from(vec!["  Keys".white(), format!("  {}", keyboard_note()).light_cyan(), "  <K> test".dark_gray()]),
+        Line::from(vec!["  Reset".white(), "  all of the above".dark_gray(), "  <X>".dark_gray()]),
+        Line::from(format!("  more fonts: {}", fonts::fonts_dir().join("*.flf").display()).dark_gray()),
+    ];
+    if cfg!(not(feature = "audio")) {
+        notes.push(Line::from("  built without the `audio` feature, sounds are disabled".dark_gray()));
     }
+
+    let [centered] = Layout::horizontal([Constraint::Length(60)]).flex(Center).areas(area);
+    let [form_area, _, notes_area] = Layout::vertical([Constraint::Max(form.height()), Constraint::Length(1), Constraint::Length(notes.len() as u16)])
+        .areas(centered.inner(Margin { horizontal: 0, vertical: 1 }));
+    form.render(form_area, buf);
+    Paragraph::new(notes).render(notes_area, buf);
+}
+
+/// The layout the key test found for this terminal.
+fn keyboard_note() -> String {
+    let quirks = keymap::quirks();
+    let layout = quirks.layout.map_or("layout not tested".to_string(), |layout| format!("{} layout", layout));
+    if quirks.remaps.is_empty() { layout } else { format!("{}, {} remapped", layout, quirks.remaps.len()) }
+}
+
+/// One field per setting, filled in from the config.
+fn build_form() -> SettingsForm {
+    let config = config::get();
+    let fonts = fonts::available();
+    let font = fonts.iter().position(|name| name == fonts::current().name()).unwrap_or(0);
+    let widths = config::Config::MAX_WIDTH_STEPS.iter()
+        .map(|width| if *width == 0 { "whole terminal".to_string() } else { format!("up to {} columns", width) })
+        .collect();
+    let width = config::Config::MAX_WIDTH_STEPS.iter().position(|width| *width == config.max_width).unwrap_or(0);
+    let backdrop = Background::iter().position(|background| background == config.background).unwrap_or(0);
+    let controls = Preset::iter().position(|preset| preset == config.controls).unwrap_or(0);
+
+    let mut fields = vec![
+        Field::toggle("sound", "Sound", !config.muted).hint("<M>"),
+        Field::slider("volume", "Volume", config.volume as i64, 0, 100, 10).unit("%").hint("<+/->"),
+        Field::slider("music", "Music", config.music_volume as i64, 0, 100, 10).unit("%").hint("<[/]>"),
+        Field::toggle("motion", "Reduce motion", config.reduce_motion).hint("<R>"),
+        Field::toggle("colorblind", "Colorblind", config.colorblind).hint("blue and orange, ✓ and ✗"),
+        Field::slider("repeats", "Repeat gap", config.question_window as i64, 0, config::Config::MAX_QUESTION_WINDOW as i64, 1).unit(" rounds").hint("<W>"),
+        Field::slider("advance", "Auto next", config.auto_advance_ms as i64, 0, config::Config::MAX_AUTO_ADVANCE_MS as i64, 250).unit(" ms").hint("0 waits for Enter"),
+        Field::choice("font", "Font", fonts, font).hint("<F>"),
+        Field::choice("backdrop", "Backdrop", Background::iter().map(|background| background.to_string()).collect(), backdrop).hint("<B>"),
+        Field::choice("width", "Width", widths, width).hint("<L>"),
+        Field::slider("margin", "Safe margin", config.safe_margin as i64, 0, config::Config::MAX_SAFE_MARGIN as i64, 1).unit(" rows").hint("for rounded corners"),
+        Field::toggle("presentation", "Presentation", config.presentation).hint("<F6> no bars"),
+        Field::toggle("summary", "Quit summary", config.session_summary),
+        Field::slider("weather", "Weather refresh", config.weather_ttl_minutes as i64, 5, config::Config::MAX_WEATHER_TTL_MINUTES as i64, 5).unit(" min"),
+        Field::toggle("offline", "Weather offline", config.weather_offline).hint("cached reports only"),
+        Field::toggle("alerts", "Weather alerts", config.weather_alert_toasts).hint("toast on start"),
+        Field::choice("controls", "Controls", Preset::iter().map(|preset| preset.label().to_string()).collect(), controls),
+        Field::toggle("quiet", "Do not disturb", config.do_not_disturb).hint("<D> toasts wait in <N>"),
+    ];
+    fields.extend(Category::iter().enumerate().map(|(i, category)| {
+        let label = format!("  during {}", category);
+        Field::toggle(category.into(), &label, config.quiet_during.contains(&category)).hint(&format!("<{}>", i + 1))
+    }));
+    SettingsForm::new(fields)
 }
 
 fn render_big_text(area: Rect, buf: &mut Buffer) {
-    let art = "
-        ███████╗███████╗████████╗████████╗██╗███╗   ██╗ ██████╗ ███████╗
-        ██╔════╝██╔════╝╚══██╔══╝╚══██╔══╝██║████╗  ██║██╔════╝ ██╔════╝
-        ███████╗█████╗     ██║      ██║   ██║██╔██╗ ██║██║  ███╗███████╗
-        ╚════██║██╔══╝     ██║      ██║   ██║██║╚██╗██║██║   ██║╚════██║
-        ███████║███████╗   ██║      ██║   ██║██║ ╚████║╚██████╔╝███████║
-        ╚══════╝╚══════╝   ╚═╝      ╚═╝   ╚═╝╚═╝  ╚═══╝ ╚═════╝ ╚══════╝
-    ".nice();
-
-    let colors = "
-        ███████R███████r████████Y████████G██C███B   ██B ██████p ███████P
-        ██RRRRRR██rrrrrrYYY██YYYYGGG██GGGG██C████B  ██B██pppppp ██PPPPPP
-        ███████R█████r     ██Y      ██G   ██C██B██B ██B██p  ███p███████P
-        RRRRR██R██rrrr     ██Y      ██G   ██C██BB██B██B██p   ██pPPPPP██P
-        ███████R███████r   ██Y      ██G   ██C██B B████Bp██████pp███████P
-        RRRRRRRRrrrrrrrr   YYY      GGG   CCCBBB  BBBBB ppppppp PPPPPPPP
-    ".nice();
-
-    let color_map = HashMap::from([
-        ('█', Color::White),
-        ('R', Color::Red),
-        ('r', Color::LightRed),
-        ('G', Color::LightGreen),
-        ('g', Color::Green),
-        ('B', Color::LightBlue),
-        ('b', Color::Blue),
-        ('Y', Color::LightYellow),
-        //('y', Color::LightYellow),
-        ('P', Color::LightMagenta),
-        ('p', Color::Magenta),
-        ('C', Color::LightCyan),
-        ('W', Color::White),
-        (' ', Color::Reset),
-    ]);
-
-    let default_color = Color::LightBlue;
-    let cells = AsciiCells::from(art.to_string(), colors.to_string(), &color_map, default_color);
+    let Ok(cells) = loader::load("settings") else { return };
     let width = cells.get_width();
     let ascii_widget = AsciiArtWidget::new(cells);
 
     let [centered] = Layout::horizontal([Constraint::Length(width)]).flex(Center).areas(area);
     ascii_widget.render(centered, buf);
-}
\ No newline at end of file
+}
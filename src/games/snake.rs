@@ -0,0 +1,390 @@
+use crate::effects;
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::music::Tune;
+use crate::random::{self, GameRng};
+use crate::report;
+use crate::sound::{self, SoundEvent};
+use crate::sprite::{Entities, Entity, Sprite};
+use crate::stats;
+use crate::storage;
+use crate::storage::migration::{self, Schema};
+use crate::storage::scores::{Ranked, ScoreStore};
+use crate::ui::theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::Rng;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+use std::collections::VecDeque;
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Snake";
+
+/// The field in cells; a cell is drawn two characters wide so it comes out about square.
+const WIDTH: i32 = 28;
+const HEIGHT: i32 = 16;
+const START_LENGTH: usize = 3;
+const START_LIVES: u32 = 3;
+/// Seconds per step at the start length, and the shortest it gets as the snake grows.
+const START_STEP: f64 = 0.16;
+const FASTEST_STEP: f64 = 0.055;
+const STEP_PER_LENGTH: f64 = 0.004;
+/// The food pulses while the snake is moving.
+const FOOD: [&str; 2] = ["●", "○"];
+const FOOD_FPS: f64 = 3.0;
+/// Turns typed ahead of the snake, so a quick U-turn of two keys is not lost between steps.
+const TURN_BUFFER: usize = 2;
+
+/// The longest snakes, on one board.
+const BEST: Schema = Schema { namespace: storage::SCORES, key: "snake", steps: &[migration::unversioned] };
+const BOARD: &str = "classic";
+pub const LEADERBOARD_SIZE: usize = 5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Direction { Up, Down, Left, Right }
+
+impl Direction {
+    fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// A finished run, ranked by the length the snake reached.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub length: u32,
+    pub score: u32,
+    pub played_at: u64,
+}
+
+impl Ranked for Run {
+    /// `length,score,played_at`
+    fn serialize(&self) -> String {
+        format!("{},{},{}", self.length, self.score, self.played_at)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut fields = value.split(',').map(|field| field.trim().parse::<u64>().ok());
+        let mut next = || fields.next().flatten();
+        Some(Self { length: next()? as u32, score: next()? as u32, played_at: next()? })
+    }
+
+    fn beats(&self, other: &Self) -> bool { self.length > other.length }
+}
+
+pub struct SnakeGame {
+    // state that survives a restore
+    score: u32,
+    lives: u32,
+    longest: u32, // the longest the snake got in this run, over all its lives
+    // state of the current life, recreated on restore
+    body: VecDeque<(i32, i32)>, // head first
+    direction: Direction,
+    turns: VecDeque<Direction>, // pressed but not taken yet, see `TURN_BUFFER`
+    growing: usize, // cells still to add to the tail
+    food: (i32, i32),
+    food_sprite: Sprite,
+    waiting: bool, // a new life starts once an arrow is pressed
+    since_step: f64,
+    crashed: bool, // the head hit something, the next arrow starts a new life
+    rng: GameRng, // places the food
+    best: ScoreStore<Run>,
+    place: Option<usize>, // of the finished run on the board
+    exit_intended: bool,
+}
+
+impl SnakeGame {
+    pub fn new() -> Self {
+        let mut game = Self {
+            score: 0,
+            lives: START_LIVES,
+            longest: START_LENGTH as u32,
+            body: VecDeque::new(),
+            direction: Direction::Right,
+            turns: VecDeque::new(),
+            growing: 0,
+            food: (0, 0),
+            food_sprite: Sprite::animated(&FOOD, FOOD_FPS),
+            waiting: true,
+            since_step: 0.0,
+            crashed: false,
+            rng: random::rng(),
+            best: ScoreStore::load(&BEST, LEADERBOARD_SIZE),
+            place: None,
+            exit_intended: false,
+        };
+        game.new_life();
+        game
+    }
+
+    /// Place the food with `rng`, e.g. a [`random::seeded`] one for the same field every time.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self.new_life();
+        self
+    }
+
+    /// Counterpart of [`MainScreenWidget::save_state`]: the score, lives and longest length,
+    /// with a new snake in the middle of the field.
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut fields = data.trim().split(',').map(|field| field.trim().parse::<u32>().ok());
+        let mut next = || fields.next().flatten();
+        let mut game = Self::new();
+        game.score = next()?;
+        game.lives = next()?.clamp(1, START_LIVES);
+        game.longest = next()?;
+        Some(game)
+    }
+
+    /// A snake of the start length in the middle, heading right, waiting for the first key.
+    fn new_life(&mut self) {
+        let (x, y) = (WIDTH / 2, HEIGHT / 2);
+        self.body = (0..START_LENGTH as i32).map(|i| (x - i, y)).collect();
+        self.direction = Direction::Right;
+        self.turns.clear();
+        self.growing = 0;
+        self.waiting = true;
+        self.since_step = 0.0;
+        self.crashed = false;
+        self.place_food();
+    }
+
+    /// On a free cell. The snake never fills the whole field in practice, but if it does the
+    /// food stays where it was.
+    fn place_food(&mut self) {
+        let free: Vec<(i32, i32)> = (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .filter(|cell| !self.body.contains(cell))
+            .collect();
+        if !free.is_empty() {
+            self.food = free[self.rng.random_range(0..free.len())];
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+
+    /// Seconds per step: a longer snake moves faster.
+    fn step_time(&self) -> f64 {
+        (START_STEP - (self.body.len() - START_LENGTH) as f64 * STEP_PER_LENGTH).max(FASTEST_STEP)
+    }
+
+    fn turn(&mut self, direction: Direction) {
+        if self.is_over() { return; }
+        if self.crashed { self.new_life(); }
+        self.waiting = false;
+        let last = self.turns.back().copied().unwrap_or(self.direction);
+        if direction != last && direction != last.opposite() && self.turns.len() < TURN_BUFFER {
+            self.turns.push_back(direction);
+        }
+    }
+
+    fn step(&mut self) {
+        if let Some(direction) = self.turns.pop_front() {
+            self.direction = direction;
+        }
+        let (dx, dy) = self.direction.offset();
+        let (x, y) = self.body[0];
+        let head = (x + dx, y + dy);
+        // the tail moves out of the way in the same step, unless the snake is growing
+        let tail_moves = self.growing == 0;
+        let body = if tail_moves { self.body.range(..self.body.len() - 1).any(|cell| *cell == head) } else { self.body.contains(&head) };
+        if head.0 < 0 || head.0 >= WIDTH || head.1 < 0 || head.1 >= HEIGHT || body {
+            return self.crash();
+        }
+        self.body.push_front(head);
+        if tail_moves { self.body.pop_back(); } else { self.growing -= 1; }
+        if head == self.food {
+            self.growing += 1;
+            self.score += self.body.len() as u32;
+            self.longest = self.longest.max((self.body.len() + self.growing) as u32);
+            sound::emit(SoundEvent::Correct);
+            stats::record_round(NAME, BOARD, true, self.body.len() as u32);
+            self.place_food();
+        }
+    }
+
+    fn crash(&mut self) {
+        self.lives -= 1;
+        self.crashed = true;
+        self.waiting = true;
+        stats::record_round(NAME, BOARD, false, 0);
+        effects::wrong_answer();
+        if self.is_over() {
+            sound::emit(SoundEvent::GameOver);
+            effects::game_over();
+            self.place = self.best.insert(BOARD, Run { length: self.longest, score: self.score, played_at: storage::unix_now() });
+            if self.place.is_some() { let _ = self.best.save(); }
+        } else {
+            sound::emit(SoundEvent::Incorrect);
+        }
+    }
+
+    fn message(&self) -> String {
+        match (self.is_over(), self.crashed) {
+            (true, _) => match self.place {
+                Some(0) => format!("Game over, a new best length of {}! Enter to play again", self.longest),
+                Some(place) => format!("Game over, place {} on the board. Enter to play again", place + 1),
+                None => "Game over. Enter to play again".to_string(),
+            },
+            (false, true) => format!("Crashed! {} {} left, an arrow goes on", self.lives, if self.lives == 1 { "life" } else { "lives" }),
+            (false, false) if self.waiting => "An arrow key starts".to_string(),
+            (false, false) => String::new(),
+        }
+    }
+
+    fn board_lines(&self) -> Vec<Line<'static>> {
+        let runs = self.best.board(BOARD);
+        if runs.is_empty() {
+            return vec![Line::from(Span::styled("No runs yet", Style::default().fg(Color::DarkGray)))];
+        }
+        runs.iter().enumerate().map(|(place, run)| {
+            let date = report::format_timestamp(run.played_at);
+            let text = format!("{:>2}. length {:>3}  score {:>5}  {}", place + 1, run.length, run.score, &date[..10]);
+            let style = if self.place == Some(place) { Style::default().fg(Color::LightGreen).bold() } else if place == 0 { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::Gray) };
+            Line::from(Span::styled(text, style))
+        }).collect()
+    }
+}
+
+impl MainScreenWidget for SnakeGame {
+    fn run(&mut self, dt: f64) {
+        if self.is_over() || self.waiting { return; }
+        self.food_sprite.tick(dt);
+        self.since_step += dt;
+        // a slow frame may owe several steps, but a crash ends them
+        while self.since_step >= self.step_time() && !self.waiting {
+            self.since_step -= self.step_time();
+            self.step();
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        match input.code {
+            KeyCode::Up => self.turn(Direction::Up),
+            KeyCode::Down => self.turn(Direction::Down),
+            KeyCode::Left => self.turn(Direction::Left),
+            KeyCode::Right => self.turn(Direction::Right),
+            KeyCode::Enter if self.is_over() => {
+                let rng = std::mem::replace(&mut self.rng, random::rng());
+                *self = Self::new().with_rng(rng);
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit_intended = true,
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn follows_controls_preset(&self) -> bool { true }
+
+    fn needs_redraw(&self) -> bool { !self.is_over() && !self.waiting }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Arrows", "steer, the first one starts"),
+            ("Enter", "play again after game over"),
+            ("Q", "quit"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    fn is_finished(&self) -> bool { self.is_over() }
+
+    fn save_state(&self) -> Option<SavedState> {
+        if self.is_over() { return None; }
+        Some(SavedState {
+            summary: format!("score {}, {} lives", self.score, self.lives),
+            data: format!("{},{},{}", self.score, self.lives, self.longest),
+        })
+    }
+
+    fn get_overview(&self) -> String {
+        "Snake: eat the food to grow, and don't run into the walls or yourself. The longer the snake, the faster it goes.".to_string()
+    }
+}
+
+impl WidgetRef for SnakeGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let palette = theme::palette();
+        let board_height = if self.is_over() { LEADERBOARD_SIZE as u16 + 3 } else { 0 };
+        let [column] = Layout::horizontal([Constraint::Length(WIDTH as u16 * 2 + 2)]).flex(Flex::Center).areas(area);
+        let [status, field, message, board] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(HEIGHT as u16 + 2),
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+        ])
+            .flex(Flex::Center)
+            .areas(column);
+
+        let best = self.best.best(BOARD).map_or(0, |run| run.length);
+        Paragraph::new(Line::from(vec![
+            format!("Score: {}  ", self.score).green(),
+            format!("Length: {}  ", self.body.len() + self.growing).light_cyan(),
+            format!("Best: {}  ", best.max(self.longest)).yellow(),
+            format!("Lives: {}", "♥".repeat(self.lives as usize)).red(),
+        ])).centered().render(status, buf);
+
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray();
+        let inner = block.inner(field);
+        block.render(field, buf);
+        // cells are two characters wide, whatever lies outside the field is clipped by the entities
+        let at = |sprite: Sprite, (x, y): (i32, i32)| Entity::new(sprite, (x * 2) as f64, y as f64);
+        let mut entities = Entities::new();
+        entities.spawn(at(self.food_sprite.clone().style(Style::default().fg(palette.warn).bold()), self.food));
+        for (index, cell) in self.body.iter().enumerate().rev() {
+            let color = if index == 0 { palette.good_light } else { palette.good };
+            entities.spawn(at(Sprite::new("██").style(Style::default().fg(color)), *cell));
+        }
+        if self.crashed {
+            entities.spawn(at(Sprite::new("✗✗").style(Style::default().fg(palette.bad).bold()), self.body[0]));
+        }
+        (&entities).render(inner, buf);
+
+        Paragraph::new(self.message().white()).centered().render(message, buf);
+        if self.is_over() {
+            let block = Block::bordered().border_type(BorderType::Rounded).dark_gray().title(format!(" Best {} ", LEADERBOARD_SIZE));
+            let inner = block.inner(board);
+            block.render(board, buf);
+            Paragraph::new(self.board_lines()).render(inner.inner(Margin { horizontal: 1, vertical: 0 }), buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_snake_and_its_food_are_drawn_two_characters_per_cell() {
+        storage::install_for_tests();
+        let game = SnakeGame::new().with_rng(random::seeded(7));
+        let area = Rect::new(0, 0, WIDTH as u16 * 2 + 2, HEIGHT as u16 + 6);
+        let mut buf = Buffer::empty(area);
+        game.render_ref(area, &mut buf);
+        // centered one row down, then the status line and the border of the field
+        let cell = |(x, y): (i32, i32), column: i32| buf[((1 + x * 2 + column) as u16, (3 + y) as u16)].symbol().to_string();
+        for segment in &game.body {
+            assert_eq!((cell(*segment, 0), cell(*segment, 1)), ("█".to_string(), "█".to_string()));
+        }
+        assert_eq!(cell(game.food, 0), FOOD[0]);
+        assert_eq!(cell(game.food, 1), " ");
+    }
+}
@@ -0,0 +1,551 @@
+use crate::effects;
+use crate::games::main_screen_widget::{MainScreenWidget, SavedState, WidgetRef};
+use crate::music::Tune;
+use crate::random::{self, GameRng};
+use crate::report;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage;
+use crate::storage::migration::{self, Schema};
+use crate::storage::scores::{Ranked, ScoreStore};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::prelude::SliceRandom;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Tetris";
+
+/// The well in cells; a cell is drawn two characters wide. The top `HIDDEN` rows are above the
+/// visible field, the pieces appear there.
+const COLS: i32 = 10;
+const ROWS: i32 = 22;
+const HIDDEN: i32 = 2;
+/// Seconds a piece may rest on the ground before it locks, and how often moving or turning it
+/// starts that time again.
+const LOCK_DELAY: f64 = 0.5;
+const LOCK_RESETS: u32 = 15;
+const LINES_PER_LEVEL: u32 = 10;
+const MAX_LEVEL: u32 = 20;
+/// Points for clearing one to four lines at once, times the level.
+const LINE_POINTS: [u32; 5] = [0, 100, 300, 500, 800];
+
+/// The best games, on one board.
+const BEST: Schema = Schema { namespace: storage::SCORES, key: "tetris", steps: &[migration::unversioned] };
+const BOARD: &str = "marathon";
+pub const LEADERBOARD_SIZE: usize = 5;
+
+/// SRS offsets tried in turn when a clockwise turn out of each rotation is blocked, x to the
+/// right and y up. A counter-clockwise turn tries the ones of the opposite turn, negated.
+const KICKS: [[(i32, i32); 5]; 4] = [
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // 0 → R
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],     // R → 2
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],    // 2 → L
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],  // L → 0
+];
+const I_KICKS: [[(i32, i32); 5]; 4] = [
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],   // 0 → R
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],   // R → 2
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],   // 2 → L
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],   // L → 0
+];
+
+/// The seven tetrominoes.
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+enum Kind { I, O, T, S, Z, J, L }
+
+impl Kind {
+    /// Cells of the spawn rotation in its box, y down.
+    fn cells(self) -> [(i32, i32); 4] {
+        match self {
+            Kind::I => [(0, 1), (1, 1), (2, 1), (3, 1)],
+            Kind::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+            Kind::T => [(1, 0), (0, 1), (1, 1), (2, 1)],
+            Kind::S => [(1, 0), (2, 0), (0, 1), (1, 1)],
+            Kind::Z => [(0, 0), (1, 0), (1, 1), (2, 1)],
+            Kind::J => [(0, 0), (0, 1), (1, 1), (2, 1)],
+            Kind::L => [(2, 0), (0, 1), (1, 1), (2, 1)],
+        }
+    }
+
+    /// Side of the box the piece turns in.
+    fn size(self) -> i32 {
+        match self {
+            Kind::I => 4,
+            Kind::O => 2,
+            _ => 3,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Kind::I => Color::Cyan,
+            Kind::O => Color::Yellow,
+            Kind::T => Color::Magenta,
+            Kind::S => Color::Green,
+            Kind::Z => Color::Red,
+            Kind::J => Color::Blue,
+            Kind::L => Color::LightRed,
+        }
+    }
+
+    /// The letter of the piece in saves.
+    fn letter(self) -> char {
+        match self {
+            Kind::I => 'I',
+            Kind::O => 'O',
+            Kind::T => 'T',
+            Kind::S => 'S',
+            Kind::Z => 'Z',
+            Kind::J => 'J',
+            Kind::L => 'L',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Kind> {
+        Kind::iter().find(|kind| kind.letter() == letter)
+    }
+}
+
+/// The falling piece: its kind, rotation (0 to 3, clockwise) and the top left of its box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Piece {
+    kind: Kind,
+    rotation: usize,
+    x: i32,
+    y: i32,
+}
+
+impl Piece {
+    /// In the middle of the hidden rows above the field.
+    fn spawn(kind: Kind) -> Self {
+        Self { kind, rotation: 0, x: if kind == Kind::O { 4 } else { 3 }, y: 0 }
+    }
+
+    fn moved(self, dx: i32, dy: i32) -> Self {
+        Self { x: self.x + dx, y: self.y + dy, ..self }
+    }
+
+    /// The cells on the board, turned clockwise in the box once per rotation.
+    fn cells(self) -> [(i32, i32); 4] {
+        let size = self.kind.size();
+        self.kind.cells().map(|cell| {
+            let (x, y) = (0..self.rotation).fold(cell, |(x, y), _| (size - 1 - y, x));
+            (self.x + x, self.y + y)
+        })
+    }
+}
+
+/// A finished game, ranked by its score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub score: u32,
+    pub lines: u32,
+    pub level: u32,
+    pub played_at: u64,
+}
+
+impl Ranked for Run {
+    /// `score,lines,level,played_at`
+    fn serialize(&self) -> String {
+        format!("{},{},{},{}", self.score, self.lines, self.level, self.played_at)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut fields = value.split(',').map(|field| field.trim().parse::<u64>().ok());
+        let mut next = || fields.next().flatten();
+        Some(Self { score: next()? as u32, lines: next()? as u32, level: next()? as u32, played_at: next()? })
+    }
+
+    fn beats(&self, other: &Self) -> bool { self.score > other.score }
+}
+
+pub struct TetrisGame {
+    // state that survives a restore
+    board: Vec<[Option<Kind>; COLS as usize]>, // `ROWS` rows, top first
+    score: u32,
+    lines: u32,
+    hold: Option<Kind>,
+    // state of the current piece, recreated on restore
+    piece: Piece,
+    bag: Vec<Kind>, // the coming pieces, the next one last; refilled with all seven, shuffled
+    held: bool, // the hold was used for this piece already
+    since_fall: f64, // seconds since the piece last fell a row
+    on_ground: Option<f64>, // seconds the piece rests on something, see `LOCK_DELAY`
+    lock_resets: u32,
+    over: bool,
+    rng: GameRng, // shuffles the bag
+    best: ScoreStore<Run>,
+    place: Option<usize>, // of the finished game on the board
+    exit_intended: bool,
+}
+
+impl TetrisGame {
+    pub fn new() -> Self {
+        let mut game = Self {
+            board: vec![[None; COLS as usize]; ROWS as usize],
+            score: 0,
+            lines: 0,
+            hold: None,
+            piece: Piece::spawn(Kind::T),
+            bag: Vec::new(),
+            held: false,
+            since_fall: 0.0,
+            on_ground: None,
+            lock_resets: 0,
+            over: false,
+            rng: random::rng(),
+            best: ScoreStore::load(&BEST, LEADERBOARD_SIZE),
+            place: None,
+            exit_intended: false,
+        };
+        let first = game.next_kind();
+        game.spawn(first);
+        game
+    }
+
+    /// Shuffle the pieces with `rng`, e.g. a [`random::seeded`] one for the same order every time.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self.bag.clear();
+        let first = self.next_kind();
+        self.spawn(first);
+        self
+    }
+
+    /// Counterpart of [`MainScreenWidget::save_state`]: `score,lines,hold` on the first line,
+    /// then the rows of the board top first, `.` for an empty cell and the letter of the piece
+    /// otherwise. The falling piece starts over with a new one.
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut lines = data.lines();
+        let mut fields = lines.next()?.split(',');
+        let mut game = Self::new();
+        game.score = fields.next()?.trim().parse().ok()?;
+        game.lines = fields.next()?.trim().parse().ok()?;
+        game.hold = fields.next().and_then(|hold| hold.trim().chars().next()).and_then(Kind::from_letter);
+        let board: Vec<[Option<Kind>; COLS as usize]> = lines.map(|line| {
+            let mut row = [None; COLS as usize];
+            for (cell, letter) in row.iter_mut().zip(line.chars()) { *cell = Kind::from_letter(letter); }
+            row
+        }).collect();
+        if board.len() != ROWS as usize { return None; }
+        game.board = board;
+        let first = game.next_kind();
+        game.spawn(first);
+        Some(game)
+    }
+
+    fn level(&self) -> u32 {
+        (1 + self.lines / LINES_PER_LEVEL).min(MAX_LEVEL)
+    }
+
+    /// Seconds per row, from one at level 1 to a blur at level 20.
+    fn gravity(&self) -> f64 {
+        let level = (self.level() - 1) as f64;
+        (0.8 - level * 0.007).powf(level)
+    }
+
+    /// The next piece from the bag, a new shuffled set of all seven once it runs out.
+    fn next_kind(&mut self) -> Kind {
+        if self.bag.len() <= 1 {
+            let mut set: Vec<Kind> = Kind::iter().collect();
+            set.shuffle(&mut self.rng);
+            self.bag.splice(0..0, set);
+        }
+        self.bag.pop().unwrap_or(Kind::T)
+    }
+
+    fn preview(&self) -> Option<Kind> {
+        self.bag.last().copied()
+    }
+
+    /// A new piece at the top; the game is over when there is no room for it.
+    fn spawn(&mut self, kind: Kind) {
+        self.piece = Piece::spawn(kind);
+        self.since_fall = 0.0;
+        self.on_ground = None;
+        self.lock_resets = 0;
+        if !self.fits(self.piece) { self.game_over(); }
+    }
+
+    fn fits(&self, piece: Piece) -> bool {
+        piece.cells().iter().all(|&(x, y)| {
+            (0..COLS).contains(&x) && (0..ROWS).contains(&y) && self.board[y as usize][x as usize].is_none()
+        })
+    }
+
+    /// Move the piece if it fits there. Moving a piece that rests on the ground gives it more time.
+    fn shift(&mut self, dx: i32, dy: i32) -> bool {
+        let moved = self.piece.moved(dx, dy);
+        if !self.fits(moved) { return false; }
+        self.piece = moved;
+        self.moved_on_ground();
+        true
+    }
+
+    fn moved_on_ground(&mut self) {
+        if self.on_ground.is_some() && self.lock_resets < LOCK_RESETS {
+            self.on_ground = Some(0.0);
+            self.lock_resets += 1;
+        }
+    }
+
+    /// Turn a quarter, trying the wall kicks of the rotation system in turn.
+    fn rotate(&mut self, clockwise: bool) {
+        let table = match self.piece.kind {
+            Kind::O => return,
+            Kind::I => &I_KICKS,
+            _ => &KICKS,
+        };
+        let from = self.piece.rotation;
+        let to = if clockwise { (from + 1) % 4 } else { (from + 3) % 4 };
+        let kicks = if clockwise { table[from] } else { table[to].map(|(x, y)| (-x, -y)) };
+        for (dx, dy) in kicks {
+            let turned = Piece { rotation: to, ..self.piece.moved(dx, -dy) };
+            if self.fits(turned) {
+                self.piece = turned;
+                self.moved_on_ground();
+                return;
+            }
+        }
+    }
+
+    /// One row down, a point for each.
+    fn soft_drop(&mut self) {
+        if self.shift(0, 1) {
+            self.score += 1;
+            self.since_fall = 0.0;
+        }
+    }
+
+    /// All the way down and locked at once, two points per row.
+    fn hard_drop(&mut self) {
+        let landing = self.landing();
+        self.score += 2 * (landing.y - self.piece.y) as u32;
+        self.piece = landing;
+        self.lock();
+    }
+
+    /// Where the piece would land, drawn as a shadow.
+    fn landing(&self) -> Piece {
+        let mut piece = self.piece;
+        while self.fits(piece.moved(0, 1)) { piece = piece.moved(0, 1); }
+        piece
+    }
+
+    /// Swap the piece for the one held, or the next one when nothing is, once per piece.
+    fn hold(&mut self) {
+        if self.held { return; }
+        let kind = self.hold.replace(self.piece.kind).unwrap_or_else(|| self.next_kind());
+        self.spawn(kind);
+        self.held = true;
+    }
+
+    fn lock(&mut self) {
+        let cells = self.piece.cells();
+        for (x, y) in cells {
+            self.board[y as usize][x as usize] = Some(self.piece.kind);
+        }
+        // a piece that comes to rest wholly above the field ends the game
+        if cells.iter().all(|(_, y)| *y < HIDDEN) { return self.game_over(); }
+        self.clear_lines();
+        self.held = false;
+        let next = self.next_kind();
+        self.spawn(next);
+    }
+
+    fn clear_lines(&mut self) {
+        self.board.retain(|row| row.iter().any(Option::is_none));
+        let cleared = ROWS as usize - self.board.len();
+        if cleared == 0 { return; }
+        self.board.splice(0..0, vec![[None; COLS as usize]; cleared]);
+        self.score += LINE_POINTS[cleared.min(4)] * self.level();
+        self.lines += cleared as u32;
+        sound::emit(SoundEvent::Correct);
+        stats::record_round(NAME, BOARD, true, cleared as u32);
+    }
+
+    fn game_over(&mut self) {
+        self.over = true;
+        sound::emit(SoundEvent::GameOver);
+        effects::game_over();
+        stats::record_round(NAME, BOARD, false, 0);
+        self.place = self.best.insert(BOARD, Run { score: self.score, lines: self.lines, level: self.level(), played_at: storage::unix_now() });
+        if self.place.is_some() { let _ = self.best.save(); }
+    }
+
+    fn board_lines(&self) -> Vec<Line<'static>> {
+        let runs = self.best.board(BOARD);
+        if runs.is_empty() {
+            return vec![Line::from(Span::styled("No games yet", Style::default().fg(Color::DarkGray)))];
+        }
+        runs.iter().enumerate().map(|(place, run)| {
+            let date = report::format_timestamp(run.played_at);
+            let text = format!("{:>2}. {:>7}  {:>3} lines  level {:>2}  {}", place + 1, run.score, run.lines, run.level, &date[..10]);
+            let style = if self.place == Some(place) { Style::default().fg(Color::LightGreen).bold() } else if place == 0 { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::Gray) };
+            Line::from(Span::styled(text, style))
+        }).collect()
+    }
+
+    /// A piece in its spawn rotation, for the next and hold boxes.
+    fn render_piece_box(title: &str, kind: Option<Kind>, dim: bool, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray().title(title.to_string());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let Some(kind) = kind else { return };
+        let color = if dim { Color::DarkGray } else { kind.color() };
+        // the I lies in the second row of its box, the others start in the first
+        let top = kind.cells().iter().map(|(_, y)| *y).min().unwrap_or(0);
+        let left = (inner.width.saturating_sub(kind.size() as u16 * 2)) / 2;
+        for (x, y) in kind.cells() {
+            let position = (inner.x + left + x as u16 * 2, inner.y + (y - top) as u16);
+            if inner.contains(position.into()) {
+                buf.set_string(position.0, position.1, "██", Style::default().fg(color));
+            }
+        }
+    }
+}
+
+impl MainScreenWidget for TetrisGame {
+    /// Gravity pulls the piece down a row at a time; on the ground it locks after `LOCK_DELAY`.
+    fn run(&mut self, dt: f64) {
+        if self.over { return; }
+        if !self.fits(self.piece.moved(0, 1)) {
+            let resting = self.on_ground.get_or_insert(0.0);
+            *resting += dt;
+            if *resting >= LOCK_DELAY { self.lock(); }
+            return;
+        }
+        self.on_ground = None;
+        self.since_fall += dt;
+        let gravity = self.gravity();
+        while self.since_fall >= gravity {
+            self.since_fall -= gravity;
+            if !self.shift(0, 1) { break; }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        match input.code {
+            KeyCode::Enter if self.over => {
+                let rng = std::mem::replace(&mut self.rng, random::rng());
+                *self = Self::new().with_rng(rng);
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit_intended = true,
+            _ if self.over => {}
+            KeyCode::Left => { self.shift(-1, 0); }
+            KeyCode::Right => { self.shift(1, 0); }
+            KeyCode::Down => self.soft_drop(),
+            KeyCode::Up | KeyCode::Char('x') | KeyCode::Char('X') => self.rotate(true),
+            KeyCode::Char('z') | KeyCode::Char('Z') => self.rotate(false),
+            KeyCode::Char(' ') => self.hard_drop(),
+            KeyCode::Char('c') | KeyCode::Char('C') => self.hold(),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn follows_controls_preset(&self) -> bool { true }
+
+    fn needs_redraw(&self) -> bool { !self.over }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Left Right", "move"),
+            ("Up / X", "turn clockwise"),
+            ("Z", "turn counter-clockwise"),
+            ("Down", "soft drop"),
+            ("Space", "hard drop"),
+            ("C", "hold the piece, once per piece"),
+            ("Enter", "play again after game over"),
+            ("Q", "quit"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+
+    fn is_finished(&self) -> bool { self.over }
+
+    fn save_state(&self) -> Option<SavedState> {
+        if self.over { return None; }
+        let hold = self.hold.map_or(String::new(), |kind| kind.letter().to_string());
+        let rows: String = self.board.iter()
+            .map(|row| row.iter().map(|cell| cell.map_or('.', Kind::letter)).collect::<String>() + "\n")
+            .collect();
+        Some(SavedState {
+            summary: format!("score {}, level {}, {} lines", self.score, self.level(), self.lines),
+            data: format!("{},{},{}\n{}", self.score, self.lines, hold, rows),
+        })
+    }
+
+    fn get_overview(&self) -> String {
+        "Tetris: move and turn the falling pieces to fill whole rows, which then clear. Every ten lines the pieces fall faster.".to_string()
+    }
+}
+
+impl WidgetRef for TetrisGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let field_width = COLS as u16 * 2 + 2;
+        let board_height = if self.over { LEADERBOARD_SIZE as u16 + 2 } else { 0 };
+        let [column] = Layout::horizontal([Constraint::Length(52)]).flex(Flex::Center).areas(area);
+        let [play, message, board] = Layout::vertical([
+            Constraint::Length((ROWS - HIDDEN) as u16 + 2),
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+        ])
+            .flex(Flex::Center)
+            .areas(column);
+        let [field, _, side] = Layout::horizontal([Constraint::Length(field_width), Constraint::Length(2), Constraint::Length(16)]).flex(Flex::Center).areas(play);
+
+        let block = Block::bordered().border_type(BorderType::Rounded).dark_gray();
+        let inner = block.inner(field);
+        block.render(field, buf);
+        let mut draw = |(x, y): (i32, i32), symbol: &str, style: Style| {
+            if y < HIDDEN { return; }
+            let position = (inner.x + x as u16 * 2, inner.y + (y - HIDDEN) as u16);
+            if inner.contains(position.into()) { buf.set_string(position.0, position.1, symbol, style); }
+        };
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                match cell {
+                    Some(kind) => draw((x as i32, y as i32), "██", Style::default().fg(if self.over { Color::DarkGray } else { kind.color() })),
+                    None => draw((x as i32, y as i32), " ·", Style::default().fg(Color::DarkGray)),
+                }
+            }
+        }
+        if !self.over {
+            for cell in self.landing().cells() { draw(cell, "░░", Style::default().fg(self.piece.kind.color())); }
+            for cell in self.piece.cells() { draw(cell, "██", Style::default().fg(self.piece.kind.color())); }
+        }
+
+        let [next_area, hold_area, _, stats_area] = Layout::vertical([Constraint::Length(4), Constraint::Length(4), Constraint::Length(1), Constraint::Fill(1)]).areas(side);
+        Self::render_piece_box(" Next ", self.preview(), false, next_area, buf);
+        Self::render_piece_box(" Hold ", self.hold, self.held, hold_area, buf);
+        let best = self.best.best(BOARD).map_or(0, |run| run.score);
+        let row = |label: &str, value: String, color: Color| Line::from(vec![Span::styled(format!("{:<7}", label), Style::default().fg(Color::Gray)), Span::styled(value, Style::default().fg(color).bold())]);
+        Paragraph::new(vec![
+            row("Score", self.score.to_string(), Color::Green),
+            row("Level", self.level().to_string(), Color::LightCyan),
+            row("Lines", self.lines.to_string(), Color::LightBlue),
+            row("Best", best.max(self.score).to_string(), Color::Yellow),
+        ]).render(stats_area, buf);
+
+        let text = match self.place {
+            _ if !self.over => String::new(),
+            Some(0) => "Game over, a new best score! Enter to play again".to_string(),
+            Some(place) => format!("Game over, place {} on the board. Enter to play again", place + 1),
+            None => "Game over. Enter to play again".to_string(),
+        };
+        Paragraph::new(text.white()).centered().render(message, buf);
+        if self.over {
+            let block = Block::bordered().border_type(BorderType::Rounded).dark_gray().title(format!(" Best {} ", LEADERBOARD_SIZE));
+            let inner = block.inner(board);
+            block.render(board, buf);
+            Paragraph::new(self.board_lines()).render(inner.inner(Margin { horizontal: 1, vertical: 0 }), buf);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::stats;
+use crate::training::{Goal, Metric, TrainingPlan};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+
+/// Name under which the screen is registered; the app reloads the plan when it closes.
+pub const NAME: &str = "Training Plan";
+
+/// Set weekly goals per trainer and follow this week's progress.
+pub struct TrainingPlanScreen {
+    exit_intended: bool,
+    trainers: Vec<String>,
+    plan: TrainingPlan,
+    selected: usize,
+}
+
+impl TrainingPlanScreen {
+    pub fn new(trainers: Vec<String>) -> Self {
+        Self { exit_intended: false, trainers, plan: TrainingPlan::load(), selected: 0 }
+    }
+
+    fn selected_trainer(&self) -> Option<&String> {
+        self.trainers.get(self.selected)
+    }
+
+    fn change_goal(&mut self, change: impl FnOnce(Option<Goal>) -> Option<Goal>) {
+        let Some(trainer) = self.selected_trainer().cloned() else { return };
+        match change(self.plan.goals.remove(&trainer)) {
+            Some(goal) if goal.target > 0 => { self.plan.goals.insert(trainer, goal); }
+            _ => {}
+        }
+        self.plan.save();
+    }
+}
+
+fn step(metric: Metric) -> u32 {
+    match metric {
+        Metric::Rounds => 10,
+        Metric::Sessions => 1,
+    }
+}
+
+impl MainScreenWidget for TrainingPlanScreen {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match input.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.trainers.len().saturating_sub(1)),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.change_goal(|goal| {
+                let goal = goal.unwrap_or(Goal { target: 0, metric: Metric::Rounds });
+                Some(Goal { target: goal.target + step(goal.metric), ..goal })
+            }),
+            KeyCode::Char('-') => self.change_goal(|goal| {
+                goal.map(|goal| Goal { target: goal.target.saturating_sub(step(goal.metric)), ..goal })
+            }),
+            KeyCode::Char('m') | KeyCode::Char('M') => self.change_goal(|goal| {
+                goal.map(|goal| match goal.metric {
+                    Metric::Rounds => Goal { target: 3, metric: Metric::Sessions },
+                    Metric::Sessions => Goal { target: 50, metric: Metric::Rounds },
+                })
+            }),
+            KeyCode::Delete | KeyCode::Backspace => self.change_goal(|_| None),
+            _ => {}
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn follows_controls_preset(&self) -> bool { true }
+
+    fn needs_redraw(&self) -> bool { false }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Up Down", "select a trainer"),
+            ("+ -", "raise / lower the weekly goal"),
+            ("M", "count rounds or sessions"),
+            ("Del", "remove the goal"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for TrainingPlanScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let days_left = stats::days_left_in_week();
+        let mut lines = vec![
+            Line::from(format!("This week, {} day{} left", days_left, if days_left == 1 { "" } else { "s" }).yellow()),
+            Line::from(""),
+        ];
+        for (i, trainer) in self.trainers.iter().enumerate() {
+            let marker = if i == self.selected { "> " } else { "  " };
+            let name = format!("{}{:<18}", marker, trainer);
+            let name = if i == self.selected { name.light_cyan().bold() } else { name.white() };
+            let mut spans = vec![name];
+            match self.plan.goals.get(trainer) {
+                Some(goal) => {
+                    let progress = goal.progress(trainer);
+                    let color = if progress >= goal.target { Color::LightGreen } else { Color::Gray };
+                    spans.push(Span::styled(progress_bar(progress, goal.target, 20), Style::default().fg(color)));
+                    spans.push(format!(" {}/{} {}", progress, goal.target, goal.metric).fg(color));
+                }
+                None => spans.push("no goal".dark_gray()),
+            }
+            lines.push(Line::from(spans));
+        }
+        if self.trainers.is_empty() {
+            lines.push(Line::from("No trainers installed.".dark_gray()));
+        }
+
+        let [content, keys] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        Paragraph::new(lines)
+            .block(Block::bordered().title("Weekly Training Plan").title_alignment(Alignment::Center))
+            .render(content, buf);
+        Line::from("<Up Down> select  <+/-> goal  <M> rounds/sessions  <Del> remove".dark_gray())
+            .centered()
+            .render(keys, buf);
+    }
+}
+
+/// `[=======-------]`
+pub fn progress_bar(progress: u32, target: u32, width: usize) -> String {
+    let filled = if target == 0 { width } else { (progress.min(target) as usize * width) / target as usize };
+    format!("[{}{}]", "=".repeat(filled), "-".repeat(width - filled))
+}
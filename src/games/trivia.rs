@@ -0,0 +1,634 @@
+use crate::config;
+use crate::effects;
+use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::games::scoring;
+use crate::music::Tune;
+use crate::random::{self, GameRng};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::storage;
+use crate::ui::countdown::{CountdownLabel, CountdownWidget};
+use crate::ui::modal::{Modal, ModalResult};
+use crate::ui::stat_bar::StatBar;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use rand::prelude::SliceRandom;
+use serde::Deserialize;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph, Wrap};
+use std::path::PathBuf;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Name under which the game is registered; also the key of its stats.
+pub const NAME: &str = "Trivia";
+
+/// Questions shipped with the binary, in the Open Trivia DB format.
+const EMBEDDED: [&str; 1] = [include_str!("../../assets/trivia/computers.json")];
+
+/// Seconds per question before [`scoring::ScoringRules::round_time`] shortens it for long streaks.
+const ROUND_TIME: f64 = 20.0;
+const LIVES: u32 = 3;
+
+#[derive(EnumIter, EnumString, Display, Clone, Copy, PartialEq, Debug)]
+#[strum(serialize_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Clone, Debug)]
+pub struct Question {
+    pub category: String,
+    pub difficulty: Difficulty,
+    pub text: String,
+    pub correct: String,
+    pub incorrect: Vec<String>,
+}
+
+/// An Open Trivia DB response, `results` is missing when the response is an error.
+#[derive(Deserialize)]
+struct Response {
+    #[serde(default)]
+    response_code: u32,
+    results: Option<Vec<ResponseQuestion>>,
+}
+
+#[derive(Deserialize)]
+struct ResponseQuestion {
+    category: String,
+    difficulty: String,
+    question: String,
+    correct_answer: String,
+    incorrect_answers: Vec<String>,
+}
+
+/// Questions of an Open Trivia DB response (`https://opentdb.com/api.php?amount=...`) with the
+/// default HTML entity encoding. True/false questions become two-answer questions.
+pub fn parse_opentdb(contents: &str) -> Result<Vec<Question>, String> {
+    let response: Response = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+    if response.response_code != 0 {
+        return Err(format!("the response has error code {}", response.response_code));
+    }
+    let results = response.results.ok_or("no \"results\" list")?;
+    results.into_iter().enumerate().map(|(i, result)| {
+        let difficulty = decode_entities(&result.difficulty);
+        Ok(Question {
+            category: decode_entities(&result.category),
+            difficulty: difficulty.parse().map_err(|_| format!("question {} has an unknown difficulty {:?}", i + 1, difficulty))?,
+            text: decode_entities(&result.question),
+            correct: decode_entities(&result.correct_answer),
+            incorrect: result.incorrect_answers.iter().map(|answer| decode_entities(answer)).collect(),
+        })
+    }).collect()
+}
+
+/// Where users can add Open Trivia DB `.json` files of their own.
+pub fn trivia_dir() -> PathBuf {
+    storage::data_dir().join("trivia")
+}
+
+/// Questions of the user's packs followed by the built-in ones, and a message for every pack that could not be read.
+pub fn load_questions() -> (Vec<Question>, Vec<String>) {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(trivia_dir()).into_iter().flatten().flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    files.sort();
+    let (mut questions, mut problems) = (Vec::new(), Vec::new());
+    for path in files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match std::fs::read_to_string(&path).map_err(|error| error.to_string()).and_then(|contents| parse_opentdb(&contents)) {
+            Ok(pack) => questions.extend(pack),
+            Err(error) => problems.push(format!("{}: {}", name, error)),
+        }
+    }
+    questions.extend(EMBEDDED.iter().flat_map(|contents| parse_opentdb(contents).unwrap_or_default()));
+    (questions, problems)
+}
+
+/// `&quot;` and friends, as Open Trivia DB escapes its text.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').filter(|end| *end <= 10).map(|end| &rest[1..end]);
+        let ch = entity.and_then(|entity| match entity {
+            "quot" => Some('"'),
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "apos" | "rsquo" | "lsquo" => Some('\''),
+            "ldquo" | "rdquo" => Some('"'),
+            "hellip" => Some('…'),
+            "eacute" => Some('é'),
+            "uuml" => Some('ü'),
+            "ouml" => Some('ö'),
+            "auml" => Some('ä'),
+            "deg" => Some('°'),
+            _ => match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => entity.strip_prefix('#').and_then(|number| number.parse().ok()).and_then(char::from_u32),
+            },
+        });
+        match (entity, ch) {
+            (Some(entity), Some(ch)) => {
+                decoded.push(ch);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Phase {
+    Setup,
+    Asking,
+    Answered,
+    Over,
+}
+
+struct Round {
+    question: usize, // index into TriviaGame::questions
+    answers: Vec<String>,
+    correct: usize,
+    selected: usize,
+    chosen: Option<usize>, // None after a timeout
+    time_left: f64,
+    time_total: f64,
+}
+
+pub struct TriviaGame {
+    questions: Vec<Question>,
+    problems: Vec<String>, // user packs that could not be read
+    categories: Vec<String>,
+    category: usize, // 0 plays all categories
+    difficulty: Option<Difficulty>, // None plays all difficulties
+    phase: Phase,
+    deck: Vec<usize>, // questions still to ask, shuffled
+    round: Option<Round>,
+    score: u32,
+    streak: u32,
+    max_streak: u32,
+    lives: u32,
+    lives_bar: StatBar,
+    asked: u32,
+    answered_correctly: u32,
+    game_over: Option<Modal>,
+    advance_in: Option<f64>, // seconds until the next question comes on its own after a correct answer
+    rng: GameRng, // shuffles the questions and their answers
+    exit_intended: bool,
+}
+
+impl TriviaGame {
+    pub fn new() -> Self {
+        let (questions, problems) = load_questions();
+        let mut categories: Vec<String> = Vec::new();
+        for question in &questions {
+            if !categories.contains(&question.category) {
+                categories.push(question.category.clone());
+            }
+        }
+        Self {
+            questions,
+            problems,
+            categories,
+            category: 0,
+            difficulty: None,
+            phase: Phase::Setup,
+            deck: Vec::new(),
+            round: None,
+            score: 0,
+            streak: 0,
+            max_streak: 0,
+            lives: LIVES,
+            lives_bar: StatBar::hearts(LIVES).label("Lives: "),
+            asked: 0,
+            answered_correctly: 0,
+            game_over: None,
+            advance_in: None,
+            rng: random::rng(),
+            exit_intended: false,
+        }
+    }
+
+    /// Shuffle with `rng`, e.g. a [`random::seeded`] one to ask the same questions in the same order.
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    fn category_label(&self) -> String {
+        if self.category == 0 { "All categories".to_string() } else { self.categories[self.category - 1].clone() }
+    }
+
+    fn difficulty_label(&self) -> String {
+        self.difficulty.map_or("Any difficulty".to_string(), |difficulty| difficulty.to_string())
+    }
+
+    /// The stats mode of a run: category and difficulty.
+    fn mode(&self) -> String {
+        format!("{}, {}", self.category_label(), self.difficulty_label())
+    }
+
+    fn matching(&self) -> Vec<usize> {
+        self.questions.iter().enumerate()
+            .filter(|(_, question)| self.category == 0 || question.category == self.categories[self.category - 1])
+            .filter(|(_, question)| self.difficulty.is_none_or(|difficulty| question.difficulty == difficulty))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn start(&mut self) {
+        let mut deck = self.matching();
+        if deck.is_empty() {
+            return;
+        }
+        deck.shuffle(&mut self.rng);
+        self.deck = deck;
+        self.score = 0;
+        self.streak = 0;
+        self.max_streak = 0;
+        self.lives = LIVES;
+        self.asked = 0;
+        self.answered_correctly = 0;
+        self.game_over = None;
+        stats::record_session(NAME);
+        self.next_question();
+    }
+
+    fn next_question(&mut self) {
+        let Some(index) = self.deck.pop() else { return self.finish() };
+        let question = &self.questions[index];
+        let mut answers: Vec<String> = question.incorrect.iter().cloned().chain([question.correct.clone()]).collect();
+        if question.incorrect.len() == 1 && answers.iter().all(|answer| answer == "True" || answer == "False") {
+            answers.sort_by(|a, b| b.cmp(a)); // True before False, like on paper
+        } else {
+            answers.shuffle(&mut self.rng);
+        }
+        let correct = answers.iter().position(|answer| *answer == question.correct).unwrap_or(0);
+        let time_total = scoring::rules().round_time(ROUND_TIME, self.streak);
+        self.round = Some(Round { question: index, answers, correct, selected: 0, chosen: None, time_left: time_total, time_total });
+        self.phase = Phase::Asking;
+        self.advance_in = None;
+    }
+
+    fn answer(&mut self, chosen: Option<usize>) {
+        let mode = self.mode();
+        let Some(round) = self.round.as_mut() else { return };
+        round.chosen = chosen;
+        let correct = chosen == Some(round.correct);
+        let question = &self.questions[round.question];
+        self.asked += 1;
+        if correct {
+            let rules = scoring::rules();
+            self.streak += 1;
+            self.max_streak = self.max_streak.max(self.streak);
+            self.answered_correctly += 1;
+            self.score += rules.points(self.streak);
+            if rules.restores_life(self.streak) && self.lives < LIVES { self.lives += 1; }
+        } else {
+            self.streak = 0;
+            self.lives = self.lives.saturating_sub(1);
+            effects::wrong_answer();
+        }
+        sound::emit(match chosen {
+            _ if correct => SoundEvent::Correct,
+            Some(_) => SoundEvent::Incorrect,
+            None => SoundEvent::Timeout,
+        });
+        storage::record_round(&storage::RoundRecord {
+            game: NAME.to_string(),
+            mode: mode.clone(),
+            played_at: storage::unix_now(),
+            correct,
+            answer_time_ms: ((round.time_total - round.time_left) * 1000.0) as u64,
+            expected: question.correct.clone(),
+            given: chosen.map_or("-".to_string(), |chosen| round.answers[chosen].clone()),
+        });
+        stats::record_round(NAME, &mode, correct, self.streak);
+        self.phase = Phase::Answered;
+        self.advance_in = if correct && self.lives > 0 { config::get().auto_advance() } else { None };
+    }
+
+    fn finish(&mut self) {
+        self.phase = Phase::Over;
+        let out_of_questions = self.lives > 0;
+        if !out_of_questions {
+            sound::emit(SoundEvent::GameOver);
+            effects::game_over();
+        }
+        let mut body = vec![
+            Line::from(format!("Score: {}", self.score).green()),
+            Line::from(format!("Correct: {} of {}", self.answered_correctly, self.asked).light_cyan()),
+            Line::from(format!("Best streak: {}", self.max_streak).cyan()),
+            Line::from(self.mode().dark_gray()),
+        ];
+        if out_of_questions {
+            body.insert(0, Line::from("You answered every question!".light_green().bold()));
+        }
+        let title = if out_of_questions { "Quiz Complete" } else { "Game Over" };
+        self.game_over = Some(Modal::new(title)
+            .body(body)
+            .buttons(&["Play again", "Change topic", "Quit"])
+            .accent(if out_of_questions { Color::LightGreen } else { Color::Red }));
+    }
+
+    fn answer_game_over(&mut self, result: Option<ModalResult>) {
+        match result {
+            Some(ModalResult::Pressed(0)) => self.start(),
+            Some(ModalResult::Pressed(1)) => {
+                self.game_over = None;
+                self.phase = Phase::Setup;
+            }
+            Some(_) => self.exit_intended = true,
+            None => {}
+        }
+    }
+
+    fn handle_setup_input(&mut self, input: KeyEvent) {
+        let difficulties: Vec<Option<Difficulty>> = [None].into_iter().chain(Difficulty::iter().map(Some)).collect();
+        let current = difficulties.iter().position(|d| *d == self.difficulty).unwrap_or(0);
+        match input.code {
+            KeyCode::Up => self.category = (self.category + self.categories.len()) % (self.categories.len() + 1),
+            KeyCode::Down => self.category = (self.category + 1) % (self.categories.len() + 1),
+            KeyCode::Left => self.difficulty = difficulties[(current + difficulties.len() - 1) % difficulties.len()],
+            KeyCode::Right => self.difficulty = difficulties[(current + 1) % difficulties.len()],
+            KeyCode::Enter => return self.start(),
+            _ => return,
+        }
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    fn handle_question_input(&mut self, input: KeyEvent) {
+        let Some(round) = self.round.as_mut() else { return };
+        let count = round.answers.len();
+        match input.code {
+            KeyCode::Up | KeyCode::Left => round.selected = (round.selected + count - 1) % count,
+            KeyCode::Down | KeyCode::Right => round.selected = (round.selected + 1) % count,
+            KeyCode::Enter => {
+                let selected = round.selected;
+                self.answer(Some(selected));
+            }
+            KeyCode::Char(key) => {
+                let index = match key.to_ascii_lowercase() {
+                    digit @ '1'..='9' => digit as usize - '1' as usize,
+                    letter @ 'a'..='z' => letter as usize - 'a' as usize,
+                    _ => return,
+                };
+                if index < count {
+                    self.answer(Some(index));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl MainScreenWidget for TriviaGame {
+    fn run(&mut self, dt: f64) {
+        self.lives_bar.set(self.lives as f64);
+        self.lives_bar.tick(dt);
+        if let Some(left) = self.advance_in.as_mut().filter(|_| self.phase == Phase::Answered) {
+            *left -= dt;
+            if *left <= 0.0 { self.next_question(); }
+        }
+        if self.phase != Phase::Asking { return; }
+        let Some(round) = self.round.as_mut() else { return };
+        round.time_left = (round.time_left - dt).max(0.0);
+        if round.time_left == 0.0 {
+            self.answer(None);
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        match self.phase {
+            Phase::Setup => self.handle_setup_input(input),
+            Phase::Asking => self.handle_question_input(input),
+            Phase::Answered if input.code == KeyCode::Enter => match self.lives {
+                0 => self.finish(),
+                _ => self.next_question(),
+            },
+            Phase::Answered => {}
+            Phase::Over => {
+                let result = self.game_over.as_mut().and_then(|prompt| prompt.handle_key(input));
+                self.answer_game_over(result);
+            }
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> () {
+        if self.phase == Phase::Over {
+            let result = self.game_over.as_mut().and_then(|prompt| prompt.handle_mouse(event));
+            self.answer_game_over(result);
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    /// The countdown of an open question, and a heart being lost or won back.
+    fn needs_redraw(&self) -> bool {
+        self.phase == Phase::Asking || self.lives_bar.is_animating()
+    }
+
+    fn music(&self) -> Option<Tune> { Some(Tune::Puzzle) }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("Up Down", "choose a category / an answer"),
+            ("Left Right", "choose the difficulty"),
+            ("Enter", "start / answer / next question"),
+            ("1-4 A-D", "answer directly"),
+            ("Esc", "pause"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
+}
+
+impl WidgetRef for TriviaGame {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let [column] = Layout::horizontal([Constraint::Max(70)]).flex(Flex::Center).horizontal_margin(1).areas(area);
+        match (self.phase, &self.round) {
+            (Phase::Setup, _) => self.render_setup(column, buf),
+            (_, Some(round)) => self.render_round(round, column, buf),
+            (_, None) => {}
+        }
+        if let Some(prompt) = &self.game_over {
+            prompt.render(column, buf);
+        }
+    }
+}
+
+impl TriviaGame {
+    fn render_setup(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![
+            Line::from("TRIVIA".yellow().bold()),
+            Line::from(format!("{} questions", self.questions.len()).dark_gray()),
+            Line::from(""),
+            Line::from("Category".white().bold()),
+        ];
+        let labels = ["All categories".to_string()].into_iter().chain(self.categories.iter().cloned());
+        for (index, label) in labels.enumerate() {
+            lines.push(if index == self.category {
+                Line::from(format!("> {}", label)).style(Style::default().fg(Color::LightCyan).bold())
+            } else {
+                Line::from(format!("  {}", label)).white()
+            });
+        }
+        let available = self.matching().len();
+        lines.extend([
+            Line::from(""),
+            Line::from(vec!["Difficulty  ".white().bold(), "< ".dark_gray(), self.difficulty_label().light_cyan(), " >".dark_gray()]),
+            Line::from(""),
+            match available {
+                0 => Line::from("No questions match, try another combination".light_red()),
+                _ => Line::from(vec![format!("{} questions  ", available).white(), "<Enter> start".dark_gray()]),
+            },
+            Line::from(""),
+            Line::from(format!("Add Open Trivia DB packs (.json) to {}", trivia_dir().display()).dark_gray()),
+        ]);
+        for problem in &self.problems {
+            lines.push(Line::from(format!("Skipped {}", problem).light_red()));
+        }
+        let [middle] = Layout::vertical([Constraint::Length(lines.len() as u16)]).flex(Flex::Center).areas(area);
+        Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: false }).render(middle, buf);
+    }
+
+    fn render_round(&self, round: &Round, area: Rect, buf: &mut Buffer) {
+        let question = &self.questions[round.question];
+        let [status_area, question_area, answers_area, timer_area, result_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(6),
+            Constraint::Length(round.answers.len() as u16 + 2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+        ]).flex(Flex::Center).areas(area);
+
+        let mut status = vec![format!("Score: {}  ", self.score).green(), format!("Streak: {}  ", self.streak).cyan()];
+        status.extend(self.lives_bar.spans());
+        status.push(format!("  {} left", self.deck.len()).dark_gray());
+        Paragraph::new(Line::from(status))
+            .alignment(Alignment::Center)
+            .block(Block::bordered().dark_gray())
+            .render(status_area, buf);
+
+        let difficulty_color = match question.difficulty {
+            Difficulty::Easy => Color::Green,
+            Difficulty::Medium => Color::Yellow,
+            Difficulty::Hard => Color::Red,
+        };
+        Paragraph::new(vec![
+            Line::from(vec![question.category.clone().dark_gray(), " · ".dark_gray(), question.difficulty.to_string().fg(difficulty_color)]),
+            Line::from(""),
+            Line::from(question.text.clone().white().bold()),
+        ])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(question_area, buf);
+
+        let answers: Vec<Line> = round.answers.iter().enumerate().map(|(index, answer)| {
+            let label = format!(" {}) {} ", (b'A' + index as u8) as char, answer);
+            let style = match (self.phase, round.chosen) {
+                (Phase::Asking, _) if index == round.selected => Style::default().fg(Color::Black).bg(Color::LightCyan).bold(),
+                (Phase::Answered | Phase::Over, _) if index == round.correct => Style::default().fg(Color::LightGreen).bold(),
+                (Phase::Answered | Phase::Over, Some(chosen)) if index == chosen => Style::default().fg(Color::LightRed).crossed_out(),
+                _ => Style::default().fg(Color::White),
+            };
+            Line::from(Span::styled(label, style))
+        }).collect();
+        Paragraph::new(answers)
+            .block(Block::bordered().border_type(BorderType::Rounded).dark_gray())
+            .render(answers_area, buf);
+
+        CountdownWidget::new(round.time_left, round.time_total)
+            .label(CountdownLabel::Short)
+            .thresholds(0.5, 0.25)
+            .warn_below(5.0)
+            .bar("█")
+            .render(timer_area, buf);
+
+        let result = match (self.phase, round.chosen) {
+            (Phase::Asking, _) => Line::from("<Up/Down> choose  <Enter> answer  <A-D> answer directly".dark_gray()),
+            (_, Some(chosen)) if chosen == round.correct => Line::from("Correct!".light_green().bold()),
+            (_, Some(_)) => Line::from(vec!["Wrong. ".light_red().bold(), format!("The answer is {}", question.correct).white()]),
+            (_, None) => Line::from(vec!["Time's up. ".yellow().bold(), format!("The answer is {}", question.correct).white()]),
+        };
+        let next = if self.phase == Phase::Answered { Line::from("<Enter> next question".dark_gray()) } else { Line::from("") };
+        Paragraph::new(vec![result, next]).alignment(Alignment::Center).render(result_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A response with one question of the given fields.
+    fn response(question: &str, correct: &str) -> String {
+        format!(r#"{{"response_code": 0, "results": [{{"type": "multiple", "difficulty": "hard", "category": "Science: Computers",
+            "question": "{}", "correct_answer": "{}", "incorrect_answers": ["A", "B", "C"]}}]}}"#, question, correct)
+    }
+
+    #[test]
+    fn a_seeded_game_asks_the_same_questions_in_the_same_order() {
+        storage::install_for_tests();
+        let asked = |seed| {
+            let mut game = TriviaGame::new().with_rng(random::seeded(seed));
+            game.start();
+            (game.deck.clone(), game.round.map(|round| round.answers))
+        };
+        assert_eq!(asked(9), asked(9));
+        assert_ne!(asked(9), asked(10));
+    }
+
+    #[test]
+    fn the_bundled_pack_is_readable() {
+        let questions = parse_opentdb(EMBEDDED[0]).unwrap();
+        assert!(!questions.is_empty());
+        assert!(questions.iter().all(|question| !question.correct.is_empty() && !question.incorrect.is_empty()));
+    }
+
+    #[test]
+    fn json_escapes_and_html_entities_are_decoded() {
+        let questions = parse_opentdb(&response(r#"Is \"é\" in \\u escapes a \t tab &amp; &quot;&#039;&#x41;&rsquo;?"#, r"Line\nbreak &unknown; & &lt;b&gt;")).unwrap();
+        assert_eq!(questions[0].text, "Is \"é\" in \\u escapes a \t tab & \"'A'?");
+        assert_eq!(questions[0].correct, "Line\nbreak &unknown; & <b>");
+        assert_eq!(questions[0].difficulty, Difficulty::Hard);
+        assert_eq!(questions[0].incorrect, ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn surrogate_pairs_make_one_character() {
+        let questions = parse_opentdb(&response(r"Which emoji is \ud83d\udc0d?", r"\uD83D\uDC0D snake")).unwrap();
+        assert_eq!(questions[0].text, "Which emoji is 🐍?");
+        assert_eq!(questions[0].correct, "🐍 snake");
+        assert!(parse_opentdb(&response(r"A lone \udc0d half", "x")).is_err());
+    }
+
+    #[test]
+    fn deep_nesting_cannot_overflow_the_stack() {
+        // unknown fields are skipped without recursing, known ones only nest as deep as the format
+        let ignored = format!(r#"{{"response_code": 0, "extra": {}{}, "results": []}}"#, "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(parse_opentdb(&ignored).unwrap().len(), 0);
+        let nested = format!(r#"{{"response_code": 0, "results": {}{}}}"#, "[".repeat(100_000), "]".repeat(100_000));
+        assert!(parse_opentdb(&nested).is_err());
+    }
+
+    #[test]
+    fn malformed_packs_say_what_is_wrong() {
+        assert!(parse_opentdb("").is_err());
+        assert!(parse_opentdb(r#"{"response_code": 0, "results": ["#).is_err());
+        assert!(parse_opentdb(r#"{"response_code": 0, "results": []} trailing"#).is_err());
+        assert!(parse_opentdb(r#"{"response_code": 0, "results": [{"question": "Q?"}]}"#).unwrap_err().contains("missing field"));
+        assert_eq!(parse_opentdb(r#"{"response_code": 1, "results": []}"#).unwrap_err(), "the response has error code 1");
+        assert_eq!(parse_opentdb(r#"{"response_code": 0}"#).unwrap_err(), "no \"results\" list");
+        assert_eq!(parse_opentdb(&response("Q?", "A").replace("hard", "tricky")).unwrap_err(), "question 1 has an unknown difficulty \"tricky\"");
+    }
+}
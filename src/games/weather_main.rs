@@ -1,36 +1,517 @@
+use crate::config::{self, TemperatureUnit, WindUnit};
+use crate::fonts;
 use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
-use crossterm::event::KeyEvent;
+use crate::games::weather_scene::{Scene, WeatherScene};
+use crate::geocoding::{self, Place};
+use crate::http;
+use crate::particles::{Emitter, ParticleSystem};
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::ui::big_text::BigText;
+use crate::ui::inspector;
+use crate::ui::marquee::Marquee;
+use crate::ui::modal::Modal;
+use crate::utils::BrailleCanvas;
+use crate::weather::{self, Alert, Conditions, FetchError, Hour, Report, Severity};
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Block, Clear, Paragraph};
+use std::cell::Cell;
+use std::sync::mpsc::Receiver;
+use strum::IntoEnumIterator;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Sky { Clear, Cloudy, Rain, Snow }
+
+impl Sky {
+    fn for_code(code: u8) -> Self {
+        match Scene::for_code(code) {
+            Scene::Sun => Sky::Clear,
+            Scene::Clouds => Sky::Cloudy,
+            Scene::Rain => Sky::Rain,
+            Scene::Snow => Sky::Snow,
+        }
+    }
+
+    /// Typical conditions under this sky, shown when cycling through them with P.
+    fn report(self) -> Report {
+        let (temperature, feels_like) = match self {
+            Sky::Clear => (24.0, 24.0),
+            Sky::Cloudy => (20.0, 18.0),
+            Sky::Rain => (14.0, 11.0),
+            Sky::Snow => (-2.0, -6.0),
+        };
+        Report { code: self.weather_code(), temperature, feels_like, wind: self.wind(), alert: None, hours: Vec::new(), days: Vec::new() }
+    }
+
+    fn glyph(self) -> Span<'static> {
+        match self {
+            Sky::Clear => "☀".light_yellow(),
+            Sky::Cloudy => "☁".gray(),
+            Sky::Rain => "☂".light_blue(),
+            Sky::Snow => "❄".white(),
+        }
+    }
+
+    fn summary(self) -> &'static str {
+        match self {
+            Sky::Clear => "Clear Sky",
+            Sky::Cloudy => "Moderately Cloudy",
+            Sky::Rain => "Light Rain",
+            Sky::Snow => "Snow Showers",
+        }
+    }
+
+    /// Wind in km/h, stormier with the worse weather.
+    fn wind(self) -> f64 {
+        match self {
+            Sky::Clear => 8.0,
+            Sky::Cloudy => 15.0,
+            Sky::Rain => 22.0,
+            Sky::Snow => 30.0,
+        }
+    }
+
+    /// The WMO code a weather service would report for this sky.
+    fn weather_code(self) -> u8 {
+        match self {
+            Sky::Clear => 0,
+            Sky::Cloudy => 3,
+            Sky::Rain => 61,
+            Sky::Snow => 71,
+        }
+    }
+}
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// The location entry: a typed city name and the places matching it.
+#[derive(Default)]
+struct LocationSearch {
+    query: String,
+    searched: Option<String>, // the query the matches are for
+    matches: Vec<Place>,
+    selected: usize,
+    pending: Option<Receiver<Result<Vec<Place>, FetchError>>>, // while the geocoding service is asked
+    error: Option<FetchError>,
+}
 
 pub struct WeatherMain {
     exit_intended: bool,
+    sky: Sky,
+    report: Report, // what the numbers show
+    conditions: Conditions, // of the location, `report` unless P picked another sky
+    pending: Option<Receiver<Conditions>>, // while the service is asked for the location
+    until_check: f64, // seconds until the cache is looked at again
+    failures: u32, // fetches in a row that failed, each doubles the wait before the next
+    scene: WeatherScene, // animated sky beside the numbers
+    precipitation: ParticleSystem,
+    size: Cell<(u16, u16)>, // of the last render, the emitters cover it
+    emitting_size: (u16, u16),
+    alerts: Marquee, // ticker below the forecast, empty on a calm day
+    alert_details: Option<Modal>, // open after I while an alert is in effect
+    search: Option<LocationSearch>, // open while a location is typed in
+    forecast_view: bool, // the week ahead instead of the current conditions
+    selected_day: usize, // in the forecast view, 0 is today
+    selected_hour: usize, // under the cursor of the hourly chart, 0 is the current hour
+    locations_view: bool, // every saved location side by side, over either view
+    locations: Vec<(Place, Conditions)>, // the saved locations as of the last refresh, for that view
+    pending_locations: Option<Receiver<Vec<(Place, Conditions)>>>, // while the service is asked for all of them
 }
 
 impl WeatherMain {
+    /// Seconds between looks at whether the cached report is due for a refresh.
+    const CHECK_INTERVAL: f64 = 60.0;
+    /// Doublings of the wait after failed fetches, up to 32 minutes.
+    const MAX_BACKOFF: u32 = 5;
+
     pub fn new() -> Self {
-        Self { exit_intended: false }
+        let mut weather = Self {
+            exit_intended: false,
+            sky: Sky::Rain,
+            report: Sky::Rain.report(),
+            conditions: Conditions::default(),
+            pending: None,
+            until_check: Self::CHECK_INTERVAL,
+            failures: 0,
+            scene: WeatherScene::new(Sky::Rain.weather_code()),
+            precipitation: ParticleSystem::new(),
+            size: Cell::new((0, 0)),
+            emitting_size: (0, 0),
+            alerts: Marquee::new(Line::default()).speed(6.0),
+            alert_details: None,
+            search: None,
+            forecast_view: false,
+            selected_day: 0,
+            selected_hour: 0,
+            locations_view: false,
+            locations: Vec::new(),
+            pending_locations: None,
+        };
+        weather.refresh(false);
+        weather
+    }
+
+    /// Show the conditions at the location: the cached report right away, and a new one once it
+    /// is older than the refresh interval; `force` asks the service right away. The service is
+    /// asked in the background, see [`Self::receive`].
+    fn refresh(&mut self, force: bool) {
+        let place = config::get().location;
+        self.conditions = Conditions { cached: place.as_ref().and_then(weather::cached), error: None };
+        if let Some(cached) = &self.conditions.cached {
+            self.show(cached.report.clone());
+        }
+        self.pending = Some(http::in_background(move || weather::conditions(place.as_ref(), force)));
+        if self.locations_view {
+            self.refresh_locations(force);
+        }
+    }
+
+    /// Take the conditions the service answered with; failures in a row wait longer and longer.
+    fn receive(&mut self, conditions: Conditions) {
+        self.conditions = conditions;
+        self.failures = match self.conditions.error {
+            None | Some(FetchError::Offline) => 0, // nothing was asked, so nothing to back off from
+            Some(_) => self.failures + 1,
+        };
+        self.until_check = Self::wait_after(self.failures, self.conditions.error);
+        if let Some(cached) = &self.conditions.cached {
+            self.show(cached.report.clone());
+        }
+    }
+
+    /// Seconds until the next check after `failures` fetches in a row failed, the last one with
+    /// `error`. A rate limited service is left alone for at least as long as it asked for.
+    fn wait_after(failures: u32, error: Option<FetchError>) -> f64 {
+        let backoff = Self::CHECK_INTERVAL * 2f64.powi(failures.min(Self::MAX_BACKOFF) as i32);
+        match error {
+            Some(FetchError::RateLimited { retry_after: Some(seconds) }) => backoff.max(seconds as f64),
+            _ => backoff,
+        }
+    }
+
+    /// Look up every saved location, each with its own cache: the cached reports right away, the
+    /// new ones in the background.
+    fn refresh_locations(&mut self, force: bool) {
+        let places = config::get().saved_locations;
+        self.locations = places.iter().map(|place| (place.clone(), Conditions { cached: weather::cached(place), error: None })).collect();
+        self.pending_locations = Some(http::in_background(move || {
+            places.into_iter().map(|place| {
+                let conditions = weather::conditions(Some(&place), force);
+                (place, conditions)
+            }).collect()
+        }));
+    }
+
+    /// Show the saved location `step` places after the current one, wrapping around.
+    fn cycle_location(&mut self, step: isize) {
+        let config = config::get();
+        let count = config.saved_locations.len() as isize;
+        if count == 0 {
+            return;
+        }
+        let current = config.location.as_ref().and_then(|place| config.saved_locations.iter().position(|saved| saved == place));
+        let next = current.map_or(0, |index| (index as isize + step).rem_euclid(count) as usize);
+        config::update(|c| c.location = c.saved_locations.get(next).cloned());
+        sound::emit(SoundEvent::MenuMove);
+        self.refresh(false);
+    }
+
+    /// Drop the current location from the saved ones and move on to the next.
+    fn forget_location(&mut self) {
+        let Some(place) = config::get().location else { return };
+        config::update(|c| {
+            let index = c.saved_locations.iter().position(|saved| *saved == place).unwrap_or(0);
+            c.saved_locations.retain(|saved| *saved != place);
+            c.location = c.saved_locations.get(index).or(c.saved_locations.first()).cloned();
+        });
+        self.refresh(false);
+    }
+
+    fn show(&mut self, report: Report) {
+        self.sky = Sky::for_code(report.code);
+        self.scene.set_code(report.code);
+        self.alerts.set_line(alert_line(report.alert.as_ref()));
+        self.selected_hour = self.selected_hour.min(report.hours.len().saturating_sub(1));
+        self.selected_day = self.selected_day.min(report.days.len().saturating_sub(1));
+        self.report = report;
+        self.restart_precipitation();
+    }
+
+    /// The whole text of the alert in effect, with where and since when it applies.
+    fn open_alert_details(&mut self) {
+        let Some(alert) = &self.report.alert else { return };
+        let mut body = vec![
+            Line::from(format!("{} severity", alert.severity).to_uppercase().fg(severity_color(alert.severity)).bold()),
+            Line::default(),
+            Line::from(alert.description.clone().white()),
+            Line::default(),
+        ];
+        if let Some(place) = config::get().location {
+            body.push(Line::from(format!("For {}", place).white()));
+        }
+        if let Some(cached) = self.conditions.cached.as_ref().filter(|cached| cached.report == self.report) {
+            body.push(Line::from(format!("Issued with the report from {}", weather::format_age(cached.age())).dark_gray()));
+        }
+        self.alert_details = Some(Modal::new(&alert.event).body(body).buttons(&["Close"]).accent(severity_color(alert.severity)));
+    }
+
+    /// How old the numbers are and why they are not newer, below the location.
+    fn status_line(&self) -> Line<'static> {
+        let cached = self.conditions.cached.as_ref().filter(|cached| cached.report == self.report);
+        let mut spans = match (cached, self.conditions.error) {
+            _ if self.pending.is_some() && config::get().location.is_some() => vec!["Updating…  ".dark_gray()],
+            (None, _) if self.conditions.cached.is_some() => vec!["Preview of another sky  ".dark_gray()],
+            (None, Some(error)) => vec![format!("{}, no report yet  ", error).light_red()],
+            (None, None) => vec!["No report yet  ".dark_gray()],
+            (Some(cached), Some(error)) => vec![format!("{}, showing the report from {}  ", error, weather::format_age(cached.age())).yellow()],
+            (Some(cached), None) if !cached.is_fresh() => vec![format!("Updated {}  ", weather::format_age(cached.age())).yellow()],
+            (Some(cached), None) => vec![format!("Updated {}  ", weather::format_age(cached.age())).dark_gray()],
+        };
+        // failed fetches are tried again later and later, see [`Self::MAX_BACKOFF`]
+        if self.conditions.error.is_some() && !config::get().weather_offline {
+            spans.push(format!("retry in {} min  ", (self.until_check / 60.0).ceil().max(1.0)).dark_gray());
+        }
+        spans.push(if config::get().weather_offline { "<O> go online".dark_gray() } else { "<R> refresh".dark_gray() });
+        Line::from(spans)
+    }
+
+    /// Enter searches the typed name, and once the matches are in picks the selected one; Enter
+    /// with nothing typed cancels.
+    fn handle_search_input(&mut self, input: KeyEvent) {
+        let Some(search) = self.search.as_mut() else { return };
+        match input.code {
+            KeyCode::Char(ch) => search.query.push(ch),
+            KeyCode::Backspace => { search.query.pop(); }
+            KeyCode::Up if !search.matches.is_empty() => search.selected = (search.selected + search.matches.len() - 1) % search.matches.len(),
+            KeyCode::Down | KeyCode::Tab if !search.matches.is_empty() => search.selected = (search.selected + 1) % search.matches.len(),
+            KeyCode::Enter if search.query.trim().is_empty() => self.search = None,
+            KeyCode::Enter if search.searched.as_ref() != Some(&search.query) || search.matches.is_empty() => {
+                let query = search.query.clone();
+                search.searched = Some(query.clone());
+                search.error = None;
+                search.pending = Some(http::in_background(move || geocoding::search(&query)));
+            }
+            KeyCode::Enter => {
+                let Some(place) = search.matches.get(search.selected).cloned() else { return };
+                config::update(|c| {
+                    if !c.saved_locations.contains(&place) { c.saved_locations.push(place.clone()); }
+                    c.location = Some(place);
+                });
+                sound::emit(SoundEvent::MenuSelect);
+                self.search = None;
+                self.refresh(false);
+            }
+            _ => {}
+        }
+        // matches of an older query would be picked by mistake
+        if let Some(search) = self.search.as_mut().filter(|search| search.searched.as_ref() != Some(&search.query)) {
+            search.searched = None;
+            search.matches.clear();
+            search.selected = 0;
+            search.pending = None;
+            search.error = None;
+        }
+    }
+
+    /// The matches of the search, once the geocoding service has answered.
+    fn receive_matches(&mut self) {
+        let Some(search) = self.search.as_mut() else { return };
+        let Some(found) = search.pending.as_ref().and_then(|pending| pending.try_recv().ok()) else { return };
+        search.pending = None;
+        search.selected = 0;
+        match found {
+            Ok(matches) => search.matches = matches,
+            Err(error) => search.error = Some(error),
+        }
+    }
+
+    fn restart_precipitation(&mut self) {
+        self.emitting_size = self.size.get();
+        let (width, height) = (self.emitting_size.0 as f64, self.emitting_size.1 as f64);
+        self.precipitation.clear_emitters();
+        match self.sky {
+            Sky::Clear | Sky::Cloudy => {}
+            Sky::Rain => self.precipitation.add_emitter(Emitter::rain(width, height)),
+            Sky::Snow => self.precipitation.add_emitter(Emitter::snow(width, height)),
+        }
     }
 }
 
 impl MainScreenWidget for WeatherMain {
-    fn run(&mut self, _dt: f64) {}
+    fn run(&mut self, dt: f64) {
+        if let Some(conditions) = self.pending.as_ref().and_then(|pending| pending.try_recv().ok()) {
+            self.pending = None;
+            self.receive(conditions);
+        }
+        if let Some(locations) = self.pending_locations.as_ref().and_then(|pending| pending.try_recv().ok()) {
+            self.pending_locations = None;
+            self.locations = locations;
+        }
+        self.receive_matches();
+        if self.size.get() != self.emitting_size { self.restart_precipitation(); }
+        self.precipitation.tick(dt);
+        self.scene.tick(dt);
+        self.alerts.tick(dt);
+        self.until_check -= dt;
+        if self.until_check <= 0.0 && !self.conditions.cached.as_ref().is_some_and(|cached| cached.is_fresh()) {
+            self.refresh(false);
+        } else if self.until_check <= 0.0 {
+            self.until_check = Self::CHECK_INTERVAL;
+        }
+    }
 
-    fn handle_input(&mut self, _input: KeyEvent) -> () {}
+    fn handle_input(&mut self, input: KeyEvent) -> () {
+        if self.search.is_some() {
+            return self.handle_search_input(input);
+        }
+        if let Some(details) = &mut self.alert_details {
+            if details.handle_key(input).is_some() { self.alert_details = None; }
+            return;
+        }
+        match input.code {
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                let sky = match self.sky { Sky::Clear => Sky::Cloudy, Sky::Cloudy => Sky::Rain, Sky::Rain => Sky::Snow, Sky::Snow => Sky::Clear };
+                self.show(sky.report());
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => self.refresh(true),
+            KeyCode::Char('i') | KeyCode::Char('I') => self.open_alert_details(),
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                config::update(|c| c.weather_offline = !c.weather_offline);
+                self.refresh(false);
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => self.search = Some(LocationSearch::default()),
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                self.forecast_view = !self.forecast_view;
+                self.locations_view = false;
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                self.locations_view = !self.locations_view;
+                if self.locations_view { self.refresh_locations(false); }
+            }
+            KeyCode::Tab => self.cycle_location(1),
+            KeyCode::BackTab => self.cycle_location(-1),
+            KeyCode::Char('d') | KeyCode::Char('D') => self.forget_location(),
+            KeyCode::Char('u') | KeyCode::Char('U') => config::update(|c| {
+                c.temperature_unit = TemperatureUnit::iter().cycle().skip_while(|unit| *unit != c.temperature_unit).nth(1).unwrap()
+            }),
+            KeyCode::Char('w') | KeyCode::Char('W') => config::update(|c| {
+                c.wind_unit = WindUnit::iter().cycle().skip_while(|unit| *unit != c.wind_unit).nth(1).unwrap()
+            }),
+            KeyCode::Left if self.forecast_view => self.selected_day = step(self.selected_day, -1, self.report.days.len()),
+            KeyCode::Right if self.forecast_view => self.selected_day = step(self.selected_day, 1, self.report.days.len()),
+            KeyCode::Left => self.selected_hour = step(self.selected_hour, -1, self.report.hours.len()),
+            KeyCode::Right => self.selected_hour = step(self.selected_hour, 1, self.report.hours.len()),
+            _ => {}
+        }
+    }
 
     fn is_exit_intended(&self) -> bool { self.exit_intended }
+
+    fn needs_redraw(&self) -> bool { self.pending.is_some() || self.pending_locations.is_some() || self.search.as_ref().is_some_and(|search| search.pending.is_some()) || !self.precipitation.is_empty() || self.alerts.is_scrolling() || !config::get().reduce_motion }
+
+    fn help_lines(&self) -> Vec<(String, String)> {
+        [
+            ("P", "cycle clear, cloudy, rain and snow"),
+            ("R", "fetch the weather again, even before the refresh interval is up"),
+            ("O", "offline mode, only cached reports are shown"),
+            ("I", "details of the weather alert in effect"),
+            ("F", "switch between now and the week ahead"),
+            ("U", "temperatures in Celsius or Fahrenheit"),
+            ("W", "wind in km/h, mph or m/s"),
+            ("Left Right", "choose an hour of the chart, or a day of the week ahead"),
+            ("L", "search the city to show the weather for, it is saved with the others"),
+            ("Tab Shift+Tab", "next or previous saved location"),
+            ("A", "compare all saved locations"),
+            ("D", "forget the current location"),
+            ("Up Down Enter", "search the typed city, then pick one of the matches; Enter with nothing typed cancels"),
+        ].iter().map(|(k, d)| (k.to_string(), d.to_string())).collect()
+    }
 }
 
 impl WidgetRef for WeatherMain {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        self.size.set((area.width, area.height));
+        self.precipitation.render(area, buf);
+
+        // an alert in effect takes the top row, above whichever view is shown
+        let view = match &self.report.alert {
+            Some(alert) if area.height > 1 => {
+                let [banner, view] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+                inspector::mark("alert banner", banner);
+                render_alert_banner(alert, banner, buf);
+                view
+            }
+            _ => area,
+        };
+        if self.locations_view {
+            self.render_locations(view, buf);
+        } else if self.forecast_view {
+            self.render_forecast(view, buf);
+        } else {
+            self.render_now(view, buf);
+        }
+
+        if let Some(search) = &self.search {
+            render_search(search, area, buf);
+        }
+        if let Some(details) = &self.alert_details {
+            details.render(area, buf);
+        }
+    }
+}
+
+impl WeatherMain {
+    /// The current conditions over the next hours.
+    fn render_now(&self, area: Rect, buf: &mut Buffer) {
+        let (temp, feels_like) = (self.report.temperature, self.report.feels_like);
+        let summary = self.sky.summary();
+        let config = config::get();
+        let (unit, wind_unit) = (config.temperature_unit, config.wind_unit);
+
+        // the temperature in big digits above the details, when there is room for it
+        let digits = format!("{:.0}", unit.convert(temp));
+        let colors: Vec<Color> = digits.chars().map(|_| Color::LightYellow).chain([Color::DarkGray; 2]).collect();
+        let banner = BigText::colored(&format!("{}{}", digits, unit.symbol()), &fonts::current(), &colors);
+        let banner_height = if area.height >= banner.height() + 14 { banner.height() } else { 0 };
+
         let width = [Constraint::Length(40)];
-        let height = [Constraint::Length(3)];
+        let height = [
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(banner_height),
+            Constraint::Length(banner_height.min(1)),
+            Constraint::Length(4),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ];
 
         // create centered area with a specific width and height
-        let [middle] = Layout::vertical(height).flex(Flex::Center).areas(area);
+        let [place, status, banner_area, _, middle, _, label, graph, bars, _, alerts] = Layout::vertical(height).flex(Flex::Center).areas(area);
+        for (name, marked) in [("place", place), ("status", status), ("banner", banner_area), ("details", middle), ("hour", label),
+            ("temperature chart", graph), ("precipitation bars", bars), ("alerts", alerts)] {
+            inspector::mark(name, marked);
+        }
+        place_line().centered().render(place, buf);
+        self.status_line().centered().render(status, buf);
+        if banner_height > 0 {
+            banner.render(banner_area, buf);
+        }
         let [center] = Layout::horizontal(width).flex(Flex::Center).areas(middle);
 
+        // the animated sky to the left of the numbers, when the terminal is wide enough
+        if center.x >= area.x + WeatherScene::WIDTH + 2 {
+            let scene_x = center.x - WeatherScene::WIDTH - 2;
+            let top = if banner_height > 0 { banner_area.y } else { middle.y };
+            let height = WeatherScene::HEIGHT.min(area.bottom().saturating_sub(top));
+            self.scene.render(Rect::new(scene_x, top, WeatherScene::WIDTH, height), buf);
+        }
+
         // create left and right areas in the center
         let widths = [Constraint::Fill(10), Constraint::Length(20)];
         let [left, right] = Layout::horizontal(widths).areas(center);
@@ -38,16 +519,260 @@ impl WidgetRef for WeatherMain {
         let left_content = Paragraph::new(Text::from(vec![
             Line::from("Current Temp:"),
             Line::from("Feels Like:"),
+            Line::from("Wind:"),
             Line::from("Weather Summary:"),
         ])).left_aligned();
 
         let right_content = Paragraph::new(Text::from(vec![
-            Line::from("20°C"),
-            Line::from("18°C"),
-            Line::from("Moderately Cloudy"),
+            Line::from(unit.format(temp)),
+            Line::from(unit.format(feels_like)),
+            Line::from(wind_unit.format(self.report.wind)),
+            Line::from(summary),
         ])).centered();
 
         left_content.render(left, buf);
         right_content.render(right, buf);
+
+        let hours = &self.report.hours;
+        let temperatures: Vec<f64> = hours.iter().map(|hour| hour.temperature).collect();
+        let (low, high) = temperatures.iter().fold((f64::MAX, f64::MIN), |(low, high), t| (low.min(*t), high.max(*t)));
+        let [label] = Layout::horizontal(width).flex(Flex::Center).areas(label);
+        let [graph] = Layout::horizontal(width).flex(Flex::Center).areas(graph);
+        let [bars] = Layout::horizontal(width).flex(Flex::Center).areas(bars);
+        if let Some(hour) = hours.get(self.selected_hour) {
+            let time = if self.selected_hour == 0 { "Now".to_string() } else { format!("{:02}:00", hour.hour) };
+            Line::from(vec![
+                format!("{:<7}", time).white().bold(),
+                format!("{:<8}", unit.format(hour.temperature)).light_yellow(),
+                "☂ ".light_blue(),
+                format!("{}%", hour.precipitation).light_blue(),
+            ]).render(label, buf);
+        }
+        if hours.is_empty() {
+            Line::from("No hourly forecast".dark_gray()).render(label, buf);
+        } else {
+            Line::from(format!("{}h {} to {}", hours.len(), unit.format(low), unit.format(high)).dark_gray()).right_aligned().render(label, buf);
+            sparkline(&temperatures, low, high, graph).render(graph, buf);
+            render_precipitation(hours, bars, buf);
+            // the cursor: the column of the selected hour, across the chart and the bars
+            let column = (self.selected_hour as f64 / (hours.len() - 1).max(1) as f64 * (graph.width * 2 - 1) as f64).round() as u16 / 2;
+            buf.set_style(Rect::new(graph.x + column, graph.y, 1, graph.height + bars.height), Style::default().bg(Color::DarkGray));
+        }
+        let [alerts] = Layout::horizontal(width).flex(Flex::Center).areas(alerts);
+        self.alerts.render(alerts, buf);
+    }
+
+    /// One row per saved location with its conditions and how old they are; the current location
+    /// is highlighted.
+    fn render_locations(&self, area: Rect, buf: &mut Buffer) {
+        let config = config::get();
+        let (unit, wind_unit) = (config.temperature_unit, config.wind_unit);
+        let header = Line::from(format!("{:<24}{:^5}{:>8}{:>8}{:>11}  {:<16}", "Location", "", "Temp", "Feels", "Wind", "Updated")).dark_gray();
+        let mut lines = vec![header];
+        for (place, conditions) in &self.locations {
+            let current = Some(place) == config.location.as_ref();
+            let name = Span::styled(format!("{:<24}", place.to_string()), if current { Style::default().fg(Color::Black).bg(Color::LightCyan) } else { Style::default().fg(Color::White) });
+            let Some(cached) = &conditions.cached else {
+                let reason = conditions.error.map_or("no report yet".to_string(), |error| error.to_string());
+                lines.push(Line::from(vec![name, format!("{:^5}", "?").dark_gray(), format!("{:>27}  ", "").into(), reason.light_red()]));
+                continue;
+            };
+            let report = &cached.report;
+            let age = weather::format_age(cached.age());
+            // stale when it could not be fetched again, e.g. offline
+            let age = if conditions.error.is_some() || !cached.is_fresh() { age.yellow() } else { age.dark_gray() };
+            lines.push(Line::from(vec![
+                name,
+                "  ".into(),
+                Sky::for_code(report.code).glyph(),
+                "  ".into(),
+                format!("{:>8}", unit.format(report.temperature)).light_yellow(),
+                format!("{:>8}", unit.format(report.feels_like)).white(),
+                format!("{:>11}", wind_unit.format(report.wind)).white(),
+                "  ".into(),
+                age,
+            ]));
+        }
+        if self.locations.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("No saved locations yet  <L> search a city".dark_gray()));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("<Tab> next  <A> back  <R> refresh all".dark_gray()));
+
+        let [table] = Layout::horizontal([Constraint::Length(76)]).flex(Flex::Center).areas(area);
+        let [table] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)]).flex(Flex::Center).areas(table);
+        Paragraph::new(lines)
+            .block(Block::bordered().title(" All locations ").title_alignment(Alignment::Center).dark_gray())
+            .render(table, buf);
+    }
+
+    /// One column per day with its sky, high, low and chance of precipitation; the selected day
+    /// is spelled out below.
+    fn render_forecast(&self, area: Rect, buf: &mut Buffer) {
+        const COLUMN: u16 = 7;
+        let config = config::get();
+        let (unit, wind_unit) = (config.temperature_unit, config.wind_unit);
+        let degrees = |celsius: f64| format!("{:.0}°", unit.convert(celsius));
+        let days = &self.report.days;
+        let [place, status, columns, _, details, _, alerts] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(5),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+        place_line().centered().render(place, buf);
+        self.status_line().centered().render(status, buf);
+
+        if days.is_empty() {
+            Line::from("No forecast for the week ahead".dark_gray()).centered().render(details, buf);
+        }
+        let [columns] = Layout::horizontal([Constraint::Length(COLUMN * days.len() as u16)]).flex(Flex::Center).areas(columns);
+        for (index, day) in days.iter().enumerate() {
+            let sky = Sky::for_code(day.code);
+            let column = Rect { x: columns.x + index as u16 * COLUMN, width: COLUMN, ..columns };
+            let name = if index == 0 { "Today" } else { &WEEKDAYS[stats::weekday(day.day) as usize][..3] };
+            let name = if index == self.selected_day { name.black().on_light_cyan() } else { name.white() };
+            Paragraph::new(vec![
+                Line::from(name),
+                Line::from(sky.glyph()),
+                Line::from(degrees(day.high).light_yellow()),
+                Line::from(degrees(day.low).dark_gray()),
+                Line::from(format!("{}%", day.precipitation).light_blue()),
+            ]).centered().render(column, buf);
+        }
+
+        if let Some(day) = days.get(self.selected_day) {
+            let name = if self.selected_day == 0 { "Today" } else { WEEKDAYS[stats::weekday(day.day) as usize] };
+            Line::from(vec![
+                format!("{}: ", name).white().bold(),
+                Sky::for_code(day.code).summary().white(),
+                format!(", {} to {}, {}% chance of precipitation, wind {}", unit.format(day.low), unit.format(day.high), day.precipitation, wind_unit.format(day.wind)).white(),
+            ]).centered().render(details, buf);
+        }
+        let [alerts] = Layout::horizontal([Constraint::Length(40)]).flex(Flex::Center).areas(alerts);
+        self.alerts.render(alerts, buf);
+    }
+}
+
+/// The chosen location above both views.
+fn place_line() -> Line<'static> {
+    match config::get().location {
+        Some(location) => Line::from(vec![location.to_string().white().bold(), format!("  {}", location.coordinates()).dark_gray()]),
+        None => Line::from(vec!["No location set".white(), "  <L> search a city".dark_gray()]),
+    }
+}
+
+/// The typed city name over the weather, with the matches below it.
+fn render_search(search: &LocationSearch, area: Rect, buf: &mut Buffer) {
+    let mut lines = vec![Line::from(vec!["> ".light_cyan(), search.query.clone().white(), "_".light_cyan().slow_blink()]), Line::from("")];
+    lines.extend(search.matches.iter().enumerate().map(|(index, place)| {
+        let style = if index == search.selected { Style::default().fg(Color::Black).bg(Color::LightCyan) } else { Style::default().fg(Color::White) };
+        Line::from(vec![Span::styled(format!(" {:<26}", place.to_string()), style), format!(" {}", place.coordinates()).dark_gray()])
+    }));
+    let hint = if search.query.trim().is_empty() {
+        "type a city name, <Enter> cancels".to_string()
+    } else if search.pending.is_some() {
+        "searching…".to_string()
+    } else if let Some(error) = search.error {
+        format!("{}, <Enter> tries again", error)
+    } else if search.searched.is_none() {
+        "<Enter> search".to_string()
+    } else if search.matches.is_empty() {
+        "no city found".to_string()
+    } else {
+        "<Up Down> choose  <Enter> pick".to_string()
+    };
+    lines.push(Line::from(hint.dark_gray()));
+
+    let [popup] = Layout::horizontal([Constraint::Length(50)]).flex(Flex::Center).areas(area);
+    let [popup] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)]).flex(Flex::Center).areas(popup);
+    Clear.render(popup, buf);
+    Paragraph::new(lines)
+        .block(Block::bordered().title(" Location ").light_cyan())
+        .render(popup, buf);
+}
+
+/// The alert in effect, scrolled through by the ticker.
+fn alert_line(alert: Option<&Alert>) -> Line<'static> {
+    let Some(alert) = alert else { return Line::default() };
+    Line::from(vec!["⚠ ".light_red().bold(), format!("{} +++ {}", alert.event, alert.description).yellow()])
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Minor => Color::Yellow,
+        Severity::Moderate => Color::LightRed,
+        Severity::Severe => Color::Red,
     }
-}
\ No newline at end of file
+}
+
+/// The name of the alert in effect across the whole row, hard to miss.
+fn render_alert_banner(alert: &Alert, area: Rect, buf: &mut Buffer) {
+    let style = match alert.severity {
+        Severity::Severe => Style::default().fg(Color::White).bg(Color::Red).bold(),
+        severity => Style::default().fg(Color::Black).bg(severity_color(severity)).bold(),
+    };
+    buf.set_style(area, style);
+    Line::from(vec![
+        Span::raw(format!("⚠ {}", alert.event.to_uppercase())),
+        Span::raw("  <I> details").not_bold(),
+    ]).centered().render(area, buf);
+}
+
+/// Chance of precipitation as a bar per column below the temperatures, at the hour the column shows.
+fn render_precipitation(hours: &[Hour], area: Rect, buf: &mut Buffer) {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let pixels = (area.width * 2).saturating_sub(1).max(1) as f64;
+    for column in 0..area.width {
+        let index = ((column * 2) as f64 / pixels * (hours.len() - 1) as f64).round() as usize;
+        let Some(hour) = hours.get(index) else { continue };
+        let bar = BARS[(hour.precipitation as usize * (BARS.len() - 1) / 100).min(BARS.len() - 1)];
+        buf.set_string(area.x + column, area.y, bar.to_string(), Style::default().fg(Color::LightBlue));
+    }
+}
+
+fn sparkline(values: &[f64], low: f64, high: f64, area: Rect) -> BrailleCanvas {
+    let mut canvas = BrailleCanvas::new(area.width, area.height).style(Style::new().light_yellow());
+    let (width, height) = (canvas.pixel_width() as f64 - 1.0, canvas.pixel_height() as f64 - 1.0);
+    let point = |i: usize| {
+        let x = i as f64 / (values.len() - 1).max(1) as f64 * width;
+        let y = height - (values[i] - low) / (high - low).max(f64::EPSILON) * height;
+        (x.round() as i32, y.round() as i32)
+    };
+    for i in 1..values.len() {
+        let ((x0, y0), (x1, y1)) = (point(i - 1), point(i));
+        canvas.line(x0, y0, x1, y1);
+    }
+    canvas
+}
+
+/// `index` moved by `by` among `count` entries, wrapping around.
+fn step(index: usize, by: isize, count: usize) -> usize {
+    if count == 0 { return 0; }
+    (index as isize + by).rem_euclid(count as isize) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_wait_longer_and_longer() {
+        assert_eq!(WeatherMain::wait_after(0, None), 60.0);
+        assert_eq!(WeatherMain::wait_after(1, Some(FetchError::Unreachable)), 120.0);
+        assert_eq!(WeatherMain::wait_after(3, Some(FetchError::Status(503))), 480.0);
+        assert_eq!(WeatherMain::wait_after(12, Some(FetchError::Unreachable)), 32.0 * 60.0);
+    }
+
+    #[test]
+    fn rate_limits_wait_as_long_as_the_service_asks() {
+        let limited = |retry_after| Some(FetchError::RateLimited { retry_after });
+        assert_eq!(WeatherMain::wait_after(1, limited(Some(3600))), 3600.0);
+        assert_eq!(WeatherMain::wait_after(4, limited(Some(30))), 960.0);
+        assert_eq!(WeatherMain::wait_after(2, limited(None)), 240.0);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::config;
+use crate::particles::{Emitter, ParticleSystem};
+use crate::utils::{AsciiAnimation, AsciiCanvas, AsciiCell, AsciiCells, LoopMode};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Modifier, Widget};
+
+const CLOUD: &str = "   .--.\n.-(    ).\n(___.__)__)";
+const SUN: &str = "  .---.\n (     )\n  '---'";
+/// Rays around the sun, from short to long; a pulse plays them back and forth.
+const RAYS: [&str; 3] = [
+    "\n      |\n\n\n\n      |",
+    "\n    \\ | /\n\n  -       -\n\n    / | \\",
+    "  \\   |   /\n   \\  |  /\n\n --       --\n\n   /  |  \\\n  /   |   \\",
+];
+
+/// What the scene shows.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scene { Sun, Clouds, Rain, Snow }
+
+impl Scene {
+    /// The scene for a WMO weather code, the codes most weather services report conditions in.
+    pub fn for_code(code: u8) -> Self {
+        match code {
+            0 | 1 => Scene::Sun,
+            71..=77 | 85 | 86 => Scene::Snow,
+            51..=67 | 80..=82 | 95..=99 => Scene::Rain,
+            _ => Scene::Clouds,
+        }
+    }
+}
+
+/// A small animated sky beside the numbers of the weather screen: a sun with pulsing rays,
+/// clouds drifting across, or a cloud with rain or snow falling from it.
+///
+/// The owner ticks it from `run`; with reduce motion on it holds still.
+pub struct WeatherScene {
+    scene: Scene,
+    elapsed: f64,
+    rays: AsciiAnimation,
+    precipitation: ParticleSystem, // falls from the cloud, in scene coordinates
+}
+
+impl WeatherScene {
+    pub const WIDTH: u16 = 22;
+    pub const HEIGHT: u16 = 8;
+    /// Cells per second the clouds drift.
+    const DRIFT: f64 = 1.5;
+
+    pub fn new(code: u8) -> Self {
+        let mut rays = AsciiAnimation::new(LoopMode::PingPong);
+        for (step, rays_art) in RAYS.iter().enumerate() {
+            rays = rays.frame(cells(rays_art, if step == 2 { Color::LightYellow } else { Color::Yellow }), 0.35);
+        }
+        let mut scene = Self { scene: Scene::Clouds, elapsed: 0.0, rays, precipitation: ParticleSystem::new() };
+        scene.set_code(code);
+        scene
+    }
+
+    pub fn set_code(&mut self, code: u8) {
+        self.scene = Scene::for_code(code);
+        self.precipitation.clear();
+        let (width, below_cloud) = (11.0, Self::HEIGHT as f64 - 3.0);
+        let emitter = match self.scene {
+            Scene::Rain => Emitter::rain(width, below_cloud),
+            Scene::Snow => Emitter::snow(width, below_cloud),
+            Scene::Sun | Scene::Clouds => return,
+        };
+        // denser than on the whole screen, the cloud is small
+        self.precipitation.add_emitter(Emitter { x: 5.0, y: 3.0, rate: emitter.rate * 4.0, ..emitter });
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        if config::get().reduce_motion { return; }
+        self.elapsed += dt;
+        self.rays.tick(dt);
+        self.precipitation.tick(dt);
+    }
+
+    /// Column of a cloud starting at `start` and drifting `speed` times the base drift; it jumps back to
+    /// the left edge once it has drifted out on the right.
+    fn drift(&self, start: f64, speed: f64) -> u16 {
+        ((start + self.elapsed * Self::DRIFT * speed) % Self::WIDTH as f64) as u16
+    }
+
+    fn canvas(&self) -> AsciiCanvas {
+        match self.scene {
+            Scene::Sun => {
+                let rays = self.rays.current_frame().cloned().unwrap_or_else(|| AsciiCells::new(Vec::new()));
+                AsciiCanvas::new().layer(0, 0, 0, rays).layer(1, 2, 2, cells(SUN, Color::LightYellow))
+            }
+            Scene::Clouds => AsciiCanvas::new()
+                .layer(0, self.drift(2.0, 0.6), 0, cells(CLOUD, Color::DarkGray))
+                .layer(1, self.drift(12.0, 1.0), 4, cells(CLOUD, Color::Gray)),
+            Scene::Rain | Scene::Snow => AsciiCanvas::new().layer(0, 5, 0, cells(CLOUD, Color::Gray)),
+        }
+    }
+}
+
+/// `art` in one color.
+fn cells(art: &str, color: Color) -> AsciiCells {
+    let cells = art.lines().enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, ch)| (x, y, ch)))
+        .map(|(x, y, ch)| AsciiCell { ch, x: x as u16, y: y as u16, color, background: None, modifier: Modifier::empty() })
+        .collect();
+    AsciiCells::new(cells)
+}
+
+impl Widget for &WeatherScene {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = Rect { width: area.width.min(WeatherScene::WIDTH), height: area.height.min(WeatherScene::HEIGHT), ..area };
+        self.canvas().render(area, buf);
+        self.precipitation.render(area, buf);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::config;
+use crate::http::{self, FetchError};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Place names of [Open-Meteo](https://open-meteo.com), from GeoNames.
+const SEARCH_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+/// Most matches a search returns.
+const MAX_MATCHES: usize = 8;
+
+/// A named spot on the map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Place {
+    pub name: String,
+    pub country: String, // ISO 3166 code, e.g. `DE`
+    pub latitude: f64, // degrees, north is positive
+    pub longitude: f64, // degrees, east is positive
+}
+
+impl Place {
+    /// `52.52°N 13.40°E`
+    pub fn coordinates(&self) -> String {
+        let (ns, ew) = (if self.latitude < 0.0 { 'S' } else { 'N' }, if self.longitude < 0.0 { 'W' } else { 'E' });
+        format!("{:.2}°{} {:.2}°{}", self.latitude.abs(), ns, self.longitude.abs(), ew)
+    }
+}
+
+impl fmt::Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.name, self.country)
+    }
+}
+
+/// `name | country | latitude | longitude`, the format of the config.
+impl FromStr for Place {
+    type Err = ();
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        let [name, country, latitude, longitude] = fields[..] else { return Err(()) };
+        Ok(Place {
+            name: name.to_string(),
+            country: country.to_string(),
+            latitude: latitude.parse().map_err(|_| ())?,
+            longitude: longitude.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+/// The answer of the search API. Without matches there are no `results` at all.
+#[derive(Deserialize)]
+struct Matches {
+    #[serde(default)]
+    results: Vec<Match>,
+}
+
+#[derive(Deserialize)]
+struct Match {
+    name: String,
+    country_code: Option<String>, // missing for places at sea or in disputed areas
+    latitude: f64,
+    longitude: f64,
+}
+
+fn parse_matches(json: &str) -> Result<Vec<Place>, FetchError> {
+    let matches: Matches = serde_json::from_str(json).map_err(|_| FetchError::Invalid)?;
+    Ok(matches.results.into_iter()
+        .map(|found| Place {
+            name: found.name,
+            country: found.country_code.unwrap_or_else(|| "--".to_string()),
+            latitude: found.latitude,
+            longitude: found.longitude,
+        })
+        .collect())
+}
+
+/// Places whose name matches `query`, the most populous first. Asks the geocoding service, so it
+/// blocks until it answers, see [`http::in_background`].
+pub fn search(query: &str) -> Result<Vec<Place>, FetchError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    if config::get().weather_offline {
+        return Err(FetchError::Offline);
+    }
+    parse_matches(&http::get(SEARCH_URL, &[
+        ("name", query.to_string()),
+        ("count", MAX_MATCHES.to_string()),
+        ("language", "en".to_string()),
+        ("format", "json".to_string()),
+    ])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_become_places() {
+        let answer = r#"{"results": [
+            {"id": 2950159, "name": "Berlin", "latitude": 52.52437, "longitude": 13.41053, "country_code": "DE", "admin1": "Land Berlin", "population": 3426354},
+            {"id": 5083330, "name": "Berlin", "latitude": 44.46867, "longitude": -71.18508, "country_code": "US", "admin1": "New Hampshire"},
+            {"id": 1, "name": "Berlin Bank", "latitude": -54.1, "longitude": -36.9}
+        ], "generationtime_ms": 0.9}"#;
+        let places = parse_matches(answer).unwrap();
+        assert_eq!(places.iter().map(Place::to_string).collect::<Vec<_>>(), ["Berlin, DE", "Berlin, US", "Berlin Bank, --"]);
+        assert_eq!(places[1].coordinates(), "44.47°N 71.19°W");
+    }
+
+    #[test]
+    fn no_matches_and_unreadable_answers() {
+        assert_eq!(parse_matches(r#"{"generationtime_ms": 0.3}"#), Ok(Vec::new()));
+        assert_eq!(parse_matches("Bad Gateway"), Err(FetchError::Invalid));
+    }
+}
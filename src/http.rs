@@ -0,0 +1,104 @@
+//! Requests to the web services behind the weather screen. They block until the service answers,
+//! so screens run them with [`in_background`].
+
+use crate::events::Message;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+/// Sent with every request; api.weather.gov refuses requests without one.
+const USER_AGENT: &str = concat!("hackerman/", env!("CARGO_PKG_VERSION"));
+
+/// Why a service gave no answer to work with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FetchError {
+    /// Offline mode is on, nothing was asked.
+    Offline,
+    /// No network, an unknown host or no answer in time.
+    Unreachable,
+    /// Asked too often (HTTP 429), with the seconds to wait if the service said so.
+    RateLimited { retry_after: Option<u64> },
+    /// Any other error status.
+    Status(u16),
+    /// An answer that could not be read.
+    Invalid,
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Offline => write!(f, "Offline"),
+            FetchError::Unreachable => write!(f, "Service unreachable"),
+            FetchError::RateLimited { .. } => write!(f, "Rate limited"),
+            FetchError::Status(status) => write!(f, "Service error {}", status),
+            FetchError::Invalid => write!(f, "Unreadable answer"),
+        }
+    }
+}
+
+/// The body of a GET request to `url` with the `query` parameters.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get(url: &str, query: &[(&str, String)]) -> Result<String, FetchError> {
+    let mut request = ureq::get(url)
+        .timeout(std::time::Duration::from_secs(10))
+        .set("User-Agent", USER_AGENT);
+    for (name, value) in query {
+        request = request.query(name, value);
+    }
+    match request.call() {
+        Ok(response) => response.into_string().map_err(|_| FetchError::Invalid),
+        Err(ureq::Error::Status(429, response)) => {
+            let retry_after = response.header("Retry-After").and_then(|seconds| seconds.trim().parse().ok());
+            Err(FetchError::RateLimited { retry_after })
+        }
+        Err(ureq::Error::Status(status, _)) => Err(FetchError::Status(status)),
+        Err(ureq::Error::Transport(_)) => Err(FetchError::Unreachable),
+    }
+}
+
+/// The browser build has no client, its requests would have to go through `fetch`.
+#[cfg(target_arch = "wasm32")]
+pub fn get(_url: &str, _query: &[(&str, String)]) -> Result<String, FetchError> {
+    let _ = USER_AGENT;
+    Err(FetchError::Unreachable)
+}
+
+static WAKER: Mutex<Option<Sender<Message>>> = Mutex::new(None);
+
+/// Send [`Message::Wake`] to `sender` whenever a result of [`in_background`] arrives, so a UI that
+/// only redraws on input still picks it up right away. The app hands in its event loop.
+pub fn wake(sender: Sender<Message>) {
+    *WAKER.lock().unwrap() = Some(sender);
+}
+
+/// Run `job` on a thread of its own; its result arrives on the returned channel. Poll it with
+/// `try_recv` while the screen keeps running, or when woken, see [`wake`].
+pub fn in_background<T: Send + 'static>(job: impl FnOnce() -> T + Send + 'static) -> Receiver<T> {
+    let (sender, receiver) = mpsc::channel();
+    // a dropped receiver means nobody waits for the result any more
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::spawn(move || {
+        if sender.send(job()).is_ok() && let Some(waker) = WAKER.lock().unwrap().as_ref() {
+            let _ = waker.send(Message::Wake);
+        }
+    });
+    // no threads in the browser, and no requests either
+    #[cfg(target_arch = "wasm32")]
+    let _ = sender.send(job());
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn a_result_wakes_the_event_loop_once_it_can_be_received() {
+        let (sender, woken) = mpsc::channel();
+        wake(sender);
+        let result = in_background(|| 42);
+        assert!(matches!(woken.recv_timeout(Duration::from_secs(5)), Ok(Message::Wake)));
+        assert_eq!(result.try_recv(), Ok(42));
+    }
+}
@@ -0,0 +1,76 @@
+//! Turn PNG and JPEG images into [`AsciiCells`]. Only built with the `images` feature.
+
+use crate::utils::{AsciiCell, AsciiCells};
+use image::imageops::FilterType;
+use image::DynamicImage;
+use ratatui::style::{Color, Modifier};
+use std::path::Path;
+
+/// Characters from dark to bright.
+const RAMP: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// The 16 terminal colors with the RGB values most terminals use for them.
+const ANSI_16: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [170, 0, 0]),
+    (Color::Green, [0, 170, 0]),
+    (Color::Yellow, [170, 85, 0]),
+    (Color::Blue, [0, 0, 170]),
+    (Color::Magenta, [170, 0, 170]),
+    (Color::Cyan, [0, 170, 170]),
+    (Color::Gray, [170, 170, 170]),
+    (Color::DarkGray, [85, 85, 85]),
+    (Color::LightRed, [255, 85, 85]),
+    (Color::LightGreen, [85, 255, 85]),
+    (Color::LightYellow, [255, 255, 85]),
+    (Color::LightBlue, [85, 85, 255]),
+    (Color::LightMagenta, [255, 85, 255]),
+    (Color::LightCyan, [85, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Palette {
+    /// Brightness only, in the terminal's default color.
+    Monochrome,
+    /// Nearest of the 16 classic terminal colors, works everywhere.
+    Ansi16,
+    /// Exact RGB, needs a terminal with true color support.
+    TrueColor,
+}
+
+/// Terminal cells are about twice as tall as wide, so rows are halved to keep the proportions.
+pub fn rows_for(image: &DynamicImage, width: u16) -> u16 {
+    ((image.height() as f64 * width as f64 / image.width().max(1) as f64) / 2.0).round().max(1.0) as u16
+}
+
+/// The image scaled to `width` columns, one character per cell picked by brightness.
+pub fn convert(image: &DynamicImage, width: u16, palette: Palette) -> AsciiCells {
+    let height = rows_for(image, width);
+    let scaled = image.resize_exact(width.max(1) as u32, height as u32, FilterType::Triangle).to_rgba8();
+
+    let cells = scaled.enumerate_pixels()
+        .map(|(x, y, pixel)| {
+            let [r, g, b, alpha] = pixel.0;
+            // perceived brightness, transparent pixels count as dark
+            let luma = (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64) * alpha as f64 / 255.0;
+            let ch = RAMP[(luma / 256.0 * RAMP.len() as f64) as usize];
+            let color = match palette {
+                Palette::Monochrome => Color::Reset,
+                Palette::Ansi16 => nearest_ansi([r, g, b]),
+                Palette::TrueColor => Color::Rgb(r, g, b),
+            };
+            AsciiCell { ch, x: x as u16, y: y as u16, color, background: None, modifier: Modifier::empty() }
+        })
+        .collect();
+    AsciiCells::new(cells)
+}
+
+fn nearest_ansi(rgb: [u8; 3]) -> Color {
+    let distance = |other: &[u8; 3]| rgb.iter().zip(other).map(|(a, b)| (*a as i32 - *b as i32).pow(2)).sum::<i32>();
+    ANSI_16.iter().min_by_key(|(_, other)| distance(other)).map(|(color, _)| *color).unwrap()
+}
+
+pub fn open(path: &Path) -> Result<DynamicImage, image::ImageError> {
+    image::open(path)
+}
@@ -0,0 +1,301 @@
+use crate::config;
+use crate::storage::migration::{self, Schema};
+use crate::ui::settings_form::key_name;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Mutex;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+const QUIRKS: Schema = Schema { namespace: "config", key: "terminals", steps: &[migration::unversioned] };
+
+/// Extra keys that stand in for the arrows and Enter. The arrows and Enter always keep working.
+///
+/// Presets apply in the menus, the dialogs of the app and the screens that
+/// [follow them](crate::games::main_screen_widget::MainScreenWidget::follows_controls_preset);
+/// games with letter hotkeys of their own keep their keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Arrows,
+    /// Everything under the left hand.
+    Wasd,
+    /// Everything under the right hand.
+    Ijkl,
+    /// The arrows move, the resting index fingers confirm.
+    HomeRow,
+}
+
+impl Preset {
+    /// Keys for up, left, down and right, if the preset moves with letters.
+    fn movement(self) -> Option<[char; 4]> {
+        match self {
+            Preset::Wasd => Some(['w', 'a', 's', 'd']),
+            Preset::Ijkl => Some(['i', 'j', 'k', 'l']),
+            Preset::Arrows | Preset::HomeRow => None,
+        }
+    }
+
+    /// Letters that act like Enter.
+    fn confirm(self) -> &'static [char] {
+        match self {
+            Preset::Wasd => &['f'],
+            Preset::Ijkl => &['h'],
+            Preset::HomeRow => &['f', 'j'],
+            Preset::Arrows => &[],
+        }
+    }
+
+    /// Name in the settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Arrows => "arrows",
+            Preset::Wasd => "left hand, WASD",
+            Preset::Ijkl => "right hand, IJKL",
+            Preset::HomeRow => "home row, F J",
+        }
+    }
+
+    /// How to move and confirm with this preset, e.g. `move W A S D  confirm F`.
+    pub fn hint(self) -> String {
+        let letters = |keys: &[char]| keys.iter().map(|key| key.to_ascii_uppercase().to_string()).collect::<Vec<_>>().join(" ");
+        let movement = self.movement().map_or("↑ ← ↓ →".to_string(), |keys| letters(&keys));
+        let confirm = if self.confirm().is_empty() { "Enter".to_string() } else { letters(self.confirm()) };
+        format!("move {}  confirm {}", movement, confirm)
+    }
+
+    /// The arrow or Enter key `input` stands for, or `input` unchanged.
+    pub fn translate(self, input: KeyEvent) -> KeyEvent {
+        let KeyCode::Char(ch) = input.code else { return input };
+        if input.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return input;
+        }
+        let ch = ch.to_ascii_lowercase();
+        let code = match self.movement().and_then(|keys| keys.iter().position(|key| *key == ch)) {
+            Some(direction) => [KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right][direction],
+            None if self.confirm().contains(&ch) => KeyCode::Enter,
+            None => return input,
+        };
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+}
+
+/// [`Preset::translate`] with the preset chosen in the settings.
+pub fn translate(input: KeyEvent) -> KeyEvent {
+    config::get().controls.translate(input)
+}
+
+/// Keyboard layouts known key by key, so keys can be matched by where they sit as well as by what
+/// they type: hotkeys keep working on a Cyrillic keyboard, digits on a French one.
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Layout {
+    Us,
+    /// German and most of central Europe.
+    Qwertz,
+    /// French and Belgian, digits need Shift.
+    Azerty,
+    /// Turkish Q, with a dotless ı where the I is.
+    Turkish,
+    Russian,
+    Greek,
+}
+
+impl Layout {
+    /// What the number row and the three letter rows type without Shift, ten keys each,
+    /// starting with the key a US keyboard has `1`, `q`, `a` and `z` on.
+    fn rows(self) -> [&'static str; 4] {
+        match self {
+            Layout::Us => ["1234567890", "qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            Layout::Qwertz => ["1234567890", "qwertzuiop", "asdfghjklö", "yxcvbnm,.-"],
+            Layout::Azerty => ["&é\"'(-è_çà", "azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+            Layout::Turkish => ["1234567890", "qwertyuıop", "asdfghjklş", "zxcvbnmöç."],
+            Layout::Russian => ["1234567890", "йцукенгшщз", "фывапролдж", "ячсмитьбю."],
+            Layout::Greek => ["1234567890", ";ςερτυθιοπ", "ασδφγηξκλ΄", "ζχψωβνμ,./"],
+        }
+    }
+
+    /// Row and column of the key typing `ch`.
+    fn position(self, ch: char) -> Option<(usize, usize)> {
+        let ch = ch.to_lowercase().next().unwrap_or(ch);
+        self.rows().iter().enumerate().find_map(|(row, keys)| keys.chars().position(|key| key == ch).map(|column| (row, column)))
+    }
+
+    /// What the key at `row`, `column` types, see [`Layout::rows`].
+    pub fn key_at(self, row: usize, column: usize) -> Option<char> {
+        self.rows().get(row).and_then(|keys| keys.chars().nth(column))
+    }
+
+    /// The US key at the position of the key that types `ch` in this layout, upper case if `ch` is.
+    pub fn physical(self, ch: char) -> Option<char> {
+        let (row, column) = self.position(ch)?;
+        let us = Layout::Us.key_at(row, column)?;
+        Some(if ch.is_uppercase() { us.to_ascii_uppercase() } else { us })
+    }
+
+    /// The layout typing the most of `typed`, given as (row, column, typed) like [`Layout::key_at`];
+    /// `None` if no layout matches all but one of them.
+    pub fn detect(typed: &[(usize, usize, char)]) -> Option<Layout> {
+        let matches = |layout: Layout| typed.iter().filter(|(row, column, ch)| layout.key_at(*row, *column) == Some(*ch)).count();
+        Layout::iter()
+            .map(|layout| (layout, matches(layout)))
+            .filter(|(_, count)| *count + 1 >= typed.len())
+            .max_by_key(|(_, count)| *count)
+            .map(|(layout, _)| layout)
+    }
+}
+
+/// What the key test found out about the keyboard and this terminal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Quirks {
+    pub layout: Option<Layout>,
+    pub remaps: Vec<(String, KeyCode)>, // a key the terminal sends, by [`key_name`], and the key meant
+}
+
+/// Names the terminal hackerman runs in, quirks are stored under it.
+pub fn terminal() -> String {
+    std::env::var("TERM_PROGRAM").or_else(|_| std::env::var("TERM")).unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Stored as `terminal: layout=azerty` and `terminal: Ctrl+H=Backspace` lines.
+fn parse_quirks(contents: &str, terminal: &str) -> Quirks {
+    let mut quirks = Quirks::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.strip_prefix(terminal).and_then(|rest| rest.strip_prefix(": ")).and_then(|rest| rest.rsplit_once('=')) else { continue };
+        match (key, value) {
+            ("layout", layout) => quirks.layout = layout.parse().ok(),
+            (key, "Backspace") => quirks.remaps.push((key.to_string(), KeyCode::Backspace)),
+            (key, "Enter") => quirks.remaps.push((key.to_string(), KeyCode::Enter)),
+            _ => {}
+        }
+    }
+    quirks
+}
+
+fn serialize_quirks(quirks: &Quirks, terminal: &str) -> String {
+    let layout = quirks.layout.map(|layout| format!("{}: layout={}\n", terminal, layout));
+    let remaps = quirks.remaps.iter().map(|(key, meant)| format!("{}: {}={:?}\n", terminal, key, meant));
+    layout.into_iter().chain(remaps).collect()
+}
+
+static TERMINAL_QUIRKS: Mutex<Option<Quirks>> = Mutex::new(None);
+
+/// Quirks of the terminal hackerman runs in, loaded on first use.
+pub fn quirks() -> Quirks {
+    TERMINAL_QUIRKS.lock().unwrap()
+        .get_or_insert_with(|| parse_quirks(&migration::load(&QUIRKS).ok().flatten().unwrap_or_default(), &terminal()))
+        .clone()
+}
+
+/// Replace the quirks of this terminal, those of other terminals are kept.
+pub fn save_quirks(quirks: Quirks) {
+    let terminal = terminal();
+    let prefix = format!("{}: ", terminal);
+    let others: String = migration::load(&QUIRKS).ok().flatten().unwrap_or_default().lines()
+        .filter(|line| !line.starts_with(&prefix))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let _ = migration::save(&QUIRKS, &(others + &serialize_quirks(&quirks, &terminal)));
+    *TERMINAL_QUIRKS.lock().unwrap() = Some(quirks);
+}
+
+/// A key as the app should see it, whatever the layout and the terminal: keys the terminal is known
+/// to send instead of another become that key, the Turkish İ and ı are read as I and i, and letters
+/// of other scripts, or any letter held with Ctrl or Alt, become the US key at the same position
+/// so Ctrl+C still quits on a Russian keyboard. Latin letters are kept as typed.
+pub fn normalize(input: KeyEvent) -> KeyEvent {
+    normalize_with(input, &quirks())
+}
+
+fn normalize_with(input: KeyEvent, quirks: &Quirks) -> KeyEvent {
+    let name = key_name(input);
+    if let Some((_, meant)) = quirks.remaps.iter().find(|(key, _)| *key == name) {
+        return KeyEvent { code: *meant, modifiers: KeyModifiers::NONE, ..input };
+    }
+    let KeyCode::Char(ch) = input.code else { return input };
+    let ch = match ch {
+        'İ' => 'I',
+        'ı' => 'i',
+        ch if ch.is_ascii() => ch,
+        ch if (ch as u32) < 0x250 && !input.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => ch, // Latin
+        ch => quirks.layout.into_iter().chain(Layout::iter()).find_map(|layout| layout.physical(ch)).unwrap_or(ch),
+    };
+    KeyEvent { code: KeyCode::Char(ch), ..input }
+}
+
+/// The digit `input` types, or the one on the same key of the number row, for layouts that
+/// need Shift for digits.
+pub fn digit(input: KeyEvent) -> Option<char> {
+    let KeyCode::Char(ch) = input.code else { return None };
+    if ch.is_ascii_digit() {
+        return Some(ch);
+    }
+    let layout = quirks().layout?;
+    match layout.position(ch) {
+        Some((0, column)) if !input.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => Layout::Us.key_at(0, column),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORED: &str = "\
+iTerm.app: layout=qwertz
+iTerm.app: Ctrl+H=Backspace
+xterm-256color: layout=russian
+iTerm.app: Ctrl+J=Enter
+iTerm.app: Ctrl+X=Escape
+iTerm.app: layout=dvorak-ish
+";
+
+    fn key(ch: char, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(ch), modifiers)
+    }
+
+    #[test]
+    fn quirks_are_read_for_this_terminal_only() {
+        let quirks = parse_quirks(STORED, "iTerm.app");
+        // the unknown layout of the last line wins over the first, known keys are the only remaps
+        assert_eq!(quirks, Quirks { layout: None, remaps: vec![("Ctrl+H".to_string(), KeyCode::Backspace), ("Ctrl+J".to_string(), KeyCode::Enter)] });
+        assert_eq!(parse_quirks(STORED, "xterm-256color").layout, Some(Layout::Russian));
+        assert_eq!(parse_quirks(STORED, "xterm"), Quirks::default());
+        assert_eq!(parse_quirks("", "iTerm.app"), Quirks::default());
+    }
+
+    #[test]
+    fn quirks_survive_a_round_trip() {
+        let quirks = Quirks { layout: Some(Layout::Azerty), remaps: vec![("Ctrl+H".to_string(), KeyCode::Backspace), ("Ctrl+M".to_string(), KeyCode::Enter)] };
+        let stored = serialize_quirks(&quirks, "WezTerm");
+        assert_eq!(stored, "WezTerm: layout=azerty\nWezTerm: Ctrl+H=Backspace\nWezTerm: Ctrl+M=Enter\n");
+        assert_eq!(parse_quirks(&stored, "WezTerm"), quirks);
+    }
+
+    #[test]
+    fn remapped_keys_become_the_key_meant() {
+        let quirks = parse_quirks(STORED, "iTerm.app");
+        assert_eq!(normalize_with(key('h', KeyModifiers::CONTROL), &quirks), KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(normalize_with(key('h', KeyModifiers::NONE), &quirks), key('h', KeyModifiers::NONE));
+        assert_eq!(normalize_with(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT), &quirks), KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT));
+    }
+
+    #[test]
+    fn letters_of_other_scripts_become_the_us_key_at_their_place() {
+        let none = Quirks::default();
+        assert_eq!(normalize_with(key('й', KeyModifiers::NONE), &none), key('q', KeyModifiers::NONE));
+        assert_eq!(normalize_with(key('С', KeyModifiers::SHIFT), &none), key('C', KeyModifiers::SHIFT));
+        assert_eq!(normalize_with(key('ς', KeyModifiers::NONE), &none), key('w', KeyModifiers::NONE));
+        assert_eq!(normalize_with(key('İ', KeyModifiers::SHIFT), &none), key('I', KeyModifiers::SHIFT));
+        assert_eq!(normalize_with(key('ı', KeyModifiers::NONE), &none), key('i', KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn latin_letters_are_kept_unless_held_with_ctrl_or_alt() {
+        let qwertz = Quirks { layout: Some(Layout::Qwertz), remaps: Vec::new() };
+        assert_eq!(normalize_with(key('é', KeyModifiers::NONE), &qwertz), key('é', KeyModifiers::NONE));
+        assert_eq!(normalize_with(key('ö', KeyModifiers::NONE), &qwertz), key('ö', KeyModifiers::NONE));
+        assert_eq!(normalize_with(key('ö', KeyModifiers::CONTROL), &qwertz), key(';', KeyModifiers::CONTROL));
+        assert_eq!(normalize_with(key('z', KeyModifiers::CONTROL), &qwertz), key('z', KeyModifiers::CONTROL));
+    }
+}
@@ -1,4 +1,32 @@
 pub mod utils;
 pub mod app;
+pub mod art;
 pub mod games;
-
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod config;
+pub mod credits;
+pub mod effects;
+pub mod embed;
+pub mod events;
+pub mod fonts;
+pub mod geocoding;
+pub mod http;
+pub mod keymap;
+#[cfg(feature = "images")]
+pub mod img2ascii;
+pub mod music;
+pub mod notifications;
+pub mod particles;
+pub mod random;
+pub mod report;
+pub mod review;
+pub mod saves;
+pub mod sound;
+pub mod sprite;
+pub mod stats;
+pub mod storage;
+pub mod training;
+pub mod tween;
+pub mod ui;
+pub mod weather;
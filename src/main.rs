@@ -1,13 +1,83 @@
 mod utils;
 mod app;
+mod art;
 mod games;
+#[cfg(feature = "audio")]
+mod audio;
+mod config;
+mod credits;
+mod effects;
+mod embed;
+mod events;
+mod fonts;
+mod geocoding;
+mod http;
+mod keymap;
+#[cfg(feature = "images")]
+mod img2ascii;
+mod music;
+mod notifications;
+mod particles;
+mod random;
+mod report;
+mod review;
+mod saves;
+mod sound;
+mod sprite;
+mod stats;
+mod storage;
+mod training;
+mod tween;
+mod ui;
+mod weather;
+// only in the binary: its wasm exports would clash with the library's
+#[cfg(target_arch = "wasm32")]
+mod web;
 
-use crate::app::App;
+#[cfg(target_arch = "wasm32")]
+fn main() -> color_eyre::Result<()> {
+    Ok(web::start()?)
+}
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> color_eyre::Result<()> {
+    use crate::app::App;
+    use crossterm::event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture};
+    use crossterm::execute;
+
     color_eyre::install()?;
-    let terminal = ratatui::init();
-    let result = App::new().run(terminal);
+    let profile = storage::profile_from_args();
+    if let Some(Err(e)) = profile.as_ref().map(storage::install_profile) {
+        ui::toast::show(e.to_string());
+    }
+    random::install_from_args();
+    #[cfg(feature = "audio")]
+    audio::start();
+    let mut terminal = ratatui::init();
+    execute!(std::io::stdout(), EnableMouseCapture, EnableFocusChange)?;
+    let result = if profile.is_some() || choose_profile(&mut terminal)? { App::new().run(terminal) } else { Ok(()) };
+    let _ = execute!(std::io::stdout(), DisableFocusChange, DisableMouseCapture);
     ratatui::restore();
     result
-}
\ No newline at end of file
+}
+
+/// Ask who is playing until the [`ProfileChooser`](games::profile_chooser::ProfileChooser) has
+/// installed a profile; false when Ctrl+C was pressed instead. The chooser only changes on keys, so
+/// they are read right here and the app starts its input thread afterwards.
+#[cfg(not(target_arch = "wasm32"))]
+fn choose_profile(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<bool> {
+    use crate::games::main_screen_widget::{MainScreenWidget, WidgetRef};
+    use crate::games::profile_chooser::ProfileChooser;
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+    let mut chooser = ProfileChooser::new();
+    while !chooser.is_exit_intended() {
+        terminal.draw(|frame| chooser.render_ref(frame.area(), frame.buffer_mut()))?;
+        match event::read()? {
+            Event::Key(key) if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL => return Ok(false),
+            Event::Key(key) if key.kind == KeyEventKind::Press => chooser.handle_input(key),
+            _ => {}
+        }
+    }
+    Ok(true)
+}
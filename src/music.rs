@@ -0,0 +1,129 @@
+//! A tiny tracker format for looping chiptune background music.
+//!
+//! ```text
+//! # comments start with a hash
+//! bpm=120
+//! C4 - E4 = G4 - C5 =
+//! ```
+//!
+//! Every other token is one step (a sixteenth note): a note name with octave (`C4`, `F#3`, `Bb2`),
+//! `-` for a rest or `=` to hold the previous note one step longer. Lines are played back to back
+//! and the whole track loops.
+
+/// Background tunes; each screen picks one via `MainScreenWidget::music`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Tune {
+    Menu,
+    Puzzle,
+}
+
+impl Tune {
+    pub fn source(&self) -> &'static str {
+        match self {
+            Tune::Menu => "
+                bpm=96
+                C4 = E4 = G4 = E4 = A3 = C4 = E4 = C4 =
+                F3 = A3 = C4 = A3 = G3 = B3 = D4 = G4 =
+            ",
+            Tune::Puzzle => "
+                bpm=132
+                A3 - C4 - E4 - A4 - G4 - E4 - C4 - E4 -
+                F3 - A3 - C4 - F4 - E4 - C4 - B3 - G#3 -
+            ",
+        }
+    }
+
+    pub fn track(&self) -> Track {
+        Track::parse(self.source()).expect("built-in tunes are valid")
+    }
+}
+
+/// A note or rest lasting `steps` sixteenth notes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub frequency: Option<f32>,
+    pub steps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub bpm: u32,
+    pub steps: Vec<Step>,
+}
+
+impl Track {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut bpm = 120;
+        let mut steps: Vec<Step> = Vec::new();
+
+        for line in source.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("bpm=") {
+                bpm = value.trim().parse().map_err(|_| format!("invalid bpm: {value}"))?;
+                continue;
+            }
+            for token in line.split_whitespace() {
+                match token {
+                    "=" => match steps.last_mut() {
+                        Some(previous) => previous.steps += 1,
+                        None => return Err("a track cannot start with a hold".to_string()),
+                    },
+                    "-" => steps.push(Step { frequency: None, steps: 1 }),
+                    note => steps.push(Step { frequency: Some(note_frequency(note)?), steps: 1 }),
+                }
+            }
+        }
+
+        if bpm == 0 || steps.is_empty() {
+            return Err("a track needs a positive bpm and at least one step".to_string());
+        }
+        Ok(Self { bpm, steps })
+    }
+
+    pub fn step_seconds(&self) -> f32 {
+        60.0 / self.bpm as f32 / 4.0
+    }
+
+    /// One loop of the track as mono square wave samples.
+    pub fn render(&self, sample_rate: u32) -> Vec<f32> {
+        let samples_per_step = (self.step_seconds() * sample_rate as f32) as usize;
+        let mut samples = Vec::new();
+        for step in &self.steps {
+            let length = samples_per_step * step.steps as usize;
+            for i in 0..length {
+                let sample = match step.frequency {
+                    Some(frequency) => {
+                        let phase = (i as f32 * frequency / sample_rate as f32).fract();
+                        let square = if phase < 0.5 { 1.0 } else { -1.0 };
+                        // short linear decay so consecutive notes do not click
+                        let envelope = 1.0 - (i as f32 / length as f32) * 0.7;
+                        square * envelope
+                    }
+                    None => 0.0,
+                };
+                samples.push(sample);
+            }
+        }
+        samples
+    }
+}
+
+fn note_frequency(note: &str) -> Result<f32, String> {
+    let invalid = || format!("invalid note: {note}");
+    let mut chars = note.chars();
+    let semitone: i32 = match chars.next().ok_or_else(invalid)? {
+        'C' => 0, 'D' => 2, 'E' => 4, 'F' => 5, 'G' => 7, 'A' => 9, 'B' => 11,
+        _ => return Err(invalid()),
+    };
+    let rest: String = chars.collect();
+    let (accidental, octave) = match rest.chars().next() {
+        Some('#') => (1, &rest[1..]),
+        Some('b') => (-1, &rest[1..]),
+        _ => (0, rest.as_str()),
+    };
+    let octave: i32 = octave.parse().map_err(|_| invalid())?;
+    let midi = 12 * (octave + 1) + semitone + accidental;
+    Ok(440.0 * 2f32.powf((midi - 69) as f32 / 12.0))
+}
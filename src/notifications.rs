@@ -0,0 +1,99 @@
+use crate::storage::migration::{self, Schema};
+use crate::storage;
+use crate::ui::toast;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use strum_macros::{Display, EnumString};
+
+const SCHEMA: Schema = Schema { namespace: "notifications", key: "inbox", steps: &[migration::unversioned] };
+
+/// Older notifications are dropped once there are more than this.
+const KEEP: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Kind {
+    Reminder,
+    Achievement,
+    Alert,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub at: u64, // unix seconds
+    pub kind: Kind,
+    pub text: String,
+    pub read: bool,
+}
+
+/// Stored oldest first as `<at>|<kind>|<read>|<text>` lines.
+fn parse(contents: &str) -> Vec<Notification> {
+    contents.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '|');
+            Some(Notification {
+                at: fields.next()?.parse().ok()?,
+                kind: fields.next()?.parse().ok()?,
+                read: fields.next()? == "read",
+                text: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn serialize(notifications: &[Notification]) -> String {
+    notifications.iter()
+        .map(|n| format!("{}|{}|{}|{}\n", n.at, n.kind, if n.read { "read" } else { "unread" }, n.text.replace('\n', " ")))
+        .collect()
+}
+
+static INBOX: Mutex<Option<Vec<Notification>>> = Mutex::new(None);
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// While quiet (do not disturb during a game), notifications only go to the notification center.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn with_inbox<R>(action: impl FnOnce(&mut Vec<Notification>) -> R) -> R {
+    let mut guard = INBOX.lock().unwrap();
+    let inbox = guard.get_or_insert_with(|| migration::load(&SCHEMA).ok().flatten().map(|c| parse(&c)).unwrap_or_default());
+    action(inbox)
+}
+
+/// Show a toast, unless [quiet](set_quiet), and keep it in the notification center.
+/// The same text is recorded only once a day, so recurring reminders do not pile up.
+pub fn notify(kind: Kind, text: impl Into<String>) {
+    let text = text.into();
+    if !QUIET.load(Ordering::Relaxed) {
+        toast::show(text.clone());
+    }
+    let now = storage::unix_now();
+    with_inbox(|inbox| {
+        if inbox.iter().any(|n| n.text == text && n.at / 86_400 == now / 86_400) {
+            return;
+        }
+        inbox.push(Notification { at: now, kind, text, read: false });
+        let excess = inbox.len().saturating_sub(KEEP);
+        inbox.drain(..excess);
+        let _ = migration::save(&SCHEMA, &serialize(inbox));
+    });
+}
+
+/// Newest first.
+pub fn recent() -> Vec<Notification> {
+    with_inbox(|inbox| inbox.iter().rev().cloned().collect())
+}
+
+pub fn unread_count() -> usize {
+    with_inbox(|inbox| inbox.iter().filter(|n| !n.read).count())
+}
+
+pub fn mark_all_read() {
+    with_inbox(|inbox| {
+        if inbox.iter().any(|n| !n.read) {
+            inbox.iter_mut().for_each(|n| n.read = true);
+            let _ = migration::save(&SCHEMA, &serialize(inbox));
+        }
+    });
+}
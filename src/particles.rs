@@ -0,0 +1,239 @@
+use crate::config;
+use crate::random::{self, GameRng};
+use rand::Rng;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::Widget;
+
+/// One particle, in cells relative to the area the [`ParticleSystem`] is rendered into.
+#[derive(Clone, Debug)]
+struct Particle {
+    x: f64,
+    y: f64,
+    vx: f64,
+    vy: f64,
+    gravity: f64,
+    age: f64,
+    lifetime: f64,
+    symbol: char,
+    colors: Vec<Color>,
+}
+
+impl Particle {
+    /// Colors are stepped through over the lifetime, so the last one is the faded look.
+    fn color(&self) -> Color {
+        let step = (self.age / self.lifetime * self.colors.len() as f64) as usize;
+        self.colors.get(step.min(self.colors.len().saturating_sub(1))).copied().unwrap_or(Color::Reset)
+    }
+}
+
+/// How new particles look and move. Position, velocity and gravity are in cells and cells per second;
+/// every particle gets a random offset within `spread` and `jitter`.
+#[derive(Clone, Debug)]
+pub struct Emitter {
+    pub x: f64,
+    pub y: f64,
+    pub spread: (f64, f64),
+    pub velocity: (f64, f64),
+    pub jitter: (f64, f64),
+    pub gravity: f64,
+    pub lifetime: f64,
+    pub symbols: Vec<char>,
+    pub colors: Vec<Color>,
+    /// Particles per second while the emitter is added to a system, unused by [`ParticleSystem::burst`].
+    pub rate: f64,
+}
+
+impl Emitter {
+    /// Sparks flying out of one point and falling down.
+    pub fn explosion(x: f64, y: f64) -> Self {
+        Self {
+            x, y,
+            spread: (0.0, 0.0),
+            velocity: (0.0, -4.0),
+            jitter: (16.0, 8.0),
+            gravity: 12.0,
+            lifetime: 0.8,
+            symbols: vec!['*', '+', '.', '\''],
+            colors: vec![Color::White, Color::Yellow, Color::LightRed, Color::Red, Color::DarkGray],
+            rate: 0.0,
+        }
+    }
+
+    /// A shell bursting into sparks of one color that flash white, then fade.
+    pub fn firework(x: f64, y: f64, color: Color) -> Self {
+        Self {
+            x, y,
+            spread: (0.0, 0.0),
+            velocity: (0.0, -1.0),
+            jitter: (28.0, 14.0), // cells are about twice as high as wide
+            gravity: 5.0,
+            lifetime: 1.4,
+            symbols: vec!['*', '+', '.', '\''],
+            colors: vec![Color::White, color, color, Color::DarkGray],
+            rate: 0.0,
+        }
+    }
+
+    /// Colorful bits drifting down from a `width` wide line.
+    pub fn confetti(width: f64) -> Self {
+        Self {
+            x: 0.0, y: 0.0,
+            spread: (width, 1.0),
+            velocity: (0.0, 2.0),
+            jitter: (6.0, 4.0),
+            gravity: 4.0,
+            lifetime: 2.5,
+            symbols: vec!['*', '~', 'o', '+', '\''],
+            colors: vec![Color::LightMagenta, Color::LightCyan, Color::LightYellow, Color::LightGreen],
+            rate: 0.0,
+        }
+    }
+
+    /// Fast streaks falling from a `width` wide line, for as long as `height` allows.
+    pub fn rain(width: f64, height: f64) -> Self {
+        Self {
+            x: 0.0, y: 0.0,
+            spread: (width, 0.0),
+            velocity: (-2.0, 24.0),
+            jitter: (0.5, 6.0),
+            gravity: 0.0,
+            lifetime: height / 24.0,
+            symbols: vec!['|', '/', '\''],
+            colors: vec![Color::LightBlue, Color::Blue],
+            rate: width / 2.0,
+        }
+    }
+
+    /// Slowly drifting flakes, see [`Emitter::rain`].
+    pub fn snow(width: f64, height: f64) -> Self {
+        Self {
+            x: 0.0, y: 0.0,
+            spread: (width, 0.0),
+            velocity: (0.0, 3.0),
+            jitter: (2.0, 1.0),
+            gravity: 0.0,
+            lifetime: height / 3.0,
+            symbols: vec!['*', '.', '+'],
+            colors: vec![Color::White, Color::Gray],
+            rate: width / 8.0,
+        }
+    }
+
+    fn spawn(&self, rng: &mut impl Rng) -> Particle {
+        let mut jittered = |base: f64, range: f64| if range > 0.0 { base + rng.random_range(-range / 2.0..range / 2.0) } else { base };
+        Particle {
+            x: jittered(self.x + self.spread.0 / 2.0, self.spread.0),
+            y: jittered(self.y + self.spread.1 / 2.0, self.spread.1),
+            vx: jittered(self.velocity.0, self.jitter.0),
+            vy: jittered(self.velocity.1, self.jitter.1),
+            gravity: self.gravity,
+            age: 0.0,
+            lifetime: self.lifetime.max(0.01),
+            symbol: self.symbols.get(rng.random_range(0..self.symbols.len().max(1))).copied().unwrap_or('*'),
+            colors: self.colors.clone(),
+        }
+    }
+}
+
+/// Particles moved by [`tick`](ParticleSystem::tick) and drawn over whatever is already in the buffer.
+/// With reduce motion enabled nothing is spawned.
+///
+/// ```ignore
+/// let mut sparks = ParticleSystem::new();
+/// sparks.burst(&Emitter::explosion(10.0, 5.0), 40);
+/// sparks.tick(dt);
+/// (&sparks).render(area, buf);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    emitters: Vec<(Emitter, f64)>, // with the particles owed since the last tick
+    rng: GameRng,
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self { particles: Vec::new(), emitters: Vec::new(), rng: random::rng() }
+    }
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scatter the particles with `rng` instead of one from [`random::rng`].
+    pub fn with_rng(mut self, rng: GameRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Spawn `count` particles at once.
+    pub fn burst(&mut self, emitter: &Emitter, count: usize) {
+        if config::get().reduce_motion {
+            return;
+        }
+        let rng = &mut self.rng;
+        self.particles.extend((0..count).map(|_| emitter.spawn(rng)));
+    }
+
+    /// Keep spawning particles at the emitter's rate until [`clear_emitters`](ParticleSystem::clear_emitters).
+    pub fn add_emitter(&mut self, emitter: Emitter) {
+        self.emitters.push((emitter, 0.0));
+    }
+
+    pub fn emitters_mut(&mut self) -> impl Iterator<Item = &mut Emitter> {
+        self.emitters.iter_mut().map(|(emitter, _)| emitter)
+    }
+
+    pub fn clear_emitters(&mut self) {
+        self.emitters.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.emitters.clear();
+    }
+
+    /// Nothing left to draw or spawn.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty() && self.emitters.is_empty()
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        for particle in &mut self.particles {
+            particle.vy += particle.gravity * dt;
+            particle.x += particle.vx * dt;
+            particle.y += particle.vy * dt;
+            particle.age += dt;
+        }
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+
+        if self.emitters.is_empty() || config::get().reduce_motion {
+            return;
+        }
+        for (emitter, owed) in &mut self.emitters {
+            *owed += emitter.rate * dt;
+            while *owed >= 1.0 {
+                *owed -= 1.0;
+                self.particles.push(emitter.spawn(&mut self.rng));
+            }
+        }
+    }
+}
+
+impl Widget for &ParticleSystem {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for particle in &self.particles {
+            if particle.x < 0.0 || particle.y < 0.0 || particle.x >= area.width as f64 || particle.y >= area.height as f64 {
+                continue;
+            }
+            let (x, y) = (area.x + particle.x as u16, area.y + particle.y as u16);
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_char(particle.symbol).set_fg(particle.color());
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Randomness of the games and effects.
+//!
+//! Everything random keeps its own [`GameRng`], made by [`rng`] unless one is handed to its
+//! constructor. They come from the operating system, or, once a seed is set with [`set_seed`]
+//! (`--seed <n>` or `$HACKERMAN_SEED`), from that seed, so a session can be played again
+//! exactly, e.g. to track down a bug.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::Mutex;
+
+pub type GameRng = StdRng;
+
+/// The seed of the session and the generators made from it so far.
+static SEED: Mutex<Option<(u64, u64)>> = Mutex::new(None);
+
+/// Make every generator from now on reproducible from `seed`.
+pub fn set_seed(seed: u64) {
+    *SEED.lock().unwrap() = Some((seed, 0));
+}
+
+/// `--seed <n>` on the command line or `$HACKERMAN_SEED`, see [`set_seed`].
+pub fn install_from_args() {
+    let args: Vec<String> = std::env::args().collect();
+    let from_args = args.windows(2).find(|pair| pair[0] == "--seed").map(|pair| pair[1].clone());
+    let seed = from_args.or_else(|| std::env::var("HACKERMAN_SEED").ok()).and_then(|seed| seed.trim().parse().ok());
+    if let Some(seed) = seed {
+        set_seed(seed);
+    }
+}
+
+/// A generator for one game or effect. With a seed set, every call gets a different one, in the
+/// same order on every run.
+pub fn rng() -> GameRng {
+    let mut seed = SEED.lock().unwrap();
+    match seed.as_mut() {
+        Some((seed, made)) => {
+            *made += 1;
+            seeded(seed.wrapping_add(made.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+        }
+        None => StdRng::from_os_rng(),
+    }
+}
+
+/// A generator that always draws the same numbers from `seed`.
+pub fn seeded(seed: u64) -> GameRng {
+    StdRng::seed_from_u64(seed)
+}
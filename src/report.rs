@@ -0,0 +1,310 @@
+use crate::stats;
+use crate::storage::{self, RoundRecord};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The rounds of one play session, rendered as a Markdown report for notes or a training journal.
+pub struct SessionReport<'a> {
+    pub game: &'a str,
+    pub rounds: &'a [RoundRecord],
+    pub score: u32,
+    pub max_streak: u32,
+}
+
+impl SessionReport<'_> {
+    pub fn to_markdown(&self) -> String {
+        let started = self.rounds.first().map(|round| round.played_at).unwrap_or_else(storage::unix_now);
+        let correct = self.rounds.iter().filter(|round| round.correct).count();
+        let times: Vec<f64> = self.rounds.iter().map(|round| round.answer_time_ms as f64 / 1000.0).collect();
+        let average_time = if times.is_empty() { 0.0 } else { times.iter().sum::<f64>() / times.len() as f64 };
+
+        let mut md = format!("# {} session, {}\n\n", self.game, format_timestamp(started));
+        md.push_str("| Rounds | Correct | Accuracy | Score | Best streak | Avg. answer time |\n");
+        md.push_str("|---:|---:|---:|---:|---:|---:|\n");
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {:.1}s |\n\n",
+            self.rounds.len(), correct, percent(correct, self.rounds.len()), self.score, self.max_streak, average_time,
+        ));
+
+        let mut modes: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for round in self.rounds {
+            let (total, right) = modes.entry(&round.mode).or_default();
+            *total += 1;
+            if round.correct { *right += 1; }
+        }
+        if modes.len() > 1 {
+            md.push_str("## Accuracy by mode\n\n| Mode | Rounds | Accuracy |\n|---|---:|---:|\n");
+            for (mode, (total, right)) in &modes {
+                md.push_str(&format!("| {} | {} | {} |\n", mode, total, percent(*right, *total)));
+            }
+            md.push('\n');
+        }
+
+        if !self.rounds.is_empty() {
+            let results: String = self.rounds.iter().map(|round| if round.correct { '█' } else { '▁' }).collect();
+            md.push_str("## Trend\n\n```\n");
+            md.push_str(&format!("answer time  {}\n", sparkline(&times)));
+            md.push_str(&format!("correct      {}\n", results));
+            md.push_str("```\n\n");
+        }
+
+        md.push_str("## Rounds\n\n| # | Mode | Expected | Given | Result | Time |\n|---:|---|---:|---:|---|---:|\n");
+        for (i, round) in self.rounds.iter().enumerate() {
+            let result = if round.correct { "correct" } else if round.given == "-" { "timeout" } else { "wrong" };
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {:.1}s |\n",
+                i + 1, round.mode, round.expected, round.given, result, round.answer_time_ms as f64 / 1000.0,
+            ));
+        }
+        md
+    }
+
+    /// Write the report to `<data dir>/reports/` and return its path.
+    pub fn export(&self) -> io::Result<PathBuf> {
+        let dir = storage::data_dir().join("reports");
+        std::fs::create_dir_all(&dir)?;
+        let slug = self.game.to_lowercase().replace(' ', "-");
+        let path = dir.join(format!("{}-{}.md", slug, storage::unix_now()));
+        std::fs::write(&path, self.to_markdown())?;
+        Ok(path)
+    }
+}
+
+/// Every run of a session as a machine-readable results file, see [`SessionResults::write`].
+#[derive(Clone)]
+pub struct SessionResults {
+    pub game: String,
+    pub runs: Vec<RunResults>,
+}
+
+/// One run of a session: its rounds and how it ended.
+#[derive(Clone)]
+pub struct RunResults {
+    pub mode: String,
+    pub score: u32,
+    pub max_streak: u32,
+    pub finished: bool, // false for a run left or still going when the file was written
+    pub rounds: Vec<RoundRecord>,
+}
+
+impl RunResults {
+    fn correct(&self) -> usize {
+        self.rounds.iter().filter(|round| round.correct).count()
+    }
+
+    fn accuracy(&self) -> f64 {
+        if self.rounds.is_empty() { 0.0 } else { self.correct() as f64 / self.rounds.len() as f64 }
+    }
+}
+
+impl SessionResults {
+    pub fn new(game: &str) -> Self {
+        Self { game: game.to_string(), runs: Vec::new() }
+    }
+
+    /// Timestamps are Unix seconds, answer times milliseconds and accuracy a fraction of 1.
+    pub fn to_json(&self) -> String {
+        let rounds = self.runs.iter().flat_map(|run| &run.rounds);
+        let session = JsonSession {
+            game: &self.game,
+            started_at: rounds.clone().map(|round| round.played_at).min().unwrap_or(0),
+            ended_at: rounds.map(|round| round.played_at).max().unwrap_or(0),
+            runs: self.runs.iter().map(|run| JsonRun {
+                mode: &run.mode,
+                started_at: run.rounds.first().map_or(0, |round| round.played_at),
+                ended_at: run.rounds.last().map_or(0, |round| round.played_at),
+                finished: run.finished,
+                score: run.score,
+                max_streak: run.max_streak,
+                answered: run.rounds.len(),
+                correct: run.correct(),
+                accuracy: round_to_thousandths(run.accuracy()),
+                rounds: run.rounds.iter().map(|round| JsonRound {
+                    mode: &round.mode,
+                    played_at: round.played_at,
+                    correct: round.correct,
+                    answer_time_ms: round.answer_time_ms,
+                    expected: &round.expected,
+                    given: &round.given,
+                }).collect(),
+            }).collect(),
+        };
+        serde_json::to_string_pretty(&session).expect("plain structs always serialize") + "\n"
+    }
+
+    /// One line per round, with the score, best streak and accuracy of its run repeated on each.
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+        let header = ["run", "round", "mode", "played_at", "correct", "answer_time_ms", "expected", "given", "run_score", "run_max_streak", "run_accuracy", "run_finished"];
+        csv.write_record(header).expect("writing to memory cannot fail");
+        for (i, run) in self.runs.iter().enumerate() {
+            for (j, round) in run.rounds.iter().enumerate() {
+                csv.serialize(CsvRow {
+                    run: i + 1,
+                    round: j + 1,
+                    mode: &round.mode,
+                    played_at: round.played_at,
+                    correct: round.correct,
+                    answer_time_ms: round.answer_time_ms,
+                    expected: &round.expected,
+                    given: &round.given,
+                    run_score: run.score,
+                    run_max_streak: run.max_streak,
+                    run_accuracy: round_to_thousandths(run.accuracy()),
+                    run_finished: run.finished,
+                }).expect("writing to memory cannot fail");
+            }
+        }
+        String::from_utf8(csv.into_inner().expect("writing to memory cannot fail")).expect("written from strings")
+    }
+
+    /// Write to `path`: CSV if it ends in `.csv`, JSON otherwise.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let csv = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, if csv { self.to_csv() } else { self.to_json() })
+    }
+}
+
+/// The layout of [`SessionResults::to_json`].
+#[derive(Serialize)]
+struct JsonSession<'a> {
+    game: &'a str,
+    started_at: u64,
+    ended_at: u64,
+    runs: Vec<JsonRun<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    mode: &'a str,
+    started_at: u64,
+    ended_at: u64,
+    finished: bool,
+    score: u32,
+    max_streak: u32,
+    answered: usize,
+    correct: usize,
+    accuracy: f64,
+    rounds: Vec<JsonRound<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonRound<'a> {
+    mode: &'a str,
+    played_at: u64,
+    correct: bool,
+    answer_time_ms: u64,
+    expected: &'a str,
+    given: &'a str,
+}
+
+/// A line of [`SessionResults::to_csv`], in the order of its header.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    run: usize,
+    round: usize,
+    mode: &'a str,
+    played_at: u64,
+    correct: bool,
+    answer_time_ms: u64,
+    expected: &'a str,
+    given: &'a str,
+    run_score: u32,
+    run_max_streak: u32,
+    run_accuracy: f64,
+    run_finished: bool,
+}
+
+fn round_to_thousandths(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+fn percent(part: usize, total: usize) -> String {
+    if total == 0 { "-".to_string() } else { format!("{:.0}%", part as f64 * 100.0 / total as f64) }
+}
+
+/// One block character per value, scaled between the smallest and largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = (max - min).max(f64::EPSILON);
+    values.iter()
+        .map(|value| SPARK_LEVELS[(((value - min) / range) * (SPARK_LEVELS.len() - 1) as f64).round() as usize])
+        .collect()
+}
+
+/// `2026-10-16 14:03 UTC`
+pub fn format_timestamp(unix: u64) -> String {
+    let (days, seconds) = (unix / 86_400, unix % 86_400);
+    let (year, month, day) = stats::civil_from_days(days as i64);
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, seconds / 3600, seconds / 60 % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(mode: &str, played_at: u64, correct: bool, expected: &str, given: &str) -> RoundRecord {
+        RoundRecord { game: "Trivia".to_string(), mode: mode.to_string(), played_at, correct, answer_time_ms: 1500, expected: expected.to_string(), given: given.to_string() }
+    }
+
+    fn results() -> SessionResults {
+        SessionResults {
+            game: "Trivia \"Deluxe\"".to_string(),
+            runs: vec![
+                RunResults {
+                    mode: "hard".to_string(), score: 20, max_streak: 2, finished: true,
+                    rounds: vec![
+                        round("hard", 100, true, "Ada, Countess of Lovelace", "Ada, Countess of Lovelace"),
+                        round("hard", 130, true, "say \"hi\"", "say \"hi\""),
+                        round("hard", 160, false, "line\nbreak\ttab\\", "-"),
+                    ],
+                },
+                RunResults { mode: "easy".to_string(), score: 0, max_streak: 0, finished: false, rounds: Vec::new() },
+            ],
+        }
+    }
+
+    #[test]
+    fn json_escapes_text_and_keeps_the_numbers() {
+        let json: serde_json::Value = serde_json::from_str(&results().to_json()).unwrap();
+        assert_eq!(json["game"], "Trivia \"Deluxe\"");
+        assert_eq!((json["started_at"].as_u64(), json["ended_at"].as_u64()), (Some(100), Some(160)));
+        let run = &json["runs"][0];
+        assert_eq!((run["answered"].as_u64(), run["correct"].as_u64(), run["accuracy"].as_f64()), (Some(3), Some(2), Some(0.667)));
+        assert_eq!(run["rounds"][2]["expected"], "line\nbreak\ttab\\");
+        assert_eq!(run["rounds"][2]["given"], "-");
+        assert_eq!(json["runs"][1]["rounds"].as_array().map(Vec::len), Some(0));
+        assert_eq!(json["runs"][1]["started_at"].as_u64(), Some(0));
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators_quotes_and_line_breaks() {
+        let csv = results().to_csv();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        assert_eq!(reader.headers().unwrap().iter().collect::<Vec<_>>(), [
+            "run", "round", "mode", "played_at", "correct", "answer_time_ms", "expected", "given", "run_score", "run_max_streak", "run_accuracy", "run_finished",
+        ]);
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(&rows[0][6], "Ada, Countess of Lovelace");
+        assert_eq!(&rows[1][6], "say \"hi\"");
+        assert_eq!(rows[2].iter().collect::<Vec<_>>(), ["1", "3", "hard", "160", "false", "1500", "line\nbreak\ttab\\", "-", "20", "2", "0.667", "true"]);
+        assert!(csv.contains("\"say \"\"hi\"\"\""));
+    }
+
+    #[test]
+    fn empty_sessions_still_have_a_header() {
+        let empty = SessionResults::new("Snake");
+        assert_eq!(empty.to_csv().lines().count(), 1);
+        let json: serde_json::Value = serde_json::from_str(&empty.to_json()).unwrap();
+        assert_eq!(json["runs"].as_array().map(Vec::len), Some(0));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::storage::{self, migration::{self, Schema}};
+use std::collections::BTreeMap;
+
+const SCHEMA: Schema = Schema { namespace: "training", key: "review", steps: &[migration::unversioned] };
+
+const DAY: u64 = 86_400;
+/// Ease of a card never answered: each review multiplies its interval by this much.
+const START_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+const MAX_EASE: f64 = 3.0;
+
+/// When one card comes back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CardSchedule {
+    pub ease: f64,
+    pub interval_days: u32,
+    pub due_at: u64, // unix seconds
+}
+
+impl CardSchedule {
+    /// The schedule after one more answer, a simplified SM-2: recalled cards come back after a
+    /// growing number of days, forgotten ones are due again right away and grow more slowly from then on.
+    fn answered(self, recalled: bool, now: u64) -> Self {
+        let (ease, interval_days) = match (recalled, self.interval_days) {
+            (false, _) => ((self.ease - 0.2).max(MIN_EASE), 0),
+            (true, 0) => (self.ease, 1),
+            (true, 1) => (self.ease, 3),
+            (true, days) => ((self.ease + 0.05).min(MAX_EASE), (days as f64 * self.ease).round() as u32),
+        };
+        Self { ease, interval_days, due_at: now + interval_days as u64 * DAY }
+    }
+}
+
+/// Spaced repetition state of every card of every deck, stored as `Deck|front=ease/interval@due` lines.
+/// Cards never answered are not stored; they are new and due right away.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReviewSchedule {
+    cards: BTreeMap<(String, String), CardSchedule>,
+}
+
+impl ReviewSchedule {
+    pub fn load() -> Self {
+        let contents = migration::load(&SCHEMA).ok().flatten().unwrap_or_default();
+        let cards = contents.lines()
+            .filter_map(|line| {
+                let (deck, rest) = line.split_once('|')?;
+                let (front, schedule) = rest.rsplit_once('=')?;
+                let (ease, rest) = schedule.split_once('/')?;
+                let (interval_days, due_at) = rest.split_once('@')?;
+                let schedule = CardSchedule { ease: ease.parse().ok()?, interval_days: interval_days.parse().ok()?, due_at: due_at.parse().ok()? };
+                Some(((deck.to_string(), front.to_string()), schedule))
+            })
+            .collect();
+        Self { cards }
+    }
+
+    pub fn save(&self) {
+        let data: String = self.cards.iter()
+            .map(|((deck, front), card)| format!("{}|{}={:.2}/{}@{}\n", deck, front, card.ease, card.interval_days, card.due_at))
+            .collect();
+        let _ = migration::save(&SCHEMA, &data);
+    }
+
+    pub fn get(&self, deck: &str, front: &str) -> Option<CardSchedule> {
+        self.cards.get(&(deck.to_string(), front.to_string())).copied()
+    }
+
+    /// Whether the card should be asked now; new cards always are.
+    pub fn is_due(&self, deck: &str, front: &str) -> bool {
+        self.get(deck, front).is_none_or(|card| card.due_at <= storage::unix_now())
+    }
+
+    pub fn answer(&mut self, deck: &str, front: &str, recalled: bool) {
+        let card = self.get(deck, front).unwrap_or(CardSchedule { ease: START_EASE, interval_days: 0, due_at: 0 });
+        self.cards.insert((deck.to_string(), front.to_string()), card.answered(recalled, storage::unix_now()));
+    }
+
+    /// Seconds until the first of `fronts` is due, `None` when one of them already is.
+    pub fn next_due(&self, deck: &str, fronts: &[&str]) -> Option<u64> {
+        let now = storage::unix_now();
+        let soonest = fronts.iter().map(|front| self.get(deck, front).map_or(now, |card| card.due_at)).min()?;
+        (soonest > now).then(|| soonest - now)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::games::main_screen_widget::MainScreenWidget;
+use crate::storage::migration::{self, Schema};
+use crate::storage;
+
+/// Periodic checkpoint of the running game, removed again when the game is closed normally.
+/// If it is still there on the next launch, the previous session ended in a crash or kill.
+pub const AUTOSAVE: Schema = Schema { namespace: "saves", key: "autosave", steps: &[migration::unversioned] };
+
+/// Snapshot taken when a game is left with Esc or by quitting, offered as "Resume last session".
+pub const SESSION: Schema = Schema { namespace: "saves", key: "session", steps: &[migration::unversioned] };
+
+/// Seconds between two autosave checkpoints.
+pub const AUTOSAVE_INTERVAL: f64 = 5.0;
+
+/// A snapshot of a game as registered in the [`GameRegistry`](crate::games::registry::GameRegistry).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub game: String,
+    pub summary: String,
+    pub state: String,
+}
+
+impl Checkpoint {
+    const SEPARATOR: &'static str = "---\n";
+
+    fn serialize(&self) -> String {
+        format!("game={}\nsummary={}\n{}{}", self.game, self.summary, Self::SEPARATOR, self.state)
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let (header, state) = contents.split_once(Self::SEPARATOR)?;
+        let mut game = None;
+        let mut summary = String::new();
+        for line in header.lines() {
+            match line.split_once('=') {
+                Some(("game", value)) => game = Some(value.to_string()),
+                Some(("summary", value)) => summary = value.to_string(),
+                _ => {}
+            }
+        }
+        Some(Self { game: game?, summary, state: state.to_string() })
+    }
+}
+
+pub fn store(schema: &Schema, checkpoint: &Checkpoint) {
+    let _ = migration::save(schema, &checkpoint.serialize());
+}
+
+pub fn load(schema: &Schema) -> Option<Checkpoint> {
+    migration::load(schema).ok().flatten().and_then(|contents| Checkpoint::parse(&contents))
+}
+
+pub fn clear(schema: &Schema) {
+    let _ = storage::remove(schema.namespace, schema.key);
+}
+
+/// Checkpoint of `widget` under the registry name `game`, if the widget supports saving right now.
+pub fn checkpoint_of(game: &str, widget: &dyn MainScreenWidget) -> Option<Checkpoint> {
+    widget.save_state().map(|state| Checkpoint { game: game.to_string(), summary: state.summary, state: state.data })
+}
+
+/// Keeps the save slots of a running app: the periodic [`AUTOSAVE`] and the [`SESSION`] snapshot.
+pub struct SaveManager {
+    autosave_timer: f64,
+    last_session: Option<Checkpoint>,
+}
+
+impl SaveManager {
+    pub fn new() -> Self {
+        Self { autosave_timer: 0.0, last_session: load(&SESSION) }
+    }
+
+    pub fn last_session(&self) -> Option<&Checkpoint> {
+        self.last_session.as_ref()
+    }
+
+    /// Hand out the session snapshot for resuming; it is removed so it cannot be resumed twice.
+    pub fn take_last_session(&mut self) -> Option<Checkpoint> {
+        clear(&SESSION);
+        self.last_session.take()
+    }
+
+    /// Reset the autosave interval, called whenever a game is launched.
+    pub fn started(&mut self) {
+        self.autosave_timer = 0.0;
+    }
+
+    /// Autosave the running game every [`AUTOSAVE_INTERVAL`] seconds. Returns true when it saved.
+    pub fn tick(&mut self, dt: f64, game: &str, widget: &dyn MainScreenWidget) -> bool {
+        self.autosave_timer += dt;
+        if self.autosave_timer < AUTOSAVE_INTERVAL {
+            return false;
+        }
+        self.autosave_timer = 0.0;
+        match checkpoint_of(game, widget) {
+            Some(checkpoint) => store(&AUTOSAVE, &checkpoint),
+            None => clear(&AUTOSAVE),
+        }
+        true
+    }
+
+    /// The game is being left on purpose: keep it as the session to resume and drop the autosave.
+    pub fn snapshot(&mut self, game: &str, widget: &dyn MainScreenWidget) {
+        clear(&AUTOSAVE);
+        if let Some(checkpoint) = checkpoint_of(game, widget) {
+            store(&SESSION, &checkpoint);
+            self.last_session = Some(checkpoint);
+        }
+    }
+
+    /// The game was played to its end: neither it nor an older session is offered for resuming.
+    pub fn finished(&mut self) {
+        clear(&AUTOSAVE);
+        clear(&SESSION);
+        self.last_session = None;
+    }
+}
@@ -0,0 +1,34 @@
+use crate::music::Tune;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+
+/// Something that happened in a menu or game which may deserve a sound.
+///
+/// Games only ever emit these; whether and how they are played is up to the listener
+/// (the `audio` module when the `audio` feature is enabled).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SoundEvent {
+    MenuMove,
+    MenuSelect,
+    Correct,
+    Incorrect,
+    Timeout,
+    GameOver,
+    /// Switch the background tune; `None` stops the music.
+    Music(Option<Tune>),
+}
+
+static SENDER: Mutex<Option<Sender<SoundEvent>>> = Mutex::new(None);
+
+/// Connect a listener. Events emitted while nobody listens are dropped.
+pub fn subscribe() -> Receiver<SoundEvent> {
+    let (sender, receiver) = channel();
+    *SENDER.lock().unwrap() = Some(sender);
+    receiver
+}
+
+pub fn emit(event: SoundEvent) {
+    if let Some(sender) = SENDER.lock().unwrap().as_ref() {
+        let _ = sender.send(event);
+    }
+}
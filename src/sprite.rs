@@ -0,0 +1,182 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+/// Multi-line text art with optional animation frames. Spaces are transparent,
+/// so sprites can overlap each other and the background.
+///
+/// ```ignore
+/// let dino = Sprite::animated(&[DINO_LEFT_FOOT, DINO_RIGHT_FOOT], 8.0).style(Style::new().green());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sprite {
+    frames: Vec<Vec<Vec<char>>>, // frame, row, column
+    seconds_per_frame: f64,
+    elapsed: f64,
+    style: Style,
+}
+
+impl Sprite {
+    pub fn new(art: &str) -> Self {
+        Self::animated(&[art], 1.0)
+    }
+
+    /// Frames cycle at `fps`; a leading newline in each frame is ignored, so raw string literals
+    /// can start on their own line.
+    pub fn animated(frames: &[&str], fps: f64) -> Self {
+        let frames = frames.iter()
+            .map(|frame| frame.strip_prefix('\n').unwrap_or(frame).lines().map(|row| row.chars().collect()).collect())
+            .collect();
+        Self { frames, seconds_per_frame: 1.0 / fps.max(0.01), elapsed: 0.0, style: Style::default() }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn width(&self) -> u16 {
+        self.frames.iter().flatten().map(|row| row.len()).max().unwrap_or(0) as u16
+    }
+
+    pub fn height(&self) -> u16 {
+        self.frames.iter().map(|frame| frame.len()).max().unwrap_or(0) as u16
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.elapsed += dt;
+    }
+
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    fn current_frame(&self) -> &[Vec<char>] {
+        let index = (self.elapsed / self.seconds_per_frame) as usize % self.frames.len().max(1);
+        self.frames.get(index).map_or(&[], |frame| frame)
+    }
+
+    /// Draw the top left corner at `(x, y)` cells inside `area`, rounded to the nearest cell.
+    /// Whatever lies outside `area` is clipped.
+    pub fn render_at(&self, x: f64, y: f64, area: Rect, buf: &mut Buffer) {
+        let (left, top) = (x.round() as i32, y.round() as i32);
+        for (row, line) in self.current_frame().iter().enumerate() {
+            for (column, ch) in line.iter().enumerate().filter(|(_, ch)| **ch != ' ') {
+                let (cell_x, cell_y) = (left + column as i32, top + row as i32);
+                if cell_x < 0 || cell_y < 0 || cell_x >= area.width as i32 || cell_y >= area.height as i32 {
+                    continue;
+                }
+                if let Some(cell) = buf.cell_mut((area.x + cell_x as u16, area.y + cell_y as u16)) {
+                    cell.set_char(*ch).set_style(self.style);
+                }
+            }
+        }
+    }
+}
+
+/// Something that moves on the playfield. Coordinates are in cells relative to the area the
+/// [`Entities`] are rendered into and may be fractional, so slow movement stays smooth.
+#[derive(Clone, Debug)]
+pub struct Entity {
+    pub sprite: Sprite,
+    pub x: f64,
+    pub y: f64,
+    pub vx: f64, // cells per second
+    pub vy: f64,
+    pub ay: f64, // acceleration, e.g. gravity for jumps
+    pub alive: bool, // dead entities are removed in the next update
+}
+
+impl Entity {
+    pub fn new(sprite: Sprite, x: f64, y: f64) -> Self {
+        Self { sprite, x, y, vx: 0.0, vy: 0.0, ay: 0.0, alive: true }
+    }
+
+    pub fn with_velocity(mut self, vx: f64, vy: f64) -> Self {
+        self.vx = vx;
+        self.vy = vy;
+        self
+    }
+
+    pub fn with_acceleration(mut self, ay: f64) -> Self {
+        self.ay = ay;
+        self
+    }
+
+    /// Whether the bounding boxes of both sprites share a cell.
+    pub fn overlaps(&self, other: &Entity) -> bool {
+        let (x, y, w, h) = (self.x.round(), self.y.round(), self.sprite.width() as f64, self.sprite.height() as f64);
+        let (ox, oy, ow, oh) = (other.x.round(), other.y.round(), other.sprite.width() as f64, other.sprite.height() as f64);
+        x < ox + ow && ox < x + w && y < oy + oh && oy < y + h
+    }
+}
+
+/// The entities of a game, updated and drawn in insertion order (later ones on top).
+///
+/// Each frame a game calls [`update`](Entities::update) to move and animate everything, applies its
+/// own rules (input, collisions, spawning) on [`iter_mut`](Entities::iter_mut), and renders the list
+/// as a widget.
+#[derive(Clone, Debug, Default)]
+pub struct Entities {
+    entities: Vec<Entity>,
+}
+
+impl Entities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entity, returning its index until entities before it are removed.
+    pub fn spawn(&mut self, entity: Entity) -> usize {
+        self.entities.push(entity);
+        self.entities.len() - 1
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Entity> {
+        self.entities.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Entity> {
+        self.entities.get_mut(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Entity> {
+        self.entities.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Drop dead entities, then move and animate the others by `dt` seconds.
+    pub fn update(&mut self, dt: f64) {
+        self.entities.retain(|entity| entity.alive);
+        for entity in &mut self.entities {
+            entity.vy += entity.ay * dt;
+            entity.x += entity.vx * dt;
+            entity.y += entity.vy * dt;
+            entity.sprite.tick(dt);
+        }
+    }
+}
+
+impl Widget for &Entities {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.entities.iter()
+            .filter(|entity| entity.alive)
+            .for_each(|entity| entity.sprite.render_at(entity.x, entity.y, area, buf));
+    }
+}
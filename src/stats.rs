@@ -0,0 +1,291 @@
+use crate::storage::migration::{self, Schema};
+use crate::storage;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+const SCHEMA: Schema = Schema { namespace: "stats", key: "games", steps: &[migration::unversioned] };
+const ACTIVITY: Schema = Schema { namespace: "stats", key: "activity", steps: &[migration::unversioned] };
+
+/// How many days of activity are kept, enough for a year-long calendar.
+const ACTIVITY_DAYS: i64 = 371;
+
+/// Answers given in one mode of a game, e.g. one bit width of Binary Numbers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModeStats {
+    pub rounds: u32,
+    pub correct: u32,
+}
+
+/// Lifetime statistics of one game, keyed by its registry name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameStats {
+    pub seconds_played: f64,
+    pub sessions: u32,
+    pub rounds: u32,
+    pub correct: u32,
+    pub best_streak: u32,
+    pub modes: BTreeMap<String, ModeStats>,
+    pub patterns: BTreeMap<String, ModeStats>, // answers grouped by what the puzzle looked like, see [`record_pattern`]
+    pub week: i64, // first day of the week the weekly counters belong to, see [`current_week`]
+    pub week_rounds: u32,
+    pub week_sessions: u32,
+    pub day: i64, // the day `day_best_streak` belongs to, see [`today`]
+    pub day_best_streak: u32,
+}
+
+impl GameStats {
+    /// Share of correct answers in percent, `None` before the first round.
+    pub fn accuracy(&self) -> Option<f64> {
+        accuracy(self.correct, self.rounds)
+    }
+
+    /// Rounds and sessions played in the current week.
+    pub fn this_week(&self) -> (u32, u32) {
+        if self.week == current_week() { (self.week_rounds, self.week_sessions) } else { (0, 0) }
+    }
+
+    /// Longest streak reached today.
+    pub fn best_streak_today(&self) -> u32 {
+        if self.day == today() { self.day_best_streak } else { 0 }
+    }
+
+    fn roll_week(&mut self) {
+        let week = current_week();
+        if self.week != week {
+            self.week = week;
+            self.week_rounds = 0;
+            self.week_sessions = 0;
+        }
+    }
+}
+
+impl ModeStats {
+    pub fn accuracy(&self) -> Option<f64> {
+        accuracy(self.correct, self.rounds)
+    }
+}
+
+fn accuracy(correct: u32, rounds: u32) -> Option<f64> {
+    if rounds == 0 { None } else { Some(correct as f64 * 100.0 / rounds as f64) }
+}
+
+/// Stats of all games. Stored as one `[Game Name]` section per game with `key=value` lines,
+/// per-mode results as `mode.<name>=<correct>/<rounds>` and per-pattern results as `pattern.<name>=<correct>/<rounds>`.
+/// Rounds per day across all games are stored separately as `<day>=<rounds>` lines.
+#[derive(Default)]
+struct Stats {
+    games: BTreeMap<String, GameStats>,
+    activity: BTreeMap<i64, u32>,
+}
+
+impl Stats {
+    fn parse(contents: &str) -> Self {
+        let mut stats = Self::default();
+        let mut current: Option<&mut GameStats> = None;
+        for line in contents.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current = Some(stats.games.entry(name.to_string()).or_default());
+                continue;
+            }
+            let (Some(game), Some((key, value))) = (current.as_deref_mut(), line.split_once('=')) else { continue };
+            match key {
+                "seconds_played" => game.seconds_played = value.parse().unwrap_or(0.0),
+                "sessions" => game.sessions = value.parse().unwrap_or(0),
+                "rounds" => game.rounds = value.parse().unwrap_or(0),
+                "correct" => game.correct = value.parse().unwrap_or(0),
+                "best_streak" => game.best_streak = value.parse().unwrap_or(0),
+                "week" => game.week = value.parse().unwrap_or(0),
+                "week_rounds" => game.week_rounds = value.parse().unwrap_or(0),
+                "week_sessions" => game.week_sessions = value.parse().unwrap_or(0),
+                "day" => game.day = value.parse().unwrap_or(0),
+                "day_best_streak" => game.day_best_streak = value.parse().unwrap_or(0),
+                _ => if let Some((correct, rounds)) = value.split_once('/') {
+                    let mode_stats = ModeStats { rounds: rounds.parse().unwrap_or(0), correct: correct.parse().unwrap_or(0) };
+                    if let Some(mode) = key.strip_prefix("mode.") {
+                        game.modes.insert(mode.to_string(), mode_stats);
+                    } else if let Some(pattern) = key.strip_prefix("pattern.") {
+                        game.patterns.insert(pattern.to_string(), mode_stats);
+                    }
+                },
+            }
+        }
+        stats
+    }
+
+    fn serialize(&self) -> String {
+        let mut data = String::new();
+        for (name, game) in &self.games {
+            data.push_str(&format!("[{}]\n", name));
+            data.push_str(&format!("seconds_played={:.1}\nsessions={}\n", game.seconds_played, game.sessions));
+            data.push_str(&format!("rounds={}\ncorrect={}\nbest_streak={}\n", game.rounds, game.correct, game.best_streak));
+            data.push_str(&format!("week={}\nweek_rounds={}\nweek_sessions={}\n", game.week, game.week_rounds, game.week_sessions));
+            data.push_str(&format!("day={}\nday_best_streak={}\n", game.day, game.day_best_streak));
+            for (mode, mode_stats) in &game.modes {
+                data.push_str(&format!("mode.{}={}/{}\n", mode, mode_stats.correct, mode_stats.rounds));
+            }
+            for (pattern, pattern_stats) in &game.patterns {
+                data.push_str(&format!("pattern.{}={}/{}\n", pattern, pattern_stats.correct, pattern_stats.rounds));
+            }
+        }
+        data
+    }
+
+    fn load() -> Self {
+        let mut stats = migration::load(&SCHEMA).ok().flatten().map(|contents| Self::parse(&contents)).unwrap_or_default();
+        stats.activity = migration::load(&ACTIVITY).ok().flatten()
+            .map(|contents| contents.lines()
+                .filter_map(|line| line.split_once('='))
+                .filter_map(|(day, rounds)| Some((day.trim().parse().ok()?, rounds.trim().parse().ok()?)))
+                .collect())
+            .unwrap_or_default();
+        stats
+    }
+
+    fn serialize_activity(&self) -> String {
+        self.activity.iter().map(|(day, rounds)| format!("{}={}\n", day, rounds)).collect()
+    }
+}
+
+static STATS: Mutex<Option<Stats>> = Mutex::new(None);
+
+fn with_stats<R>(action: impl FnOnce(&mut Stats) -> R) -> R {
+    action(STATS.lock().unwrap().get_or_insert_with(Stats::load))
+}
+
+fn with_game<R>(game: &str, action: impl FnOnce(&mut GameStats) -> R) -> R {
+    with_stats(|stats| action(stats.games.entry(game.to_string()).or_default()))
+}
+
+pub fn get(game: &str) -> GameStats {
+    let mut guard = STATS.lock().unwrap();
+    guard.get_or_insert_with(Stats::load).games.get(game).cloned().unwrap_or_default()
+}
+
+/// Stats of every game played so far.
+pub fn all() -> BTreeMap<String, GameStats> {
+    with_stats(|stats| stats.games.clone())
+}
+
+/// Sessions, rounds and correct answers summed over all games; the difference between two calls
+/// is what was played in between.
+pub fn totals() -> (u32, u32, u32) {
+    with_stats(|stats| stats.games.values().fold((0, 0, 0), |(sessions, rounds, correct), game| {
+        (sessions + game.sessions, rounds + game.rounds, correct + game.correct)
+    }))
+}
+
+pub fn record_session(game: &str) {
+    with_game(game, |stats| {
+        stats.roll_week();
+        stats.sessions += 1;
+        stats.week_sessions += 1;
+    });
+}
+
+pub fn add_time(game: &str, seconds: f64) {
+    with_game(game, |stats| stats.seconds_played += seconds);
+}
+
+/// Count one answered round; `streak` is the streak after this answer.
+pub fn record_round(game: &str, mode: &str, correct: bool, streak: u32) {
+    with_stats(|stats| {
+        let today = today();
+        *stats.activity.entry(today).or_default() += 1;
+        stats.activity.retain(|day, _| *day > today - ACTIVITY_DAYS);
+    });
+    with_game(game, |stats| {
+        stats.roll_week();
+        stats.rounds += 1;
+        stats.week_rounds += 1;
+        stats.best_streak = stats.best_streak.max(streak);
+        stats.day_best_streak = if stats.day == today() { stats.day_best_streak.max(streak) } else { streak };
+        stats.day = today();
+        let mode_stats = stats.modes.entry(mode.to_string()).or_default();
+        mode_stats.rounds += 1;
+        if correct {
+            stats.correct += 1;
+            mode_stats.correct += 1;
+        }
+    });
+}
+
+/// Count one answered round under `pattern`, a game-specific name for what the puzzle looked like,
+/// so mistakes can be told apart by more than the mode.
+pub fn record_pattern(game: &str, pattern: &str, correct: bool) {
+    with_game(game, |stats| {
+        let pattern_stats = stats.patterns.entry(pattern.to_string()).or_default();
+        pattern_stats.rounds += 1;
+        if correct { pattern_stats.correct += 1; }
+    });
+}
+
+/// Write the collected stats. Called on checkpoints and when a game closes, not on every change.
+pub fn flush() {
+    if let Some(stats) = STATS.lock().unwrap().as_ref() {
+        let _ = migration::save(&SCHEMA, &stats.serialize());
+        let _ = migration::save(&ACTIVITY, &stats.serialize_activity());
+    }
+}
+
+/// Rounds answered per day (see [`today`]) across all games, for roughly the past year.
+pub fn activity() -> BTreeMap<i64, u32> {
+    with_stats(|stats| stats.activity.clone())
+}
+
+/// `1h 05m`, `12m 30s` or `45s`.
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Days since 1970-01-01 (UTC).
+pub fn today() -> i64 {
+    (storage::unix_now() / 86_400) as i64
+}
+
+/// Days since 1970-01-01 (UTC) of the Monday starting the current week.
+pub fn current_week() -> i64 {
+    today() - weekday(today()) as i64
+}
+
+/// Days left in the current week after today, 0 on Sunday.
+pub fn days_left_in_week() -> u32 {
+    6 - weekday(today())
+}
+
+/// 0 for Monday through 6 for Sunday; 1970-01-01 was a Thursday.
+pub fn weekday(days: i64) -> u32 {
+    (days + 3).rem_euclid(7) as u32
+}
+
+/// Days from 1970-01-01 to a calendar date, the inverse of [`civil_from_days`].
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Calendar date of a day counted from 1970-01-01 (proleptic Gregorian, H. Hinnant's algorithm).
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,155 @@
+use super::{unix_now, BACKUPS};
+use std::fmt::{Display, Formatter};
+
+/// First line of every versioned record, e.g. `version=2`.
+const HEADER: &str = "version=";
+
+/// Upgrades the body of a record by exactly one version.
+pub type Step = fn(&str) -> Result<String, String>;
+
+/// Describes one persisted record and how to upgrade it.
+pub struct Schema {
+    pub namespace: &'static str,
+    pub key: &'static str,
+    /// `steps[n]` turns version `n` into version `n + 1`. Data without a header is version 0,
+    /// so the current version is always `steps.len()`. Only ever append to this list.
+    pub steps: &'static [Step],
+}
+
+impl Schema {
+    pub fn version(&self) -> u32 { self.steps.len() as u32 }
+}
+
+/// First step of every schema: data written before versioning existed is taken over unchanged.
+pub fn unversioned(body: &str) -> Result<String, String> { Ok(body.to_string()) }
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// Written by a newer build; left alone so that build can still read it.
+    TooNew { found: u32, supported: u32 },
+    StepFailed { from: u32, reason: String },
+    Io(std::io::Error),
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::TooNew { found, supported } => write!(f, "data has version {found}, this build supports up to {supported}"),
+            MigrationError::StepFailed { from, reason } => write!(f, "upgrade from version {from} failed: {reason}"),
+            MigrationError::Io(e) => write!(f, "could not write migrated data: {e}"),
+        }
+    }
+}
+
+fn split_version(raw: &str) -> (u32, &str) {
+    let (first, rest) = raw.split_once('\n').unwrap_or((raw, ""));
+    match first.strip_prefix(HEADER).and_then(|v| v.trim().parse().ok()) {
+        Some(version) => (version, rest),
+        None => (0, raw),
+    }
+}
+
+/// Load a record and bring it to the current version.
+///
+/// Whenever the stored version differs from the current one, the raw data is copied to the
+/// backups namespace before anything else happens. On failure the original record is not
+/// touched; callers fall back to defaults and the backup stays around for recovery.
+pub fn load(schema: &Schema) -> Result<Option<String>, MigrationError> {
+    match super::load(schema.namespace, schema.key) {
+        Some(raw) => upgrade(schema, &raw).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Same as [`load`] for data that came from somewhere else, e.g. a file from an older release.
+/// The upgraded data is stored under the schema's key.
+pub fn upgrade(schema: &Schema, raw: &str) -> Result<String, MigrationError> {
+    let (version, body) = split_version(raw);
+    if version == schema.version() {
+        return Ok(body.to_string());
+    }
+
+    backup(schema, version, raw).map_err(MigrationError::Io)?;
+    if version > schema.version() {
+        return Err(MigrationError::TooNew { found: version, supported: schema.version() });
+    }
+
+    let mut data = body.to_string();
+    for (from, step) in schema.steps.iter().enumerate().skip(version as usize) {
+        data = step(&data).map_err(|reason| MigrationError::StepFailed { from: from as u32, reason })?;
+    }
+    save(schema, &data).map_err(MigrationError::Io)?;
+    Ok(data)
+}
+
+/// Store a record tagged with the current schema version.
+pub fn save(schema: &Schema, body: &str) -> std::io::Result<()> {
+    super::save(schema.namespace, schema.key, &format!("{}{}\n{}", HEADER, schema.version(), body))
+}
+
+fn backup(schema: &Schema, version: u32, raw: &str) -> std::io::Result<()> {
+    let key = format!("{}.{}.v{}.{}", schema.namespace, schema.key, version, unix_now());
+    super::save(BACKUPS, &key, raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage;
+
+    fn double(body: &str) -> Result<String, String> { Ok(format!("{body}{body}")) }
+    fn refuse(_: &str) -> Result<String, String> { Err("no can do".to_string()) }
+
+    const DOUBLED: Schema = Schema { namespace: "migration-tests", key: "doubled", steps: &[unversioned, double] };
+
+    /// Backups of `key` in the migration tests, with the timestamp cut off.
+    fn backups(key: &str) -> Vec<String> {
+        storage::list(BACKUPS).into_iter()
+            .filter(|backup| backup.starts_with(&format!("migration-tests.{key}.")))
+            .map(|backup| backup.rsplit_once('.').unwrap().0.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn old_records_are_upgraded_stored_and_backed_up() {
+        storage::install_for_tests();
+        let schema = Schema { key: "old", ..DOUBLED };
+        storage::save(schema.namespace, schema.key, "ab").unwrap();
+        assert_eq!(load(&schema).unwrap().as_deref(), Some("abab"));
+        assert_eq!(storage::load(schema.namespace, schema.key).as_deref(), Some("version=2\nabab"));
+        assert_eq!(backups("old"), ["migration-tests.old.v0"]);
+        // a version 1 record only takes the steps after it
+        storage::save(schema.namespace, schema.key, "version=1\nxy").unwrap();
+        assert_eq!(load(&schema).unwrap().as_deref(), Some("xyxy"));
+    }
+
+    #[test]
+    fn current_records_are_read_as_they_are() {
+        storage::install_for_tests();
+        let schema = Schema { key: "current", ..DOUBLED };
+        assert_eq!(load(&schema).unwrap(), None);
+        save(&schema, "line one\nversion=9").unwrap();
+        assert_eq!(load(&schema).unwrap().as_deref(), Some("line one\nversion=9"));
+        assert!(backups("current").is_empty());
+    }
+
+    #[test]
+    fn newer_records_are_left_alone() {
+        storage::install_for_tests();
+        let schema = Schema { key: "newer", ..DOUBLED };
+        storage::save(schema.namespace, schema.key, "version=3\nfrom the future").unwrap();
+        assert!(matches!(load(&schema), Err(MigrationError::TooNew { found: 3, supported: 2 })));
+        assert_eq!(storage::load(schema.namespace, schema.key).as_deref(), Some("version=3\nfrom the future"));
+    }
+
+    #[test]
+    fn failed_steps_keep_the_original() {
+        storage::install_for_tests();
+        let schema = Schema { namespace: "migration-tests", key: "failing", steps: &[unversioned, refuse] };
+        storage::save(schema.namespace, schema.key, "precious").unwrap();
+        let error = load(&schema).unwrap_err();
+        assert_eq!(error.to_string(), "upgrade from version 1 failed: no can do");
+        assert_eq!(storage::load(schema.namespace, schema.key).as_deref(), Some("precious"));
+        assert_eq!(backups("failing"), ["migration-tests.failing.v0"]);
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use profiles::Profile;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH}; // std's clock panics in the browser
+
+pub mod migration;
+pub mod profiles;
+pub mod scores;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// Namespace holding persisted high scores.
+pub const SCORES: &str = "scores";
+/// Copies of records taken before they were migrated.
+pub const BACKUPS: &str = "backups";
+
+/// Persistence backend used by everything that reads or writes user data.
+///
+/// Data is addressed by a namespace (scores, stats, saves, ...) and a key inside it.
+pub trait Storage: Send {
+    fn load(&self, namespace: &str, key: &str) -> Option<String>;
+    fn save(&mut self, namespace: &str, key: &str, data: &str) -> std::io::Result<()>;
+    fn remove(&mut self, namespace: &str, key: &str) -> std::io::Result<()>;
+
+    /// All keys stored in a namespace, sorted.
+    fn list(&self, namespace: &str) -> Vec<String>;
+
+    /// Whether data written to this backend is kept; false only for the guest backend, which
+    /// keeps nothing at all.
+    fn is_persistent(&self) -> bool { true }
+}
+
+/// Stores every key as a plain file at `<root>/<namespace>/<key>`.
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The directory of the profile in use, see [`profile_dir`].
+    pub fn default_location() -> Self {
+        Self::new(profile_dir())
+    }
+
+    fn path(&self, namespace: &str, key: &str) -> PathBuf {
+        self.root.join(namespace).join(key)
+    }
+}
+
+impl Storage for FileStorage {
+    fn load(&self, namespace: &str, key: &str) -> Option<String> {
+        fs::read_to_string(self.path(namespace, key)).ok()
+    }
+
+    fn save(&mut self, namespace: &str, key: &str, data: &str) -> std::io::Result<()> {
+        fs::create_dir_all(self.root.join(namespace))?;
+        fs::write(self.path(namespace, key), data)
+    }
+
+    fn remove(&mut self, namespace: &str, key: &str) -> std::io::Result<()> {
+        match fs::remove_file(self.path(namespace, key)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    fn list(&self, namespace: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.root.join(namespace)) else { return Vec::new() };
+        let mut keys: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// Keeps everything in memory for the lifetime of the process. Handy for hermetic tests.
+#[derive(Default)]
+pub struct MemoryStorage {
+    entries: HashMap<(String, String), String>,
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self, namespace: &str, key: &str) -> Option<String> {
+        self.entries.get(&(namespace.to_string(), key.to_string())).cloned()
+    }
+
+    fn save(&mut self, namespace: &str, key: &str, data: &str) -> std::io::Result<()> {
+        self.entries.insert((namespace.to_string(), key.to_string()), data.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, namespace: &str, key: &str) -> std::io::Result<()> {
+        self.entries.remove(&(namespace.to_string(), key.to_string()));
+        Ok(())
+    }
+
+    fn list(&self, namespace: &str) -> Vec<String> {
+        let mut keys: Vec<String> = self.entries.keys()
+            .filter(|(ns, _)| ns == namespace)
+            .map(|(_, key)| key.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// Guest profile backend: nothing is read and nothing ever touches the disk.
+pub struct NullStorage;
+
+impl Storage for NullStorage {
+    fn load(&self, _namespace: &str, _key: &str) -> Option<String> { None }
+    fn save(&mut self, _namespace: &str, _key: &str, _data: &str) -> std::io::Result<()> { Ok(()) }
+    fn remove(&mut self, _namespace: &str, _key: &str) -> std::io::Result<()> { Ok(()) }
+    fn list(&self, _namespace: &str) -> Vec<String> { Vec::new() }
+    fn is_persistent(&self) -> bool { false }
+}
+
+/// `$HACKERMAN_DATA` if set, otherwise `.hackerman` in the home directory.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("HACKERMAN_DATA") {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match home {
+        Some(home) => PathBuf::from(home).join(".hackerman"),
+        None => PathBuf::from(".hackerman"),
+    }
+}
+
+/// Seconds since the unix epoch, the timestamp format used by everything persisted.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// One answered puzzle. Kept in the round history when a history backend (`sqlite`) is compiled in.
+#[derive(Clone, Debug)]
+pub struct RoundRecord {
+    pub game: String,
+    pub mode: String,
+    pub played_at: u64,
+    pub correct: bool,
+    pub answer_time_ms: u64,
+    pub expected: String,
+    pub given: String,
+}
+
+/// Append a round to the history. A no-op for guests and builds without a history backend.
+pub fn record_round(round: &RoundRecord) {
+    if is_guest() {
+        return;
+    }
+    #[cfg(feature = "sqlite")]
+    sqlite::record_round(round);
+    #[cfg(not(feature = "sqlite"))]
+    let _ = round;
+}
+
+static BACKEND: Mutex<Option<Box<dyn Storage>>> = Mutex::new(None);
+
+/// Replace the process-wide backend. Call once at startup, before any game is created.
+pub fn install(backend: Box<dyn Storage>) {
+    *BACKEND.lock().unwrap() = Some(backend);
+}
+
+/// Directory of the profile chosen with [`install_profile`].
+static PROFILE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Where the profile in use keeps its data: the data directory itself until another profile is chosen.
+pub fn profile_dir() -> PathBuf {
+    PROFILE_DIR.lock().unwrap().clone().unwrap_or_else(data_dir)
+}
+
+/// The profile asked for on the command line: `--guest`, or `--profile NAME`.
+pub fn profile_from_args() -> Option<Profile> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--guest") {
+        return Some(Profile::Guest);
+    }
+    args.windows(2)
+        .find(|pair| pair[0] == "--profile" && profiles::is_valid_name(&pair[1]))
+        .map(|pair| Profile::Named(pair[1].clone()))
+}
+
+/// Switch to the backend of `profile`. Call at startup, before anything is read.
+///
+/// Guests get [`NullStorage`]. Named profiles keep files in their [directory](profiles::dir), or with
+/// `--sqlite` a single database file there (only with the `sqlite` feature). When the database
+/// cannot be opened the files stay in use and the error says so, for the caller to show.
+pub fn install_profile(profile: &Profile) -> std::io::Result<()> {
+    let Profile::Named(name) = profile else {
+        install(Box::new(NullStorage));
+        return Ok(());
+    };
+    *PROFILE_DIR.lock().unwrap() = Some(profiles::dir(name));
+    profiles::remember(name);
+    install(Box::new(FileStorage::default_location()));
+    #[cfg(feature = "sqlite")]
+    if std::env::args().any(|arg| arg == "--sqlite") {
+        let backend = sqlite::SqliteStorage::open_default().map_err(|e| {
+            std::io::Error::other(format!("Could not open the sqlite database, keeping files instead: {}", e))
+        })?;
+        install(Box::new(backend));
+    }
+    Ok(())
+}
+
+/// Keep everything the tests of this process write in memory. Installed once, so tests running in
+/// parallel share the backend; they use keys of their own.
+#[cfg(test)]
+pub fn install_for_tests() {
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+    INSTALLED.call_once(|| install(Box::new(MemoryStorage::default())));
+}
+
+fn with_backend<R>(action: impl FnOnce(&mut dyn Storage) -> R) -> R {
+    let mut guard = BACKEND.lock().unwrap();
+    let backend = guard.get_or_insert_with(|| Box::new(FileStorage::default_location()));
+    action(backend.as_mut())
+}
+
+pub fn load(namespace: &str, key: &str) -> Option<String> { with_backend(|s| s.load(namespace, key)) }
+
+pub fn save(namespace: &str, key: &str, data: &str) -> std::io::Result<()> { with_backend(|s| s.save(namespace, key, data)) }
+
+pub fn remove(namespace: &str, key: &str) -> std::io::Result<()> { with_backend(|s| s.remove(namespace, key)) }
+
+pub fn list(namespace: &str) -> Vec<String> { with_backend(|s| s.list(namespace)) }
+
+/// True when nothing written this session will be kept, i.e. the `--guest` profile.
+pub fn is_guest() -> bool { !with_backend(|s| s.is_persistent()) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_do_not_play_as_guests() {
+        install_for_tests();
+        assert!(!is_guest());
+    }
+}
@@ -0,0 +1,85 @@
+use super::data_dir;
+use std::fs;
+use std::path::PathBuf;
+
+/// The profile everyone has; its data stays directly in the data directory, where it was kept
+/// before there were profiles.
+pub const DEFAULT: &str = "default";
+/// Longest profile name, it has to fit the chooser.
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// Who is playing: a named profile with a directory of its own, or a guest who keeps nothing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Profile {
+    Named(String),
+    Guest,
+}
+
+/// Where the other profiles live, one directory each.
+fn root() -> PathBuf {
+    data_dir().join("profiles")
+}
+
+/// The directory holding everything a profile keeps.
+pub fn dir(name: &str) -> PathBuf {
+    if name == DEFAULT { data_dir() } else { root().join(name) }
+}
+
+/// Letters, digits, spaces, `-` and `_`, so a name is also a directory name everywhere.
+pub fn is_valid_name(name: &str) -> bool {
+    let length = name.chars().count();
+    (1..=MAX_NAME_LENGTH).contains(&length)
+        && name.trim() == name
+        && name.chars().all(|ch| ch.is_alphanumeric() || matches!(ch, ' ' | '-' | '_'))
+}
+
+/// The default profile followed by the others found on disk, sorted by name.
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(root()).into_iter().flatten().flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_name(name) && name != DEFAULT)
+        .collect();
+    names.sort();
+    [DEFAULT.to_string()].into_iter().chain(names).collect()
+}
+
+/// Make the directory of a new profile, so it is listed from now on.
+pub fn create(name: &str) -> std::io::Result<()> {
+    fs::create_dir_all(dir(name))
+}
+
+/// The profile picked the last time, if it still exists. Guests are never remembered.
+pub fn last_used() -> Option<String> {
+    let name = fs::read_to_string(root().join(".last")).ok()?;
+    let name = name.trim();
+    list().into_iter().find(|known| known == name)
+}
+
+pub fn remember(name: &str) {
+    if fs::create_dir_all(root()).is_ok() {
+        let _ = fs::write(root().join(".last"), name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_have_to_work_as_directories() {
+        assert!(is_valid_name("ada"));
+        assert!(is_valid_name("Ada Lovelace-2_b"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name(" ada"));
+        assert!(!is_valid_name("../ada"));
+        assert!(!is_valid_name(".last"));
+        assert!(!is_valid_name(&"a".repeat(MAX_NAME_LENGTH + 1)));
+    }
+
+    #[test]
+    fn the_default_profile_keeps_the_data_directory() {
+        assert_eq!(dir(DEFAULT), data_dir());
+        assert_eq!(dir("ada"), data_dir().join("profiles").join("ada"));
+    }
+}
@@ -0,0 +1,126 @@
+use super::migration::{self, Schema};
+use std::collections::HashMap;
+
+/// An entry that can be kept on a leaderboard.
+pub trait Ranked: Sized {
+    /// One line of text without `=` or newlines.
+    fn serialize(&self) -> String;
+    fn parse(value: &str) -> Option<Self>;
+    /// Whether this entry ranks above `other`. Entries that tie keep the order they were made in.
+    fn beats(&self, other: &Self) -> bool;
+}
+
+/// Leaderboards for one game: the best entries per board, best first.
+///
+/// Boards are named by the game, usually after the difficulty and mode they rank, and all of them
+/// are stored in one record as `<board>=<entry>` lines.
+pub struct ScoreStore<R> {
+    schema: &'static Schema,
+    size: usize,
+    boards: HashMap<String, Vec<R>>,
+}
+
+impl<R: Ranked> ScoreStore<R> {
+    /// Read the record described by `schema`, keeping at most `size` entries per board.
+    pub fn load(schema: &'static Schema, size: usize) -> Self {
+        let mut boards: HashMap<String, Vec<R>> = HashMap::new();
+        for line in migration::load(schema).ok().flatten().unwrap_or_default().lines() {
+            if let Some((board, entry)) = line.split_once('=').and_then(|(board, value)| Some((board.trim(), R::parse(value.trim())?))) {
+                boards.entry(board.to_string()).or_default().push(entry);
+            }
+        }
+        for entries in boards.values_mut() {
+            entries.truncate(size);
+        }
+        Self { schema, size, boards }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut names: Vec<&String> = self.boards.keys().collect();
+        names.sort();
+        let data: String = names.into_iter()
+            .flat_map(|board| self.boards[board].iter().map(move |entry| format!("{}={}\n", board, entry.serialize())))
+            .collect();
+        migration::save(self.schema, &data)
+    }
+
+    /// The entries of a board, best first. Empty for a board nobody played yet.
+    pub fn board(&self, board: &str) -> &[R] {
+        self.boards.get(board).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn best(&self, board: &str) -> Option<&R> {
+        self.board(board).first()
+    }
+
+    /// Put `entry` on the board if it makes it and return its place, 0 for the best.
+    pub fn insert(&mut self, board: &str, entry: R) -> Option<usize> {
+        let entries = self.boards.entry(board.to_string()).or_default();
+        let place = entries.iter().position(|other| entry.beats(other)).unwrap_or(entries.len());
+        if place >= self.size { return None; }
+        entries.insert(place, entry);
+        entries.truncate(self.size);
+        Some(place)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage;
+
+    /// A score and who made it; higher scores rank above lower ones.
+    #[derive(Debug, PartialEq)]
+    struct Entry(u32, &'static str);
+
+    impl Ranked for Entry {
+        fn serialize(&self) -> String { format!("{} {}", self.0, self.1) }
+        fn parse(value: &str) -> Option<Self> {
+            let (score, name) = value.split_once(' ')?;
+            let name = ["ada", "bob", "cy", "dee"].into_iter().find(|known| *known == name)?;
+            Some(Entry(score.parse().ok()?, name))
+        }
+        fn beats(&self, other: &Self) -> bool { self.0 > other.0 }
+    }
+
+    const SCORES: Schema = Schema { namespace: "score-tests", key: "scores", steps: &[migration::unversioned] };
+
+    #[test]
+    fn entries_are_kept_best_first_and_ties_by_age() {
+        storage::install_for_tests();
+        let mut store: ScoreStore<Entry> = ScoreStore::load(&Schema { key: "ranking", ..SCORES }, 3);
+        assert_eq!(store.best("easy"), None);
+        assert_eq!(store.insert("easy", Entry(10, "ada")), Some(0));
+        assert_eq!(store.insert("easy", Entry(30, "bob")), Some(0));
+        assert_eq!(store.insert("easy", Entry(10, "cy")), Some(2));
+        assert_eq!(store.insert("easy", Entry(5, "dee")), None);
+        assert_eq!(store.insert("easy", Entry(20, "dee")), Some(1));
+        assert_eq!(store.board("easy"), [Entry(30, "bob"), Entry(20, "dee"), Entry(10, "ada")]);
+        assert!(store.board("hard").is_empty());
+    }
+
+    #[test]
+    fn boards_survive_a_round_trip() {
+        storage::install_for_tests();
+        let schema = &Schema { key: "round-trip", ..SCORES };
+        let mut store: ScoreStore<Entry> = ScoreStore::load(schema, 5);
+        store.insert("hard", Entry(7, "cy"));
+        store.insert("easy", Entry(3, "ada"));
+        store.insert("easy", Entry(4, "bob"));
+        store.save().unwrap();
+        assert_eq!(storage::load("score-tests", "round-trip").as_deref(), Some("version=1\neasy=4 bob\neasy=3 ada\nhard=7 cy\n"));
+        let loaded: ScoreStore<Entry> = ScoreStore::load(schema, 5);
+        assert_eq!(loaded.board("easy"), store.board("easy"));
+        assert_eq!(loaded.best("hard"), Some(&Entry(7, "cy")));
+    }
+
+    #[test]
+    fn unreadable_lines_are_skipped_and_boards_cut_to_size() {
+        storage::install_for_tests();
+        let schema = &Schema { key: "messy", ..SCORES };
+        storage::save("score-tests", "messy", "easy=9 ada\nno separator\neasy=8 mallory\n hard = 1 cy \neasy=7 bob\neasy=6 cy\n").unwrap();
+        let store: ScoreStore<Entry> = ScoreStore::load(schema, 2);
+        assert_eq!(store.board("easy"), [Entry(9, "ada"), Entry(7, "bob")]);
+        assert_eq!(store.board("hard"), [Entry(1, "cy")]);
+    }
+}
@@ -0,0 +1,172 @@
+use super::{profile_dir, RoundRecord, Storage};
+use rusqlite::{params, Connection};
+use std::io::Error;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Schema changes, applied in order. `PRAGMA user_version` remembers how many already ran,
+/// so new entries must only ever be appended.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE kv (
+        namespace TEXT NOT NULL,
+        key TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (namespace, key)
+    );",
+    "CREATE TABLE round_history (
+        id INTEGER PRIMARY KEY,
+        game TEXT NOT NULL,
+        mode TEXT NOT NULL,
+        played_at INTEGER NOT NULL,
+        correct INTEGER NOT NULL,
+        answer_time_ms INTEGER NOT NULL,
+        expected TEXT NOT NULL,
+        given TEXT NOT NULL
+    );
+    CREATE INDEX round_history_by_game ON round_history (game, mode, played_at);",
+    // the next two tables are not used any more, the steps stay so the version numbers do
+    "CREATE TABLE srs_cards (
+        deck TEXT NOT NULL,
+        card TEXT NOT NULL,
+        ease REAL NOT NULL,
+        interval_days INTEGER NOT NULL,
+        due_at INTEGER NOT NULL,
+        PRIMARY KEY (deck, card)
+    );
+    CREATE INDEX srs_cards_by_due ON srs_cards (deck, due_at);",
+    "CREATE TABLE weather_samples (
+        location TEXT NOT NULL,
+        observed_at INTEGER NOT NULL,
+        payload TEXT NOT NULL,
+        PRIMARY KEY (location, observed_at)
+    );",
+];
+
+/// Storage backend keeping everything in one sqlite database.
+///
+/// Besides the plain key/value [`Storage`] data it keeps the history of every round played in a
+/// table of its own, see [`record_round`].
+pub struct SqliteStorage {
+    connection: Connection,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// `hackerman.db` in the directory of the profile in use.
+    pub fn open_default() -> rusqlite::Result<Self> {
+        let _ = std::fs::create_dir_all(profile_dir());
+        Self::open(&profile_dir().join("hackerman.db"))
+    }
+
+    fn from_connection(mut connection: Connection) -> rusqlite::Result<Self> {
+        migrate(&mut connection, MIGRATIONS)?;
+        Ok(Self { connection })
+    }
+
+    pub fn record_round(&self, round: &RoundRecord) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT INTO round_history (game, mode, played_at, correct, answer_time_ms, expected, given)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![round.game, round.mode, round.played_at, round.correct, round.answer_time_ms, round.expected, round.given],
+        )?;
+        Ok(())
+    }
+}
+
+fn migrate(connection: &mut Connection, migrations: &[&str]) -> rusqlite::Result<()> {
+    let applied: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, migration) in migrations.iter().enumerate().skip(applied) {
+        // each step runs in its own transaction, rolled back when dropped on an error, so a
+        // failure leaves the previous version intact
+        let tx = connection.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+fn to_io_error(error: rusqlite::Error) -> Error {
+    Error::other(error)
+}
+
+impl Storage for SqliteStorage {
+    fn load(&self, namespace: &str, key: &str) -> Option<String> {
+        self.connection.query_row(
+            "SELECT data FROM kv WHERE namespace = ?1 AND key = ?2",
+            params![namespace, key],
+            |row| row.get(0),
+        ).ok()
+    }
+
+    fn save(&mut self, namespace: &str, key: &str, data: &str) -> std::io::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO kv (namespace, key, data) VALUES (?1, ?2, ?3)",
+            params![namespace, key, data],
+        ).map_err(to_io_error)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, namespace: &str, key: &str) -> std::io::Result<()> {
+        self.connection.execute("DELETE FROM kv WHERE namespace = ?1 AND key = ?2", params![namespace, key])
+            .map_err(to_io_error)?;
+        Ok(())
+    }
+
+    fn list(&self, namespace: &str) -> Vec<String> {
+        let Ok(mut statement) = self.connection.prepare("SELECT key FROM kv WHERE namespace = ?1 ORDER BY key") else {
+            return Vec::new();
+        };
+        statement.query_map(params![namespace], |row| row.get(0))
+            .map(|rows| rows.filter_map(|row| row.ok()).collect())
+            .unwrap_or_default()
+    }
+}
+
+static HISTORY: Mutex<Option<SqliteStorage>> = Mutex::new(None);
+
+/// Append to the round history in the default database, opening it on first use.
+pub fn record_round(round: &RoundRecord) {
+    let mut guard = HISTORY.lock().unwrap();
+    if guard.is_none() {
+        *guard = SqliteStorage::open_default().ok();
+    }
+    if let Some(history) = guard.as_ref() {
+        let _ = history.record_round(round);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::OptionalExtension;
+
+    fn version(connection: &Connection) -> usize {
+        connection.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn a_failing_step_is_rolled_back() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        let steps = ["CREATE TABLE first (id INTEGER);", "CREATE TABLE second (id INTEGER); INSERT INTO missing VALUES (1);"];
+        assert!(migrate(&mut connection, &steps).is_err());
+        assert_eq!(version(&connection), 1);
+        let second: Option<String> = connection
+            .query_row("SELECT name FROM sqlite_master WHERE name = 'second'", [], |row| row.get(0))
+            .optional()
+            .unwrap();
+        assert_eq!(second, None);
+        // a fixed step is picked up where the failed one stopped
+        migrate(&mut connection, &[steps[0], "CREATE TABLE second (id INTEGER);"]).unwrap();
+        assert_eq!(version(&connection), 2);
+    }
+
+    #[test]
+    fn a_new_database_gets_every_step() {
+        let storage = SqliteStorage::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        assert_eq!(version(&storage.connection), MIGRATIONS.len());
+    }
+}
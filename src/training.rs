@@ -0,0 +1,121 @@
+use crate::stats;
+use crate::storage::migration::{self, Schema};
+use std::collections::BTreeMap;
+use std::fmt;
+
+const SCHEMA: Schema = Schema { namespace: "training", key: "plan", steps: &[migration::unversioned] };
+
+/// Reminders start this many days before the week ends.
+const REMINDER_DAYS: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    Rounds,
+    Sessions,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Metric::Rounds => "rounds",
+            Metric::Sessions => "sessions",
+        })
+    }
+}
+
+/// A weekly target for one trainer, e.g. 50 rounds of Binary Numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Goal {
+    pub target: u32,
+    pub metric: Metric,
+}
+
+impl Goal {
+    /// Progress towards the goal in the current week.
+    pub fn progress(&self, game: &str) -> u32 {
+        let (rounds, sessions) = stats::get(game).this_week();
+        match self.metric {
+            Metric::Rounds => rounds,
+            Metric::Sessions => sessions,
+        }
+    }
+}
+
+/// What to play next and why, suggested on the home screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recommendation {
+    pub game: String,
+    pub reason: String,
+}
+
+/// Weekly goals per trainer, stored as `Game Name=50 rounds` lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrainingPlan {
+    pub goals: BTreeMap<String, Goal>,
+}
+
+impl TrainingPlan {
+    pub fn load() -> Self {
+        let contents = migration::load(&SCHEMA).ok().flatten().unwrap_or_default();
+        let goals = contents.lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(game, goal)| {
+                let (target, metric) = goal.trim().split_once(' ')?;
+                let metric = match metric {
+                    "rounds" => Metric::Rounds,
+                    "sessions" => Metric::Sessions,
+                    _ => return None,
+                };
+                Some((game.to_string(), Goal { target: target.parse().ok()?, metric }))
+            })
+            .collect();
+        Self { goals }
+    }
+
+    pub fn save(&self) {
+        let data: String = self.goals.iter()
+            .map(|(game, goal)| format!("{}={} {}\n", game, goal.target, goal.metric))
+            .collect();
+        let _ = migration::save(&SCHEMA, &data);
+    }
+
+    /// The trainer whose goal is furthest behind this week, otherwise the one practiced least.
+    pub fn recommend(&self, trainers: &[String]) -> Option<Recommendation> {
+        let share = |progress: u32, goal: &Goal| progress as f64 / goal.target as f64;
+        let behind = self.goals.iter()
+            .filter(|(game, _)| trainers.contains(game))
+            .map(|(game, goal)| (game, goal, goal.progress(game)))
+            .filter(|(_, goal, progress)| *progress < goal.target)
+            .min_by(|(_, a, a_progress), (_, b, b_progress)| share(*a_progress, a).total_cmp(&share(*b_progress, b)));
+        if let Some((game, goal, progress)) = behind {
+            let reason = format!("{}/{} {} of this week's goal", progress, goal.target, goal.metric);
+            return Some(Recommendation { game: game.clone(), reason });
+        }
+
+        let least_practiced = trainers.iter()
+            .map(|game| (game, stats::get(game)))
+            .min_by_key(|(_, stats)| (stats.this_week().0, stats.rounds))?;
+        let reason = match least_practiced.1.this_week().0 {
+            0 => "not played this week".to_string(),
+            rounds => format!("least practiced, {} rounds this week", rounds),
+        };
+        Some(Recommendation { game: least_practiced.0.clone(), reason })
+    }
+
+    /// Gentle nudges for goals that are behind when the week is nearly over.
+    pub fn reminders(&self) -> Vec<String> {
+        let days_left = stats::days_left_in_week();
+        if days_left > REMINDER_DAYS {
+            return Vec::new();
+        }
+        let when = match days_left {
+            0 => "today is the last day".to_string(),
+            1 => "1 day left".to_string(),
+            days => format!("{} days left", days),
+        };
+        self.goals.iter()
+            .filter(|(game, goal)| goal.progress(game) < goal.target)
+            .map(|(game, goal)| format!("{}: {}/{} {} this week, {}.", game, goal.progress(game), goal.target, goal.metric, when))
+            .collect()
+    }
+}
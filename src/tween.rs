@@ -0,0 +1,109 @@
+use crate::config;
+
+/// Shape of a transition, mapping linear progress 0..=1 to eased progress.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    /// Starts slow, ends fast.
+    EaseIn,
+    /// Starts fast, settles gently; the usual choice for things appearing.
+    EaseOut,
+    EaseInOut,
+    /// Overshoots the end a few times like a dropped ball.
+    Bounce,
+}
+
+impl Easing {
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::Bounce => bounce_out(t),
+        }
+    }
+}
+
+fn bounce_out(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+pub fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+/// A value that moves towards its target over `duration` seconds instead of jumping,
+/// e.g. a score counter or the height of an opening box. With reduce motion enabled it jumps.
+///
+/// ```ignore
+/// let mut score = Animator::new(0.0, 0.5, Easing::EaseOut);
+/// score.animate_to(120.0);
+/// score.tick(dt);
+/// let shown = score.value().round() as u32;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Animator {
+    from: f64,
+    to: f64,
+    elapsed: f64,
+    duration: f64,
+    easing: Easing,
+}
+
+impl Animator {
+    /// At rest at `value`.
+    pub fn new(value: f64, duration: f64, easing: Easing) -> Self {
+        Self { from: value, to: value, elapsed: duration, duration, easing }
+    }
+
+    /// Start moving from wherever the value is now, so a new target mid-way does not jump.
+    pub fn animate_to(&mut self, target: f64) {
+        if config::get().reduce_motion {
+            return self.jump_to(target);
+        }
+        self.from = self.value();
+        self.to = target;
+        self.elapsed = 0.0;
+    }
+
+    pub fn jump_to(&mut self, value: f64) {
+        self.from = value;
+        self.to = value;
+        self.elapsed = self.duration;
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+    }
+
+    pub fn value(&self) -> f64 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        lerp(self.from, self.to, self.easing.apply(self.elapsed / self.duration))
+    }
+
+    pub fn target(&self) -> f64 {
+        self.to
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.elapsed < self.duration
+    }
+}
@@ -0,0 +1,27 @@
+use crate::config::Background;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style, Widget};
+
+const FILL: Color = Color::Black;
+
+/// Fills the area according to the [`Background`] setting. Rendered first, so widgets draw on top.
+pub struct BackgroundFill(pub Background);
+
+impl Widget for BackgroundFill {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.0 == Background::None {
+            return;
+        }
+        buf.set_style(area, Style::default().bg(FILL));
+        if self.0 == Background::Pattern {
+            for y in (area.top()..area.bottom()).step_by(2) {
+                // shift every other dotted row so the dots form a diagonal grid
+                let offset = if (y - area.y) % 4 == 0 { 0 } else { 3 };
+                for x in (area.left() + offset..area.right()).step_by(6) {
+                    buf[(x, y)].set_char('·').set_fg(Color::DarkGray);
+                }
+            }
+        }
+    }
+}
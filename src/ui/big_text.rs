@@ -0,0 +1,50 @@
+use crate::fonts::Font;
+use crate::utils::AsciiCells;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style, Widget};
+
+/// Text drawn in a [`Font`], centered horizontally in the area it is given.
+pub struct BigText {
+    cells: AsciiCells,
+    style: Style,
+}
+
+impl BigText {
+    pub fn new(text: &str, font: &Font) -> Self {
+        Self::colored(text, font, &[])
+    }
+
+    /// Letter `i` gets `colors[i % colors.len()]` as its foreground, over the [style](BigText::style).
+    pub fn colored(text: &str, font: &Font, colors: &[Color]) -> Self {
+        Self { cells: font.cells(text, colors), style: Style::default() }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn width(&self) -> u16 {
+        if self.cells.cells.is_empty() { 0 } else { self.cells.get_width() }
+    }
+
+    pub fn height(&self) -> u16 {
+        if self.cells.cells.is_empty() { 0 } else { self.cells.get_height() }
+    }
+}
+
+impl Widget for BigText {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let x = area.x + area.width.saturating_sub(self.width()) / 2;
+        for cell in &self.cells.cells {
+            if x + cell.x >= area.right() || cell.y >= area.height {
+                continue;
+            }
+            let style = if cell.color == Color::Reset { self.style } else { self.style.fg(cell.color) };
+            if let Some(target) = buf.cell_mut((x + cell.x, area.y + cell.y)) {
+                target.set_char(cell.ch).set_style(style);
+            }
+        }
+    }
+}
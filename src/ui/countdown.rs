@@ -0,0 +1,145 @@
+use crate::config;
+use crate::ui::theme;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
+use ratatui::widgets::Block;
+
+/// How the time left is written next to the bar.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CountdownLabel {
+    /// "4.25 seconds left"
+    SecondsLeft,
+    /// "4.2s"
+    Short,
+    /// "1:05", for rounds of a minute or more
+    Clock,
+    Hidden,
+}
+
+impl CountdownLabel {
+    fn format(self, seconds: f64) -> String {
+        match self {
+            CountdownLabel::SecondsLeft => format!("{:.2} seconds left", seconds),
+            CountdownLabel::Short => format!("{:.1}s", seconds),
+            CountdownLabel::Clock => {
+                let whole = seconds.ceil() as u64;
+                format!("{}:{:02}", whole / 60, whole % 60)
+            }
+            CountdownLabel::Hidden => String::new(),
+        }
+    }
+}
+
+/// A bar that empties as a round runs out of time, green while there is plenty left, then yellow
+/// and red. In two rows the label goes below the bar, in one row it is written over it.
+///
+/// ```ignore
+/// CountdownWidget::new(time_left, time_total).label(CountdownLabel::Short).warn_below(5.0).render(area, buf);
+/// ```
+pub struct CountdownWidget<'a> {
+    remaining: f64,
+    total: f64,
+    label: CountdownLabel,
+    thresholds: (f64, f64), // ratios below which the bar turns yellow, then red
+    warn_below: Option<f64>, // seconds left from which the label blinks
+    bar: &'a str,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> CountdownWidget<'a> {
+    pub fn new(remaining: f64, total: f64) -> Self {
+        Self { remaining, total, label: CountdownLabel::SecondsLeft, thresholds: (0.6, 0.3), warn_below: None, bar: "=", block: None }
+    }
+
+    pub fn label(mut self, label: CountdownLabel) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Share of the time left below which the bar turns yellow, and below which it turns red.
+    pub fn thresholds(mut self, yellow: f64, red: f64) -> Self {
+        self.thresholds = (yellow, red);
+        self
+    }
+
+    /// Blink the label for the last `seconds`. With reduce motion on it turns bold instead.
+    pub fn warn_below(mut self, seconds: f64) -> Self {
+        self.warn_below = Some(seconds);
+        self
+    }
+
+    /// Symbol the filled part of the bar is drawn with.
+    pub fn bar(mut self, symbol: &'a str) -> Self {
+        self.bar = symbol;
+        self
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.total > 0.0 { (self.remaining / self.total).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    /// In the colors of the [palette](theme::palette), so the thresholds read with color blindness too.
+    pub fn color(&self) -> Color {
+        let palette = theme::palette();
+        match self.ratio() {
+            ratio if ratio > self.thresholds.0 => palette.good,
+            ratio if ratio > self.thresholds.1 => palette.warn,
+            _ => palette.bad,
+        }
+    }
+
+    fn is_warning(&self) -> bool {
+        self.warn_below.is_some_and(|seconds| self.remaining > 0.0 && self.remaining <= seconds)
+    }
+
+    fn label_line(&self) -> Line<'static> {
+        let text = self.label.format(self.remaining);
+        if !self.is_warning() {
+            return Line::from(text.fg(self.color()));
+        }
+        // two blinks per second, timed by the countdown itself so every frame agrees
+        let blink_off = (self.remaining * 4.0) as u64 % 2 == 1;
+        if blink_off && !config::get().reduce_motion { Line::default() } else { Line::from(text.fg(self.color()).bold()) }
+    }
+}
+
+impl Widget for CountdownWidget<'_> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let inner = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        if inner.is_empty() { return; }
+
+        let color = self.color();
+        let filled = (inner.width as f64 * self.ratio()).round() as u16;
+        for x in 0..inner.width {
+            let (symbol, style) = if x < filled { (self.bar, Style::default().fg(color)) } else { (" ", Style::default().fg(Color::DarkGray)) };
+            buf[(inner.x + x, inner.y)].set_symbol(symbol).set_style(style);
+        }
+
+        let label = self.label_line();
+        if inner.height >= 2 {
+            label.alignment(Alignment::Center).render(Rect { y: inner.y + 1, height: 1, ..inner }, buf);
+        } else if label.width() > 0 {
+            // over the bar: dark on the filled part, in the bar color on the empty part
+            let width = (label.width() as u16).min(inner.width);
+            let start = inner.x + (inner.width - width) / 2;
+            label.render(Rect { x: start, width, ..inner }, buf);
+            for x in start..start + width {
+                let cell = &mut buf[(x, inner.y)];
+                if x - inner.x < filled { cell.set_style(Style::default().fg(Color::Black).bg(color)); }
+            }
+        }
+    }
+}
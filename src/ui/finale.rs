@@ -0,0 +1,111 @@
+use crate::config;
+use crate::fonts;
+use crate::particles::{Emitter, ParticleSystem};
+use crate::random::{self, GameRng};
+use crate::ui::big_text::BigText;
+use rand::Rng;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::{Color, Line, Modifier, Style, Stylize, Widget};
+use std::cell::Cell;
+
+const COLORS: [Color; 6] = [Color::LightRed, Color::LightYellow, Color::LightGreen, Color::LightCyan, Color::LightBlue, Color::LightMagenta];
+
+/// Fireworks over a dimmed screen with a record in big text, for a new personal best.
+///
+/// The owner ticks it, hands it any key as a skip and drops it once [`is_done`](Finale::is_done).
+/// It is rendered last, over the whole area of the game. With reduce motion on, only the text shows.
+///
+/// ```ignore
+/// let finale = Finale::new("NEW BEST", &score.to_string());
+/// ```
+pub struct Finale {
+    title: String,
+    record: String,
+    sparks: ParticleSystem,
+    elapsed: f64,
+    next_shell: f64, // seconds into the show the next shell bursts
+    size: Cell<(u16, u16)>, // of the last render, shells burst inside it
+    rng: GameRng, // where, when and in which color the shells burst
+}
+
+impl Finale {
+    /// Seconds the show runs without a skip; the last shells burst a little before.
+    const DURATION: f64 = 6.0;
+    const LAST_SHELL: f64 = 4.5;
+
+    pub fn new(title: &str, record: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            record: record.to_string(),
+            sparks: ParticleSystem::new(),
+            elapsed: 0.0,
+            next_shell: 0.0,
+            size: Cell::new((0, 0)),
+            rng: random::rng(),
+        }
+    }
+
+    /// Fire the show from `rng`, sparks included.
+    pub fn with_rng(mut self, mut rng: GameRng) -> Self {
+        self.sparks = ParticleSystem::new().with_rng(random::seeded(rng.random()));
+        self.rng = rng;
+        self
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.elapsed += dt;
+        self.sparks.tick(dt);
+        let (width, height) = self.size.get();
+        let rng = &mut self.rng;
+        while self.next_shell <= self.elapsed.min(Self::LAST_SHELL) {
+            self.next_shell += rng.random_range(0.2..0.6);
+            if width == 0 { continue; }
+            let (x, y) = (rng.random_range(0.0..width as f64), rng.random_range(0.0..height as f64 * 0.6));
+            let color = COLORS[rng.random_range(0..COLORS.len())];
+            self.sparks.burst(&Emitter::firework(x, y, color), 40);
+        }
+    }
+
+    pub fn skip(&mut self) {
+        self.elapsed = Self::DURATION;
+        self.sparks.clear();
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= Self::DURATION
+    }
+}
+
+impl Widget for &Finale {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.size.set((area.width, area.height));
+        // gray instead of the DIM modifier, which would dim the sparks drawn on top as well
+        buf.set_style(area, Style::default().fg(Color::DarkGray).remove_modifier(Modifier::BOLD));
+        self.sparks.render(area, buf);
+
+        let font = fonts::current();
+        let title = BigText::colored(&self.title, &font, &COLORS).style(Style::default().bold());
+        let record = BigText::new(&self.record, &font).style(Style::default().fg(Color::White).bold());
+        // big text where it fits, plain lines otherwise
+        let big = title.width().max(record.width()) <= area.width && title.height() + record.height() + 3 <= area.height;
+        let heights = if big { [title.height(), record.height()] } else { [1, 1] };
+        let [title_area, _, record_area, _, hint_area] = Layout::vertical([
+            Constraint::Length(heights[0]),
+            Constraint::Length(1),
+            Constraint::Length(heights[1]),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).flex(Flex::Center).areas(area);
+
+        if big {
+            title.render(title_area, buf);
+            record.render(record_area, buf);
+        } else {
+            Line::from(self.title.clone().light_yellow().bold()).centered().render(title_area, buf);
+            Line::from(self.record.clone().white().bold()).centered().render(record_area, buf);
+        }
+        let hint = if config::get().reduce_motion { "<any key> continue" } else { "<any key> skip" };
+        Line::from(hint.dark_gray()).centered().render(hint_area, buf);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::stats;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style, Widget};
+use std::collections::BTreeMap;
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+const WEEKDAYS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+const LEVELS: [(char, Color); 5] = [
+    ('·', Color::DarkGray),
+    ('░', Color::Green),
+    ('▒', Color::Green),
+    ('▓', Color::LightGreen),
+    ('█', Color::LightGreen),
+];
+
+/// Calendar of the past year in the style of a contribution graph:
+/// one column per week, one row per weekday, shaded by the rounds played that day.
+pub struct Heatmap<'a> {
+    activity: &'a BTreeMap<i64, u32>,
+    today: i64,
+}
+
+impl<'a> Heatmap<'a> {
+    /// Month labels, seven weekdays and the legend.
+    pub const HEIGHT: u16 = 9;
+    const MAX_WEEKS: i64 = 53;
+    const LABEL_WIDTH: u16 = 4;
+
+    /// `activity` maps days since 1970-01-01 to rounds played, see [`stats::activity`].
+    pub fn new(activity: &'a BTreeMap<i64, u32>, today: i64) -> Self {
+        Self { activity, today }
+    }
+
+    /// Shade 0 for days without rounds, 1 to 4 in quarters of the busiest day.
+    fn level(&self, rounds: u32, busiest: u32) -> usize {
+        if rounds == 0 { 0 } else { 1 + ((rounds - 1) * 4 / busiest.max(1)).min(3) as usize }
+    }
+}
+
+impl Widget for Heatmap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let weeks = (area.width.saturating_sub(Heatmap::LABEL_WIDTH) / 2) as i64;
+        let weeks = weeks.min(Heatmap::MAX_WEEKS);
+        if weeks == 0 || area.height < Heatmap::HEIGHT {
+            return;
+        }
+        let this_monday = self.today - stats::weekday(self.today) as i64;
+        let first_monday = this_monday - (weeks - 1) * 7;
+        let busiest = self.activity.range(first_monday..).map(|(_, rounds)| *rounds).max().unwrap_or(0);
+        let label_style = Style::default().fg(Color::DarkGray);
+
+        for (row, label) in WEEKDAYS.iter().enumerate() {
+            buf.set_string(area.x, area.y + 1 + row as u16, label, label_style);
+        }
+
+        let mut last_month = None;
+        for week in 0..weeks {
+            let monday = first_monday + week * 7;
+            let x = area.x + Heatmap::LABEL_WIDTH + week as u16 * 2;
+
+            // label the first column of each month, as long as the name fits
+            let (_, month, _) = stats::civil_from_days(monday + 6);
+            if last_month != Some(month) && x + 3 <= area.right() {
+                buf.set_string(x, area.y, MONTHS[month as usize - 1], label_style);
+                last_month = Some(month);
+            }
+
+            for weekday in 0..7 {
+                let day = monday + weekday;
+                if day > self.today {
+                    break;
+                }
+                let rounds = self.activity.get(&day).copied().unwrap_or(0);
+                let (symbol, color) = LEVELS[self.level(rounds, busiest)];
+                let style = if day == self.today { Style::default().fg(color).bg(Color::DarkGray) } else { Style::default().fg(color) };
+                buf.set_string(x, area.y + 1 + weekday as u16, symbol.to_string(), style);
+            }
+        }
+
+        let legend_y = area.y + 8;
+        let days_played = self.activity.range(first_monday..).filter(|(_, rounds)| **rounds > 0).count();
+        let summary = format!("{} day{} played  ", days_played, if days_played == 1 { "" } else { "s" });
+        buf.set_string(area.x + Heatmap::LABEL_WIDTH, legend_y, &summary, Style::default().fg(Color::White));
+        let mut x = area.x + Heatmap::LABEL_WIDTH + summary.chars().count() as u16;
+        if x + 5 + LEVELS.len() as u16 * 2 + 4 > area.right() {
+            return;
+        }
+        buf.set_string(x, legend_y, "Less ", label_style);
+        x += 5;
+        for (symbol, color) in LEVELS {
+            buf.set_string(x, legend_y, symbol.to_string(), Style::default().fg(color));
+            x += 2;
+        }
+        buf.set_string(x, legend_y, "More", label_style);
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize, Widget};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph};
+
+/// Key bindings that work everywhere in the app, shown above the per-game controls.
+pub const GLOBAL_KEYS: [(&str, &str); 12] = [
+    ("F1", "toggle this help"),
+    ("F4", "toggle debug bars"),
+    ("F5", "layout inspector, Tab walks through the areas of the screen"),
+    ("F6", "presentation mode, no bars at all for clean screenshots"),
+    ("Space", "toggle real time / performance loop"),
+    ("Up Down", "navigate the main menu"),
+    ("Enter Left", "open a category / back to the parent menu"),
+    ("/", "filter the main menu by name"),
+    ("N", "notifications, from the main menu"),
+    ("P", "play the suggested game, from the home screen"),
+    ("Esc", "pause the game / leave the current screen"),
+    ("Ctrl+C", "quit"),
+];
+
+/// Centered overlay listing the global keys plus the controls of the active screen.
+pub struct HelpOverlay {
+    screen_name: Option<String>,
+    screen_lines: Vec<(String, String)>,
+}
+
+impl HelpOverlay {
+    pub fn new(screen_name: Option<String>, screen_lines: Vec<(String, String)>) -> Self {
+        Self { screen_name, screen_lines }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from("Global".bold().yellow())];
+        lines.extend(GLOBAL_KEYS.iter().map(|(key, description)| key_line(key, description)));
+
+        if let Some(name) = &self.screen_name {
+            lines.push(Line::from(""));
+            lines.push(Line::from(name.clone().bold().yellow()));
+            if self.screen_lines.is_empty() {
+                lines.push(Line::from("no additional controls".dark_gray()));
+            }
+            lines.extend(self.screen_lines.iter().map(|(key, description)| key_line(key, description)));
+        }
+        lines
+    }
+}
+
+fn key_line(key: &str, description: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:>12}  ", format!("<{}>", key)), Style::default().fg(Color::LightCyan)),
+        Span::styled(description.to_string(), Style::default().fg(Color::White)),
+    ])
+}
+
+impl Widget for HelpOverlay {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let width = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 4;
+        let height = lines.len() as u16 + 2;
+
+        let [middle] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(middle);
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered()
+                .border_type(BorderType::Double)
+                .title(" Help - <Esc>/<F1> to close ")
+                .title_style(Style::default().fg(Color::LightCyan))
+                .padding(Padding::horizontal(1)))
+            .render(popup, buf);
+    }
+}
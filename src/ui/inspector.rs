@@ -0,0 +1,99 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
+use ratatui::widgets::{Block, Clear};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static MARKED: Mutex<Vec<(String, Rect)>> = Mutex::new(Vec::new()); // areas of the frame being drawn, in layout order
+
+/// Note that `area` was laid out as `name`, for the [`Inspector`]. Does nothing while it is closed,
+/// so screens may mark their areas on every frame.
+pub fn mark(name: &str, area: Rect) {
+    if ENABLED.load(Ordering::Relaxed) {
+        MARKED.lock().unwrap().push((name.to_string(), area));
+    }
+}
+
+/// True while an [`Inspector`] is open, for names that take work to build.
+pub fn is_open() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Forget the areas of the previous frame, called by the app before it draws.
+pub fn begin_frame() {
+    if ENABLED.load(Ordering::Relaxed) {
+        MARKED.lock().unwrap().clear();
+    }
+}
+
+/// Outlines every [marked](mark) area of the frame over the screen, with one of them selected and
+/// named together with its position and size.
+pub struct Inspector {
+    selected: usize,
+}
+
+impl Inspector {
+    /// Starts collecting areas until it is dropped.
+    pub fn open() -> Self {
+        ENABLED.store(true, Ordering::Relaxed);
+        Self { selected: 0 }
+    }
+
+    pub fn select_next(&mut self) {
+        let count = MARKED.lock().unwrap().len().max(1);
+        self.selected = (self.selected + 1) % count;
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = MARKED.lock().unwrap().len().max(1);
+        self.selected = (self.selected + count - 1) % count;
+    }
+}
+
+impl Drop for Inspector {
+    fn drop(&mut self) {
+        ENABLED.store(false, Ordering::Relaxed);
+        MARKED.lock().unwrap().clear();
+    }
+}
+
+/// The corners of `area`, enough to see it without hiding what is inside.
+fn outline_corners(area: Rect, style: Style, buf: &mut Buffer) {
+    if area.width < 2 || area.height < 2 {
+        return;
+    }
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    for (x, y, corner) in [(area.x, area.y, "┌"), (right, area.y, "┐"), (area.x, bottom, "└"), (right, bottom, "┘")] {
+        buf.set_string(x, y, corner, style);
+    }
+}
+
+impl Widget for &Inspector {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let marked = MARKED.lock().unwrap().clone();
+        let dim = Style::default().fg(Color::DarkGray);
+        for (_, marked_area) in &marked {
+            outline_corners(marked_area.intersection(area), dim, buf);
+        }
+
+        let status = match marked.get(self.selected % marked.len().max(1)) {
+            Some((name, selected)) => {
+                let selected = selected.intersection(area);
+                Block::bordered().border_style(Style::default().fg(Color::LightMagenta)).render(selected, buf);
+                let size = format!(" {} {}x{} at {},{} ", name, selected.width, selected.height, selected.x, selected.y);
+                buf.set_string(selected.x + 1, selected.y, &size, Style::default().fg(Color::Black).bg(Color::LightMagenta));
+                Line::from(vec![
+                    format!(" {}/{} ", self.selected % marked.len() + 1, marked.len()).black().on_light_magenta(),
+                    format!(" {}  {}x{} at {},{}", name, selected.width, selected.height, selected.x, selected.y).white(),
+                    "   <Tab> next  <Shift+Tab> previous  <F5> close ".dark_gray(),
+                ])
+            }
+            None => Line::from(" No areas marked on this screen  <F5> close ".dark_gray()),
+        };
+        let bottom = Rect { y: area.bottom().saturating_sub(1), height: 1.min(area.height), ..area };
+        Clear.render(bottom, buf);
+        status.render(bottom, buf);
+    }
+}
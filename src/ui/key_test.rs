@@ -0,0 +1,118 @@
+use crate::keymap::{self, Layout, Quirks};
+use crate::ui::modal::Modal;
+use crate::ui::settings_form::key_name;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Stylize, Widget};
+
+/// A key the test asks for.
+enum Step {
+    /// Whatever the key at this position of [`Layout::key_at`] types.
+    Position { row: usize, column: usize, prompt: &'static str },
+    /// A key the terminal may send as something else.
+    Named { code: KeyCode, prompt: &'static str },
+}
+
+const STEPS: [Step; 6] = [
+    Step::Position { row: 1, column: 0, prompt: "the first letter of the top row, right of Tab" },
+    Step::Position { row: 1, column: 5, prompt: "the sixth letter of the top row" },
+    Step::Position { row: 1, column: 7, prompt: "the eighth letter of the top row" },
+    Step::Position { row: 0, column: 0, prompt: "the key with the 1 on it, without Shift" },
+    Step::Named { code: KeyCode::Backspace, prompt: "Backspace" },
+    Step::Named { code: KeyCode::Enter, prompt: "Enter" },
+];
+
+/// Shows what the app receives for every key and asks for a few keys by their position, to learn
+/// the keyboard layout and the keys this terminal sends differently. What it finds is stored as
+/// the [quirks](keymap::Quirks) of this terminal once every key was pressed.
+///
+/// The owner feeds it keys exactly as the terminal sent them, see
+/// [`wants_raw_keys`](crate::games::main_screen_widget::MainScreenWidget::wants_raw_keys).
+pub struct KeyTest {
+    log: Vec<(KeyEvent, KeyEvent)>, // as received and as read after normalizing, newest last
+    pressed: Vec<KeyEvent>, // one per step answered so far
+    found: Option<Quirks>, // saved once every step is answered
+}
+
+impl KeyTest {
+    const LOG_LINES: usize = 6;
+
+    pub fn new() -> Self {
+        Self { log: Vec::new(), pressed: Vec::new(), found: None }
+    }
+
+    /// True once the player is done with the test.
+    pub fn handle_key(&mut self, input: KeyEvent) -> bool {
+        if self.found.is_some() && keymap::normalize(input).code == KeyCode::Enter {
+            return true;
+        }
+        self.log.push((input, keymap::normalize(input)));
+        if self.log.len() > Self::LOG_LINES {
+            self.log.remove(0);
+        }
+        if self.found.is_none() {
+            self.pressed.push(input);
+            if self.pressed.len() == STEPS.len() {
+                let quirks = self.quirks();
+                keymap::save_quirks(quirks.clone());
+                self.found = Some(quirks);
+            }
+        }
+        false
+    }
+
+    fn quirks(&self) -> Quirks {
+        let mut typed = Vec::new();
+        let mut remaps = Vec::new();
+        for (step, key) in STEPS.iter().zip(&self.pressed) {
+            match (step, key.code) {
+                (Step::Position { row, column, .. }, KeyCode::Char(ch)) => typed.push((*row, *column, ch.to_lowercase().next().unwrap_or(ch))),
+                // a stray letter is a mistake, not a quirk; control keys and other named keys are
+                (Step::Named { code, .. }, received) if received != *code
+                    && (key.modifiers.contains(KeyModifiers::CONTROL) || !matches!(received, KeyCode::Char(_))) => {
+                    remaps.push((key_name(*key), *code));
+                }
+                _ => {}
+            }
+        }
+        Quirks { layout: Layout::detect(&typed), remaps }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(vec!["Terminal ".dark_gray(), keymap::terminal().light_cyan()]), Line::from("")];
+        match (&self.found, STEPS.get(self.pressed.len())) {
+            (Some(quirks), _) => {
+                let layout = quirks.layout.map_or("not recognized, keys are read as typed".to_string(), |layout| layout.to_string());
+                lines.push(Line::from(vec!["Layout ".white(), layout.light_green()]));
+                lines.extend(quirks.remaps.iter().map(|(key, meant)| Line::from(vec![format!("{} ", key).light_green(), format!("is read as {:?}", meant).white()])));
+                lines.push(Line::from("Saved for this terminal".dark_gray()));
+            }
+            (None, Some(Step::Position { prompt, .. } | Step::Named { prompt, .. })) => {
+                lines.push(Line::from(format!("Step {} of {}", self.pressed.len() + 1, STEPS.len()).dark_gray()));
+                lines.push(Line::from(vec!["Press ".white(), prompt.to_string().light_yellow()]));
+            }
+            (None, None) => {}
+        }
+        lines.push(Line::from(""));
+        if self.log.is_empty() {
+            lines.push(Line::from("keys show up here as they arrive".dark_gray()));
+        }
+        lines.extend(self.log.iter().map(|(received, read)| {
+            let mut spans = vec![key_name(*received).light_cyan(), format!("  {:?}", received.code).dark_gray()];
+            if read != received {
+                spans.push(format!("  read as {}", key_name(*read)).white());
+            }
+            Line::from(spans)
+        }));
+        lines
+    }
+}
+
+impl Widget for &KeyTest {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let buttons: &[&str] = if self.found.is_some() { &["Done"] } else { &[] };
+        let modal = Modal::new("Test your keys").body(self.lines()).buttons(buttons).accent(Color::LightCyan);
+        modal.render(area, buf);
+    }
+}
@@ -0,0 +1,102 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Position, Rect};
+use ratatui::prelude::{Color, Style, Stylize, Widget};
+use ratatui::text::Line;
+use std::cell::RefCell;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeypadKey {
+    Digit(char),
+    Backspace,
+    Enter,
+}
+
+impl KeypadKey {
+    fn label(self) -> String {
+        match self {
+            KeypadKey::Digit(digit) => digit.to_string(),
+            KeypadKey::Backspace => "<-".to_string(),
+            KeypadKey::Enter => "OK".to_string(),
+        }
+    }
+}
+
+/// On-screen number pad for typed answers, usable with the arrow keys or the mouse.
+/// Rendered below the input field it feeds.
+pub struct Keypad {
+    rows: Vec<Vec<KeypadKey>>,
+    cursor: (usize, usize), // row, column
+    key_areas: RefCell<Vec<(Rect, KeypadKey)>>, // last rendered keys, used for mouse hit testing
+}
+
+impl Keypad {
+    /// Rows needed to render the pad.
+    pub const HEIGHT: u16 = 4;
+    const KEY_WIDTH: u16 = 6;
+
+    /// Phone-style 3x4 grid of digits with backspace and confirm.
+    pub fn numeric() -> Self {
+        let digits = |s: &str| s.chars().map(KeypadKey::Digit).collect::<Vec<_>>();
+        let rows = vec![
+            digits("789"),
+            digits("456"),
+            digits("123"),
+            vec![KeypadKey::Backspace, KeypadKey::Digit('0'), KeypadKey::Enter],
+        ];
+        Self { rows, cursor: (3, 1), key_areas: RefCell::new(Vec::new()) }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.rows.iter().map(|row| row.len() as u16).max().unwrap_or(0) * Self::KEY_WIDTH
+    }
+
+    /// Arrows move the cursor, Space presses the key under it. Returns the pressed key.
+    pub fn handle_key(&mut self, input: KeyEvent) -> Option<KeypadKey> {
+        let (row, column) = self.cursor;
+        match input.code {
+            KeyCode::Up => self.cursor.0 = row.saturating_sub(1),
+            KeyCode::Down => self.cursor.0 = (row + 1).min(self.rows.len() - 1),
+            KeyCode::Left => self.cursor.1 = column.saturating_sub(1),
+            KeyCode::Right => self.cursor.1 = (column + 1).min(self.rows[row].len() - 1),
+            KeyCode::Char(' ') => return self.rows[row].get(column).copied(),
+            _ => {}
+        }
+        self.cursor.1 = self.cursor.1.min(self.rows[self.cursor.0].len() - 1);
+        None
+    }
+
+    /// A left click presses the key under the mouse and moves the cursor there.
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> Option<KeypadKey> {
+        if event.kind != MouseEventKind::Down(MouseButton::Left) { return None; }
+        let position = Position::new(event.column, event.row);
+        let key = self.key_areas.borrow().iter().find(|(area, _)| area.contains(position)).map(|(_, key)| *key)?;
+        if let Some((row, column)) = self.rows.iter().enumerate()
+            .find_map(|(row, keys)| keys.iter().position(|k| *k == key).map(|column| (row, column))) {
+            self.cursor = (row, column);
+        }
+        Some(key)
+    }
+}
+
+impl Widget for &Keypad {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [pad] = Layout::horizontal([Constraint::Length(self.width())]).flex(Flex::Center).areas(area);
+        let mut key_areas = Vec::new();
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let y = pad.y + row_index as u16;
+            if y >= area.bottom() { break; }
+            for (column, key) in row.iter().enumerate() {
+                let key_area = Rect::new(pad.x + column as u16 * Keypad::KEY_WIDTH, y, Keypad::KEY_WIDTH, 1).intersection(area);
+                let style = if self.cursor == (row_index, column) {
+                    Style::default().fg(Color::Black).bg(Color::LightCyan).bold()
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(format!("[{:^2}]", key.label())).style(style).centered().render(key_area, buf);
+                key_areas.push((key_area, *key));
+            }
+        }
+        *self.key_areas.borrow_mut() = key_areas;
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Line, Span, Style, Widget};
+use std::cell::Cell;
+
+/// A line that scrolls from right to left when it is wider than its area, like a news ticker.
+///
+/// The owner calls [`tick`](Marquee::tick) every frame and renders the marquee by reference.
+/// Lines that fit, and every line while reduce motion is on, stand still at the left edge.
+///
+/// ```ignore
+/// let mut ticker = Marquee::new(Line::from("Storm warning until 18:00".yellow())).speed(6.0);
+/// ticker.tick(dt);
+/// ticker.render(area, buf);
+/// ```
+pub struct Marquee {
+    line: Line<'static>,
+    speed: f64, // columns per second
+    gap: u16, // blank columns before the line comes around again
+    offset: f64, // columns scrolled since the line was set
+    width: Cell<u16>, // of the last render
+}
+
+impl Marquee {
+    pub fn new(line: impl Into<Line<'static>>) -> Self {
+        Self { line: line.into(), speed: 8.0, gap: 6, offset: 0.0, width: Cell::new(0) }
+    }
+
+    pub fn speed(mut self, columns_per_second: f64) -> Self {
+        self.speed = columns_per_second;
+        self
+    }
+
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Show another line, starting over at the left edge.
+    pub fn set_line(&mut self, line: impl Into<Line<'static>>) {
+        self.line = line.into();
+        self.offset = 0.0;
+    }
+
+    pub fn line(&self) -> &Line<'static> {
+        &self.line
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.offset += self.speed * dt;
+    }
+
+    /// Times the line has gone all the way around since it was set. Keeps counting at the same
+    /// pace when the line stands still, so rotating texts rotate either way.
+    pub fn laps(&self) -> u32 {
+        (self.offset / (self.line.width() as f64 + self.gap as f64).max(1.0)) as u32
+    }
+
+    /// Whether the line is moving, i.e. needs to be redrawn every frame.
+    pub fn is_scrolling(&self) -> bool {
+        self.line.width() > self.width.get() as usize && !config::get().reduce_motion
+    }
+}
+
+impl Widget for &Marquee {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.width.set(area.width);
+        if !self.is_scrolling() {
+            return self.line.clone().left_aligned().render(area, buf);
+        }
+
+        // one pass of the line followed by the gap, as (symbol, width, style) cells
+        let mut cells: Vec<(&str, usize, Style)> = self.line.styled_graphemes(Style::default())
+            .map(|grapheme| (grapheme.symbol, Span::raw(grapheme.symbol).width(), grapheme.style))
+            .collect();
+        cells.extend(std::iter::repeat_n((" ", 1, Style::default()), self.gap as usize));
+        let period: usize = cells.iter().map(|(_, width, _)| width).sum();
+        let start = self.offset as usize % period.max(1);
+
+        let (mut column, mut x) = (0, 0);
+        for &(symbol, width, style) in cells.iter().cycle() {
+            if x >= area.width as usize { break; }
+            if column >= start {
+                buf.set_stringn(area.x + x as u16, area.y, symbol, area.width as usize - x, style);
+                x += width;
+            } else if column + width > start {
+                x += column + width - start; // a wide character cut in half by the left edge
+            }
+            column += width;
+        }
+    }
+}
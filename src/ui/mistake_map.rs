@@ -0,0 +1,145 @@
+use crate::stats::ModeStats;
+use crate::ui::theme::{self, Palette};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+const MAGNITUDES: [&str; 4] = ["0-25%", "25-50%", "50-75%", "75%+"];
+/// Shades by share of rounds missed, from none to every one.
+const LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+/// Yellow for a few misses, red for many, or their colorblind counterparts.
+fn level_color(level: usize, palette: &Palette) -> Color {
+    match level {
+        0 => Color::DarkGray,
+        1 | 2 => palette.warn,
+        _ => palette.bad_light,
+    }
+}
+
+const LABEL_WIDTH: usize = 14;
+const CELL_WIDTH: usize = 8;
+
+/// What stands out about the bits of a number. Each number gets the first that applies.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum BitPattern { Alternating, LeadingOnes, Sparse, LongRun, Mixed }
+
+impl BitPattern {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BitPattern::Alternating => "alternating",
+            BitPattern::LeadingOnes => "leading ones",
+            BitPattern::Sparse => "few ones",
+            BitPattern::LongRun => "long runs",
+            BitPattern::Mixed => "mixed",
+        }
+    }
+
+    /// The pattern of `raw` written with `width` bits: 1010…, 11…, at most a quarter of the bits set,
+    /// three or more equal bits in a row, or none of those.
+    pub fn of(raw: u32, width: u32) -> Self {
+        let mask = |bits: u32| if bits >= 32 { u32::MAX } else { (1 << bits) - 1 };
+        let changes = (raw ^ (raw >> 1)) & mask(width.saturating_sub(1));
+        if width >= 3 && changes == mask(width - 1) {
+            return BitPattern::Alternating;
+        }
+        if width >= 2 && raw >> (width - 2) == 0b11 {
+            return BitPattern::LeadingOnes;
+        }
+        if raw.count_ones() * 4 <= width {
+            return BitPattern::Sparse;
+        }
+        let longest_run = (0..width).fold((0, 0, None), |(longest, run, last), bit| {
+            let value = raw >> bit & 1;
+            let run = if last == Some(value) { run + 1 } else { 1 };
+            (longest.max(run), run, Some(value))
+        }).0;
+        if longest_run >= 3 { BitPattern::LongRun } else { BitPattern::Mixed }
+    }
+}
+
+/// Quarter of the range of `width` bits that `raw` falls into, 0 for the smallest numbers.
+pub fn magnitude(raw: u32, width: u32) -> usize {
+    ((raw as u64 * 4) >> width).min(3) as usize
+}
+
+/// Name under which a round is counted with [`stats::record_pattern`](crate::stats::record_pattern).
+pub fn key(raw: u32, width: u32) -> String {
+    format!("{}.{}", BitPattern::of(raw, width), magnitude(raw, width))
+}
+
+/// Answers by bit pattern and magnitude of the number, as a table shaded by how many were missed,
+/// so a player sees which kind of number trips them up rather than just how many they got wrong.
+#[derive(Default)]
+pub struct MistakeMap {
+    cells: BTreeMap<(BitPattern, usize), ModeStats>,
+}
+
+impl MistakeMap {
+    /// From lifetime stats, see [`GameStats::patterns`](crate::stats::GameStats::patterns).
+    pub fn from_patterns(patterns: &BTreeMap<String, ModeStats>) -> Self {
+        let cells = patterns.iter()
+            .filter_map(|(key, stats)| {
+                let (pattern, magnitude) = key.split_once('.')?;
+                Some(((BitPattern::from_str(pattern).ok()?, magnitude.parse().ok().filter(|m| *m < 4)?), stats.clone()))
+            })
+            .collect();
+        Self { cells }
+    }
+
+    pub fn record(&mut self, raw: u32, width: u32, correct: bool) {
+        let cell = self.cells.entry((BitPattern::of(raw, width), magnitude(raw, width))).or_default();
+        cell.rounds += 1;
+        if correct { cell.correct += 1; }
+    }
+
+    pub fn is_empty(&self) -> bool { self.cells.is_empty() }
+
+    /// Header, one row per pattern and the weakest cell, if anything was missed.
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let palette = theme::palette();
+        let mut header = vec![Span::styled(format!("{:<LABEL_WIDTH$}", "missed"), label_style)];
+        header.extend(MAGNITUDES.iter().map(|name| Span::styled(format!("{:<CELL_WIDTH$}", name), label_style)));
+        let mut lines = vec![Line::from(header)];
+
+        for pattern in BitPattern::iter() {
+            let mut spans = vec![Span::styled(format!("{:<LABEL_WIDTH$}", pattern.label()), Style::default().fg(Color::White))];
+            for magnitude in 0..MAGNITUDES.len() {
+                let cell = match self.cells.get(&(pattern, magnitude)) {
+                    Some(stats) if stats.rounds > 0 => {
+                        let level = level(stats);
+                        Span::styled(format!("{} {:<w$}", LEVELS[level], format!("{}/{}", stats.rounds - stats.correct, stats.rounds), w = CELL_WIDTH - 2), Style::default().fg(level_color(level, &palette)))
+                    }
+                    _ => Span::styled(format!("{:<CELL_WIDTH$}", LEVELS[0]), label_style),
+                };
+                spans.push(cell);
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let weakest = self.cells.iter()
+            .filter(|(_, stats)| stats.correct < stats.rounds)
+            .max_by(|(_, a), (_, b)| miss_rate(a).total_cmp(&miss_rate(b)).then((a.rounds - a.correct).cmp(&(b.rounds - b.correct))));
+        if let Some(((pattern, magnitude), stats)) = weakest {
+            lines.push(Line::from(vec![
+                "Weakest: ".white(),
+                format!("{}, {} of the range", pattern.label(), MAGNITUDES[*magnitude]).light_red(),
+                format!(" ({:.0}% missed)", miss_rate(stats) * 100.0).dark_gray(),
+            ]));
+        }
+        lines
+    }
+}
+
+fn miss_rate(stats: &ModeStats) -> f64 {
+    if stats.rounds == 0 { 0.0 } else { (stats.rounds - stats.correct) as f64 / stats.rounds as f64 }
+}
+
+/// Shade 0 when nothing was missed, 1 to 4 in quarters of the share missed.
+fn level(stats: &ModeStats) -> usize {
+    let missed = stats.rounds - stats.correct;
+    if missed == 0 { 0 } else { 1 + ((missed * 4 - 1) / stats.rounds).min(3) as usize }
+}
@@ -0,0 +1,19 @@
+pub mod background;
+pub mod big_text;
+pub mod countdown;
+pub mod finale;
+pub mod heatmap;
+pub mod help_overlay;
+pub mod inspector;
+pub mod key_test;
+pub mod keypad;
+pub mod marquee;
+pub mod mistake_map;
+pub mod modal;
+pub mod notification_center;
+pub mod pause_menu;
+pub mod session_summary;
+pub mod settings_form;
+pub mod stat_bar;
+pub mod theme;
+pub mod toast;
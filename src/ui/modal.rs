@@ -0,0 +1,191 @@
+use crate::fonts;
+use crate::sound::{self, SoundEvent};
+use crate::ui::big_text::BigText;
+use crate::ui::inspector;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Position, Rect};
+use ratatui::prelude::{Color, Line, Modifier, Span, Style, Stylize, Widget};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+use std::cell::RefCell;
+
+/// What the player did with a [`Modal`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ModalResult {
+    /// The button at this index was pressed.
+    Pressed(usize),
+    /// Esc: the question was dismissed without an answer.
+    Dismissed,
+}
+
+/// A question over a dimmed screen, answered with one of a row of buttons.
+///
+/// Left/Right and Tab move the focus, Enter presses the focused button and the first letter of a
+/// label presses that button directly. The owner keeps the modal while it is open, feeds it input
+/// and renders it last, over the area it should dim.
+///
+/// ```ignore
+/// let modal = Modal::new("Quit").body(vec![Line::from("Leave hackerman?")]).buttons(&["Quit", "Cancel"]);
+/// ```
+pub struct Modal {
+    title: String,
+    banner: Option<String>,
+    body: Vec<Line<'static>>,
+    buttons: Vec<String>,
+    focused: usize,
+    accent: Color,
+    button_areas: RefCell<Vec<Rect>>, // last rendered buttons, used for mouse hit testing
+}
+
+impl Modal {
+    const MAX_WIDTH: u16 = 60;
+
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            banner: None,
+            body: Vec::new(),
+            buttons: vec!["OK".to_string()],
+            focused: 0,
+            accent: Color::LightCyan,
+            button_areas: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Big text above the body in the current font, left out when the modal would not fit.
+    pub fn banner(mut self, text: &str) -> Self {
+        self.banner = Some(text.to_string());
+        self
+    }
+
+    pub fn body(mut self, body: Vec<Line<'static>>) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Button labels from left to right; the focus starts on the first one.
+    pub fn buttons(mut self, labels: &[&str]) -> Self {
+        self.buttons = labels.iter().map(|label| label.to_string()).collect();
+        self.focused = 0;
+        self
+    }
+
+    /// Color of the border, title and focused button.
+    pub fn accent(mut self, accent: Color) -> Self {
+        self.accent = accent;
+        self
+    }
+
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focused = (self.focused + self.buttons.len() - 1) % self.buttons.len();
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.buttons.len();
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    pub fn handle_key(&mut self, input: KeyEvent) -> Option<ModalResult> {
+        match input.code {
+            KeyCode::Left | KeyCode::Up | KeyCode::BackTab => self.focus_previous(),
+            KeyCode::Right | KeyCode::Down | KeyCode::Tab => self.focus_next(),
+            KeyCode::Enter | KeyCode::Char(' ') => return Some(self.press(self.focused)),
+            KeyCode::Esc => return Some(ModalResult::Dismissed),
+            KeyCode::Char(ch) => {
+                let hotkey = self.buttons.iter()
+                    .position(|label| label.chars().next().is_some_and(|first| first.eq_ignore_ascii_case(&ch)))?;
+                return Some(self.press(hotkey));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// A left click on a button presses it.
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> Option<ModalResult> {
+        if event.kind != MouseEventKind::Down(MouseButton::Left) { return None; }
+        let position = Position::new(event.column, event.row);
+        let index = self.button_areas.borrow().iter().position(|area| area.contains(position))?;
+        Some(self.press(index))
+    }
+
+    fn press(&mut self, index: usize) -> ModalResult {
+        self.focused = index;
+        sound::emit(SoundEvent::MenuSelect);
+        ModalResult::Pressed(index)
+    }
+
+    fn button_spans(&self) -> Vec<Span<'static>> {
+        self.buttons.iter().enumerate().flat_map(|(index, label)| {
+            let style = if index == self.focused {
+                Style::default().fg(Color::Black).bg(self.accent).bold()
+            } else {
+                Style::default().fg(Color::White)
+            };
+            [Span::styled(format!("[ {} ]", label), style), Span::raw("  ")]
+        }).collect()
+    }
+}
+
+impl Widget for &Modal {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+        let mut buttons = self.button_spans();
+        buttons.pop(); // no gap after the last button
+        let buttons = Line::from(buttons);
+        let banner = self.banner.as_ref().map(|text| BigText::new(text, &fonts::current()).style(Style::default().fg(self.accent)));
+        let banner_width = banner.as_ref().map_or(0, |banner| banner.width() as usize);
+        let content_width = self.body.iter().map(Line::width).chain([buttons.width(), self.title.len() + 2, banner_width]).max().unwrap_or(0) as u16;
+        let width = (content_width + 4).min(Modal::MAX_WIDTH).min(area.width);
+        let text_width = width.saturating_sub(4).max(1) as usize;
+        let wrapped_body = self.body.iter().map(|line| line.width().div_ceil(text_width).max(1)).sum::<usize>() as u16;
+        let banner = banner.filter(|banner| banner.width() + 4 <= width && wrapped_body + banner.height() + 5 <= area.height);
+        let banner_height = banner.as_ref().map_or(0, |banner| banner.height() + 1);
+
+        let height = (banner_height + wrapped_body + 4).min(area.height);
+        let [middle] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(middle);
+
+        if inspector::is_open() {
+            inspector::mark(&format!("modal {}", self.title), popup);
+        }
+        Clear.render(popup, buf);
+        let block = Block::bordered()
+            .border_type(BorderType::Double)
+            .border_style(Style::default().fg(self.accent))
+            .title(format!(" {} ", self.title))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().fg(self.accent))
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let [banner_area, body_area, _, buttons_area] = Layout::vertical([
+            Constraint::Length(banner_height),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).areas(inner);
+        if let Some(banner) = banner {
+            banner.render(banner_area, buf);
+        }
+        Paragraph::new(self.body.clone()).alignment(Alignment::Center).wrap(Wrap { trim: false }).render(body_area, buf);
+
+        // buttons are centered as one row; remember where each one landed for clicks
+        let row_width = buttons.width() as u16;
+        let mut x = buttons_area.x + buttons_area.width.saturating_sub(row_width) / 2;
+        let mut button_areas = Vec::new();
+        for span in buttons.spans.iter().filter(|span| !span.content.trim().is_empty()) {
+            button_areas.push(Rect::new(x, buttons_area.y, span.width() as u16, 1).intersection(buttons_area));
+            x += span.width() as u16 + 2;
+        }
+        buttons.alignment(Alignment::Center).render(buttons_area, buf);
+        *self.button_areas.borrow_mut() = button_areas;
+    }
+}
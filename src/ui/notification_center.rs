@@ -0,0 +1,56 @@
+use crate::notifications::{Kind, Notification};
+use crate::report;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize, Widget};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph};
+
+/// Centered panel listing recent notifications, unread ones marked.
+pub struct NotificationCenter {
+    notifications: Vec<Notification>,
+}
+
+impl NotificationCenter {
+    pub fn new(notifications: Vec<Notification>) -> Self {
+        Self { notifications }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        if self.notifications.is_empty() {
+            return vec![Line::from("Nothing yet.".dark_gray())];
+        }
+        self.notifications.iter().map(|n| {
+            let color = match n.kind {
+                Kind::Reminder => Color::LightYellow,
+                Kind::Achievement => Color::LightGreen,
+                Kind::Alert => Color::LightRed,
+            };
+            Line::from(vec![
+                Span::styled(if n.read { "  " } else { "● " }, Style::default().fg(Color::LightCyan)),
+                Span::styled(format!("{}  ", report::format_timestamp(n.at)), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{:<12}", n.kind), Style::default().fg(color)),
+                Span::styled(n.text.clone(), Style::default().fg(if n.read { Color::Gray } else { Color::White })),
+            ])
+        }).collect()
+    }
+}
+
+impl Widget for NotificationCenter {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let width = (lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 4).max(40).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+
+        let [middle] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(middle);
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered()
+                .border_type(BorderType::Double)
+                .title(" Notifications - <Esc>/<N> to close ")
+                .title_style(Style::default().fg(Color::LightCyan))
+                .padding(Padding::horizontal(1)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::sound::{self, SoundEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+#[derive(EnumIter, Display, Clone, Copy, PartialEq, Debug)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    Settings,
+    #[strum(to_string = "Quit to Menu")]
+    QuitToMenu,
+}
+
+/// Modal shown when Esc is pressed during a game, so a stray key does not throw the game away.
+pub struct PauseMenu {
+    selected: usize,
+}
+
+impl PauseMenu {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn selected(&self) -> PauseOption {
+        PauseOption::iter().nth(self.selected).unwrap_or(PauseOption::Resume)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(PauseOption::iter().count() - 1);
+        sound::emit(SoundEvent::MenuMove);
+    }
+}
+
+impl Widget for &PauseMenu {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines: Vec<Line> = PauseOption::iter().map(|option| {
+            if option == self.selected() {
+                Line::from(format!("> {}", option)).style(Style::default().fg(Color::LightCyan).bold())
+            } else {
+                Line::from(format!("  {}", option)).white()
+            }
+        }).collect();
+
+        let height = lines.len() as u16 + 2;
+        let [middle] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(24)]).flex(Flex::Center).areas(middle);
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered()
+                .border_type(BorderType::Double)
+                .title(" Paused ")
+                .title_style(Style::default().fg(Color::LightCyan))
+                .padding(Padding::horizontal(1)))
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::stats;
+use crate::ui::modal::Modal;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Stylize, Widget};
+
+/// What one run of hackerman added up to, shown for a moment when it quits.
+///
+/// The owner ticks it and quits once it [`is_done`](SessionSummary::is_done) or on any key.
+///
+/// ```ignore
+/// let summary = SessionSummary::new(seconds, games, (rounds, correct)).streaks(streaks);
+/// ```
+pub struct SessionSummary {
+    seconds: f64,
+    games: u32,
+    rounds: u32,
+    correct: u32,
+    streaks: Vec<(String, u32)>, // best of today per game, longest first
+    achievements: Vec<String>,
+    remaining: f64,
+}
+
+impl SessionSummary {
+    /// Seconds the summary stays without a key press.
+    const DURATION: f64 = 5.0;
+    const MAX_STREAKS: usize = 3;
+
+    pub fn new(seconds: f64, games: u32, (rounds, correct): (u32, u32)) -> Self {
+        Self { seconds, games, rounds, correct, streaks: Vec::new(), achievements: Vec::new(), remaining: Self::DURATION }
+    }
+
+    /// Best streaks of today as (game, streak); the longest few are shown.
+    pub fn streaks(mut self, mut streaks: Vec<(String, u32)>) -> Self {
+        streaks.retain(|(_, streak)| *streak > 0);
+        streaks.sort_by(|a, b| b.1.cmp(&a.1));
+        streaks.truncate(Self::MAX_STREAKS);
+        self.streaks = streaks;
+        self
+    }
+
+    pub fn achievements(mut self, achievements: Vec<String>) -> Self {
+        self.achievements = achievements;
+        self
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.remaining -= dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut played = vec![self.games.to_string().light_cyan(), if self.games == 1 { " game".white() } else { " games".white() }];
+        if self.rounds > 0 {
+            let accuracy = self.correct as f64 * 100.0 / self.rounds as f64;
+            played.extend([", ".white(), self.rounds.to_string().light_cyan(), format!(" rounds, {:.0}% correct", accuracy).white()]);
+        }
+        let mut lines = vec![
+            Line::from(vec![stats::format_duration(self.seconds).light_cyan(), " played".white()]),
+            Line::from(played),
+        ];
+        if !self.streaks.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Best streaks today".yellow()));
+            lines.extend(self.streaks.iter().map(|(game, streak)| Line::from(vec![format!("{} ", game).white(), streak.to_string().light_green()])));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Achievements".yellow()));
+        if self.achievements.is_empty() {
+            lines.push(Line::from("none this time".dark_gray()));
+        }
+        lines.extend(self.achievements.iter().map(|achievement| Line::from(achievement.clone().light_green())));
+        lines.push(Line::from(""));
+        lines.push(Line::from("<any key> quit now".dark_gray()));
+        lines
+    }
+}
+
+impl Widget for &SessionSummary {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let quit = format!("Quit ({})", self.remaining.max(0.0).ceil());
+        let modal = Modal::new("Session").body(self.lines()).buttons(&[&quit]).accent(Color::LightCyan);
+        modal.render(area, buf);
+    }
+}
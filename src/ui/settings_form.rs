@@ -0,0 +1,286 @@
+use crate::sound::{self, SoundEvent};
+use crate::ui::theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize, Widget};
+use std::cell::RefCell;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Toggle(bool),
+    Choice { options: Vec<String>, selected: usize },
+    Slider { value: i64, min: i64, max: i64, step: i64, unit: &'static str },
+    Key(Option<KeyEvent>),
+}
+
+/// One row of a [`SettingsForm`]: a label and a value the player edits in place.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    id: &'static str,
+    label: String,
+    value: Value,
+    hint: Option<String>,
+}
+
+impl Field {
+    /// On or off; Enter, Space and the arrows flip it.
+    pub fn toggle(id: &'static str, label: &str, on: bool) -> Self {
+        Self::new(id, label, Value::Toggle(on))
+    }
+
+    /// One of `options`; Left and Right cycle through them, Enter moves on to the next.
+    pub fn choice(id: &'static str, label: &str, options: Vec<String>, selected: usize) -> Self {
+        let selected = selected.min(options.len().saturating_sub(1));
+        Self::new(id, label, Value::Choice { options, selected })
+    }
+
+    /// A number in `min..=max`, changed by `step` with Left and Right.
+    pub fn slider(id: &'static str, label: &str, value: i64, min: i64, max: i64, step: i64) -> Self {
+        Self::new(id, label, Value::Slider { value: value.clamp(min, max), min, max, step: step.max(1), unit: "" })
+    }
+
+    /// A key binding; Enter waits for the next key press and takes it.
+    pub fn key(id: &'static str, label: &str, key: Option<KeyEvent>) -> Self {
+        Self::new(id, label, Value::Key(key))
+    }
+
+    fn new(id: &'static str, label: &str, value: Value) -> Self {
+        Self { id, label: label.to_string(), value, hint: None }
+    }
+
+    /// Dim text after the value, e.g. the hotkey that changes it from anywhere on the screen.
+    pub fn hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    /// Written after the number of a slider, e.g. `%`.
+    pub fn unit(mut self, unit: &'static str) -> Self {
+        if let Value::Slider { unit: ref mut current, .. } = self.value {
+            *current = unit;
+        }
+        self
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn is_on(&self) -> bool {
+        matches!(self.value, Value::Toggle(true))
+    }
+
+    pub fn selected(&self) -> usize {
+        if let Value::Choice { selected, .. } = self.value { selected } else { 0 }
+    }
+
+    pub fn number(&self) -> i64 {
+        if let Value::Slider { value, .. } = self.value { value } else { 0 }
+    }
+
+    pub fn key_event(&self) -> Option<KeyEvent> {
+        if let Value::Key(key) = self.value { key } else { None }
+    }
+
+    /// Step the value forwards or backwards; true if it changed.
+    fn step(&mut self, forward: bool) -> bool {
+        match &mut self.value {
+            Value::Toggle(on) => *on = !*on,
+            Value::Choice { options, selected } if !options.is_empty() => {
+                *selected = if forward { (*selected + 1) % options.len() } else { (*selected + options.len() - 1) % options.len() };
+            }
+            Value::Slider { value, min, max, step, .. } => {
+                let next = if forward { *value + *step } else { *value - *step }.clamp(*min, *max);
+                if next == *value { return false; }
+                *value = next;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn value_spans(&self, capturing: bool) -> Vec<Span<'static>> {
+        match &self.value {
+            Value::Toggle(true) => {
+                let on = theme::palette().good_light;
+                vec!["[x] ".fg(on), "on".fg(on)]
+            }
+            Value::Toggle(false) => vec!["[ ] ".dark_gray(), "off".white()],
+            Value::Choice { options, selected } => vec![
+                "< ".dark_gray(),
+                options.get(*selected).cloned().unwrap_or_default().light_cyan(),
+                " >".dark_gray(),
+            ],
+            Value::Slider { value, min, max, unit, .. } => {
+                const WIDTH: i64 = 10;
+                let filled = if max > min { (value - min) * WIDTH / (max - min) } else { WIDTH };
+                vec![
+                    "━".repeat(filled as usize).light_cyan(),
+                    "─".repeat((WIDTH - filled) as usize).dark_gray(),
+                    format!(" {}{}", value, unit).light_cyan(),
+                ]
+            }
+            Value::Key(_) if capturing => vec!["[ press a key ]".black().on_light_yellow()],
+            Value::Key(key) => vec![format!("[ {} ]", key.map_or("none".to_string(), key_name)).light_cyan()],
+        }
+    }
+}
+
+/// `Ctrl+Q`, `F5`, `Space`: how a captured key is shown.
+pub fn key_name(key: KeyEvent) -> String {
+    let code = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(ch) => ch.to_uppercase().to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        code => format!("{:?}", code),
+    };
+    let modifiers: String = [(KeyModifiers::CONTROL, "Ctrl+"), (KeyModifiers::ALT, "Alt+"), (KeyModifiers::SHIFT, "Shift+")].iter()
+        .filter(|(modifier, _)| key.modifiers.contains(*modifier))
+        .map(|(_, name)| *name)
+        .collect();
+    format!("{}{}", modifiers, code)
+}
+
+/// A column of labeled settings edited in place: Up and Down move the focus, Left and Right
+/// change the focused value, Enter flips toggles and starts capturing a key.
+///
+/// The form only holds the values; the owner reads a field back whenever
+/// [`handle_key`](SettingsForm::handle_key) or [`handle_mouse`](SettingsForm::handle_mouse)
+/// reports it changed, and stores it wherever the setting lives.
+///
+/// ```ignore
+/// let mut form = SettingsForm::new(vec![Field::toggle("muted", "Mute", config.muted)]);
+/// if let Some("muted") = form.handle_key(input) { config::update(|c| c.muted = form.field("muted").unwrap().is_on()) }
+/// ```
+pub struct SettingsForm {
+    fields: Vec<Field>,
+    focused: usize,
+    capturing: bool, // the focused key field waits for a key
+    label_width: usize,
+    row_areas: RefCell<Vec<(Rect, usize)>>, // last rendered rows, used for mouse hit testing
+}
+
+impl SettingsForm {
+    pub fn new(fields: Vec<Field>) -> Self {
+        let label_width = fields.iter().map(|field| field.label.chars().count()).max().unwrap_or(0) + 2;
+        Self { fields, focused: 0, capturing: false, label_width, row_areas: RefCell::new(Vec::new()) }
+    }
+
+    /// Move the focus to the field at `index`, e.g. to keep it when the form is rebuilt.
+    pub fn with_focus(mut self, index: usize) -> Self {
+        self.focused = index.min(self.fields.len().saturating_sub(1));
+        self
+    }
+
+    /// Rows needed to show every field; with fewer the form scrolls along with the focus.
+    pub fn height(&self) -> u16 {
+        self.fields.len() as u16
+    }
+
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    pub fn field(&self, id: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.id == id)
+    }
+
+    /// Waiting for the key of a key field; the owner should not treat the next key as a shortcut.
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// The id of the field the key changed, if any.
+    pub fn handle_key(&mut self, input: KeyEvent) -> Option<&'static str> {
+        if self.capturing {
+            self.capturing = false;
+            let field = self.fields.get_mut(self.focused)?;
+            field.value = Value::Key(Some(input));
+            sound::emit(SoundEvent::MenuSelect);
+            return Some(field.id);
+        }
+        match input.code {
+            KeyCode::Up | KeyCode::BackTab => self.move_focus(-1),
+            KeyCode::Down | KeyCode::Tab => self.move_focus(1),
+            KeyCode::Left => return self.step_focused(false),
+            KeyCode::Right => return self.step_focused(true),
+            KeyCode::Enter | KeyCode::Char(' ') => return self.activate_focused(),
+            _ => {}
+        }
+        None
+    }
+
+    /// A left click focuses a row and changes it like Enter; the scroll wheel moves the focus.
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> Option<&'static str> {
+        match event.kind {
+            MouseEventKind::ScrollUp => self.move_focus(-1),
+            MouseEventKind::ScrollDown => self.move_focus(1),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(event.column, event.row);
+                let index = self.row_areas.borrow().iter().find(|(area, _)| area.contains(position)).map(|(_, index)| *index)?;
+                self.focused = index;
+                self.capturing = false;
+                return self.activate_focused();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn move_focus(&mut self, delta: isize) {
+        if self.fields.is_empty() { return; }
+        self.focused = (self.focused as isize + delta).rem_euclid(self.fields.len() as isize) as usize;
+        sound::emit(SoundEvent::MenuMove);
+    }
+
+    fn step_focused(&mut self, forward: bool) -> Option<&'static str> {
+        let field = self.fields.get_mut(self.focused)?;
+        field.step(forward).then(|| {
+            sound::emit(SoundEvent::MenuMove);
+            field.id
+        })
+    }
+
+    fn activate_focused(&mut self) -> Option<&'static str> {
+        let field = self.fields.get_mut(self.focused)?;
+        match field.value {
+            Value::Key(_) => {
+                self.capturing = true;
+                None
+            }
+            Value::Slider { .. } => None,
+            _ => {
+                field.step(true);
+                sound::emit(SoundEvent::MenuSelect);
+                Some(field.id)
+            }
+        }
+    }
+}
+
+impl Widget for &SettingsForm {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // scroll so the focused row stays visible
+        let rows = area.height as usize;
+        let first = self.focused.saturating_sub(rows.saturating_sub(1));
+        let mut row_areas = Vec::new();
+        for (row, (index, field)) in self.fields.iter().enumerate().skip(first).take(rows).enumerate() {
+            let focused = index == self.focused;
+            let label_style = if focused { Style::default().fg(Color::Black).bg(Color::LightCyan).bold() } else { Style::default().fg(Color::White) };
+            let mut spans = vec![
+                Span::raw(if focused { "> " } else { "  " }),
+                Span::styled(format!("{:<width$}", field.label, width = self.label_width), label_style),
+                Span::raw(" "),
+            ];
+            spans.extend(field.value_spans(focused && self.capturing));
+            if let Some(hint) = &field.hint {
+                spans.push(format!("  {}", hint).dark_gray());
+            }
+            let row_area = Rect { y: area.y + row as u16, height: 1, ..area };
+            Line::from(spans).render(row_area, buf);
+            row_areas.push((row_area, index));
+        }
+        *self.row_areas.borrow_mut() = row_areas;
+    }
+}
@@ -0,0 +1,152 @@
+use crate::tween::{Animator, Easing};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Span, Style, Stylize, Widget};
+
+/// Eighths of a cell, for the end of a [`StatBar::fill`] bar.
+const PARTIAL: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Meter {
+    /// One symbol per point, full or empty.
+    Symbols { full: &'static str, empty: &'static str },
+    /// A bar of `width` cells filled in proportion, down to eighths of a cell.
+    Fill { width: u16 },
+}
+
+/// Lives, health, energy or experience drawn the same way in every game. Changes slide over
+/// instead of jumping; a point that is being gained or lost shows in white while it does.
+///
+/// The owner keeps the bar, [`set`](StatBar::set)s it whenever the value may have changed and
+/// [`tick`](StatBar::tick)s it every frame. It renders on its own or as [`spans`](StatBar::spans)
+/// inside a status line.
+///
+/// ```ignore
+/// let mut lives = StatBar::hearts(3).label("Lives: ");
+/// lives.set(self.lives as f64);
+/// Line::from([score_spans, lives.spans()].concat())
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatBar {
+    meter: Meter,
+    max: f64,
+    value: Animator,
+    color: Color,
+    label: Option<String>,
+}
+
+impl StatBar {
+    fn new(meter: Meter, max: u32, color: Color) -> Self {
+        Self { meter, max: max as f64, value: Animator::new(max as f64, 0.4, Easing::EaseOut), color, label: None }
+    }
+
+    /// `♥♥♡`, full at the start.
+    pub fn hearts(max: u32) -> Self {
+        Self::new(Meter::Symbols { full: "♥", empty: "♡" }, max, Color::Red)
+    }
+
+    /// `■■■□□`, for energy, ammo and the like; full at the start.
+    pub fn segments(max: u32) -> Self {
+        Self::new(Meter::Symbols { full: "■", empty: "□" }, max, Color::LightCyan)
+    }
+
+    /// An experience style bar of `width` cells, empty at the start.
+    pub fn fill(max: u32, width: u16) -> Self {
+        Self::new(Meter::Fill { width }, max, Color::LightGreen).starting_at(0.0)
+    }
+
+    /// Start at `value` instead of full (or empty, for a fill bar).
+    pub fn starting_at(mut self, value: f64) -> Self {
+        self.jump(value);
+        self
+    }
+
+    /// Other symbols for a full and an empty point of [`hearts`](StatBar::hearts) and [`segments`](StatBar::segments).
+    pub fn symbols(mut self, full: &'static str, empty: &'static str) -> Self {
+        if let Meter::Symbols { .. } = self.meter {
+            self.meter = Meter::Symbols { full, empty };
+        }
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Text in front of the bar, in the bar's color.
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Slide to `value`, if that is not where the bar is already heading.
+    pub fn set(&mut self, value: f64) {
+        let value = value.clamp(0.0, self.max);
+        if value != self.value.target() {
+            self.value.animate_to(value);
+        }
+    }
+
+    /// Show `value` right away, e.g. for a restored game.
+    pub fn jump(&mut self, value: f64) {
+        self.value.jump_to(value.clamp(0.0, self.max));
+    }
+
+    /// A new maximum, e.g. after a level up; the value is kept within it.
+    pub fn set_max(&mut self, max: u32) {
+        self.max = max as f64;
+        if self.value.target() > self.max {
+            self.jump(self.max);
+        }
+    }
+
+    pub fn tick(&mut self, dt: f64) {
+        self.value.tick(dt);
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.value.is_animating()
+    }
+
+    /// The label and the bar as they look right now.
+    pub fn spans(&self) -> Vec<Span<'static>> {
+        let shown = self.value.value();
+        let mut spans: Vec<Span<'static>> = self.label.iter().map(|label| Span::styled(label.clone(), Style::default().fg(self.color))).collect();
+        match self.meter {
+            Meter::Symbols { full, empty } => {
+                let target = self.value.target();
+                for point in 0..self.max as usize {
+                    let point = point as f64;
+                    let span = if point + 1.0 <= shown.min(target) {
+                        Span::styled(full, Style::default().fg(self.color))
+                    } else if point < shown.max(target) && self.is_animating() {
+                        Span::styled(full, Style::default().fg(Color::White).bold()) // changing hands
+                    } else {
+                        Span::styled(empty, Style::default().fg(Color::DarkGray))
+                    };
+                    spans.push(span);
+                }
+            }
+            Meter::Fill { width } => {
+                let ratio = if self.max > 0.0 { shown / self.max } else { 0.0 };
+                let eighths = (ratio * width as f64 * 8.0).round() as usize;
+                let (cells, rest) = (eighths / 8, eighths % 8);
+                let mut filled = "█".repeat(cells.min(width as usize));
+                if cells < width as usize && rest > 0 {
+                    filled.push_str(PARTIAL[rest]);
+                }
+                let empty = "░".repeat((width as usize).saturating_sub(cells + (rest > 0) as usize));
+                spans.push(Span::styled(filled, Style::default().fg(self.color)));
+                spans.push(Span::styled(empty, Style::default().fg(Color::DarkGray)));
+            }
+        }
+        spans
+    }
+}
+
+impl Widget for &StatBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Line::from(self.spans()).render(area, buf);
+    }
+}
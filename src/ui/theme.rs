@@ -0,0 +1,113 @@
+use crate::config;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Colors of a finished frame. Widgets draw in their usual colors and the theme maps them
+/// afterwards, so nothing has to know about it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Theme {
+    /// The colors as drawn.
+    #[default]
+    Classic,
+    /// Shades of green, like an old phosphor screen.
+    Phosphor,
+    /// Shades of orange, like an amber monitor.
+    Amber,
+    /// Shades of gray.
+    Mono,
+}
+
+impl Theme {
+    /// Recolor every cell of `buf`. The terminal's own colors (`Reset`) are left alone.
+    pub fn apply(self, buf: &mut Buffer) {
+        if self == Theme::Classic { return; }
+        for cell in buf.content.iter_mut() {
+            cell.fg = self.map(cell.fg);
+            cell.bg = self.map(cell.bg);
+        }
+    }
+
+    fn map(self, color: Color) -> Color {
+        let Some(level) = brightness(color) else { return color };
+        let shades: [(u8, u8, u8); 4] = match self {
+            Theme::Classic => return color,
+            Theme::Phosphor => [(0, 0, 0), (0, 110, 30), (40, 200, 70), (150, 255, 160)],
+            Theme::Amber => [(0, 0, 0), (120, 60, 0), (230, 140, 20), (255, 210, 120)],
+            Theme::Mono => [(0, 0, 0), (100, 100, 100), (180, 180, 180), (250, 250, 250)],
+        };
+        let (r, g, b) = shades[level];
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// The colors that tell right from wrong, and plenty from little on gauges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub good: Color,
+    pub good_light: Color,
+    pub warn: Color,
+    pub bad: Color,
+    pub bad_light: Color,
+    right_mark: &'static str,
+    wrong_mark: &'static str,
+}
+
+impl Palette {
+    pub const STANDARD: Palette = Palette {
+        good: Color::Green,
+        good_light: Color::LightGreen,
+        warn: Color::Yellow,
+        bad: Color::Red,
+        bad_light: Color::LightRed,
+        right_mark: "",
+        wrong_mark: "",
+    };
+
+    /// Blue, yellow and orange from the Okabe-Ito palette, which stay apart with the common kinds
+    /// of color blindness, and marks so nothing depends on color alone.
+    pub const COLORBLIND: Palette = Palette {
+        good: Color::Rgb(0, 114, 178),
+        good_light: Color::Rgb(86, 180, 233),
+        warn: Color::Rgb(240, 228, 66),
+        bad: Color::Rgb(213, 94, 0),
+        bad_light: Color::Rgb(230, 159, 0),
+        right_mark: "✓ ",
+        wrong_mark: "✗ ",
+    };
+
+    /// Written before a right or wrong answer: `✓ ` or `✗ `, nothing in the standard palette.
+    pub fn mark(&self, right: bool) -> &'static str {
+        if right { self.right_mark } else { self.wrong_mark }
+    }
+}
+
+/// The palette of the colorblind setting.
+pub fn palette() -> Palette {
+    if config::get().colorblind { Palette::COLORBLIND } else { Palette::STANDARD }
+}
+
+/// How bright a color reads, from 0 (black) to 3 (white); `None` for the terminal's default.
+fn brightness(color: Color) -> Option<usize> {
+    let level = match color {
+        Color::Reset => return None,
+        Color::Black => 0,
+        Color::DarkGray | Color::Blue | Color::Red | Color::Magenta => 1,
+        Color::Gray | Color::Green | Color::Cyan | Color::Yellow => 2,
+        Color::White | Color::LightRed | Color::LightGreen | Color::LightYellow | Color::LightBlue | Color::LightMagenta | Color::LightCyan => 3,
+        Color::Rgb(r, g, b) => match (r as u32 * 3 + g as u32 * 6 + b as u32) / 10 {
+            0..=20 => 0,
+            21..=100 => 1,
+            101..=190 => 2,
+            _ => 3,
+        },
+        Color::Indexed(index) => match index {
+            0 | 16 | 232..=237 => 0,
+            1..=6 | 238..=243 => 1,
+            7 | 244..=249 => 2,
+            _ => 3,
+        },
+    };
+    Some(level)
+}
@@ -0,0 +1,58 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style, Widget};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Wrap};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Seconds a toast stays on screen.
+const DURATION: f64 = 6.0;
+
+struct Toast {
+    text: String,
+    remaining: f64,
+}
+
+static QUEUE: Mutex<VecDeque<Toast>> = Mutex::new(VecDeque::new());
+
+/// Queue a short notice; toasts are shown one after another in the corner of the screen.
+pub fn show(text: impl Into<String>) {
+    QUEUE.lock().unwrap().push_back(Toast { text: text.into(), remaining: DURATION });
+}
+
+/// Count down the toast on screen, called once per frame by the app.
+pub fn advance(dt: f64) {
+    let mut queue = QUEUE.lock().unwrap();
+    if let Some(toast) = queue.front_mut() {
+        toast.remaining -= dt;
+        if toast.remaining <= 0.0 {
+            queue.pop_front();
+        }
+    }
+}
+
+pub fn is_active() -> bool {
+    !QUEUE.lock().unwrap().is_empty()
+}
+
+/// Draws the current toast in the top right corner of `area`.
+pub struct ToastArea;
+
+impl Widget for ToastArea {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let queue = QUEUE.lock().unwrap();
+        let Some(toast) = queue.front() else { return };
+
+        let width = (toast.text.chars().count() as u16 + 4).min(area.width).min(44);
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let height = (toast.text.chars().count().div_ceil(inner_width) as u16 + 2).min(area.height);
+        let popup = Rect::new(area.right().saturating_sub(width + 1), area.y + 1, width, height).intersection(area);
+
+        Clear.render(popup, buf);
+        Paragraph::new(toast.text.clone())
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(Color::White))
+            .block(Block::bordered().border_type(BorderType::Rounded).border_style(Style::default().fg(Color::LightYellow)))
+            .render(popup, buf);
+    }
+}
@@ -14,11 +14,28 @@ impl ToDuration for u64 {
     }
 }
 
+#[derive(Clone, Debug)]
 pub struct AsciiCell {
     pub ch: char,
     pub x: u16,
     pub y: u16,
     pub color: Color,
+    pub background: Option<Color>, // `None` keeps whatever is behind the art
+    pub modifier: Modifier,
+}
+
+/// Modifier for a character of a modifier layer: `b`old, `d`im, `i`talic, `u`nderlined,
+/// `k` for blink, `r`eversed. Anything else adds no modifier.
+pub fn modifier_for(ch: char) -> Modifier {
+    match ch {
+        'b' => Modifier::BOLD,
+        'd' => Modifier::DIM,
+        'i' => Modifier::ITALIC,
+        'u' => Modifier::UNDERLINED,
+        'k' => Modifier::SLOW_BLINK,
+        'r' => Modifier::REVERSED,
+        _ => Modifier::empty(),
+    }
 }
 
 pub fn parse_ascii_art(
@@ -44,6 +61,8 @@ pub fn parse_ascii_art(
                 x: x as u16,
                 y: y as u16,
                 color,
+                background: None,
+                modifier: Modifier::empty(),
             });
         }
     }
@@ -51,6 +70,115 @@ pub fn parse_ascii_art(
     pixels
 }
 
+/// Upper half of code page 437, the DOS character set classic ANSI art is drawn in.
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// How the bytes of an ANSI art file map to characters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AnsiCharset {
+    Utf8,
+    /// DOS code page 437. Lines wrap after 80 columns like on the DOS console the art was made for.
+    Cp437,
+}
+
+/// Turn text colored with ANSI escape sequences into cells.
+///
+/// Understands SGR colors (the 16 classic colors, where bold selects the light foreground,
+/// 256-color `38;5;n` and true color `38;2;r;g;b`, likewise for backgrounds), blink and cursor-forward
+/// `ESC[nC`. Other sequences are skipped. Reading stops at the DOS end-of-file mark, so SAUCE metadata is ignored.
+pub fn parse_ansi_art(input: &[u8], charset: AnsiCharset) -> AsciiCells {
+    const CLASSIC: [Color; 8] = [Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan, Color::Gray];
+    const LIGHT: [Color; 8] = [Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow, Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White];
+
+    let chars: Vec<char> = match charset {
+        AnsiCharset::Utf8 => String::from_utf8_lossy(input).chars().collect(),
+        AnsiCharset::Cp437 => input.iter()
+            .map(|&byte| if byte < 0x80 { byte as char } else { CP437_HIGH.chars().nth(byte as usize - 0x80).unwrap() })
+            .collect(),
+    };
+    let wrap_at = if charset == AnsiCharset::Cp437 { Some(80) } else { None };
+
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0u16, 0u16);
+    let mut classic: Option<usize> = None; // index into CLASSIC/LIGHT, resolved late because bold may come after the color
+    let mut extended: Option<Color> = None;
+    let mut bold = false;
+    let mut background: Option<Color> = None;
+    let mut blink = false;
+    let mut chars = chars.into_iter().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1a}' => break,
+            '\n' => { x = 0; y = y.saturating_add(1); }
+            '\r' => x = 0,
+            '\u{1b}' if chars.peek() == Some(&'[') => {
+                chars.next();
+                let mut sequence = String::new();
+                let command = loop {
+                    match chars.next() {
+                        Some(c) if c.is_ascii_digit() || c == ';' || c == '?' => sequence.push(c),
+                        other => break other,
+                    }
+                };
+                let params: Vec<u16> = sequence.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+                match command {
+                    Some('C') => {
+                        let moved = x.saturating_add(params[0].max(1));
+                        // like the console cursor, it stops at the right edge instead of wrapping
+                        x = match wrap_at { Some(width) if x < width => moved.min(width - 1), _ => moved };
+                    }
+                    Some('m') => {
+                        let mut params = params.into_iter();
+                        while let Some(param) = params.next() {
+                            match param {
+                                0 => { classic = None; extended = None; bold = false; background = None; blink = false; }
+                                1 => bold = true,
+                                5 => blink = true,
+                                22 => bold = false,
+                                25 => blink = false,
+                                40..=47 => background = Some(CLASSIC[param as usize - 40]),
+                                100..=107 => background = Some(LIGHT[param as usize - 100]),
+                                49 => background = None,
+                                30..=37 => { classic = Some(param as usize - 30); extended = None; }
+                                90..=97 => { extended = Some(LIGHT[param as usize - 90]); }
+                                39 => { classic = None; extended = None; }
+                                38 | 48 => {
+                                    let color = match params.next() {
+                                        Some(5) => params.next().map(|n| Color::Indexed(n as u8)),
+                                        Some(2) => match (params.next(), params.next(), params.next()) {
+                                            (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r as u8, g as u8, b as u8)),
+                                            _ => None,
+                                        },
+                                        _ => None,
+                                    };
+                                    if param == 38 { extended = color } else { background = color }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ if ch.is_control() => {}
+            _ => {
+                if wrap_at.is_some_and(|width| x >= width) {
+                    x = 0;
+                    y = y.saturating_add(1);
+                }
+                let color = extended
+                    .or(classic.map(|i| if bold { LIGHT[i] } else { CLASSIC[i] }))
+                    .unwrap_or(if bold { Color::White } else { Color::Reset });
+                let modifier = if blink { Modifier::SLOW_BLINK } else { Modifier::empty() };
+                cells.push(AsciiCell { ch, x, y, color, background, modifier });
+                x = x.saturating_add(1);
+            }
+        }
+    }
+    AsciiCells::new(cells)
+}
+
+#[derive(Clone, Debug)]
 pub struct AsciiCells {
     pub cells: Vec<AsciiCell>,
 }
@@ -69,6 +197,32 @@ impl AsciiCells {
         Self { cells: parse_ascii_art(art, color_map_str, color_map, default_color) }
     }
 
+    /// Fill cells with the colors of a background layer. The layer has the size of the art,
+    /// characters missing from `color_map` leave the background alone.
+    pub fn with_background_layer(mut self, layer: &str, color_map: &HashMap<char, Color>) -> Self {
+        self.overlay(layer, |cell, ch| {
+            if let Some(color) = color_map.get(&ch) {
+                cell.background = Some(*color);
+            }
+        });
+        self
+    }
+
+    /// Emphasize cells with a layer of modifier characters, see [`modifier_for`].
+    pub fn with_modifier_layer(mut self, layer: &str) -> Self {
+        self.overlay(layer, |cell, ch| cell.modifier |= modifier_for(ch));
+        self
+    }
+
+    fn overlay(&mut self, layer: &str, mut apply: impl FnMut(&mut AsciiCell, char)) {
+        let rows: Vec<Vec<char>> = layer.lines().map(|line| line.chars().collect()).collect();
+        for cell in &mut self.cells {
+            if let Some(&ch) = rows.get(cell.y as usize).and_then(|row| row.get(cell.x as usize)) {
+                apply(cell, ch);
+            }
+        }
+    }
+
     pub fn get_width(&self) -> u16 {
         self.cells.iter().map(|cell| cell.x).max().unwrap_or(0) + 1
     }
@@ -99,14 +253,288 @@ impl AsciiArtWidget {
 
 impl Widget for AsciiArtWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        for pixel in self.collection.cells {
+        self.collection.render(area, buf);
+    }
+}
+
+impl Widget for &AsciiCells {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for pixel in &self.cells {
             let position = Position::new(pixel.x + area.x, pixel.y + area.y);
 
             if area.contains(position) {
-                buf.cell_mut(position)
+                let cell = buf.cell_mut(position)
                     .expect("Failed to get cell at position")
                     .set_char(pixel.ch)
-                    .set_fg(pixel.color);
+                    .set_fg(pixel.color)
+                    .set_style(Style::default().add_modifier(pixel.modifier));
+                if let Some(background) = pixel.background {
+                    cell.set_bg(background);
+                }
+            }
+        }
+    }
+}
+
+struct CanvasLayer {
+    z: i32,
+    x: u16,
+    y: u16,
+    cells: AsciiCells,
+}
+
+/// Layers of [`AsciiCells`] drawn on top of each other: a scene, sprites, a HUD.
+///
+/// Layers with a higher `z` cover lower ones, layers with equal `z` cover those added before them.
+/// Cells showing the transparent character (a space unless changed) let the layers below show through.
+///
+/// ```ignore
+/// let canvas = AsciiCanvas::new().layer(0, 0, 0, background).layer(1, dino_x, ground_y, dino);
+/// canvas.render(area, buf);
+/// ```
+pub struct AsciiCanvas {
+    layers: Vec<CanvasLayer>,
+    transparent: Option<char>,
+}
+
+impl AsciiCanvas {
+    pub fn new() -> Self {
+        Self { layers: Vec::new(), transparent: Some(' ') }
+    }
+
+    /// The character treated as see-through; `None` makes every cell opaque.
+    pub fn transparent(mut self, ch: Option<char>) -> Self {
+        self.transparent = ch;
+        self
+    }
+
+    /// Add `cells` with their top left corner at `x`, `y` of the canvas.
+    pub fn layer(mut self, z: i32, x: u16, y: u16, cells: AsciiCells) -> Self {
+        self.add_layer(z, x, y, cells);
+        self
+    }
+
+    pub fn add_layer(&mut self, z: i32, x: u16, y: u16, cells: AsciiCells) {
+        self.layers.push(CanvasLayer { z, x, y, cells });
+        self.layers.sort_by_key(|layer| layer.z); // stable, so equal z keeps insertion order
+    }
+
+    /// All layers merged into one set of cells, the visible cell at each position.
+    pub fn flatten(&self) -> AsciiCells {
+        let mut merged: HashMap<(u16, u16), AsciiCell> = HashMap::new();
+        for layer in &self.layers {
+            for cell in layer.cells.cells.iter().filter(|cell| Some(cell.ch) != self.transparent) {
+                // far off layers pile up at the last position, outside of any area
+                let (x, y) = (cell.x.saturating_add(layer.x), cell.y.saturating_add(layer.y));
+                merged.insert((x, y), AsciiCell { x, y, ..cell.clone() });
+            }
+        }
+        let mut cells: Vec<AsciiCell> = merged.into_values().collect();
+        cells.sort_by_key(|cell| (cell.y, cell.x));
+        AsciiCells::new(cells)
+    }
+}
+
+impl Widget for &AsciiCanvas {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.flatten().render(area, buf);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LoopMode {
+    /// Stop on the last frame.
+    Once,
+    /// Start over after the last frame.
+    Loop,
+    /// Play forwards, then backwards, and so on.
+    PingPong,
+}
+
+/// Art made of several frames, each shown for its own number of seconds.
+///
+/// ```ignore
+/// let mut animation = AsciiAnimation::new(LoopMode::Loop).frame(still, 2.0).frame(blink, 0.2);
+/// animation.tick(dt);
+/// animation.current_frame().render(area, buf);
+/// ```
+pub struct AsciiAnimation {
+    frames: Vec<(AsciiCells, f64)>,
+    mode: LoopMode,
+    current: usize,
+    elapsed: f64,
+    backwards: bool,
+}
+
+impl AsciiAnimation {
+    pub fn new(mode: LoopMode) -> Self {
+        Self { frames: Vec::new(), mode, current: 0, elapsed: 0.0, backwards: false }
+    }
+
+    pub fn frame(mut self, cells: AsciiCells, seconds: f64) -> Self {
+        self.frames.push((cells, seconds.max(0.01))); // a zero duration would never let `tick` return
+        self
+    }
+
+    /// Advance by `dt` seconds, skipping as many frames as have elapsed.
+    pub fn tick(&mut self, dt: f64) {
+        if self.frames.len() < 2 {
+            return;
+        }
+        self.elapsed += dt;
+        while self.elapsed >= self.frames[self.current].1 && !self.is_finished() {
+            self.elapsed -= self.frames[self.current].1;
+            self.current = self.next_index();
+        }
+    }
+
+    fn next_index(&mut self) -> usize {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            LoopMode::Once => (self.current + 1).min(last),
+            LoopMode::Loop => (self.current + 1) % self.frames.len(),
+            LoopMode::PingPong => {
+                if self.current == last { self.backwards = true }
+                if self.current == 0 { self.backwards = false }
+                if self.backwards { self.current - 1 } else { self.current + 1 }
+            }
+        }
+    }
+
+    /// Only a [`LoopMode::Once`] animation ever finishes.
+    pub fn is_finished(&self) -> bool {
+        self.mode == LoopMode::Once && self.current + 1 >= self.frames.len()
+    }
+
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.elapsed = 0.0;
+        self.backwards = false;
+    }
+
+    /// `None` while the animation has no frames.
+    pub fn current_frame(&self) -> Option<&AsciiCells> {
+        self.frames.get(self.current).map(|(cells, _)| cells)
+    }
+}
+
+/// A pixel grid drawn with braille characters, 2 pixels wide and 4 high per cell,
+/// for graphs and graphics finer than whole cells. Cells without pixels are left untouched.
+///
+/// ```ignore
+/// let mut canvas = BrailleCanvas::new(area.width, area.height);
+/// canvas.line(0, 0, canvas.pixel_width() as i32 - 1, canvas.pixel_height() as i32 - 1);
+/// canvas.circle(20, 8, 6);
+/// canvas.style(Style::new().light_cyan()).render(area, buf);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BrailleCanvas {
+    width: u16, // in cells
+    height: u16,
+    dots: Vec<u8>, // braille dot bits per cell, row by row
+    style: Style,
+}
+
+impl BrailleCanvas {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height, dots: vec![0; width as usize * height as usize], style: Style::default() }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn pixel_width(&self) -> u16 {
+        self.width * 2
+    }
+
+    pub fn pixel_height(&self) -> u16 {
+        self.height * 4
+    }
+
+    /// Index of the cell holding pixel `(x, y)` and the bit of its dot, `None` outside the canvas.
+    fn dot(&self, x: i32, y: i32) -> Option<(usize, u8)> {
+        if x < 0 || y < 0 || x >= self.pixel_width() as i32 || y >= self.pixel_height() as i32 {
+            return None;
+        }
+        // braille numbers its dots down the left column, then down the right one, with the bottom row last
+        const BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        let cell = (y / 4) as usize * self.width as usize + (x / 2) as usize;
+        Some((cell, BITS[(y % 4) as usize][(x % 2) as usize]))
+    }
+
+    /// Pixels outside the canvas are ignored, so shapes may be drawn partly off screen.
+    pub fn set(&mut self, x: i32, y: i32) {
+        if let Some((cell, bit)) = self.dot(x, y) {
+            self.dots[cell] |= bit;
+        }
+    }
+
+    pub fn clear(&mut self, x: i32, y: i32) {
+        if let Some((cell, bit)) = self.dot(x, y) {
+            self.dots[cell] &= !bit;
+        }
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> bool {
+        self.dot(x, y).is_some_and(|(cell, bit)| self.dots[cell] & bit != 0)
+    }
+
+    pub fn clear_all(&mut self) {
+        self.dots.fill(0);
+    }
+
+    /// Bresenham's line, both ends included.
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (step_x, step_y) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        loop {
+            self.set(x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Midpoint circle outline around `(cx, cy)`.
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32) {
+        let (mut x, mut y, mut error) = (radius, 0, 1 - radius);
+        while x >= y {
+            for (px, py) in [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)] {
+                self.set(cx + px, cy + py);
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+}
+
+impl Widget for &BrailleCanvas {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (index, dots) in self.dots.iter().enumerate().filter(|(_, dots)| **dots != 0) {
+            let (x, y) = ((index % self.width as usize) as u16, (index / self.width as usize) as u16);
+            if x >= area.width || y >= area.height {
+                continue;
+            }
+            let symbol = char::from_u32(0x2800 + *dots as u32).unwrap_or(' ');
+            if let Some(cell) = buf.cell_mut((area.x + x, area.y + y)) {
+                cell.set_char(symbol).set_style(self.style);
             }
         }
     }
@@ -147,4 +575,60 @@ impl<T> When for T {
             self
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(cells: &AsciiCells) -> Vec<(char, u16, u16)> {
+        cells.cells.iter().map(|cell| (cell.ch, cell.x, cell.y)).collect()
+    }
+
+    #[test]
+    fn cursor_forward_stops_at_the_right_edge_of_dos_art() {
+        let art = parse_ansi_art(b"a\x1b[65535Cb\x1b[10Cc\r\nd\x1b[Ce", AnsiCharset::Cp437);
+        assert_eq!(positions(&art), [('a', 0, 0), ('b', 79, 0), ('c', 0, 1), ('d', 0, 2), ('e', 2, 2)]);
+    }
+
+    #[test]
+    fn huge_moves_do_not_overflow() {
+        let art = parse_ansi_art(b"\x1b[65535Cab\x1b[65535C\x1b[99999999Cc", AnsiCharset::Utf8);
+        assert_eq!(positions(&art), [('a', u16::MAX, 0), ('b', u16::MAX, 0), ('c', u16::MAX, 0)]);
+        let tall = parse_ansi_art(&[b"\n".repeat(70_000), b"x".to_vec()].concat(), AnsiCharset::Utf8);
+        assert_eq!(positions(&tall), [('x', 0, u16::MAX)]);
+    }
+
+    #[test]
+    fn colors_follow_the_sgr_sequences() {
+        let art = parse_ansi_art(b"\x1b[31ma\x1b[1mb\x1b[0;44;5mc\x1b[38;5;208;48;2;1;2;3md\x1b[92;25me\x1b[39;49mf", AnsiCharset::Utf8);
+        let styles: Vec<(char, Color, Option<Color>, Modifier)> = art.cells.iter().map(|cell| (cell.ch, cell.color, cell.background, cell.modifier)).collect();
+        assert_eq!(styles, [
+            ('a', Color::Red, None, Modifier::empty()),
+            ('b', Color::LightRed, None, Modifier::empty()), // bold picks the light color
+            ('c', Color::Reset, Some(Color::Blue), Modifier::SLOW_BLINK),
+            ('d', Color::Indexed(208), Some(Color::Rgb(1, 2, 3)), Modifier::SLOW_BLINK),
+            ('e', Color::LightGreen, Some(Color::Rgb(1, 2, 3)), Modifier::empty()),
+            ('f', Color::Reset, None, Modifier::empty()),
+        ]);
+    }
+
+    #[test]
+    fn dos_art_is_read_in_code_page_437_up_to_the_end_of_file_mark() {
+        let art = parse_ansi_art(b"\xdb\xb0\x1b[2C\xc9\x1aSAUCE00", AnsiCharset::Cp437);
+        assert_eq!(positions(&art), [('█', 0, 0), ('░', 1, 0), ('╔', 4, 0)]);
+    }
+
+    #[test]
+    fn canvas_layers_cover_each_other_by_z_and_far_off_ones_do_not_overflow() {
+        let art = |text: &str| AsciiCells::new(text.lines().enumerate().flat_map(|(y, row)| row.chars().enumerate().map(move |(x, ch)| {
+            AsciiCell { ch, x: x as u16, y: y as u16, color: Color::White, background: None, modifier: Modifier::empty() }
+        })).collect());
+        let canvas = AsciiCanvas::new()
+            .layer(1, 1, 0, art("b b"))
+            .layer(0, 0, 0, art("aaaa"))
+            .layer(2, u16::MAX, u16::MAX, art("cc\ncc"));
+        let cells = positions(&canvas.flatten());
+        assert_eq!(&cells[..4], [('a', 0, 0), ('b', 1, 0), ('a', 2, 0), ('b', 3, 0)]);
+        assert_eq!(cells[4..], [('c', u16::MAX, u16::MAX)]);
+    }
+}
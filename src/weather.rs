@@ -0,0 +1,401 @@
+use crate::config;
+use crate::geocoding::Place;
+use crate::http;
+use crate::stats;
+use crate::storage::migration::{self, Schema};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum_macros::{Display, EnumString};
+
+pub use crate::http::FetchError;
+
+const CACHE: Schema = Schema { namespace: "weather", key: "cache", steps: &[migration::unversioned, forget_made_up, forget_keyed_by_name] };
+
+/// Forecasts of [Open-Meteo](https://open-meteo.com), free and without an API key.
+const FORECAST_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Warnings of the US National Weather Service, the only ones with a free API for any point.
+const ALERTS_URL: &str = "https://api.weather.gov/alerts/active";
+
+/// Reports cached before there was a weather service were made up from the place and the hour.
+fn forget_made_up(_: &str) -> Result<String, String> { Ok(String::new()) }
+
+/// Reports were kept by `Name, CC`, so places of the same name shared one; a name does not tell
+/// which of them it was.
+fn forget_keyed_by_name(_: &str) -> Result<String, String> { Ok(String::new()) }
+
+/// Current conditions at one place, and what comes next.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub code: u8, // WMO weather code, see [`crate::games::weather_scene::Scene::for_code`]
+    pub temperature: f64, // °C
+    pub feels_like: f64, // °C
+    pub wind: f64, // km/h
+    pub alert: Option<Alert>, // the most severe warning in effect, if any
+    pub hours: Vec<Hour>, // the next 24, starting with the one of the report
+    pub days: Vec<Day>, // the week ahead, starting with the day of the report
+}
+
+/// One hour of the forecast.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hour {
+    pub hour: u8, // of the day at the place, 0 to 23
+    pub temperature: f64, // °C
+    pub precipitation: u8, // chance in percent
+}
+
+/// One day of the forecast.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Day {
+    pub day: i64, // at the place, see [`stats::today`]
+    pub code: u8, // WMO weather code
+    pub low: f64, // °C
+    pub high: f64, // °C
+    pub precipitation: u8, // highest chance in percent
+    pub wind: f64, // strongest, km/h
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Display, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Severity { Minor, Moderate, Severe }
+
+/// A warning issued for the place, e.g. a winter storm warning.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub severity: Severity,
+    pub event: String, // what it is called, `Flood watch`
+    pub description: String,
+}
+
+impl Alert {
+    pub fn new(severity: Severity, event: &str, description: &str) -> Self {
+        Self { severity, event: event.to_string(), description: description.to_string() }
+    }
+}
+
+/// The last report fetched for a place and when it arrived.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cached {
+    pub fetched_at: u64, // unix seconds
+    pub report: Report,
+}
+
+impl Cached {
+    /// Seconds since the report was fetched.
+    pub fn age(&self) -> u64 {
+        storage::unix_now().saturating_sub(self.fetched_at)
+    }
+
+    /// True while the report is younger than the refresh interval of the config.
+    pub fn is_fresh(&self) -> bool {
+        self.age() < config::get().weather_ttl_minutes as u64 * 60
+    }
+}
+
+/// What the weather screen shows: the newest report there is for a place, and why it is not newer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Conditions {
+    pub cached: Option<Cached>,
+    pub error: Option<FetchError>,
+}
+
+/// Where the cache keeps the report of `place`: its coordinates, as precise as they are asked for
+/// in [`fetch`]. Names are not enough, there are Springfields all over the US.
+fn key(place: &Place) -> String {
+    format!("{:.4},{:.4}", place.latitude, place.longitude)
+}
+
+/// Reports by [`key`], stored as JSON.
+fn load_cache() -> BTreeMap<String, Cached> {
+    migration::load(&CACHE).ok().flatten().and_then(|contents| serde_json::from_str(&contents).ok()).unwrap_or_default()
+}
+
+/// The last report stored for `place`, however old.
+pub fn cached(place: &Place) -> Option<Cached> {
+    load_cache().remove(&key(place))
+}
+
+fn store(place: &Place, cached: Cached) {
+    let mut cache = load_cache();
+    cache.insert(key(place), cached);
+    let _ = migration::save(&CACHE, &serde_json::to_string_pretty(&cache).expect("plain structs always serialize"));
+}
+
+/// The answer of the forecast API, with the variables asked for in [`fetch`].
+#[derive(Deserialize)]
+struct Forecast {
+    current: CurrentValues,
+    hourly: HourlyValues,
+    daily: DailyValues,
+}
+
+#[derive(Deserialize)]
+struct CurrentValues {
+    time: String, // local, `2026-10-16T14:15`
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    weather_code: u8,
+    wind_speed_10m: f64,
+}
+
+/// One list per variable, the values of an hour at the same index. Values the models do not have
+/// are null.
+#[derive(Deserialize)]
+struct HourlyValues {
+    time: Vec<String>, // local, `2026-10-16T14:00`
+    temperature_2m: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<u8>>,
+}
+
+#[derive(Deserialize)]
+struct DailyValues {
+    time: Vec<String>, // local, `2026-10-16`
+    weather_code: Vec<Option<u8>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    temperature_2m_max: Vec<Option<f64>>,
+    precipitation_probability_max: Vec<Option<u8>>,
+    wind_speed_10m_max: Vec<Option<f64>>,
+}
+
+/// Days since 1970-01-01 of a `2026-10-16` date.
+fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.get(..10)?.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    Some(stats::days_from_civil(year, month as u32, day as u32))
+}
+
+/// A report from the answer of the forecast API: the current conditions, the 24 hours from the
+/// current one on and the days. Hours and days with missing values are left out.
+fn parse_forecast(json: &str) -> Result<Report, FetchError> {
+    let forecast: Forecast = serde_json::from_str(json).map_err(|_| FetchError::Invalid)?;
+    let (current, hourly, daily) = (&forecast.current, &forecast.hourly, &forecast.daily);
+    let this_hour = current.time.get(..13).ok_or(FetchError::Invalid)?;
+    let first = hourly.time.iter().position(|time| time.get(..13).is_some_and(|hour| hour >= this_hour)).unwrap_or(hourly.time.len());
+    let hours = (first..hourly.time.len())
+        .filter_map(|index| Some(Hour {
+            hour: hourly.time[index].get(11..13)?.parse().ok()?,
+            temperature: (*hourly.temperature_2m.get(index)?)?,
+            precipitation: (*hourly.precipitation_probability.get(index)?)?,
+        }))
+        .take(24)
+        .collect();
+    let days = (0..daily.time.len())
+        .filter_map(|index| Some(Day {
+            day: parse_date(&daily.time[index])?,
+            code: (*daily.weather_code.get(index)?)?,
+            low: (*daily.temperature_2m_min.get(index)?)?,
+            high: (*daily.temperature_2m_max.get(index)?)?,
+            precipitation: (*daily.precipitation_probability_max.get(index)?)?,
+            wind: (*daily.wind_speed_10m_max.get(index)?)?,
+        }))
+        .collect();
+    Ok(Report {
+        code: current.weather_code,
+        temperature: current.temperature_2m,
+        feels_like: current.apparent_temperature,
+        wind: current.wind_speed_10m,
+        alert: None,
+        hours,
+        days,
+    })
+}
+
+/// The answer of the alerts API, a GeoJSON collection with one feature per alert.
+#[derive(Deserialize)]
+struct Alerts {
+    features: Vec<AlertFeature>,
+}
+
+#[derive(Deserialize)]
+struct AlertFeature {
+    properties: AlertProperties,
+}
+
+#[derive(Deserialize)]
+struct AlertProperties {
+    event: String, // `Flood Warning`
+    severity: String, // `Extreme`, `Severe`, `Moderate`, `Minor` or `Unknown`
+    headline: Option<String>,
+    description: Option<String>,
+}
+
+/// The most severe alert in the answer of the alerts API, the first one among equals. Extreme
+/// alerts count as severe, unknown ones as minor.
+fn parse_alerts(json: &str) -> Result<Option<Alert>, FetchError> {
+    let alerts: Alerts = serde_json::from_str(json).map_err(|_| FetchError::Invalid)?;
+    let alerts = alerts.features.into_iter().map(|feature| {
+        let properties = feature.properties;
+        let severity = match properties.severity.as_str() {
+            "Extreme" | "Severe" => Severity::Severe,
+            "Moderate" => Severity::Moderate,
+            _ => Severity::Minor,
+        };
+        // descriptions come wrapped at 70 columns, with blank lines between paragraphs
+        let text = properties.description.or(properties.headline).unwrap_or_default();
+        let description = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        Alert::new(severity, &properties.event, &description)
+    });
+    Ok(alerts.fold(None, |most: Option<Alert>, alert| match most {
+        Some(most) if most.severity >= alert.severity => Some(most),
+        _ => Some(alert),
+    }))
+}
+
+/// The alert in effect at `place`. Only places in the US have alerts.
+fn fetch_alert(place: &Place) -> Result<Option<Alert>, FetchError> {
+    if place.country != "US" {
+        return Ok(None);
+    }
+    let point = format!("{:.4},{:.4}", place.latitude, place.longitude);
+    parse_alerts(&http::get(ALERTS_URL, &[("point", point)])?)
+}
+
+/// Ask the weather services for the conditions at `place` and the alert in effect there. Blocks until it answers, see
+/// [`http::in_background`].
+pub fn fetch(place: &Place) -> Result<Report, FetchError> {
+    if config::get().weather_offline {
+        return Err(FetchError::Offline);
+    }
+    let json = http::get(FORECAST_URL, &[
+        ("latitude", format!("{:.4}", place.latitude)),
+        ("longitude", format!("{:.4}", place.longitude)),
+        ("current", "temperature_2m,apparent_temperature,weather_code,wind_speed_10m".to_string()),
+        ("hourly", "temperature_2m,precipitation_probability".to_string()),
+        ("daily", "weather_code,temperature_2m_min,temperature_2m_max,precipitation_probability_max,wind_speed_10m_max".to_string()),
+        ("timezone", "auto".to_string()), // times and days of the place
+        ("forecast_days", "7".to_string()),
+    ])?;
+    let mut report = parse_forecast(&json)?;
+    // a forecast is worth showing even when the alerts service fails
+    report.alert = fetch_alert(place).unwrap_or(None);
+    Ok(report)
+}
+
+/// The conditions at `place`: the cached report while it is fresh, a new one otherwise. When
+/// fetching fails the cached report is kept, however old, together with the reason. `force`
+/// fetches even while the cache is fresh. Without a place there is nothing to show.
+pub fn conditions(place: Option<&Place>, force: bool) -> Conditions {
+    let Some(place) = place else { return Conditions::default() };
+    let cached = cached(place);
+    if !force && cached.as_ref().is_some_and(|cached| cached.is_fresh()) {
+        return Conditions { cached, error: None };
+    }
+    match fetch(place) {
+        Ok(report) => {
+            let fresh = Cached { fetched_at: storage::unix_now(), report };
+            store(place, fresh.clone());
+            Conditions { cached: Some(fresh), error: None }
+        }
+        Err(error) => Conditions { cached, error: Some(error) },
+    }
+}
+
+/// The alert in effect at the current location, as the text of a toast shown on start when
+/// [`Config::weather_alert_toasts`](config::Config::weather_alert_toasts) is on. May ask the
+/// service, so run it in the background.
+pub fn alert_notice() -> Option<String> {
+    let config = config::get();
+    let place = config.location.as_ref().filter(|_| config.weather_alert_toasts)?;
+    let alert = conditions(Some(place), false).cached?.report.alert?;
+    Some(format!("{} for {}: {}", alert.event, place, alert.description))
+}
+
+/// `just now`, `12 min ago`, `3 h ago`, `2 days ago`
+pub fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", seconds / 60),
+        3600..86400 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} day{} ago", seconds / 86400, if seconds < 2 * 86400 { "" } else { "s" }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWER: &str = r#"{
+        "latitude": 52.52, "longitude": 13.42, "timezone": "Europe/Berlin",
+        "current": {"time": "2026-10-16T14:15", "interval": 900, "temperature_2m": 11.3, "apparent_temperature": 9.1, "weather_code": 61, "wind_speed_10m": 14.8},
+        "hourly": {
+            "time": ["2026-10-16T13:00", "2026-10-16T14:00", "2026-10-16T15:00", "2026-10-16T16:00"],
+            "temperature_2m": [11.0, 11.2, null, 10.4],
+            "precipitation_probability": [80, 75, 60, 40]
+        },
+        "daily": {
+            "time": ["2026-10-16", "2026-10-17"],
+            "weather_code": [61, 3],
+            "temperature_2m_min": [7.2, 5.9],
+            "temperature_2m_max": [12.1, 13.4],
+            "precipitation_probability_max": [85, null],
+            "wind_speed_10m_max": [21.6, 12.0]
+        }
+    }"#;
+
+    #[test]
+    fn forecasts_start_with_the_current_hour_and_skip_missing_values() {
+        let report = parse_forecast(ANSWER).unwrap();
+        assert_eq!((report.code, report.temperature, report.feels_like, report.wind), (61, 11.3, 9.1, 14.8));
+        assert_eq!(report.hours, vec![
+            Hour { hour: 14, temperature: 11.2, precipitation: 75 },
+            Hour { hour: 16, temperature: 10.4, precipitation: 40 },
+        ]);
+        assert_eq!(report.days, vec![
+            Day { day: stats::days_from_civil(2026, 10, 16), code: 61, low: 7.2, high: 12.1, precipitation: 85, wind: 21.6 },
+        ]);
+        assert_eq!(report.alert, None);
+    }
+
+    #[test]
+    fn unexpected_answers_are_invalid() {
+        assert_eq!(parse_forecast("{\"error\": true, \"reason\": \"Latitude must be in range\"}"), Err(FetchError::Invalid));
+        assert_eq!(parse_forecast("<html>"), Err(FetchError::Invalid));
+    }
+
+    #[test]
+    fn the_most_severe_alert_is_shown() {
+        let answer = r#"{"type": "FeatureCollection", "features": [
+            {"properties": {"event": "Wind Advisory", "severity": "Moderate", "headline": "Wind Advisory until 6 PM", "description": "Gusts up to 45 mph."}},
+            {"properties": {"event": "Flash Flood Warning", "severity": "Extreme", "headline": null, "description": "* WHAT...Flash flooding caused by\nexcessive rainfall.\n\n* WHERE...Harris County."}},
+            {"properties": {"event": "Flood Watch", "severity": "Severe", "description": null, "headline": "Flood Watch through Friday"}}
+        ]}"#;
+        let alert = parse_alerts(answer).unwrap().unwrap();
+        assert_eq!(alert, Alert::new(Severity::Severe, "Flash Flood Warning", "* WHAT...Flash flooding caused by excessive rainfall. * WHERE...Harris County."));
+        assert_eq!(parse_alerts(r#"{"features": [], "title": "Current watches, warnings, and advisories"}"#), Ok(None));
+        assert_eq!(parse_alerts(r#"{"status": 400, "detail": "Invalid point"}"#), Err(FetchError::Invalid));
+    }
+
+    #[test]
+    fn the_cache_keeps_reports_as_they_were() {
+        let berlin = Place { name: "Berlin".to_string(), country: "DE".to_string(), latitude: 52.52, longitude: 13.42 };
+        let mut report = parse_forecast(ANSWER).unwrap();
+        report.alert = Some(Alert::new(Severity::Severe, "Flood Warning", "Rivers above\nflood stage"));
+        let cache = BTreeMap::from([(key(&berlin), Cached { fetched_at: 1_792_000_000, report })]);
+        let json = serde_json::to_string_pretty(&cache).unwrap();
+        assert_eq!(serde_json::from_str::<BTreeMap<String, Cached>>(&json).unwrap(), cache);
+    }
+
+    #[test]
+    fn places_of_the_same_name_keep_reports_of_their_own() {
+        storage::install_for_tests();
+        let springfield = |latitude, longitude| Place { name: "Springfield".to_string(), country: "US".to_string(), latitude, longitude };
+        let (illinois, missouri) = (springfield(39.80, -89.64), springfield(37.21, -93.29));
+        let report = parse_forecast(ANSWER).unwrap();
+        let warm = Cached { fetched_at: 1_792_000_000, report: Report { temperature: 24.0, ..report.clone() } };
+        let cold = Cached { fetched_at: 1_792_000_600, report: Report { temperature: -3.0, ..report } };
+        store(&illinois, warm.clone());
+        store(&missouri, cold.clone());
+        assert_eq!(cached(&illinois), Some(warm));
+        assert_eq!(cached(&missouri), Some(cold));
+    }
+
+    #[test]
+    fn dates_count_days_since_1970() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-03-01"), Some(11_017));
+        assert_eq!(parse_date("2026-10-16").map(stats::civil_from_days), Some((2026, 10, 16)));
+        assert_eq!(parse_date("16.10.2026"), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! Browser frontend, built for `wasm32-unknown-unknown` (see `web/index.html`).
+//!
+//! The terminal is drawn into the page by ratzilla. Frames are paced by the browser's
+//! `requestAnimationFrame` instead of the tick thread of the terminal build, and key presses come
+//! from the page's keyboard events or from the on-screen keys through [`press_key`].
+
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratzilla::event::{KeyCode as WebKeyCode, KeyEvent as WebKeyEvent};
+use ratzilla::ratatui::Terminal;
+use ratzilla::{DomBackend, WebRenderer};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::prelude::wasm_bindgen;
+use web_time::Instant;
+
+thread_local! {
+    /// Keys pressed on the on-screen keyboard, handed to the app on the next frame.
+    static VIRTUAL_KEYS: RefCell<VecDeque<KeyEvent>> = RefCell::new(VecDeque::new());
+}
+
+/// Called by the on-screen keys of the page with a key name: `Up`, `Down`, `Left`, `Right`,
+/// `Enter`, `Esc`, `Backspace`, `Space`, `F1` or a single character.
+#[wasm_bindgen]
+pub fn press_key(name: &str) {
+    let code = match name {
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Backspace" => KeyCode::Backspace,
+        "Space" => KeyCode::Char(' '),
+        "F1" => KeyCode::F(1),
+        _ => match name.chars().collect::<Vec<_>>()[..] {
+            [ch] => KeyCode::Char(ch),
+            _ => return,
+        },
+    };
+    VIRTUAL_KEYS.with_borrow_mut(|keys| keys.push_back(KeyEvent::from(code)));
+}
+
+/// The browser's key events in the terminal's terms, so games see no difference.
+fn convert(event: WebKeyEvent) -> Option<KeyEvent> {
+    let code = match event.code {
+        WebKeyCode::Char(ch) => KeyCode::Char(ch),
+        WebKeyCode::F(n) => KeyCode::F(n),
+        WebKeyCode::Backspace => KeyCode::Backspace,
+        WebKeyCode::Enter => KeyCode::Enter,
+        WebKeyCode::Left => KeyCode::Left,
+        WebKeyCode::Right => KeyCode::Right,
+        WebKeyCode::Up => KeyCode::Up,
+        WebKeyCode::Down => KeyCode::Down,
+        WebKeyCode::Tab => KeyCode::Tab,
+        WebKeyCode::Delete => KeyCode::Delete,
+        WebKeyCode::Home => KeyCode::Home,
+        WebKeyCode::End => KeyCode::End,
+        WebKeyCode::PageUp => KeyCode::PageUp,
+        WebKeyCode::PageDown => KeyCode::PageDown,
+        WebKeyCode::Esc => KeyCode::Esc,
+        _ => return None,
+    };
+    let mut modifiers = KeyModifiers::empty();
+    modifiers.set(KeyModifiers::CONTROL, event.ctrl);
+    modifiers.set(KeyModifiers::ALT, event.alt);
+    modifiers.set(KeyModifiers::SHIFT, event.shift);
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// Mount the app in the page and keep drawing it on every animation frame.
+pub fn start() -> std::io::Result<()> {
+    let backend = DomBackend::new().map_err(|error| std::io::Error::other(error.to_string()))?;
+    let terminal = Terminal::new(backend)?;
+    let app = Rc::new(RefCell::new(App::new()));
+
+    terminal.on_key_event({
+        let app = app.clone();
+        move |event| {
+            if let Some(key) = convert(event) {
+                app.borrow_mut().on_key_press(key);
+            }
+        }
+    });
+
+    let mut last_frame = Instant::now();
+    terminal.draw_web(move |frame| {
+        let mut app = app.borrow_mut();
+        VIRTUAL_KEYS.with_borrow_mut(|keys| keys.drain(..).for_each(|key| app.on_key_press(key)));
+        // a page cannot close itself, quitting starts over at the main menu
+        if !app.is_running() {
+            *app = App::new();
+        }
+        let now = Instant::now();
+        app.update((now - last_frame).as_secs_f64());
+        last_frame = now;
+        frame.render_widget(&mut *app, frame.area());
+    });
+    Ok(())
+}